@@ -0,0 +1,365 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use pest::error::LineColLocation;
+use serde::Deserialize;
+
+use crate::configs::custom::CustomConfig;
+use crate::configs::env_var::EnvVarConfig;
+use crate::configs::fill::FillConfig;
+use crate::configs::{FullConfig, StarshipRootConfig};
+use crate::formatter::string_formatter::StringFormatterError;
+use crate::formatter::StringFormatter;
+use crate::module::ALL_MODULES;
+use crate::serde_utils::ValueDeserializer;
+
+/// A problem found in the configuration file
+#[derive(Debug, PartialEq, Eq)]
+pub struct ConfigIssue {
+    /// Line and column of the offending key, both starting at 1
+    pub location: Option<(usize, usize)>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.location {
+            Some((line, column)) => write!(f, "{line}:{column}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Checks the content of a configuration file for syntax errors, unknown keys, values of the
+/// wrong type and format strings that can't be parsed.
+///
+/// Every key is checked on its own, so that a single mistake doesn't hide the ones after it.
+pub fn validate(content: &str) -> Vec<ConfigIssue> {
+    let config = match toml::from_str::<toml::Table>(content) {
+        Ok(config) => config,
+        Err(error) => {
+            return vec![ConfigIssue {
+                location: error.span().map(|span| line_column(content, span.start)),
+                message: error.message().trim().replace('\n', ", "),
+            }]
+        }
+    };
+
+    let mut issues = Vec::new();
+    let mut report = |path: &[&str], message: String| {
+        issues.push(ConfigIssue {
+            location: locate_key(content, path),
+            message,
+        });
+    };
+
+    for (key, value) in &config {
+        match (key.as_str(), value.as_table()) {
+            ("$schema", _) => {}
+            ("custom", Some(commands)) => {
+                for (name, command) in commands {
+                    for_each_key(command, |option, value| {
+                        if let Some(message) = check::<CustomConfig>(&single_key(option, value)) {
+                            report(&["custom", name, option], message);
+                        }
+                    });
+                }
+            }
+            ("env_var", Some(variables)) => {
+                for (name, variable) in variables {
+                    if variable.is_table() {
+                        for_each_key(variable, |option, value| {
+                            if let Some(message) = check::<EnvVarConfig>(&single_key(option, value))
+                            {
+                                report(&["env_var", name, option], message);
+                            }
+                        });
+                    } else if let Some(message) = check::<EnvVarConfig>(&single_key(name, variable))
+                    {
+                        report(&["env_var", name], message);
+                    }
+                }
+            }
+            ("fill", Some(options)) => {
+                for (option, value) in options {
+                    if value.is_table() {
+                        for_each_key(value, |key, value| {
+                            if let Some(message) = check::<FillConfig>(&single_key(key, value)) {
+                                report(&["fill", option, key], message);
+                            }
+                        });
+                    } else if let Some(message) = check::<FillConfig>(&single_key(option, value)) {
+                        report(&["fill", option], message);
+                    }
+                }
+            }
+            (module, Some(options)) if ALL_MODULES.contains(&module) => {
+                for (option, value) in options {
+                    let options = single_key(option, value).into();
+                    if let Some(message) = check::<FullConfig>(&single_key(module, &options)) {
+                        report(&[module, option], message);
+                    }
+                }
+            }
+            (name, table) => {
+                if let Some(message) = check::<StarshipRootConfig>(&single_key(name, value)) {
+                    let message = match table {
+                        Some(_) if message.ends_with("Unknown key") => {
+                            format!("Unknown module '{name}'{}", did_you_mean(name, ALL_MODULES))
+                        }
+                        _ => message,
+                    };
+                    report(&[name], message);
+                }
+            }
+        }
+    }
+
+    check_format_strings(&mut Vec::new(), &toml::Value::Table(config), &mut report);
+    issues
+}
+
+fn single_key(key: &str, value: &toml::Value) -> toml::Table {
+    toml::Table::from_iter([(key.to_owned(), value.clone())])
+}
+
+/// Deserializes a config struct from the table, returning the error
+fn check<'a, T: Deserialize<'a>>(table: &'a toml::Table) -> Option<String> {
+    T::deserialize(ValueDeserializer::new(table))
+        .err()
+        .map(|error| {
+            // Errors of nested structs are prefixed with the same struct name and their key
+            let error = error.to_string();
+            let innermost = error.rfind("Error in '").unwrap_or_default();
+            error[innermost..].to_owned()
+        })
+}
+
+fn for_each_key(value: &toml::Value, mut f: impl FnMut(&str, &toml::Value)) {
+    if let Some(table) = value.as_table() {
+        for (key, value) in table {
+            f(key, value);
+        }
+    }
+}
+
+fn did_you_mean(name: &str, candidates: &[&str]) -> String {
+    candidates
+        .iter()
+        .map(|candidate| (strsim::jaro_winkler(name, candidate), candidate))
+        .filter(|(score, _)| *score > 0.8)
+        .max_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+        .map(|(_, candidate)| format!(" (Did you mean '{candidate}'?)"))
+        .unwrap_or_default()
+}
+
+/// Parses every format string of the configuration with the formatter
+fn check_format_strings<'a>(
+    path: &mut Vec<&'a str>,
+    value: &'a toml::Value,
+    report: &mut impl FnMut(&[&str], String),
+) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table {
+                path.push(key);
+                check_format_strings(path, value, report);
+                path.pop();
+            }
+        }
+        toml::Value::Array(values) => {
+            for value in values {
+                check_format_strings(path, value, report);
+            }
+        }
+        toml::Value::String(format) if is_format_key(path.last().copied()) => {
+            if let Err(error) = StringFormatter::new(format) {
+                report(path, format_error_message(&error));
+            }
+        }
+        _ => {}
+    }
+}
+
+fn is_format_key(key: Option<&str>) -> bool {
+    key.map_or(false, |key| {
+        key == "format" || key.ends_with("_format") || key == "continuation_prompt"
+    })
+}
+
+fn format_error_message(error: &StringFormatterError) -> String {
+    match error {
+        StringFormatterError::Parse(error) => {
+            let (line, column) = match error.line_col {
+                LineColLocation::Pos(position) | LineColLocation::Span(position, _) => position,
+            };
+            let position = if line > 1 {
+                format!("line {line}, column {column}")
+            } else {
+                format!("column {column}")
+            };
+            format!(
+                "Invalid format string at {position}: {}",
+                error.variant.message()
+            )
+        }
+        StringFormatterError::Custom(error) => format!("Invalid format string: {error}"),
+    }
+}
+
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Finds the line and column of a key, given as the path from the root of the configuration.
+///
+/// The file is only scanned for table headers and `key = value` lines, which is enough to find
+/// the keys of a configuration. If the key itself isn't found, the table it is in is returned.
+fn locate_key(content: &str, path: &[&str]) -> Option<(usize, usize)> {
+    let mut table = Vec::new();
+    let mut table_location = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        let location = (
+            index + 1,
+            line[..line.len() - trimmed.len()].chars().count() + 1,
+        );
+
+        if let Some(header) = trimmed.strip_prefix('[') {
+            let header = header.trim_start_matches('[');
+            table = split_key(&header[..header.find(']').unwrap_or(header.len())]);
+            if path.starts_with(&table) && table_location.is_none() {
+                table_location = Some(location);
+            }
+            continue;
+        }
+
+        let Some((key, _)) = trimmed.split_once('=') else {
+            continue;
+        };
+        let key_path = table
+            .iter()
+            .copied()
+            .chain(split_key(key))
+            .collect::<Vec<_>>();
+        if key_path == path {
+            return Some(location);
+        }
+    }
+    table_location
+}
+
+fn split_key(key: &str) -> Vec<&str> {
+    key.split('.')
+        .map(|part| part.trim().trim_matches('"').trim_matches('\''))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(content: &str) -> Vec<String> {
+        validate(content).iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn valid_config() {
+        let content = concat!(
+            "format = \"$directory$character\"\n",
+            "\n",
+            "[git_branch]\n",
+            "symbol = \"git \"\n",
+            "\n",
+            "[custom.foo]\n",
+            "command = \"echo foo\"\n",
+            "format = \"[$output]($style) \"\n",
+            "\n",
+            "[env_var.SHELL]\n",
+            "default = \"unknown\"\n",
+            "\n",
+            "[fill.center]\n",
+            "weight = 2\n",
+        );
+        assert_eq!(messages(content), Vec::<String>::new());
+    }
+
+    #[test]
+    fn unknown_keys_and_modules() {
+        let content = concat!(
+            "add_newlin = false\n",
+            "\n",
+            "[git_branch]\n",
+            "  symbl = \"git \"\n",
+            "\n",
+            "[git_brnach]\n",
+            "disabled = true\n",
+            "\n",
+            "[custom.foo]\n",
+            "comand = \"echo foo\"\n",
+        );
+        assert_eq!(
+            messages(content),
+            vec![
+                "1:1: Error in 'StarshipRoot' at 'add_newlin': Unknown key (Did you mean 'add_newline'?)",
+                "4:3: Error in 'GitBranch' at 'symbl': Unknown key (Did you mean 'symbol'?)",
+                "6:1: Unknown module 'git_brnach' (Did you mean 'git_branch'?)",
+                "10:1: Error in 'Custom' at 'comand': Unknown key (Did you mean 'command'?)",
+            ]
+        );
+    }
+
+    #[test]
+    fn type_mismatch() {
+        let content = "directory.truncate_to_repo = \"yes\"\n";
+        assert_eq!(
+            messages(content),
+            vec!["1:1: Error in 'Directory' at 'truncate_to_repo': invalid type: string \"yes\", expected a boolean"]
+        );
+    }
+
+    #[test]
+    fn unknown_variant() {
+        let content = "contrast_action = \"ajust\"\n";
+        assert_eq!(
+            messages(content),
+            vec!["1:1: Error in 'StarshipRoot' at 'contrast_action': unknown variant `ajust`, expected `adjust` or `warn`"]
+        );
+        let content = "escape_audit = \"fixed\"\n";
+        assert_eq!(
+            messages(content),
+            vec!["1:1: Error in 'StarshipRoot' at 'escape_audit': unknown variant `fixed`, expected one of `off`, `warn`, `fix`"]
+        );
+        let content = "[logging]\nlevel = \"verbose\"\n";
+        assert_eq!(
+            messages(content),
+            vec!["1:1: Error in 'Logging' at 'level': unknown variant `verbose`, expected one of `trace`, `debug`, `info`, `warn`, `error`"]
+        );
+    }
+
+    #[test]
+    fn malformed_format_string() {
+        let content = concat!("[battery]\n", "format = \"[$symbol($style)\"\n",);
+        assert_eq!(
+            messages(content),
+            vec!["2:1: Invalid format string at column 17: expected variable, string, textgroup, or conditional"]
+        );
+    }
+
+    #[test]
+    fn syntax_error() {
+        assert_eq!(
+            validate("[git_branch]\nsymbol = \n"),
+            vec![ConfigIssue {
+                location: Some((2, 10)),
+                message: "invalid string, expected `\"`, `'`".to_owned(),
+            }]
+        );
+    }
+}
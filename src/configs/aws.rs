@@ -27,6 +27,9 @@ use std::collections::HashMap;
 /// When using [`AWSume`](https://awsu.me) the profile
 /// is read from the `AWSUME_PROFILE` env var and the credentials expiration
 /// date is read from the `AWSUME_EXPIRATION` env var.
+///
+/// The expiration of SSO tokens and of the credentials of assumed roles is read
+/// from the caches of the AWS CLI in `~/.aws/sso/cache` and `~/.aws/cli/cache`.
 pub struct AwsConfig<'a> {
     /// The format for the module.
     pub format: &'a str,
@@ -44,6 +47,10 @@ pub struct AwsConfig<'a> {
     pub expiration_symbol: &'a str,
     /// If true displays info even if `credentials`, `credential_process` or `sso_start_url` have not been setup.
     pub force_display: bool,
+    /// The number of seconds before the credentials or the SSO token expire from which on `expiration_warning_style` is used.
+    pub expiration_warning_threshold: i64,
+    /// The style for the module when the credentials or the SSO token are about to expire.
+    pub expiration_warning_style: &'a str,
 }
 
 impl<'a> Default for AwsConfig<'a> {
@@ -57,6 +64,8 @@ impl<'a> Default for AwsConfig<'a> {
             profile_aliases: HashMap::new(),
             expiration_symbol: "X",
             force_display: false,
+            expiration_warning_threshold: 300,
+            expiration_warning_style: "bold red",
         }
     }
 }
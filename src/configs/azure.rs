@@ -14,6 +14,8 @@ pub struct AzureConfig<'a> {
     pub style: &'a str,
     pub disabled: bool,
     pub subscription_aliases: HashMap<String, &'a str>,
+    pub subscription_styles: HashMap<String, &'a str>,
+    pub tenant_aliases: HashMap<String, &'a str>,
 }
 
 impl<'a> Default for AzureConfig<'a> {
@@ -24,6 +26,8 @@ impl<'a> Default for AzureConfig<'a> {
             style: "blue bold",
             disabled: true,
             subscription_aliases: HashMap::new(),
+            subscription_styles: HashMap::new(),
+            tenant_aliases: HashMap::new(),
         }
     }
 }
@@ -15,6 +15,7 @@ pub struct BatteryConfig<'a> {
     pub empty_symbol: &'a str,
     #[serde(borrow)]
     pub display: Vec<BatteryDisplayConfig<'a>>,
+    pub per_battery: bool,
     pub disabled: bool,
     pub format: &'a str,
 }
@@ -29,6 +30,7 @@ impl<'a> Default for BatteryConfig<'a> {
             empty_symbol: "󰂎 ",
             format: "[$symbol$percentage]($style) ",
             display: vec![BatteryDisplayConfig::default()],
+            per_battery: false,
             disabled: false,
         }
     }
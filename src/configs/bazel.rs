@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct BazelConfig<'a> {
+    pub format: &'a str,
+    pub version_format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub disabled: bool,
+    pub recursive: bool,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
+}
+
+impl<'a> Default for BazelConfig<'a> {
+    fn default() -> Self {
+        BazelConfig {
+            format: "via [$symbol($version )(\\($mode\\) )]($style)",
+            version_format: "v${raw}",
+            symbol: "🌿 ",
+            style: "bold green",
+            disabled: false,
+            recursive: false,
+            detect_extensions: vec![],
+            detect_files: vec![
+                "MODULE.bazel",
+                "REPO.bazel",
+                "WORKSPACE",
+                "WORKSPACE.bazel",
+                ".bazelversion",
+            ],
+            detect_folders: vec![],
+        }
+    }
+}
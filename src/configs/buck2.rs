@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct Buck2Config<'a> {
+    pub format: &'a str,
+    pub version_format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub disabled: bool,
+    pub version_cache: u64,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
+}
+
+impl<'a> Default for Buck2Config<'a> {
+    fn default() -> Self {
+        Buck2Config {
+            format: "via [$symbol($version )(in $cell )]($style)",
+            version_format: "${raw}",
+            symbol: "🦌 ",
+            style: "bold yellow",
+            disabled: false,
+            version_cache: 3600,
+            detect_extensions: vec![],
+            detect_files: vec![".buckconfig", ".buckroot", "BUCK", "BUCK.v2"],
+            detect_folders: vec![],
+        }
+    }
+}
@@ -16,6 +16,7 @@ pub struct CharacterConfig<'a> {
     pub vimcmd_visual_symbol: &'a str,
     pub vimcmd_replace_symbol: &'a str,
     pub vimcmd_replace_one_symbol: &'a str,
+    pub bash_vi_mode_strings: bool,
     pub disabled: bool,
 }
 
@@ -29,6 +30,7 @@ impl<'a> Default for CharacterConfig<'a> {
             vimcmd_visual_symbol: "[❮](bold yellow)",
             vimcmd_replace_symbol: "[❮](bold purple)",
             vimcmd_replace_one_symbol: "[❮](bold purple)",
+            bash_vi_mode_strings: false,
             disabled: false,
         }
     }
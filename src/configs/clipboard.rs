@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct ClipboardConfig<'a> {
+    pub format: &'a str,
+    pub text_symbol: &'a str,
+    pub image_symbol: &'a str,
+    pub files_symbol: &'a str,
+    pub style: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> Default for ClipboardConfig<'a> {
+    fn default() -> Self {
+        ClipboardConfig {
+            format: "[$symbol($size)]($style) ",
+            text_symbol: "📋 ",
+            image_symbol: "🖼️ ",
+            files_symbol: "📁 ",
+            style: "bold blue",
+            disabled: true,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct CmdCounterConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> Default for CmdCounterConfig<'a> {
+    fn default() -> Self {
+        CmdCounterConfig {
+            format: "[$symbol$number]($style) ",
+            symbol: "#",
+            style: "dimmed",
+            disabled: true,
+        }
+    }
+}
@@ -11,6 +11,8 @@ pub struct CondaConfig<'a> {
     pub truncation_length: usize,
     pub format: &'a str,
     pub symbol: &'a str,
+    pub prefix_symbol: &'a str,
+    pub version_format: &'a str,
     pub style: &'a str,
     pub ignore_base: bool,
     pub disabled: bool,
@@ -22,6 +24,8 @@ impl<'a> Default for CondaConfig<'a> {
             truncation_length: 1,
             format: "via [$symbol$environment]($style) ",
             symbol: "🅒 ",
+            prefix_symbol: "📁 ",
+            version_format: "v${raw}",
             style: "green bold",
             ignore_base: true,
             disabled: false,
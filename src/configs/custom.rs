@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crate::config::{Either, VecOr};
 
 use serde::{self, Deserialize, Serialize};
@@ -13,6 +15,7 @@ pub struct CustomConfig<'a> {
     pub format: &'a str,
     pub symbol: &'a str,
     pub command: &'a str,
+    pub output: &'a str,
     pub when: Either<bool, &'a str>,
     pub require_repo: bool,
     pub shell: VecOr<&'a str>,
@@ -25,11 +28,15 @@ pub struct CustomConfig<'a> {
     pub detect_extensions: Vec<&'a str>,
     #[serde(alias = "directories")]
     pub detect_folders: Vec<&'a str>,
+    pub detect_contents: HashMap<String, &'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub os: Option<&'a str>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub use_stdin: Option<bool>,
     pub ignore_timeout: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command_timeout: Option<u64>,
+    pub cache_duration: u64,
 }
 
 impl<'a> Default for CustomConfig<'a> {
@@ -38,6 +45,7 @@ impl<'a> Default for CustomConfig<'a> {
             format: "[$symbol($output )]($style)",
             symbol: "",
             command: "",
+            output: "text",
             when: Either::First(false),
             require_repo: false,
             shell: VecOr::default(),
@@ -47,9 +55,12 @@ impl<'a> Default for CustomConfig<'a> {
             detect_files: Vec::default(),
             detect_extensions: Vec::default(),
             detect_folders: Vec::default(),
+            detect_contents: HashMap::default(),
             os: None,
             use_stdin: None,
             ignore_timeout: false,
+            command_timeout: None,
+            cache_duration: 0,
         }
     }
 }
@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct DevEnvConfig<'a> {
+    pub format: &'a str,
+    pub symbols: HashMap<String, &'a str>,
+    pub style: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> Default for DevEnvConfig<'a> {
+    fn default() -> Self {
+        DevEnvConfig {
+            format: "via [$symbol$name]($style) ",
+            symbols: HashMap::from([
+                ("devbox".to_string(), "📦 "),
+                ("devenv".to_string(), "❄️ "),
+                ("flox".to_string(), "🌀 "),
+            ]),
+            style: "bold cyan",
+            disabled: false,
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::config::Either;
 use indexmap::IndexMap;
 
 use serde::{Deserialize, Serialize};
@@ -10,9 +11,12 @@ use serde::{Deserialize, Serialize};
 )]
 #[serde(default)]
 pub struct DirectoryConfig<'a> {
-    pub truncation_length: i64,
+    pub truncation_length: Either<i64, &'a str>,
     pub truncate_to_repo: bool,
+    pub repo_relative: bool,
+    pub repo_symbol: &'a str,
     pub substitutions: IndexMap<String, &'a str>,
+    pub regex_substitutions: IndexMap<String, &'a str>,
     pub fish_style_pwd_dir_length: i64,
     pub use_logical_path: bool,
     pub format: &'a str,
@@ -23,6 +27,7 @@ pub struct DirectoryConfig<'a> {
     pub disabled: bool,
     pub read_only: &'a str,
     pub read_only_style: &'a str,
+    pub fs_remote: &'a str,
     pub truncation_symbol: &'a str,
     pub home_symbol: &'a str,
     pub use_os_path_sep: bool,
@@ -31,11 +36,14 @@ pub struct DirectoryConfig<'a> {
 impl<'a> Default for DirectoryConfig<'a> {
     fn default() -> Self {
         DirectoryConfig {
-            truncation_length: 3,
+            truncation_length: Either::First(3),
             truncate_to_repo: true,
+            repo_relative: false,
+            repo_symbol: "",
             fish_style_pwd_dir_length: 0,
             use_logical_path: true,
             substitutions: IndexMap::new(),
+            regex_substitutions: IndexMap::new(),
             format: "[$path]($style)[$read_only]($read_only_style) ",
             repo_root_format: "[$before_root_path]($before_repo_root_style)[$repo_root]($repo_root_style)[$path]($style)[$read_only]($read_only_style) ",
             style: "cyan bold",
@@ -44,6 +52,7 @@ impl<'a> Default for DirectoryConfig<'a> {
             disabled: false,
             read_only: "🔒",
             read_only_style: "red",
+            fs_remote: "🌐",
             truncation_symbol: "",
             home_symbol: "~",
             use_os_path_sep: true,
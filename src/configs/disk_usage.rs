@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct DiskUsageConfig<'a> {
+    pub threshold: i64,
+    pub format: &'a str,
+    pub style: &'a str,
+    pub symbol: &'a str,
+    #[serde(borrow)]
+    pub display: Vec<DiskUsageDisplayConfig<'a>>,
+    pub disabled: bool,
+}
+
+impl<'a> Default for DiskUsageConfig<'a> {
+    fn default() -> Self {
+        DiskUsageConfig {
+            threshold: 80,
+            format: "[$symbol$free free]($style) ",
+            style: "bold yellow",
+            symbol: "💾 ",
+            display: vec![DiskUsageDisplayConfig::default()],
+            disabled: true,
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct DiskUsageDisplayConfig<'a> {
+    pub threshold: i64,
+    pub style: &'a str,
+    pub symbol: Option<&'a str>,
+}
+
+impl<'a> Default for DiskUsageDisplayConfig<'a> {
+    fn default() -> Self {
+        DiskUsageDisplayConfig {
+            threshold: 90,
+            style: "bold red",
+            symbol: None,
+        }
+    }
+}
@@ -16,6 +16,7 @@ pub struct DockerContextConfig<'a> {
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub compose_files: Vec<&'a str>,
 }
 
 impl<'a> Default for DockerContextConfig<'a> {
@@ -27,8 +28,20 @@ impl<'a> Default for DockerContextConfig<'a> {
             only_with_files: true,
             disabled: false,
             detect_extensions: vec![],
-            detect_files: vec!["docker-compose.yml", "docker-compose.yaml", "Dockerfile"],
+            detect_files: vec![
+                "compose.yml",
+                "compose.yaml",
+                "docker-compose.yml",
+                "docker-compose.yaml",
+                "Dockerfile",
+            ],
             detect_folders: vec![],
+            compose_files: vec![
+                "compose.yaml",
+                "compose.yml",
+                "docker-compose.yaml",
+                "docker-compose.yml",
+            ],
         }
     }
 }
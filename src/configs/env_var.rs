@@ -12,6 +12,10 @@ pub struct EnvVarConfig<'a> {
     pub style: &'a str,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub variable: Option<&'a str>,
+    pub variables: Vec<&'a str>,
+    #[serde(rename = "match", skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<&'a str>,
+    pub separator: &'a str,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default: Option<&'a str>,
     pub format: &'a str,
@@ -25,6 +29,9 @@ impl<'a> Default for EnvVarConfig<'a> {
             symbol: "",
             style: "black bold dimmed",
             variable: None,
+            variables: vec![],
+            pattern: None,
+            separator: " ",
             default: None,
             format: "with [$env_value]($style) ",
             disabled: false,
@@ -10,6 +10,10 @@ use serde::{Deserialize, Serialize};
 pub struct FillConfig<'a> {
     pub style: &'a str,
     pub symbol: &'a str,
+    pub weight: usize,
+    pub min_width: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_width: Option<usize>,
     pub disabled: bool,
 }
 
@@ -18,6 +22,9 @@ impl<'a> Default for FillConfig<'a> {
         FillConfig {
             style: "bold black",
             symbol: ".",
+            weight: 1,
+            min_width: 0,
+            max_width: None,
             disabled: false,
         }
     }
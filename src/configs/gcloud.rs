@@ -16,6 +16,7 @@ pub struct GcloudConfig<'a> {
     pub region_aliases: HashMap<String, &'a str>,
     pub project_aliases: HashMap<String, &'a str>,
     pub detect_env_vars: Vec<&'a str>,
+    pub adc_mismatch_symbol: &'a str,
 }
 
 impl<'a> Default for GcloudConfig<'a> {
@@ -28,6 +29,7 @@ impl<'a> Default for GcloudConfig<'a> {
             region_aliases: HashMap::new(),
             project_aliases: HashMap::new(),
             detect_env_vars: vec![],
+            adc_mismatch_symbol: "≠ADC",
         }
     }
 }
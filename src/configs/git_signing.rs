@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct GitSigningConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub unavailable_style: &'a str,
+    pub only_when_signing: bool,
+    pub disabled: bool,
+}
+
+impl<'a> Default for GitSigningConfig<'a> {
+    fn default() -> Self {
+        GitSigningConfig {
+            format: "[$symbol$key]($style) ",
+            symbol: "🔏 ",
+            style: "bold green",
+            unavailable_style: "bold red",
+            only_when_signing: false,
+            disabled: true,
+        }
+    }
+}
@@ -13,6 +13,7 @@ pub struct HelmConfig<'a> {
     pub symbol: &'a str,
     pub style: &'a str,
     pub disabled: bool,
+    pub version_cache: u64,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
@@ -21,11 +22,12 @@ pub struct HelmConfig<'a> {
 impl<'a> Default for HelmConfig<'a> {
     fn default() -> Self {
         HelmConfig {
-            format: "via [$symbol($version )]($style)",
+            format: "via [$symbol($chart@$chart_version )($version )]($style)",
             version_format: "v${raw}",
             symbol: "⎈ ",
             style: "bold white",
             disabled: false,
+            version_cache: 3600,
             detect_extensions: vec![],
             detect_files: vec!["helmfile.yaml", "Chart.yaml"],
             detect_folders: vec![],
@@ -11,6 +11,7 @@ pub struct HostnameConfig<'a> {
     pub ssh_only: bool,
     pub ssh_symbol: &'a str,
     pub trim_at: &'a str,
+    pub jump_separator: &'a str,
     pub format: &'a str,
     pub style: &'a str,
     pub disabled: bool,
@@ -22,7 +23,8 @@ impl<'a> Default for HostnameConfig<'a> {
             ssh_only: true,
             ssh_symbol: "🌐 ",
             trim_at: ".",
-            format: "[$ssh_symbol$hostname]($style) in ",
+            jump_separator: "→",
+            format: "[$ssh_symbol$jump_hosts$hostname]($style) in ",
             style: "green dimmed bold",
             disabled: false,
         }
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Clone, Deserialize, Serialize)]
 #[cfg_attr(
@@ -13,6 +14,7 @@ pub struct JavaConfig<'a> {
     pub version_format: &'a str,
     pub style: &'a str,
     pub symbol: &'a str,
+    pub vendor_symbols: HashMap<String, &'a str>,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
@@ -26,6 +28,7 @@ impl<'a> Default for JavaConfig<'a> {
             disabled: false,
             style: "red dimmed",
             symbol: "☕ ",
+            vendor_symbols: HashMap::new(),
             detect_extensions: vec!["java", "class", "jar", "gradle", "clj", "cljc"],
             detect_files: vec![
                 "pom.xml",
@@ -14,6 +14,9 @@ pub struct JobsConfig<'a> {
     pub format: &'a str,
     pub symbol: &'a str,
     pub style: &'a str,
+    pub name_truncation_length: i64,
+    pub truncation_symbol: &'a str,
+    pub name_separator: &'a str,
     pub disabled: bool,
 }
 
@@ -26,6 +29,9 @@ impl<'a> Default for JobsConfig<'a> {
             format: "[$symbol$number]($style) ",
             symbol: "✦",
             style: "bold blue",
+            name_truncation_length: 20,
+            truncation_symbol: "…",
+            name_separator: ", ",
             disabled: false,
         }
     }
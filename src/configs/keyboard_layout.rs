@@ -0,0 +1,29 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct KeyboardLayoutConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub layout_aliases: IndexMap<String, &'a str>,
+    pub disabled: bool,
+}
+
+impl<'a> Default for KeyboardLayoutConfig<'a> {
+    fn default() -> Self {
+        KeyboardLayoutConfig {
+            format: "[$symbol$layout]($style) ",
+            symbol: "⌨️ ",
+            style: "bold purple",
+            layout_aliases: IndexMap::new(),
+            disabled: true,
+        }
+    }
+}
@@ -15,9 +15,16 @@ pub struct KubernetesConfig<'a> {
     pub disabled: bool,
     pub context_aliases: HashMap<String, &'a str>,
     pub user_aliases: HashMap<String, &'a str>,
+    #[serde(borrow)]
+    pub providers: HashMap<String, KubernetesProviderConfig<'a>>,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub check_reachability: bool,
+    pub reachability_timeout: u64,
+    pub reachability_cache: u64,
+    pub unreachable_style: &'a str,
+    pub unauthorized_style: &'a str,
 }
 
 impl<'a> Default for KubernetesConfig<'a> {
@@ -29,9 +36,27 @@ impl<'a> Default for KubernetesConfig<'a> {
             disabled: true,
             context_aliases: HashMap::new(),
             user_aliases: HashMap::new(),
+            providers: HashMap::new(),
             detect_extensions: vec![],
             detect_files: vec![],
             detect_folders: vec![],
+            check_reachability: false,
+            reachability_timeout: 500,
+            reachability_cache: 60,
+            unreachable_style: "red bold",
+            unauthorized_style: "yellow bold",
         }
     }
 }
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct KubernetesProviderConfig<'a> {
+    pub symbol: Option<&'a str>,
+    pub style: Option<&'a str>,
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct LoadAverageConfig<'a> {
+    pub threshold: f64,
+    pub per_core: bool,
+    pub format: &'a str,
+    pub style: &'a str,
+    pub symbol: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> Default for LoadAverageConfig<'a> {
+    fn default() -> Self {
+        LoadAverageConfig {
+            threshold: 0.0,
+            per_core: false,
+            format: "[$symbol$one $five $fifteen]($style) ",
+            style: "white bold dimmed",
+            symbol: "📈 ",
+            disabled: true,
+        }
+    }
+}
@@ -9,6 +9,9 @@ use serde::{Deserialize, Serialize};
 #[serde(default)]
 pub struct LocalipConfig<'a> {
     pub ssh_only: bool,
+    pub prefer_ipv6: bool,
+    pub interface: &'a str,
+    pub cidr: &'a str,
     pub format: &'a str,
     pub style: &'a str,
     pub disabled: bool,
@@ -18,7 +21,10 @@ impl<'a> Default for LocalipConfig<'a> {
     fn default() -> Self {
         LocalipConfig {
             ssh_only: true,
-            format: "[$localipv4]($style) ",
+            prefer_ipv6: false,
+            interface: "",
+            cidr: "",
+            format: "[$localip]($style) ",
             style: "yellow bold",
             disabled: true,
         }
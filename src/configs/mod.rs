@@ -4,11 +4,15 @@ use serde::{self, Deserialize, Serialize};
 pub mod aws;
 pub mod azure;
 pub mod battery;
+pub mod bazel;
+pub mod buck2;
 pub mod buf;
 pub mod bun;
 pub mod c;
 pub mod character;
+pub mod clipboard;
 pub mod cmake;
+pub mod cmd_counter;
 pub mod cmd_duration;
 pub mod cobol;
 pub mod conda;
@@ -18,7 +22,10 @@ pub mod custom;
 pub mod daml;
 pub mod dart;
 pub mod deno;
+pub mod dev_env;
 pub mod directory;
+pub mod disk_usage;
+pub mod do_not_disturb;
 pub mod docker_context;
 pub mod dotnet;
 pub mod elixir;
@@ -32,6 +39,7 @@ pub mod gcloud;
 pub mod git_branch;
 pub mod git_commit;
 pub mod git_metrics;
+pub mod git_signing;
 pub mod git_state;
 pub mod git_status;
 pub mod go;
@@ -45,13 +53,17 @@ pub mod hostname;
 pub mod java;
 pub mod jobs;
 pub mod julia;
+pub mod keyboard_layout;
 pub mod kotlin;
 pub mod kubernetes;
 pub mod line_break;
+pub mod load_average;
 pub mod localip;
 pub mod lua;
 pub mod memory_usage;
 pub mod meson;
+pub mod multiplexer;
+pub mod network;
 pub mod nim;
 pub mod nix_shell;
 pub mod nodejs;
@@ -63,6 +75,7 @@ pub mod package;
 pub mod perl;
 pub mod php;
 pub mod pijul_channel;
+pub mod public_ip;
 pub mod pulumi;
 pub mod purescript;
 pub mod python;
@@ -77,16 +90,19 @@ pub mod shlvl;
 pub mod singularity;
 pub mod solidity;
 pub mod spack;
+pub mod ssh_agent;
 mod starship_root;
 pub mod status;
 pub mod sudo;
 pub mod swift;
 pub mod terraform;
 pub mod time;
+pub mod tool_versions;
 pub mod username;
 pub mod v;
 pub mod vagrant;
 pub mod vcsh;
+pub mod volume;
 pub mod zig;
 
 pub use starship_root::*;
@@ -113,6 +129,10 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     battery: battery::BatteryConfig<'a>,
     #[serde(borrow)]
+    bazel: bazel::BazelConfig<'a>,
+    #[serde(borrow)]
+    buck2: buck2::Buck2Config<'a>,
+    #[serde(borrow)]
     buf: buf::BufConfig<'a>,
     #[serde(borrow)]
     bun: bun::BunConfig<'a>,
@@ -121,8 +141,12 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     character: character::CharacterConfig<'a>,
     #[serde(borrow)]
+    clipboard: clipboard::ClipboardConfig<'a>,
+    #[serde(borrow)]
     cmake: cmake::CMakeConfig<'a>,
     #[serde(borrow)]
+    cmd_counter: cmd_counter::CmdCounterConfig<'a>,
+    #[serde(borrow)]
     cmd_duration: cmd_duration::CmdDurationConfig<'a>,
     #[serde(borrow)]
     cobol: cobol::CobolConfig<'a>,
@@ -139,8 +163,14 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     deno: deno::DenoConfig<'a>,
     #[serde(borrow)]
+    dev_env: dev_env::DevEnvConfig<'a>,
+    #[serde(borrow)]
     directory: directory::DirectoryConfig<'a>,
     #[serde(borrow)]
+    disk_usage: disk_usage::DiskUsageConfig<'a>,
+    #[serde(borrow)]
+    do_not_disturb: do_not_disturb::DoNotDisturbConfig<'a>,
+    #[serde(borrow)]
     docker_context: docker_context::DockerContextConfig<'a>,
     #[serde(borrow)]
     dotnet: dotnet::DotnetConfig<'a>,
@@ -167,6 +197,8 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     git_metrics: git_metrics::GitMetricsConfig<'a>,
     #[serde(borrow)]
+    git_signing: git_signing::GitSigningConfig<'a>,
+    #[serde(borrow)]
     git_state: git_state::GitStateConfig<'a>,
     #[serde(borrow)]
     git_status: git_status::GitStatusConfig<'a>,
@@ -193,11 +225,15 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     julia: julia::JuliaConfig<'a>,
     #[serde(borrow)]
+    keyboard_layout: keyboard_layout::KeyboardLayoutConfig<'a>,
+    #[serde(borrow)]
     kotlin: kotlin::KotlinConfig<'a>,
     #[serde(borrow)]
     kubernetes: kubernetes::KubernetesConfig<'a>,
     line_break: line_break::LineBreakConfig,
     #[serde(borrow)]
+    load_average: load_average::LoadAverageConfig<'a>,
+    #[serde(borrow)]
     localip: localip::LocalipConfig<'a>,
     #[serde(borrow)]
     lua: lua::LuaConfig<'a>,
@@ -206,6 +242,10 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     meson: meson::MesonConfig<'a>,
     #[serde(borrow)]
+    multiplexer: multiplexer::MultiplexerConfig<'a>,
+    #[serde(borrow)]
+    network: network::NetworkConfig<'a>,
+    #[serde(borrow)]
     nim: nim::NimConfig<'a>,
     #[serde(borrow)]
     nix_shell: nix_shell::NixShellConfig<'a>,
@@ -228,6 +268,8 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     pijul_channel: pijul_channel::PijulConfig<'a>,
     #[serde(borrow)]
+    public_ip: public_ip::PublicIpConfig<'a>,
+    #[serde(borrow)]
     pulumi: pulumi::PulumiConfig<'a>,
     #[serde(borrow)]
     purescript: purescript::PureScriptConfig<'a>,
@@ -256,6 +298,8 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     spack: spack::SpackConfig<'a>,
     #[serde(borrow)]
+    ssh_agent: ssh_agent::SshAgentConfig<'a>,
+    #[serde(borrow)]
     status: status::StatusConfig<'a>,
     #[serde(borrow)]
     sudo: sudo::SudoConfig<'a>,
@@ -266,6 +310,8 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     time: time::TimeConfig<'a>,
     #[serde(borrow)]
+    tool_versions: tool_versions::ToolVersionsConfig<'a>,
+    #[serde(borrow)]
     username: username::UsernameConfig<'a>,
     #[serde(borrow)]
     vagrant: vagrant::VagrantConfig<'a>,
@@ -274,6 +320,8 @@ pub struct FullConfig<'a> {
     #[serde(borrow)]
     vlang: v::VConfig<'a>,
     #[serde(borrow)]
+    volume: volume::VolumeConfig<'a>,
+    #[serde(borrow)]
     zig: zig::ZigConfig<'a>,
     #[serde(borrow)]
     custom: IndexMap<String, custom::CustomConfig<'a>>,
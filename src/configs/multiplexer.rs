@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct MultiplexerConfig<'a> {
+    pub format: &'a str,
+    pub tmux_symbol: &'a str,
+    pub zellij_symbol: &'a str,
+    pub style: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> Default for MultiplexerConfig<'a> {
+    fn default() -> Self {
+        MultiplexerConfig {
+            format: "[$symbol$session]($style) ",
+            tmux_symbol: "tmux ",
+            zellij_symbol: "zellij ",
+            style: "bold cyan",
+            disabled: true,
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct NetworkConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub interface_pattern: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> Default for NetworkConfig<'a> {
+    fn default() -> Self {
+        NetworkConfig {
+            format: "[$symbol$interface]($style) ",
+            symbol: "🔒 ",
+            style: "bold green",
+            interface_pattern: r"^(wg|tun|utun|tap|ppp|ipsec|tailscale|nordlynx|proton)\d*$",
+            disabled: true,
+        }
+    }
+}
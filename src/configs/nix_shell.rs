@@ -24,7 +24,7 @@ unless you can show that your changes do not affect this workaround.  */
 impl<'a> Default for NixShellConfig<'a> {
     fn default() -> Self {
         NixShellConfig {
-            format: "via [$symbol$state( \\($name\\))]($style) ",
+            format: "via [$symbol$state( \\($name\\))( $flake#$dev_shell)]($style) ",
             symbol: "❄️  ",
             style: "bold blue",
             impure_msg: "impure",
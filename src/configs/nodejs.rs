@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Clone, Deserialize, Serialize)]
 #[cfg_attr(
@@ -17,6 +18,7 @@ pub struct NodejsConfig<'a> {
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
     pub detect_folders: Vec<&'a str>,
+    pub package_manager_symbols: HashMap<String, &'a str>,
 }
 
 impl<'a> Default for NodejsConfig<'a> {
@@ -31,6 +33,12 @@ impl<'a> Default for NodejsConfig<'a> {
             detect_extensions: vec!["js", "mjs", "cjs", "ts", "mts", "cts"],
             detect_files: vec!["package.json", ".node-version", ".nvmrc"],
             detect_folders: vec!["node_modules"],
+            package_manager_symbols: HashMap::from([
+                ("npm".to_string(), "📦 "),
+                ("yarn".to_string(), "🧶 "),
+                ("pnpm".to_string(), "🟧 "),
+                ("bun".to_string(), "🥟 "),
+            ]),
         }
     }
 }
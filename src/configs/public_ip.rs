@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct PublicIpConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub url: &'a str,
+    pub cache_duration: u64,
+    pub disabled: bool,
+}
+
+impl<'a> Default for PublicIpConfig<'a> {
+    fn default() -> Self {
+        PublicIpConfig {
+            format: "[$symbol$ip]($style) ",
+            symbol: "🌐 ",
+            style: "bold blue",
+            url: "https://api.ipify.org",
+            cache_duration: 300,
+            disabled: true,
+        }
+    }
+}
@@ -17,6 +17,7 @@ pub struct PythonConfig<'a> {
     pub version_format: &'a str,
     pub style: &'a str,
     pub symbol: &'a str,
+    pub uv_symbol: &'a str,
     pub disabled: bool,
     pub detect_extensions: Vec<&'a str>,
     pub detect_files: Vec<&'a str>,
@@ -33,6 +34,7 @@ impl<'a> Default for PythonConfig<'a> {
             version_format: "v${raw}",
             style: "yellow bold",
             symbol: "🐍 ",
+            uv_symbol: "uv ",
             disabled: false,
             detect_extensions: vec!["py"],
             detect_files: vec![
@@ -43,6 +45,7 @@ impl<'a> Default for PythonConfig<'a> {
                 "tox.ini",
                 "setup.py",
                 "__init__.py",
+                "uv.lock",
             ],
             detect_folders: vec![],
         }
@@ -12,6 +12,7 @@ pub struct ShLvlConfig<'a> {
     pub format: &'a str,
     pub symbol: &'a str,
     pub repeat: bool,
+    pub auto_baseline: bool,
     pub style: &'a str,
     pub disabled: bool,
 }
@@ -23,6 +24,7 @@ impl<'a> Default for ShLvlConfig<'a> {
             format: "[$symbol$shlvl]($style) ",
             symbol: "↕️  ", // extra space for emoji
             repeat: false,
+            auto_baseline: false,
             style: "bold yellow",
             disabled: true,
         }
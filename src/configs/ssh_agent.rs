@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct SshAgentConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub forwarded_style: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> Default for SshAgentConfig<'a> {
+    fn default() -> Self {
+        SshAgentConfig {
+            format: "[$symbol$keys]($style) ",
+            symbol: "🔑 ",
+            style: "bold green",
+            forwarded_style: "bold yellow",
+            disabled: true,
+        }
+    }
+}
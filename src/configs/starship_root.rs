@@ -15,9 +15,25 @@ pub struct StarshipRootConfig {
     pub format: String,
     pub right_format: String,
     pub continuation_prompt: String,
+    pub continuation_format: String,
     pub scan_timeout: u64,
     pub command_timeout: u64,
     pub add_newline: bool,
+    pub separator: String,
+    pub separator_style: String,
+    pub separator_overrides: IndexMap<String, String>,
+    pub min_contrast: f64,
+    pub contrast_action: ContrastAction,
+    pub terminal_background: String,
+    pub show_module_errors: bool,
+    pub module_error_format: String,
+    pub module_error_log_interval: u64,
+    pub user_vars: bool,
+    pub directory_rules: Vec<DirectoryRule>,
+    pub compact: bool,
+    pub instant_prompt: bool,
+    pub escape_audit: EscapeAuditMode,
+    pub logging: LoggingConfig,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub palette: Option<String>,
     pub palettes: HashMap<String, Palette>,
@@ -26,6 +42,94 @@ pub struct StarshipRootConfig {
 
 pub type Palette = HashMap<String, String>;
 
+/// What to do with text below `min_contrast`
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum ContrastAction {
+    /// Change the foreground color until the contrast is high enough
+    #[default]
+    Adjust,
+    /// Only log a warning
+    Warn,
+}
+
+/// What to do with escape sequences that the shell may measure incorrectly
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum EscapeAuditMode {
+    /// Don't check the prompt
+    #[default]
+    Off,
+    /// Log a warning for each problem
+    Warn,
+    /// Log a warning and wrap the sequences so the shell doesn't count them
+    Fix,
+}
+
+/// Modules to show or hide whenever the current directory is within `path`
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct DirectoryRule {
+    pub path: String,
+    pub show: Vec<String>,
+    pub hide: Vec<String>,
+}
+
+/// Log levels for starship and for single modules, and the format of the log file
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct LoggingConfig {
+    pub level: LogLevel,
+    pub modules: IndexMap<String, LogLevel>,
+    pub format: LogFormat,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: LogLevel::Warn,
+            modules: IndexMap::default(),
+            format: LogFormat::Text,
+        }
+    }
+}
+
+/// The minimum level of the messages that are logged
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    #[default]
+    Warn,
+    Error,
+}
+
+/// The format of the lines written to the log file
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "config-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Text,
+    /// One JSON object per line, with the `level`, `module` and `message` of the record
+    Json,
+}
+
 // List of default prompt order
 // NOTE: If this const value is changed then Default prompt order subheading inside
 // prompt heading of config docs needs to be updated according to changes made here.
@@ -33,6 +137,10 @@ pub const PROMPT_ORDER: &[&str] = &[
     "username",
     "hostname",
     "localip",
+    "public_ip",
+    "network",
+    "multiplexer",
+    "ssh_agent",
     "shlvl",
     "singularity",
     "kubernetes",
@@ -44,12 +152,15 @@ pub const PROMPT_ORDER: &[&str] = &[
     "git_state",
     "git_metrics",
     "git_status",
+    "git_signing",
     "hg_branch",
     "pijul_channel",
     "docker_context",
     "package",
     // ↓ Toolchain version modules ↓
     // (Let's keep these sorted alphabetically)
+    "bazel",
+    "buck2",
     "bun",
     "c",
     "cmake",
@@ -93,13 +204,17 @@ pub const PROMPT_ORDER: &[&str] = &[
     "vagrant",
     "zig",
     // ↑ Toolchain version modules ↑
+    "tool_versions",
     "buf",
     "guix_shell",
     "nix_shell",
+    "dev_env",
     "conda",
     "meson",
     "spack",
     "memory_usage",
+    "disk_usage",
+    "load_average",
     "aws",
     "gcloud",
     "openstack",
@@ -108,6 +223,11 @@ pub const PROMPT_ORDER: &[&str] = &[
     "crystal",
     "custom",
     "sudo",
+    "keyboard_layout",
+    "volume",
+    "do_not_disturb",
+    "clipboard",
+    "cmd_counter",
     "cmd_duration",
     "line_break",
     "jobs",
@@ -121,6 +241,27 @@ pub const PROMPT_ORDER: &[&str] = &[
     "character",
 ];
 
+impl StarshipRootConfig {
+    /// Whether separators should be placed between modules
+    pub fn has_separators(&self) -> bool {
+        !self.separator.is_empty() || !self.separator_overrides.is_empty()
+    }
+
+    /// Returns the separator to place between two adjacent modules.
+    ///
+    /// Overrides are looked up as `left:right`, then `left:*` and finally `*:right`.
+    pub fn separator_between(&self, left: &str, right: &str) -> &str {
+        [
+            format!("{left}:{right}"),
+            format!("{left}:*"),
+            format!("*:{right}"),
+        ]
+        .iter()
+        .find_map(|key| self.separator_overrides.get(key))
+        .unwrap_or(&self.separator)
+    }
+}
+
 // On changes please also update `Default` for the `FullConfig` struct in `mod.rs`
 impl Default for StarshipRootConfig {
     fn default() -> Self {
@@ -129,10 +270,26 @@ impl Default for StarshipRootConfig {
             format: "$all".to_string(),
             right_format: String::new(),
             continuation_prompt: "[∙](bright-black) ".to_string(),
+            continuation_format: String::new(),
             profiles: Default::default(),
             scan_timeout: 30,
             command_timeout: 500,
             add_newline: true,
+            separator: String::new(),
+            separator_style: String::new(),
+            separator_overrides: Default::default(),
+            min_contrast: 0.0,
+            contrast_action: ContrastAction::Adjust,
+            terminal_background: String::new(),
+            show_module_errors: false,
+            module_error_format: "[⚠ $module](bold red) ".to_string(),
+            module_error_log_interval: 300,
+            user_vars: false,
+            directory_rules: Vec::new(),
+            compact: false,
+            instant_prompt: false,
+            escape_audit: EscapeAuditMode::Off,
+            logging: LoggingConfig::default(),
             palette: None,
             palettes: HashMap::default(),
         }
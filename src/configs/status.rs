@@ -1,3 +1,4 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -23,6 +24,8 @@ pub struct StatusConfig<'a> {
     pub pipestatus_format: &'a str,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pipestatus_segment_format: Option<&'a str>,
+    #[serde(borrow)]
+    pub map: IndexMap<String, StatusMapConfig<'a>>,
     pub disabled: bool,
 }
 
@@ -44,7 +47,22 @@ impl<'a> Default for StatusConfig<'a> {
             pipestatus_format:
                 "\\[$pipestatus\\] => [$symbol$common_meaning$signal_name$maybe_int]($style)",
             pipestatus_segment_format: None,
+            map: IndexMap::new(),
             disabled: true,
         }
     }
 }
+
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct StatusMapConfig<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<&'a str>,
+}
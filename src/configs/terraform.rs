@@ -11,6 +11,7 @@ pub struct TerraformConfig<'a> {
     pub format: &'a str,
     pub version_format: &'a str,
     pub symbol: &'a str,
+    pub tofu_symbol: &'a str,
     pub style: &'a str,
     pub disabled: bool,
     pub detect_extensions: Vec<&'a str>,
@@ -24,6 +25,7 @@ impl<'a> Default for TerraformConfig<'a> {
             format: "via [$symbol$workspace]($style) ",
             version_format: "v${raw}",
             symbol: "💠 ",
+            tofu_symbol: "🟨 ",
             style: "bold 105",
             disabled: false,
             detect_extensions: vec!["tf", "tfplan", "tfstate"],
@@ -1,3 +1,4 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -16,6 +17,10 @@ pub struct TimeConfig<'a> {
     pub disabled: bool,
     pub utc_time_offset: &'a str,
     pub time_range: &'a str,
+    pub time_zones: Vec<&'a str>,
+    pub time_zone_format: &'a str,
+    pub time_zone_separator: &'a str,
+    pub time_zone_labels: IndexMap<String, &'a str>,
 }
 
 impl<'a> Default for TimeConfig<'a> {
@@ -28,6 +33,10 @@ impl<'a> Default for TimeConfig<'a> {
             disabled: true,
             utc_time_offset: "local",
             time_range: "-",
+            time_zones: vec![],
+            time_zone_format: "$label $time",
+            time_zone_separator: " ",
+            time_zone_labels: IndexMap::new(),
         }
     }
 }
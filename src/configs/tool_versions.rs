@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct ToolVersionsConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub tools: Vec<&'a str>,
+    pub disabled: bool,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
+}
+
+impl<'a> Default for ToolVersionsConfig<'a> {
+    fn default() -> Self {
+        ToolVersionsConfig {
+            format: "via [$symbol$manager $versions]($style) ",
+            symbol: "🛠️ ",
+            style: "bold purple",
+            tools: vec![],
+            disabled: false,
+            detect_extensions: vec![],
+            detect_files: vec![
+                "mise.toml",
+                ".mise.toml",
+                "mise.local.toml",
+                ".mise.local.toml",
+                ".tool-versions",
+            ],
+            detect_folders: vec![],
+        }
+    }
+}
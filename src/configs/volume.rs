@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Deserialize, Serialize)]
+#[cfg_attr(
+    feature = "config-schema",
+    derive(schemars::JsonSchema),
+    schemars(deny_unknown_fields)
+)]
+#[serde(default)]
+pub struct VolumeConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub muted_symbol: &'a str,
+    pub style: &'a str,
+    pub muted_style: &'a str,
+    pub cache_duration: u64,
+    pub disabled: bool,
+}
+
+impl<'a> Default for VolumeConfig<'a> {
+    fn default() -> Self {
+        VolumeConfig {
+            format: "[$symbol$volume%]($style) ",
+            symbol: "🔊 ",
+            muted_symbol: "🔇 ",
+            style: "bold white",
+            muted_style: "bold red",
+            cache_duration: 5,
+            disabled: true,
+        }
+    }
+}
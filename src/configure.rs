@@ -4,12 +4,14 @@ use std::str::FromStr;
 
 use crate::config::ModuleConfig;
 use crate::config::StarshipConfig;
+use crate::config_validation;
 use crate::configs::PROMPT_ORDER;
 use crate::context::Context;
+use crate::shadow;
 use crate::utils;
 use std::fs::File;
 use std::io::Write;
-use toml_edit::Document;
+use toml_edit::{Document, Item, TableLike};
 
 #[cfg(not(windows))]
 const STD_EDITOR: &str = "vi";
@@ -187,6 +189,38 @@ pub fn toggle_configuration(context: &Context, name: &str, key: &str) {
     }
 }
 
+pub fn toggle_compact(context: &Context) {
+    let mut doc = get_configuration_edit(context);
+
+    match handle_toggle_compact(&mut doc) {
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+        _ => write_configuration(context, &doc),
+    }
+}
+
+fn handle_toggle_compact(doc: &mut Document) -> Result<(), String> {
+    let table = doc.as_table_mut();
+
+    let Some(old_value) = table.get("compact") else {
+        table.insert("compact", toml_edit::value(true));
+        return Ok(());
+    };
+
+    let old = old_value
+        .as_bool()
+        .ok_or_else(|| "Config key 'compact' must be in 'boolean' format".to_owned())?;
+
+    let mut new_value = toml_edit::value(!old);
+    // Above code already checks if it is a value (bool)
+    *new_value.as_value_mut().unwrap().decor_mut() = old_value.as_value().unwrap().decor().clone();
+
+    table.insert("compact", new_value);
+    Ok(())
+}
+
 fn handle_toggle_configuration(doc: &mut Document, name: &str, key: &str) -> Result<(), String> {
     if name.is_empty() || key.is_empty() {
         return Err("Empty table keys are not supported".to_owned());
@@ -216,6 +250,198 @@ fn handle_toggle_configuration(doc: &mut Document, name: &str, key: &str) -> Res
     Ok(())
 }
 
+/// A setting of a preset, compared with the configuration it's merged into
+#[derive(Debug, PartialEq, Eq)]
+enum PresetChange {
+    /// The configuration doesn't have the setting, so the value of the preset is added
+    Added { key: String, value: String },
+    /// The configuration sets its own value, which is kept instead of the preset's
+    Kept {
+        key: String,
+        value: String,
+        preset_value: String,
+    },
+}
+
+/// Merges a preset into the configuration, keeping every setting the user has already made.
+///
+/// With `show_diff`, the changes are only printed and the configuration isn't written.
+pub fn apply_preset(context: &Context, name: &str, show_diff: bool) {
+    let preset = std::str::from_utf8(shadow::get_preset_content(name))
+        .ok()
+        .and_then(|content| content.parse::<Document>().ok())
+        .unwrap_or_else(|| {
+            eprintln!("Preset '{name}' is not a valid configuration");
+            process::exit(1);
+        });
+    let mut doc = get_configuration_edit(context);
+    let changes = handle_apply_preset(&mut doc, &preset);
+
+    if show_diff {
+        for change in &changes {
+            match change {
+                PresetChange::Added { key, value } => println!("+ {key} = {value}"),
+                PresetChange::Kept {
+                    key,
+                    value,
+                    preset_value,
+                } => println!("  {key} = {value} (keeping your value instead of {preset_value})"),
+            }
+        }
+        return;
+    }
+
+    let added = changes
+        .iter()
+        .filter(|change| matches!(change, PresetChange::Added { .. }))
+        .count();
+    if added == 0 {
+        println!("Your configuration already has every setting of the preset '{name}'");
+        return;
+    }
+
+    if let Some(backup_path) = backup_configuration(context) {
+        println!("Saved a backup of your configuration to {backup_path:?}");
+    }
+    write_configuration(context, &doc);
+    println!("Added {added} settings of the preset '{name}' to your configuration");
+}
+
+fn handle_apply_preset(doc: &mut Document, preset: &Document) -> Vec<PresetChange> {
+    // New tables are placed after the existing ones, in the order of the preset
+    let mut next_position = max_table_position(doc.as_table()) + 1;
+    let mut changes = Vec::new();
+    merge_preset_table(
+        doc.as_table_mut(),
+        preset.as_table(),
+        "",
+        &mut next_position,
+        &mut changes,
+    );
+    changes
+}
+
+fn merge_preset_table(
+    table: &mut dyn TableLike,
+    preset: &toml_edit::Table,
+    path: &str,
+    next_position: &mut usize,
+    changes: &mut Vec<PresetChange>,
+) {
+    for (key, preset_item) in preset.iter() {
+        let key_path = if path.is_empty() {
+            key.to_owned()
+        } else {
+            format!("{path}.{key}")
+        };
+
+        if let Some(preset_table) = preset_item.as_table() {
+            if !table.contains_key(key) {
+                let mut new_table = toml_edit::Table::new();
+                new_table.set_implicit(preset_table.is_implicit());
+                new_table.set_position(*next_position);
+                *next_position += 1;
+                table.insert(key, Item::Table(new_table));
+            }
+            if let Some(table) = table.get_mut(key).and_then(Item::as_table_like_mut) {
+                merge_preset_table(table, preset_table, &key_path, next_position, changes);
+                continue;
+            }
+        }
+
+        let preset_value = preset_item.to_string().trim().to_owned();
+        match table.get(key) {
+            None => {
+                table.insert(key, preset_item.clone());
+                changes.push(PresetChange::Added {
+                    key: key_path,
+                    value: preset_value,
+                });
+            }
+            Some(item) => {
+                let value = item.to_string().trim().to_owned();
+                if value != preset_value {
+                    changes.push(PresetChange::Kept {
+                        key: key_path,
+                        value,
+                        preset_value,
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn max_table_position(table: &toml_edit::Table) -> usize {
+    table
+        .iter()
+        .filter_map(|(_, item)| item.as_table())
+        .map(|table| {
+            table
+                .position()
+                .unwrap_or_default()
+                .max(max_table_position(table))
+        })
+        .max()
+        .unwrap_or_default()
+}
+
+/// Copies the configuration file next to itself, named after the current time so earlier backups
+/// are kept, and returns the path of the copy
+fn backup_configuration(context: &Context) -> Option<std::path::PathBuf> {
+    let config_path = context.get_config_path_os()?;
+    if !std::path::Path::new(&config_path).exists() {
+        return None;
+    }
+
+    let mut backup_path = config_path.clone();
+    backup_path.push(
+        chrono::Local::now()
+            .format(".%Y%m%d-%H%M%S.bak")
+            .to_string(),
+    );
+    let backup_path = std::path::PathBuf::from(backup_path);
+    // An existing backup is never overwritten
+    let result = File::open(&config_path).and_then(|mut config| {
+        let mut backup = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&backup_path)?;
+        std::io::copy(&mut config, &mut backup)
+    });
+    if let Err(err) = result {
+        eprintln!("Unable to back up the configuration to {backup_path:?}: {err}");
+        process::exit(1);
+    }
+    Some(backup_path)
+}
+
+/// Prints the problems found in the configuration file and exits with an error if there are any
+pub fn validate_configuration(context: &Context) {
+    let config_path = context.get_config_path_os().unwrap_or_else(|| {
+        eprintln!("config path required to validate configuration");
+        process::exit(1);
+    });
+    let display_path = config_path.to_string_lossy().into_owned();
+    let Some(content) = StarshipConfig::read_config_content_as_str(&Some(config_path)) else {
+        eprintln!("Unable to read the configuration file {display_path}");
+        process::exit(1);
+    };
+
+    let issues = config_validation::validate(&content);
+    if issues.is_empty() {
+        println!("No problems found in {display_path}");
+        return;
+    }
+    for issue in &issues {
+        match issue.location {
+            Some(_) => println!("{display_path}:{issue}"),
+            None => println!("{display_path}: {issue}"),
+        }
+    }
+    process::exit(1);
+}
+
 pub fn get_configuration(context: &Context) -> toml::Table {
     let starship_config = StarshipConfig::initialize(&context.get_config_path_os());
 
@@ -460,6 +686,20 @@ mod tests {
         assert_eq!(doc.to_string(), new_config)
     }
 
+    #[test]
+    fn test_toggle_compact() {
+        let mut doc = create_doc();
+
+        handle_toggle_compact(&mut doc).unwrap();
+        assert!(doc["compact"].as_bool().unwrap());
+
+        handle_toggle_compact(&mut doc).unwrap();
+        assert!(!doc["compact"].as_bool().unwrap());
+
+        doc["compact"] = toml_edit::value("yes");
+        assert!(handle_toggle_compact(&mut doc).is_err());
+    }
+
     #[test]
     fn test_toggle_missing_module() {
         let mut doc = create_doc();
@@ -587,6 +827,96 @@ mod tests {
         dir.close()
     }
 
+    #[test]
+    fn apply_preset_keeps_existing_settings() {
+        let mut doc = concat!(
+            "format = \"$all\"\n",
+            "\n",
+            "[git_branch] # mine\n",
+            "symbol = \"git \"\n",
+        )
+        .parse::<Document>()
+        .unwrap();
+        let preset = concat!(
+            "format = \"$directory$character\"\n",
+            "add_newline = false\n",
+            "\n",
+            "[aws]\n",
+            "symbol = \"aws \"\n",
+            "\n",
+            "[git_branch]\n",
+            "symbol = \"git \"\n",
+            "style = \"bold\"\n",
+        )
+        .parse::<Document>()
+        .unwrap();
+
+        let changes = handle_apply_preset(&mut doc, &preset);
+
+        assert_eq!(
+            changes,
+            vec![
+                PresetChange::Kept {
+                    key: "format".to_owned(),
+                    value: "\"$all\"".to_owned(),
+                    preset_value: "\"$directory$character\"".to_owned(),
+                },
+                PresetChange::Added {
+                    key: "add_newline".to_owned(),
+                    value: "false".to_owned(),
+                },
+                PresetChange::Added {
+                    key: "aws.symbol".to_owned(),
+                    value: "\"aws \"".to_owned(),
+                },
+                PresetChange::Added {
+                    key: "git_branch.style".to_owned(),
+                    value: "\"bold\"".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(
+            doc.to_string(),
+            concat!(
+                "format = \"$all\"\n",
+                "add_newline = false\n",
+                "\n",
+                "[git_branch] # mine\n",
+                "symbol = \"git \"\n",
+                "style = \"bold\"\n",
+                "\n",
+                "[aws]\n",
+                "symbol = \"aws \"\n",
+            )
+        );
+    }
+
+    #[test]
+    fn apply_preset_writes_backup() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let context = setup_config(&dir, true, StarshipConfigEnvScenario::NotSpecified)?;
+        let config_dir = dir.path().join(".config");
+        let old_backup = config_dir.join("starship.toml.20000101-000000.bak");
+        std::fs::write(&old_backup, "old")?;
+        apply_preset(&context, "nerd-font-symbols", false);
+
+        let backups = std::fs::read_dir(&config_dir)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<io::Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|path| {
+                path.extension().map_or(false, |ext| ext == "bak") && *path != old_backup
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(utils::read_file(&backups[0])?, PRINT_CONFIG_HOME);
+        assert_eq!(utils::read_file(&old_backup)?, "old");
+        let doc = get_configuration_edit(&context);
+        assert!(doc["custom"]["home"].is_table());
+        assert!(doc["aws"]["symbol"].is_str());
+        dir.close()
+    }
+
     const PRINT_CONFIG_DEFAULT: &str = "[custom]";
     const PRINT_CONFIG_HOME: &str = "[custom.home]";
     const PRINT_CONFIG_ENV: &str = "[custom.STARSHIP_CONFIG]";
@@ -664,7 +994,7 @@ mod tests {
             }
         };
 
-        let mut env = Env::default();
+        let mut env = Env::mocked();
         if let Some(v) = env_starship_config {
             env.insert("STARSHIP_CONFIG", v.to_string_lossy().to_string());
         }
@@ -2,7 +2,11 @@ use crate::config::{ModuleConfig, StarshipConfig};
 use crate::configs::StarshipRootConfig;
 use crate::context_env::Env;
 use crate::module::Module;
-use crate::utils::{create_command, exec_timeout, read_file, CommandOutput, PathExt};
+use crate::session_state::SessionState;
+use crate::trace::{self, TraceKind};
+use crate::utils::{
+    create_command, exec_timeout, exec_timeout_with_failure, read_file, CommandOutput, PathExt,
+};
 
 use crate::modules;
 use crate::utils;
@@ -12,21 +16,49 @@ use gix::{
     state as git_state, Repository, ThreadSafeRepository,
 };
 use once_cell::sync::OnceCell;
-#[cfg(test)]
+use std::cell::Cell;
+#[cfg(any(test, feature = "testing"))]
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::env;
 use std::ffi::{OsStr, OsString};
-use std::fmt::Debug;
+use std::fmt::{Debug, Display};
 use std::fs;
 use std::marker::PhantomData;
 use std::num::ParseIntError;
 use std::path::{Path, PathBuf};
+use std::process::Stdio;
 use std::str::FromStr;
 use std::string::String;
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
 use terminal_size::terminal_size;
 
+thread_local! {
+    // The `command_timeout` of the module that is being rendered on this thread, if it sets one
+    static MODULE_COMMAND_TIMEOUT: Cell<Option<u64>> = const { Cell::new(None) };
+    // The width left on its line for the module that is being rendered on this thread, if known
+    static MODULE_WIDTH: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Restores the command timeout that applied before a module was rendered when dropped
+pub struct ModuleCommandTimeout(Option<u64>);
+
+impl Drop for ModuleCommandTimeout {
+    fn drop(&mut self) {
+        MODULE_COMMAND_TIMEOUT.with(|timeout| timeout.set(self.0));
+    }
+}
+
+/// Restores the width that applied before a module was rendered when dropped
+pub struct ModuleWidth(Option<usize>);
+
+impl Drop for ModuleWidth {
+    fn drop(&mut self) {
+        MODULE_WIDTH.with(|width| width.set(self.0));
+    }
+}
+
 /// Context contains data or common methods that may be used by multiple modules.
 /// The data contained within Context will be relevant to this particular rendering
 /// of the prompt.
@@ -51,6 +83,9 @@ pub struct Context<'a> {
     /// Private field to store Git information for modules who need it
     repo: OnceCell<Repo>,
 
+    /// Information about the operating system, detected on first use
+    pub os_info: OnceCell<os_info::Info>,
+
     /// The shell the user is assumed to be running
     pub shell: Shell,
 
@@ -64,12 +99,16 @@ pub struct Context<'a> {
     pub env: Env<'a>,
 
     /// A HashMap of command mocks
-    #[cfg(test)]
+    #[cfg(any(test, feature = "testing"))]
     pub cmd: HashMap<&'a str, Option<CommandOutput>>,
 
-    /// a mock of the root directory
-    #[cfg(test)]
-    pub root_dir: tempfile::TempDir,
+    /// A HashMap of mocks of commands that exit with an error
+    #[cfg(any(test, feature = "testing"))]
+    pub cmd_failures: HashMap<&'a str, CommandOutput>,
+
+    /// a mock of the root directory, only created for a mocked environment
+    #[cfg(any(test, feature = "testing"))]
+    pub root_dir: Option<tempfile::TempDir>,
 
     #[cfg(feature = "battery")]
     pub battery_info_provider: &'a (dyn crate::modules::BatteryInfoProvider + Send + Sync),
@@ -77,6 +116,15 @@ pub struct Context<'a> {
     /// Starship root config
     pub root_config: StarshipRootConfig,
 
+    /// Names of the modules that failed to render because of an error
+    module_errors: Mutex<HashSet<String>>,
+
+    /// Values stored for the current shell session, loaded on first use
+    session_state: OnceCell<Mutex<SessionState>>,
+
+    /// Values stored for all shells of the current terminal, loaded on first use
+    terminal_state: OnceCell<Mutex<SessionState>>,
+
     /// Avoid issues with unused lifetimes when features are disabled
     _marker: PhantomData<&'a ()>,
 }
@@ -157,29 +205,38 @@ impl<'a> Context<'a> {
             .config
             .as_ref()
             .map_or_else(StarshipRootConfig::default, StarshipRootConfig::load);
+        crate::logger::configure(&root_config.logging);
 
         let width = properties.terminal_width;
 
-        Context {
+        let mut context = Context {
             config,
             properties,
             current_dir,
             logical_dir,
             dir_contents: OnceCell::new(),
             repo: OnceCell::new(),
+            os_info: OnceCell::new(),
             shell,
             target,
             width,
+            #[cfg(any(test, feature = "testing"))]
+            root_dir: env.is_mocked().then(|| tempfile::TempDir::new().unwrap()),
             env,
-            #[cfg(test)]
-            root_dir: tempfile::TempDir::new().unwrap(),
-            #[cfg(test)]
+            #[cfg(any(test, feature = "testing"))]
             cmd: HashMap::new(),
+            #[cfg(any(test, feature = "testing"))]
+            cmd_failures: HashMap::new(),
             #[cfg(feature = "battery")]
             battery_info_provider: &crate::modules::BatteryInfoProviderImpl,
             root_config,
+            module_errors: Mutex::new(HashSet::new()),
+            session_state: OnceCell::new(),
+            terminal_state: OnceCell::new(),
             _marker: PhantomData,
-        }
+        };
+        context.apply_directory_rules();
+        context
     }
 
     /// Sets the context config, overwriting the existing config
@@ -188,9 +245,37 @@ impl<'a> Context<'a> {
         self.config = StarshipConfig {
             config: Some(config),
         };
+        self.apply_directory_rules();
         self
     }
 
+    /// Enables or disables modules in the config according to the `directory_rules`
+    /// that match the current directory. Later rules take precedence over earlier ones.
+    fn apply_directory_rules(&mut self) {
+        if self.root_config.directory_rules.is_empty() {
+            return;
+        }
+
+        let home = self.get_home();
+        let dirs = [self.logical_dir.as_path(), self.current_dir.as_path()];
+        let Some(config) = self.config.config.as_mut() else {
+            return;
+        };
+
+        for rule in &self.root_config.directory_rules {
+            if !directory_rule_matches(&rule.path, &dirs, home.as_deref()) {
+                continue;
+            }
+            log::debug!("Applying directory rule for {:?}", rule.path);
+            for module in &rule.show {
+                set_module_disabled(config, module, false);
+            }
+            for module in &rule.hide {
+                set_module_disabled(config, module, true);
+            }
+        }
+    }
+
     // Tries to retrieve home directory from a table in testing mode or else retrieves it from the os
     pub fn get_home(&self) -> Option<PathBuf> {
         home_dir(&self.env)
@@ -208,6 +293,18 @@ impl<'a> Context<'a> {
         self.env.get_env_os(key)
     }
 
+    // Checks whether any environment variable name starts with the prefix, e.g. `TOFU_`
+    #[inline]
+    pub fn has_env_prefix(&self, prefix: &str) -> bool {
+        self.env.has_prefix(prefix)
+    }
+
+    // Lists the names and values of all environment variables
+    #[inline]
+    pub fn get_env_vars(&self) -> Vec<(String, String)> {
+        self.env.vars()
+    }
+
     /// Convert a `~` in a path to the home directory
     pub fn expand_tilde(dir: PathBuf) -> PathBuf {
         if dir.starts_with("~") {
@@ -225,6 +322,16 @@ impl<'a> Context<'a> {
         Module::new(name, desc, config)
     }
 
+    /// Whether modules should only show their symbol.
+    /// `STARSHIP_COMPACT` takes precedence over the `compact` config option.
+    pub fn is_compact(&self) -> bool {
+        match self.get_env("STARSHIP_COMPACT").as_deref() {
+            Some("1" | "true") => true,
+            Some("0" | "false") => false,
+            _ => self.root_config.compact,
+        }
+    }
+
     /// Check if `disabled` option of the module is true in configuration file.
     pub fn is_module_disabled_in_config(&self, name: &str) -> bool {
         let config = self.config.get_module_config(name);
@@ -311,6 +418,7 @@ impl<'a> Context<'a> {
                 );
 
                 let branch = get_current_branch(&repository);
+                let head = repository.head().ok();
                 let remote = get_remote_repository_info(&repository, branch.as_deref());
                 let path = repository.path().to_path_buf();
                 Ok(Repo {
@@ -320,6 +428,8 @@ impl<'a> Context<'a> {
                     path,
                     state: repository.state(),
                     remote,
+                    head_id: head.as_ref().and_then(gix::Head::id).map(gix::Id::detach),
+                    is_detached: head.as_ref().map_or(false, gix::Head::is_detached),
                 })
             })
     }
@@ -331,6 +441,11 @@ impl<'a> Context<'a> {
         })
     }
 
+    /// Returns information about the operating system, detecting it on first use
+    pub fn os_info(&self) -> &os_info::Info {
+        self.os_info.get_or_init(os_info::get)
+    }
+
     fn get_shell() -> Shell {
         let shell = env::var("STARSHIP_SHELL").unwrap_or_default();
         match shell.as_str() {
@@ -368,24 +483,124 @@ impl<'a> Context<'a> {
             cmd,
             args
         );
-        #[cfg(test)]
-        {
-            let command = crate::utils::display_command(&cmd, args);
-            if let Some(output) = self
-                .cmd
-                .get(command.as_str())
-                .cloned()
-                .or_else(|| crate::utils::mock_cmd(&cmd, args))
-            {
-                return output;
-            }
+        #[cfg(any(test, feature = "testing"))]
+        if let Some(output) = self.mocked_cmd(&cmd, args) {
+            return output?.ok();
         }
         let mut cmd = create_command(cmd).ok()?;
         cmd.args(args).current_dir(&self.current_dir);
-        exec_timeout(
-            &mut cmd,
-            Duration::from_millis(self.root_config.command_timeout),
-        )
+        exec_timeout(&mut cmd, self.command_timeout())
+    }
+
+    /// Execute a command with its own time limit instead of `command_timeout`. The output of a
+    /// command that exited with an error is returned as `Err`, so that the caller can tell why
+    /// it failed. `None` means that the command couldn't be run or timed out.
+    pub fn exec_cmd_with_timeout<T: AsRef<OsStr> + Debug, U: AsRef<OsStr> + Debug>(
+        &self,
+        cmd: T,
+        args: &[U],
+        time_limit: Duration,
+    ) -> Option<Result<CommandOutput, CommandOutput>> {
+        log::trace!(
+            "Executing command {:?} with args {:?} from context",
+            cmd,
+            args
+        );
+        #[cfg(any(test, feature = "testing"))]
+        if let Some(output) = self.mocked_cmd(&cmd, args) {
+            return output;
+        }
+        let mut cmd = create_command(cmd).ok()?;
+        cmd.args(args).current_dir(&self.current_dir);
+        exec_timeout_with_failure(&mut cmd, time_limit)
+    }
+
+    /// Looks a command up in the mocks of a mocked context
+    #[cfg(any(test, feature = "testing"))]
+    fn mocked_cmd<T: AsRef<OsStr> + Debug, U: AsRef<OsStr> + Debug>(
+        &self,
+        cmd: &T,
+        args: &[U],
+    ) -> Option<Option<Result<CommandOutput, CommandOutput>>> {
+        if !self.env.is_mocked() {
+            return None;
+        }
+        let command = crate::utils::display_command(cmd, args);
+        if let Some(failure) = self.cmd_failures.get(command.as_str()) {
+            return Some(Some(Err(failure.clone())));
+        }
+        let output = self.cmd.get(command.as_str()).cloned();
+        #[cfg(test)]
+        let output = output.or_else(|| crate::utils::mock_cmd(cmd, args));
+        output.map(|output| output.map(Ok))
+    }
+
+    /// Starts a command in the background without waiting for it, e.g. to refresh a cache.
+    /// Returns whether the command could be started.
+    pub fn spawn_cmd<T: AsRef<OsStr> + Debug, U: AsRef<OsStr> + Debug>(
+        &self,
+        cmd: T,
+        args: &[U],
+        envs: &[(&str, &str)],
+    ) -> bool {
+        log::trace!(
+            "Spawning command {:?} with args {:?} from context",
+            cmd,
+            args
+        );
+        let start = Instant::now();
+        let command = crate::utils::display_command(&cmd, args);
+        #[cfg(any(test, feature = "testing"))]
+        if self.env.is_mocked() {
+            if let Some(output) = self.cmd.get(command.as_str()) {
+                return output.is_some();
+            }
+        }
+        let spawned = create_command(cmd).and_then(|mut cmd| {
+            cmd.args(args)
+                .envs(envs.iter().copied())
+                .current_dir(&self.current_dir)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+        });
+        if let Err(error) = &spawned {
+            log::debug!("Unable to spawn {:?}: {}", command, error);
+        }
+        trace::record(TraceKind::Command, || command, start, spawned.is_ok());
+        spawned.is_ok()
+    }
+
+    /// Applies the `command_timeout` of a module's config, which overrides the global one,
+    /// to the commands run on this thread until the returned value is dropped
+    pub fn module_command_timeout(&self, module: &str) -> ModuleCommandTimeout {
+        let mut path = module.split('.').collect::<Vec<_>>();
+        path.push("command_timeout");
+        let timeout = self
+            .config
+            .get_config(&path)
+            .and_then(toml::Value::as_integer)
+            .and_then(|timeout| u64::try_from(timeout).ok());
+        ModuleCommandTimeout(MODULE_COMMAND_TIMEOUT.with(|current| current.replace(timeout)))
+    }
+
+    /// Limits the width available to the modules rendered on this thread until the returned
+    /// value is dropped
+    pub fn limit_module_width(&self, width: usize) -> ModuleWidth {
+        ModuleWidth(MODULE_WIDTH.with(|current| current.replace(Some(width))))
+    }
+
+    /// The width available to the module that is being rendered on this thread, which is the
+    /// terminal width unless the other modules on its line are known
+    pub fn module_width(&self) -> usize {
+        MODULE_WIDTH.with(Cell::get).unwrap_or(self.width)
+    }
+
+    /// The timeout for commands run by the module that is being rendered on this thread
+    pub fn command_timeout(&self) -> Duration {
+        let timeout = MODULE_COMMAND_TIMEOUT.with(Cell::get);
+        Duration::from_millis(timeout.unwrap_or(self.root_config.command_timeout))
     }
 
     /// Attempt to execute several commands with `exec_cmd`, return the results of the first that works
@@ -395,6 +610,112 @@ impl<'a> Context<'a> {
             .find_map(|attempt| self.exec_cmd(attempt[0], &attempt[1..]))
     }
 
+    /// Logs an error that prevented a module from rendering and remembers it,
+    /// so that the module can be replaced by an error badge
+    pub fn report_module_error(&self, module: &str, error: &dyn Display) {
+        if self.should_log_module_error(module) {
+            log::warn!("Error in module `{}`:\n{}", module, error);
+        }
+        if let Ok(mut module_errors) = self.module_errors.lock() {
+            module_errors.insert(module.to_string());
+        }
+    }
+
+    /// Whether the module reported an error during this rendering
+    pub fn has_module_error(&self, module: &str) -> bool {
+        self.module_errors
+            .lock()
+            .map_or(false, |module_errors| module_errors.contains(module))
+    }
+
+    /// With `show_module_errors`, errors of a module are logged at most once per
+    /// `module_error_log_interval`, tracked across prompts by the modification time of a marker file
+    fn should_log_module_error(&self, module: &str) -> bool {
+        let interval = self.root_config.module_error_log_interval;
+        if !self.root_config.show_module_errors || interval == 0 {
+            return true;
+        }
+
+        let marker = utils::cache_path(self, "module_errors").join(module);
+        let logged_recently = fs::metadata(&marker)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .map_or(false, |elapsed| elapsed.as_secs() < interval);
+        if logged_recently {
+            return false;
+        }
+
+        if let Some(dir) = marker.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        if let Err(error) = fs::write(&marker, "") {
+            log::debug!(
+                "Unable to write module error marker {:?}: {}",
+                marker,
+                error
+            );
+        }
+        true
+    }
+
+    fn session_state(&self) -> &Mutex<SessionState> {
+        self.session_state.get_or_init(|| {
+            let session_key = self.get_env("STARSHIP_SESSION_KEY");
+            let sessions_dir = utils::cache_path(self, "sessions");
+            Mutex::new(SessionState::load(&sessions_dir, session_key.as_deref()))
+        })
+    }
+
+    /// Every shell gets its own session key, so the shells of a terminal are told apart by the
+    /// id of their process session instead, which is shared by all shells started in it
+    fn terminal_state(&self) -> &Mutex<SessionState> {
+        self.terminal_state.get_or_init(|| {
+            #[cfg(unix)]
+            let terminal_key = nix::unistd::getsid(None)
+                .ok()
+                .map(|sid| format!("terminal-{sid}"));
+            #[cfg(not(unix))]
+            let terminal_key: Option<String> = None;
+            let sessions_dir = utils::cache_path(self, "sessions");
+            Mutex::new(SessionState::load(&sessions_dir, terminal_key.as_deref()))
+        })
+    }
+
+    /// Returns the value that was stored under `key` for the current shell session
+    pub fn get_session_value(&self, key: &str) -> Option<String> {
+        let state = self.session_state().lock().ok()?;
+        state.get(key).map(ToString::to_string)
+    }
+
+    /// Stores a value for the current shell session, which later prompts of the session can read
+    pub fn set_session_value(&self, key: &str, value: &str) {
+        if let Ok(mut state) = self.session_state().lock() {
+            state.set(key, value);
+        }
+    }
+
+    /// Removes a value stored for the current shell session
+    pub fn remove_session_value(&self, key: &str) {
+        if let Ok(mut state) = self.session_state().lock() {
+            state.remove(key);
+        }
+    }
+
+    /// Returns the value that was stored under `key` by any shell of the current terminal.
+    /// Values are only stored on unix, where terminals can be told apart.
+    pub fn get_terminal_value(&self, key: &str) -> Option<String> {
+        let state = self.terminal_state().lock().ok()?;
+        state.get(key).map(ToString::to_string)
+    }
+
+    /// Stores a value for the current terminal, which all of its shells can read
+    pub fn set_terminal_value(&self, key: &str, value: &str) {
+        if let Ok(mut state) = self.terminal_state().lock() {
+            state.set(key, value);
+        }
+    }
+
     /// Returns the string contents of a file from the current working directory
     pub fn read_file_from_pwd(&self, file_name: &str) -> Option<String> {
         if !self.try_begin_scan()?.set_files(&[file_name]).is_match() {
@@ -418,8 +739,63 @@ impl Default for Context<'_> {
     }
 }
 
+/// Checks whether any of the directories, or one of their parents, matches the glob pattern.
+///
+/// `*` and `?` match within a single path component, `**` matches across components
+/// and a leading `~` is expanded to the home directory.
+fn directory_rule_matches(pattern: &str, dirs: &[&Path], home: Option<&Path>) -> bool {
+    let pattern = match (pattern.strip_prefix('~'), home) {
+        (Some(rest), Some(home)) => format!("{}{rest}", home.to_string_lossy()),
+        (Some(_), None) => return false,
+        (None, _) => pattern.to_string(),
+    };
+    let to_slashes = |path: &str| path.replace('\\', "/");
+    let pattern = to_slashes(&pattern);
+    let pattern = pattern.trim_end_matches('/');
+
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+
+    let Ok(regex) = regex::Regex::new(&regex) else {
+        log::warn!("Invalid directory rule path {:?}", pattern);
+        return false;
+    };
+
+    dirs.iter()
+        .flat_map(|dir| dir.ancestors())
+        .any(|dir| regex.is_match(&to_slashes(&dir.to_string_lossy())))
+}
+
+/// Sets `disabled` in the config table of the module, creating the table if needed
+fn set_module_disabled(config: &mut toml::Table, module: &str, disabled: bool) {
+    let mut table = config;
+    for key in module.split('.') {
+        let value = table
+            .entry(key)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        let Some(next) = value.as_table_mut() else {
+            log::warn!("Unable to apply directory rule, `{module}` is not a table");
+            return;
+        };
+        table = next;
+    }
+    table.insert("disabled".to_string(), toml::Value::Boolean(disabled));
+}
+
 fn home_dir(env: &Env) -> Option<PathBuf> {
-    if cfg!(test) {
+    if env.is_mocked() {
         if let Some(home) = env.get_env("HOME") {
             return Some(PathBuf::from(home));
         }
@@ -447,11 +823,6 @@ pub struct DirContents {
 }
 
 impl DirContents {
-    #[cfg(test)]
-    fn from_path(base: &Path) -> Result<Self, std::io::Error> {
-        Self::from_path_with_timeout(base, Duration::from_secs(30))
-    }
-
     fn from_path_with_timeout(base: &Path, timeout: Duration) -> Result<Self, std::io::Error> {
         let start = Instant::now();
 
@@ -463,8 +834,7 @@ impl DirContents {
         fs::read_dir(base)?
             .enumerate()
             .take_while(|(n, _)| {
-                cfg!(test) // ignore timeout during tests
-                || n & 0xFF != 0 // only check timeout once every 2^8 entries
+                n & 0xFF != 0 // only check timeout once every 2^8 entries
                 || start.elapsed() < timeout
             })
             .filter_map(|(_, entry)| entry.ok())
@@ -589,10 +959,19 @@ pub struct Repo {
 
     /// Remote repository
     pub remote: Option<Remote>,
+
+    /// The commit `HEAD` points to, unless the branch has no commits yet.
+    pub head_id: Option<gix::ObjectId>,
+
+    /// Whether `HEAD` points to a commit instead of a branch.
+    pub is_detached: bool,
 }
 
 impl Repo {
     /// Opens the associated git repository.
+    ///
+    /// The branch, `HEAD` and state are read when the repository is discovered, so modules that
+    /// only need those should use the fields instead of opening the repository again.
     pub fn open(&self) -> Repository {
         self.repo.to_thread_local()
     }
@@ -759,12 +1138,30 @@ pub struct Properties {
     /// The execution duration of the last command, in milliseconds
     #[clap(short = 'd', long)]
     pub cmd_duration: Option<String>,
+    /// The user CPU time of the last command, in clock ticks
+    #[clap(long)]
+    pub cmd_user_ticks: Option<String>,
+    /// The system CPU time of the last command, in clock ticks
+    #[clap(long)]
+    pub cmd_sys_ticks: Option<String>,
+    /// An identifier of the last command, which changes with every command that is run
+    #[clap(long)]
+    pub cmd_id: Option<String>,
     /// The keymap of fish/zsh/cmd
     #[clap(short = 'k', long, default_value = "viins")]
     pub keymap: String,
     /// The number of currently running jobs
     #[clap(short, long, default_value_t, value_parser=parse_jobs)]
     pub jobs: i64,
+    /// The commands of the currently running jobs, separated by newlines
+    #[clap(long, value_delimiter = '\n')]
+    pub job_names: Option<Vec<String>>,
+    /// The line of the command that the continuation prompt is shown for, starting at 2
+    #[clap(long)]
+    pub line_number: Option<usize>,
+    /// The constructs that are still open at the continuation prompt, e.g. `dquote cmdsubst`
+    #[clap(long)]
+    pub parser_state: Option<String>,
 }
 
 impl Default for Properties {
@@ -776,8 +1173,14 @@ impl Default for Properties {
             path: None,
             logical_path: None,
             cmd_duration: None,
+            cmd_user_ticks: None,
+            cmd_sys_ticks: None,
+            cmd_id: None,
             keymap: "viins".to_string(),
             jobs: 0,
+            job_names: None,
+            line_number: None,
+            parser_state: None,
         }
     }
 }
@@ -809,6 +1212,67 @@ mod tests {
     use crate::test::default_context;
     use std::io;
 
+    impl DirContents {
+        fn from_path(base: &Path) -> Result<Self, std::io::Error> {
+            Self::from_path_with_timeout(base, Duration::from_secs(30))
+        }
+    }
+
+    #[test]
+    fn module_errors_are_logged_once_per_interval() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut context = default_context().set_config(toml::toml! {
+            show_module_errors = true
+        });
+        context
+            .env
+            .insert("STARSHIP_CACHE", dir.path().to_string_lossy().to_string());
+
+        assert!(context.should_log_module_error("character"));
+        assert!(!context.should_log_module_error("character"));
+        assert!(context.should_log_module_error("directory"));
+        assert!(dir.path().join("module_errors").join("character").is_file());
+        dir.close()
+    }
+
+    #[test]
+    fn module_errors_without_badges_are_always_logged() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut context = default_context();
+        context
+            .env
+            .insert("STARSHIP_CACHE", dir.path().to_string_lossy().to_string());
+
+        assert!(context.should_log_module_error("character"));
+        assert!(context.should_log_module_error("character"));
+        assert!(!dir.path().join("module_errors").exists());
+        dir.close()
+    }
+
+    #[test]
+    fn module_command_timeout_overrides_global() {
+        let context = default_context().set_config(toml::toml! {
+            command_timeout = 100
+            [kubernetes]
+            command_timeout = 2000
+            [custom.slow]
+            command_timeout = 3000
+        });
+
+        assert_eq!(context.command_timeout(), Duration::from_millis(100));
+        {
+            let _timeout = context.module_command_timeout("kubernetes");
+            assert_eq!(context.command_timeout(), Duration::from_millis(2000));
+            {
+                let _timeout = context.module_command_timeout("git_status");
+                assert_eq!(context.command_timeout(), Duration::from_millis(100));
+            }
+            assert_eq!(context.command_timeout(), Duration::from_millis(2000));
+        }
+        let _timeout = context.module_command_timeout("custom.slow");
+        assert_eq!(context.command_timeout(), Duration::from_millis(3000));
+    }
+
     fn testdir(paths: &[&str]) -> Result<tempfile::TempDir, std::io::Error> {
         let dir = tempfile::tempdir()?;
         for path in paths {
@@ -939,7 +1403,7 @@ mod tests {
             Target::Main,
             test_path.clone(),
             test_path.clone(),
-            Default::default(),
+            Env::mocked(),
         );
 
         assert_ne!(context.current_dir, context.logical_dir);
@@ -964,7 +1428,7 @@ mod tests {
             Target::Main,
             test_path.clone(),
             test_path.clone(),
-            Default::default(),
+            Env::mocked(),
         );
 
         let expected_current_dir = &test_path;
@@ -986,7 +1450,7 @@ mod tests {
             Target::Main,
             test_path.clone(),
             test_path.clone(),
-            Default::default(),
+            Env::mocked(),
         );
 
         let expected_current_dir = home_dir()
@@ -1008,6 +1472,106 @@ mod tests {
         assert_ne!(context.config.config, mod_context.config.config);
     }
 
+    #[test]
+    fn job_names_are_split_by_line() {
+        let properties =
+            Properties::try_parse_from(["prompt", "--job-names=npm run watch &\nvim"]).unwrap();
+        assert_eq!(
+            properties.job_names,
+            Some(vec!["npm run watch &".to_string(), "vim".to_string()])
+        );
+    }
+
+    #[test]
+    fn compact_env_var_overrides_config() {
+        let mut context = default_context().set_config(toml::toml! {
+            compact = true
+        });
+        assert!(context.is_compact());
+
+        context.env.insert("STARSHIP_COMPACT", "0".into());
+        assert!(!context.is_compact());
+
+        let mut context = default_context();
+        context.env.insert("STARSHIP_COMPACT", "1".into());
+        assert!(context.is_compact());
+    }
+
+    fn context_in(dir: &Path, config: toml::Table) -> Context<'static> {
+        let mut context = default_context();
+        context.current_dir = dir.to_path_buf();
+        context.logical_dir = dir.to_path_buf();
+        context.set_config(config)
+    }
+
+    #[test]
+    fn directory_rules_show_and_hide_modules() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let infra = dir.path().join("infra/prod");
+        let config: toml::Table = toml::from_str(&format!(
+            r#"
+            [aws]
+            disabled = true
+
+            [[directory_rules]]
+            path = '{}/infra'
+            show = ["aws", "custom.deploy"]
+            hide = ["nodejs"]
+            "#,
+            dir.path().to_string_lossy()
+        ))
+        .unwrap();
+
+        let context = context_in(&infra, config.clone());
+        assert!(!context.is_module_disabled_in_config("aws"));
+        assert!(!context.is_module_disabled_in_config("custom.deploy"));
+        assert!(context.is_module_disabled_in_config("nodejs"));
+
+        let context = context_in(dir.path(), config);
+        assert!(context.is_module_disabled_in_config("aws"));
+        assert!(!context.is_module_disabled_in_config("nodejs"));
+        dir.close()
+    }
+
+    #[test]
+    fn directory_rules_later_rules_take_precedence() {
+        let context = context_in(
+            Path::new("/notes/work"),
+            toml::toml! {
+                [[directory_rules]]
+                path = "/notes"
+                hide = ["nodejs"]
+
+                [[directory_rules]]
+                path = "/notes/work"
+                show = ["nodejs"]
+            },
+        );
+        assert!(!context.is_module_disabled_in_config("nodejs"));
+    }
+
+    #[test]
+    fn directory_rule_glob_matching() {
+        let home = Path::new("/home/user");
+        let matches = |pattern: &str, dir: &str| {
+            directory_rule_matches(pattern, &[Path::new(dir)], Some(home))
+        };
+
+        assert!(matches("~/infra", "/home/user/infra"));
+        assert!(matches("~/infra", "/home/user/infra/modules"));
+        assert!(!matches("~/infra", "/home/user/infrastructure"));
+        assert!(matches("~/src/*-infra", "/home/user/src/team-infra/env"));
+        assert!(!matches("~/src/*", "/home/user/other"));
+        assert!(matches("/srv/**/deploy", "/srv/a/b/deploy"));
+        assert!(matches("/srv/?", "/srv/a"));
+        assert!(!matches("/srv/?", "/srv/ab"));
+        assert!(!directory_rule_matches(
+            "~/infra",
+            &[Path::new("/home/user/infra")],
+            None
+        ));
+    }
+
     #[cfg(windows)]
     #[test]
     fn strip_extended_path_prefix() {
@@ -1018,7 +1582,7 @@ mod tests {
             Target::Main,
             test_path.clone(),
             test_path,
-            Default::default(),
+            Env::mocked(),
         );
 
         let expected_path = Path::new(r"C:\");
@@ -1,48 +1,69 @@
-#[cfg(test)]
 use std::collections::HashMap;
-#[cfg(not(test))]
 use std::env;
 use std::ffi::OsString;
 
 #[derive(Default)]
 pub struct Env<'a> {
-    /// A HashMap of environment variable mocks
-    #[cfg(test)]
-    pub env: HashMap<&'a str, String>,
-
-    #[cfg(not(test))]
-    _marker: std::marker::PhantomData<&'a ()>,
+    /// A HashMap of environment variable mocks, or `None` to read the real environment
+    mocks: Option<HashMap<&'a str, String>>,
 }
 
 impl<'a> Env<'a> {
-    // Retrieves a environment variable from the os or from a table if in testing mode
-    #[cfg(test)]
-    pub fn get_env<K: AsRef<str>>(&self, key: K) -> Option<String> {
-        self.env
-            .get(key.as_ref())
-            .map(std::string::ToString::to_string)
+    /// Creates an environment that only contains the variables inserted into it
+    pub fn mocked() -> Self {
+        Self {
+            mocks: Some(HashMap::new()),
+        }
+    }
+
+    /// Whether this environment is a table of mocks rather than the real environment
+    pub fn is_mocked(&self) -> bool {
+        self.mocks.is_some()
     }
 
-    #[cfg(not(test))]
-    #[inline]
+    // Retrieves a environment variable from the os or from the table of mocks
     pub fn get_env<K: AsRef<str>>(&self, key: K) -> Option<String> {
-        env::var(key.as_ref()).ok()
+        match &self.mocks {
+            Some(mocks) => mocks.get(key.as_ref()).map(ToString::to_string),
+            None => env::var(key.as_ref()).ok(),
+        }
     }
 
-    // Retrieves a environment variable from the os or from a table if in testing mode (os version)
-    #[cfg(test)]
+    // Retrieves a environment variable from the os or from the table of mocks (os version)
     pub fn get_env_os<K: AsRef<str>>(&self, key: K) -> Option<OsString> {
-        self.env.get(key.as_ref()).map(OsString::from)
+        match &self.mocks {
+            Some(mocks) => mocks.get(key.as_ref()).map(OsString::from),
+            None => env::var_os(key.as_ref()),
+        }
     }
 
-    #[cfg(not(test))]
-    #[inline]
-    pub fn get_env_os<K: AsRef<str>>(&self, key: K) -> Option<OsString> {
-        env::var_os(key.as_ref())
+    // Checks whether any environment variable name starts with the prefix
+    pub fn has_prefix(&self, prefix: &str) -> bool {
+        match &self.mocks {
+            Some(mocks) => mocks.keys().any(|key| key.starts_with(prefix)),
+            None => env::vars_os()
+                .any(|(key, _)| key.to_str().map_or(false, |key| key.starts_with(prefix))),
+        }
+    }
+
+    // Lists the names and values of all environment variables that are valid unicode
+    pub fn vars(&self) -> Vec<(String, String)> {
+        match &self.mocks {
+            Some(mocks) => mocks
+                .iter()
+                .map(|(key, value)| ((*key).to_string(), value.clone()))
+                .collect(),
+            None => env::vars_os()
+                .filter_map(|(key, value)| {
+                    Some((key.into_string().ok()?, value.into_string().ok()?))
+                })
+                .collect(),
+        }
     }
 
-    #[cfg(test)]
+    /// Mocks an environment variable. Once a variable is mocked, the real environment
+    /// is no longer read.
     pub fn insert(&mut self, k: &'a str, v: String) -> Option<String> {
-        self.env.insert(k, v)
+        self.mocks.get_or_insert_with(HashMap::new).insert(k, v)
     }
 }
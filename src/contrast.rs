@@ -0,0 +1,246 @@
+use nu_ansi_term::{Color, Style};
+
+use crate::config::parse_style_string;
+use crate::configs::ContrastAction;
+use crate::context::Context;
+use crate::segment::Segment;
+
+// The xterm defaults for the 16 standard colors
+const BASE_COLORS: [(u8, u8, u8); 16] = [
+    (0x00, 0x00, 0x00),
+    (0xcd, 0x00, 0x00),
+    (0x00, 0xcd, 0x00),
+    (0xcd, 0xcd, 0x00),
+    (0x00, 0x00, 0xee),
+    (0xcd, 0x00, 0xcd),
+    (0x00, 0xcd, 0xcd),
+    (0xe5, 0xe5, 0xe5),
+    (0x7f, 0x7f, 0x7f),
+    (0xff, 0x00, 0x00),
+    (0x00, 0xff, 0x00),
+    (0xff, 0xff, 0x00),
+    (0x5c, 0x5c, 0xff),
+    (0xff, 0x00, 0xff),
+    (0x00, 0xff, 0xff),
+    (0xff, 0xff, 0xff),
+];
+
+/// Converts a terminal color to RGB, using the xterm defaults for the indexed colors
+pub fn color_to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    let rgb = match color {
+        Color::Black => BASE_COLORS[0],
+        Color::Red => BASE_COLORS[1],
+        Color::Green => BASE_COLORS[2],
+        Color::Yellow => BASE_COLORS[3],
+        Color::Blue => BASE_COLORS[4],
+        Color::Purple | Color::Magenta => BASE_COLORS[5],
+        Color::Cyan => BASE_COLORS[6],
+        Color::White => BASE_COLORS[7],
+        Color::DarkGray => BASE_COLORS[8],
+        Color::LightRed => BASE_COLORS[9],
+        Color::LightGreen => BASE_COLORS[10],
+        Color::LightYellow => BASE_COLORS[11],
+        Color::LightBlue => BASE_COLORS[12],
+        Color::LightPurple | Color::LightMagenta => BASE_COLORS[13],
+        Color::LightCyan => BASE_COLORS[14],
+        Color::LightGray => BASE_COLORS[15],
+        Color::Fixed(n @ 0..=15) => BASE_COLORS[n as usize],
+        Color::Fixed(n @ 16..=231) => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let n = n - 16;
+            (level(n / 36), level((n / 6) % 6), level(n % 6))
+        }
+        Color::Fixed(n) => {
+            let gray = 8 + (n - 232) * 10;
+            (gray, gray, gray)
+        }
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Default => return None,
+    };
+    Some(rgb)
+}
+
+/// The relative luminance of a color as defined by WCAG 2
+fn relative_luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    let channel = |c: u8| {
+        let c = f64::from(c) / 255.0;
+        if c <= 0.03928 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * channel(r) + 0.7152 * channel(g) + 0.0722 * channel(b)
+}
+
+/// The WCAG 2 contrast ratio between two colors, ranging from 1 to 21
+pub fn contrast_ratio(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Moves `fg` towards black or white, whichever contrasts more with `bg`,
+/// until the contrast ratio reaches `min_ratio`
+fn adjust_foreground(fg: (u8, u8, u8), bg: (u8, u8, u8), min_ratio: f64) -> (u8, u8, u8) {
+    let target = if contrast_ratio((0, 0, 0), bg) > contrast_ratio((255, 255, 255), bg) {
+        0.0
+    } else {
+        255.0
+    };
+    let mix = |c: u8, amount: f64| (f64::from(c) + (target - f64::from(c)) * amount).round() as u8;
+
+    (1..=20)
+        .map(|step| {
+            let amount = f64::from(step) / 20.0;
+            (mix(fg.0, amount), mix(fg.1, amount), mix(fg.2, amount))
+        })
+        .find(|&candidate| contrast_ratio(candidate, bg) >= min_ratio)
+        .unwrap_or((target as u8, target as u8, target as u8))
+}
+
+/// Checks the contrast between the foreground and background of every segment.
+///
+/// Segments without a background are checked against `terminal_background`, if set.
+/// Depending on `contrast_action`, segments below `min_contrast` either get their
+/// foreground adjusted or a warning is logged.
+pub fn enforce_contrast(segments: &mut [Segment], context: &Context) {
+    let config = &context.root_config;
+    if config.min_contrast <= 1.0 {
+        return;
+    }
+
+    let terminal_background =
+        parse_style_string(&format!("fg:{}", config.terminal_background), Some(context))
+            .and_then(|style| style.foreground)
+            .and_then(color_to_rgb);
+
+    for segment in segments.iter_mut() {
+        let Some(style) = segment.style() else {
+            continue;
+        };
+        if segment.value().trim().is_empty() {
+            continue;
+        }
+
+        let (fg, bg) = if style.is_reverse {
+            (style.background, style.foreground)
+        } else {
+            (style.foreground, style.background)
+        };
+        let Some(fg) = fg.and_then(color_to_rgb) else {
+            continue;
+        };
+        let Some(bg) = bg.and_then(color_to_rgb).or(terminal_background) else {
+            continue;
+        };
+
+        let ratio = contrast_ratio(fg, bg);
+        if ratio >= config.min_contrast {
+            continue;
+        }
+
+        if config.contrast_action == ContrastAction::Warn {
+            log::warn!(
+                "Low contrast ({:.2}:1) for {:?}, consider changing its style",
+                ratio,
+                segment.value()
+            );
+            continue;
+        }
+
+        let (r, g, b) = adjust_foreground(fg, bg, config.min_contrast);
+        let adjusted = Color::Rgb(r, g, b);
+        segment.set_style(if style.is_reverse {
+            Style {
+                background: Some(adjusted),
+                ..style
+            }
+        } else {
+            Style {
+                foreground: Some(adjusted),
+                ..style
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::default_context;
+
+    fn enforce(config: toml::Table, style: Style) -> Option<Style> {
+        let context = default_context().set_config(config);
+        let mut segments = Segment::from_text(Some(style), "text");
+        enforce_contrast(&mut segments, &context);
+        segments[0].style()
+    }
+
+    #[test]
+    fn contrast_ratio_extremes() {
+        assert!((contrast_ratio((0, 0, 0), (255, 255, 255)) - 21.0).abs() < 1e-9);
+        assert!((contrast_ratio((12, 34, 56), (12, 34, 56)) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let style = Color::Blue.on(Color::Black);
+        assert_eq!(enforce(toml::Table::new(), style), Some(style));
+    }
+
+    #[test]
+    fn adjusts_low_contrast_foreground() {
+        let style = Color::Blue.on(Color::Black).bold();
+        let actual = enforce(toml::toml! { min_contrast = 4.5 }, style).unwrap();
+
+        let Some(Color::Rgb(r, g, b)) = actual.foreground else {
+            panic!("Expected an adjusted RGB foreground");
+        };
+        assert!(contrast_ratio((r, g, b), (0, 0, 0)) >= 4.5);
+        assert_eq!(actual.background, Some(Color::Black));
+        assert!(actual.is_bold);
+    }
+
+    #[test]
+    fn keeps_sufficient_contrast() {
+        let style = Color::White.on(Color::Black);
+        assert_eq!(
+            enforce(toml::toml! { min_contrast = 4.5 }, style),
+            Some(style)
+        );
+    }
+
+    #[test]
+    fn uses_terminal_background() {
+        let style = Color::Yellow.normal();
+        assert_eq!(
+            enforce(toml::toml! { min_contrast = 4.5 }, style),
+            Some(style)
+        );
+
+        let actual = enforce(
+            toml::toml! {
+                min_contrast = 4.5
+                terminal_background = "#ffffff"
+            },
+            style,
+        )
+        .unwrap();
+        assert_ne!(actual.foreground, Some(Color::Yellow));
+    }
+
+    #[test]
+    fn warn_leaves_style_unchanged() {
+        let style = Color::Blue.on(Color::Black);
+        assert_eq!(
+            enforce(
+                toml::toml! {
+                    min_contrast = 4.5
+                    contrast_action = "warn"
+                },
+                style
+            ),
+            Some(style)
+        );
+    }
+}
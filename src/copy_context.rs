@@ -0,0 +1,103 @@
+use std::io::{self, Write};
+
+use crate::context::{Context, Properties, Target};
+use crate::modules::get_current_kube_context;
+use crate::utils::wrap_tmux_passthrough;
+
+/// Copies a summary of the prompt context to the clipboard through OSC 52,
+/// or prints it to stdout instead if `print` is set
+pub fn copy_context(args: Properties, print: bool) {
+    let context = Context::new(args, Target::Main);
+    let summary = context_summary(&context);
+
+    let output = if print {
+        summary
+    } else {
+        osc52_sequence(&summary, context.get_env("TMUX").is_some())
+    };
+
+    if let Err(err) = io::stdout().write_all(output.as_bytes()) {
+        eprintln!("Error writing context: {err}");
+        std::process::exit(1);
+    }
+}
+
+/// Collects the current directory, git branch, kubernetes context and exit code, one per line
+fn context_summary(context: &Context) -> String {
+    let mut lines = vec![format!("cwd: {}", context.logical_dir.to_string_lossy())];
+
+    if let Some(branch) = context
+        .get_repo()
+        .ok()
+        .and_then(|repo| repo.branch.as_ref())
+    {
+        lines.push(format!("branch: {branch}"));
+    }
+    if let Some(kube_context) = get_current_kube_context(context) {
+        lines.push(format!("kubernetes context: {kube_context}"));
+    }
+    if let Some(status_code) = &context.properties.status_code {
+        lines.push(format!("exit code: {status_code}"));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// Wraps the text in an OSC 52 sequence, which asks the terminal to set the clipboard.
+/// This also works over SSH, as long as the local terminal supports it.
+fn osc52_sequence(text: &str, tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", base64::encode(text));
+    if tmux {
+        wrap_tmux_passthrough(&sequence)
+    } else {
+        sequence
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::default_context;
+    use std::fs;
+    use std::io;
+
+    #[test]
+    fn osc52_encodes_text() {
+        assert_eq!(
+            osc52_sequence("cwd: /tmp\n", false),
+            "\x1b]52;c;Y3dkOiAvdG1wCg==\x07"
+        );
+    }
+
+    #[test]
+    fn osc52_tmux_passthrough() {
+        assert_eq!(
+            osc52_sequence("hi", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn summary_contains_context() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let kubeconfig = dir.path().join("config");
+        fs::write(&kubeconfig, "current-context: test_context\n")?;
+
+        let mut context = default_context();
+        context.logical_dir = dir.path().to_path_buf();
+        context.current_dir = dir.path().to_path_buf();
+        context.properties.status_code = Some("127".to_string());
+        context
+            .env
+            .insert("KUBECONFIG", kubeconfig.to_string_lossy().to_string());
+
+        assert_eq!(
+            context_summary(&context),
+            format!(
+                "cwd: {}\nkubernetes context: test_context\nexit code: 127\n",
+                dir.path().to_string_lossy()
+            )
+        );
+        dir.close()
+    }
+}
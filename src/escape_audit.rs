@@ -0,0 +1,276 @@
+use crate::context::Shell;
+use crate::print::UnicodeWidthGraphemes;
+
+const ESC: char = '\x1b';
+const BEL: char = '\x07';
+// Selects the emoji presentation of the preceding character
+const EMOJI_PRESENTATION_SELECTOR: char = '\u{fe0f}';
+
+/// A part of the rendered prompt, either printable text or a single escape sequence
+#[derive(Debug, PartialEq, Eq)]
+enum Chunk<'a> {
+    Text(&'a str),
+    /// A `CSI ... m` sequence that sets colors and text attributes
+    Sgr(&'a str),
+    /// Any other complete escape sequence, e.g. a cursor movement or an OSC hyperlink
+    Control(&'a str),
+    /// An escape sequence that doesn't end before the prompt does
+    Unterminated(&'a str),
+}
+
+/// Splits the prompt into printable text and complete escape sequences
+fn split_escapes(prompt: &str) -> Vec<Chunk<'_>> {
+    let mut chunks = Vec::new();
+    let mut rest = prompt;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find(ESC) else {
+            chunks.push(Chunk::Text(rest));
+            break;
+        };
+        if start > 0 {
+            chunks.push(Chunk::Text(&rest[..start]));
+        }
+        rest = &rest[start..];
+
+        match sequence_len(rest) {
+            Some(len) => {
+                let sequence = &rest[..len];
+                if sequence.starts_with("\x1b[") && sequence.ends_with('m') {
+                    chunks.push(Chunk::Sgr(sequence));
+                } else {
+                    chunks.push(Chunk::Control(sequence));
+                }
+                rest = &rest[len..];
+            }
+            None => {
+                chunks.push(Chunk::Unterminated(rest));
+                break;
+            }
+        }
+    }
+    chunks
+}
+
+/// Returns the length in bytes of the escape sequence at the start of `text`
+fn sequence_len(text: &str) -> Option<usize> {
+    let mut chars = text.char_indices().skip(1);
+    let (_, kind) = chars.next()?;
+    match kind {
+        // CSI: parameter and intermediate bytes, followed by a final byte
+        '[' => chars
+            .find(|(_, c)| !('\x20'..='\x3f').contains(c))
+            .filter(|(_, c)| ('\x40'..='\x7e').contains(c))
+            .map(|(i, _)| i + 1),
+        // OSC: terminated by BEL or ST (`ESC \`)
+        ']' => {
+            let mut previous = None;
+            chars
+                .find(|&(_, c)| {
+                    let end = c == BEL || (previous == Some(ESC) && c == '\\');
+                    previous = Some(c);
+                    end
+                })
+                .map(|(i, _)| i + 1)
+        }
+        // DCS, SOS, PM and APC: terminated by ST. Escaped ESCs inside, like in
+        // tmux passthrough sequences, are followed by another ESC rather than `\`
+        'P' | 'X' | '^' | '_' => {
+            let mut previous = None;
+            chars
+                .find(|&(_, c)| {
+                    let end = previous == Some(ESC) && c == '\\';
+                    previous = if previous == Some(ESC) && c == ESC {
+                        None
+                    } else {
+                        Some(c)
+                    };
+                    end
+                })
+                .map(|(i, _)| i + 1)
+        }
+        c if ('\x20'..='\x7e').contains(&c) => Some(1 + c.len_utf8()),
+        _ => None,
+    }
+}
+
+/// Whether the line editor of the shell measures the prompt itself, without zero-width markers
+fn measures_prompt_itself(shell: Shell) -> bool {
+    matches!(shell, Shell::PowerShell | Shell::Cmd)
+}
+
+/// Returns the zero-width markers that the shell expects around escape sequences
+fn zero_width_markers(shell: Shell) -> Option<(&'static str, &'static str)> {
+    match shell {
+        Shell::Bash => Some(("\\[", "\\]")),
+        Shell::Zsh | Shell::Tcsh => Some(("%{", "%}")),
+        _ => None,
+    }
+}
+
+/// Checks the escape sequences and characters of a raw, unwrapped prompt for anything that
+/// the line editor of the shell is likely to measure incorrectly, which shows up as
+/// characters being overwritten or the cursor jumping when editing or resizing.
+pub fn audit(prompt: &str, shell: Shell, width: usize) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    for chunk in split_escapes(prompt) {
+        match chunk {
+            Chunk::Text(text) if measures_prompt_itself(shell) => {
+                if let Some(c) = text.chars().find(|&c| c.is_control() && c != '\n') {
+                    issues.push(format!(
+                        "The control character {c:?} is not measured correctly by {shell:?}"
+                    ));
+                }
+                if text.contains(EMOJI_PRESENTATION_SELECTOR) {
+                    issues.push(format!(
+                        "Emoji with a presentation selector in {text:?} may be measured with the wrong width by {shell:?}"
+                    ));
+                }
+            }
+            Chunk::Control(sequence) if measures_prompt_itself(shell) => issues.push(format!(
+                "{shell:?} can't tell that the escape sequence {sequence:?} doesn't take up space"
+            )),
+            // The default wrapping only covers the range from ESC to the next `m`
+            Chunk::Control(sequence)
+                if zero_width_markers(shell).is_some()
+                    && sequence.find('m') != Some(sequence.len() - 1) =>
+            {
+                issues.push(format!(
+                    "The zero-width markers around the escape sequence {sequence:?} are misplaced for {shell:?}"
+                ));
+            }
+            Chunk::Unterminated(sequence) => issues.push(format!(
+                "The unterminated escape sequence {sequence:?} is measured as printable text"
+            )),
+            _ => {}
+        }
+    }
+
+    let last_line = prompt.rsplit('\n').next().unwrap_or_default();
+    let last_line_width: usize = split_escapes(last_line)
+        .iter()
+        .map(|chunk| match chunk {
+            Chunk::Text(text) => text.width_graphemes(),
+            _ => 0,
+        })
+        .sum();
+    if width > 0 && last_line_width >= width {
+        issues.push(format!(
+            "The last line of the prompt is {last_line_width} columns wide and fills the terminal ({width} columns), so it wraps when the terminal is resized"
+        ));
+    }
+
+    issues
+}
+
+/// Rewrites a raw, unwrapped prompt so the line editor of the shell can measure it.
+///
+/// Every complete escape sequence is wrapped in the zero-width markers of the shell. Shells
+/// without such markers only keep the color sequences they understand, and incomplete
+/// sequences are dropped for all shells.
+pub fn make_safe(prompt: &str, shell: Shell) -> String {
+    let mut safe = String::with_capacity(prompt.len());
+
+    for chunk in split_escapes(prompt) {
+        match (chunk, zero_width_markers(shell)) {
+            (Chunk::Text(text), _) if measures_prompt_itself(shell) => {
+                safe.extend(text.chars().filter(|&c| {
+                    !(c.is_control() && c != '\n') && c != EMOJI_PRESENTATION_SELECTOR
+                }))
+            }
+            (Chunk::Text(text), _) => safe.push_str(text),
+            (Chunk::Sgr(sequence) | Chunk::Control(sequence), Some((begin, end))) => {
+                safe.push_str(begin);
+                safe.push_str(sequence);
+                safe.push_str(end);
+            }
+            (Chunk::Control(_), None) if measures_prompt_itself(shell) => {}
+            (Chunk::Sgr(sequence) | Chunk::Control(sequence), None) => safe.push_str(sequence),
+            (Chunk::Unterminated(_), _) => {}
+        }
+    }
+    safe
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HYPERLINK: &str = "\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\";
+
+    #[test]
+    fn splits_sequences() {
+        assert_eq!(
+            split_escapes("a\x1b[1;31mb\x1b[2Kc\x1b]0;title\x07\x1b="),
+            vec![
+                Chunk::Text("a"),
+                Chunk::Sgr("\x1b[1;31m"),
+                Chunk::Text("b"),
+                Chunk::Control("\x1b[2K"),
+                Chunk::Text("c"),
+                Chunk::Control("\x1b]0;title\x07"),
+                Chunk::Control("\x1b="),
+            ]
+        );
+        assert_eq!(
+            split_escapes(HYPERLINK),
+            vec![
+                Chunk::Control("\x1b]8;;https://example.com\x1b\\"),
+                Chunk::Text("link"),
+                Chunk::Control("\x1b]8;;\x1b\\"),
+            ]
+        );
+        assert_eq!(
+            split_escapes("\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\x"),
+            vec![
+                Chunk::Control("\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"),
+                Chunk::Text("x"),
+            ]
+        );
+        assert_eq!(
+            split_escapes("a\x1b]0;title"),
+            vec![Chunk::Text("a"), Chunk::Unterminated("\x1b]0;title")]
+        );
+    }
+
+    #[test]
+    fn wraps_whole_sequences() {
+        assert_eq!(
+            make_safe(HYPERLINK, Shell::Bash),
+            "\\[\x1b]8;;https://example.com\x1b\\\\]link\\[\x1b]8;;\x1b\\\\]"
+        );
+        assert_eq!(
+            make_safe("\x1b[31ma\x1b[0m", Shell::Zsh),
+            "%{\x1b[31m%}a%{\x1b[0m%}"
+        );
+    }
+
+    #[test]
+    fn strips_unmeasurable_sequences() {
+        let prompt = format!("\x1b[31m{HYPERLINK}\x1b[0m\t☁\u{fe0f} \x1b[1");
+        assert_eq!(
+            make_safe(&prompt, Shell::PowerShell),
+            "\x1b[31mlink\x1b[0m☁ "
+        );
+        assert_eq!(
+            make_safe(&prompt, Shell::Fish),
+            format!("\x1b[31m{HYPERLINK}\x1b[0m\t☁\u{fe0f} ")
+        );
+    }
+
+    #[test]
+    fn audit_finds_issues() {
+        assert_eq!(
+            audit("\x1b[31m>\x1b[0m ", Shell::Bash, 80),
+            Vec::<String>::new()
+        );
+        assert_eq!(audit(HYPERLINK, Shell::Bash, 80).len(), 2);
+        assert_eq!(audit(HYPERLINK, Shell::PowerShell, 80).len(), 2);
+        assert_eq!(audit(HYPERLINK, Shell::Fish, 80), Vec::<String>::new());
+        assert_eq!(audit("\x1b[31", Shell::Fish, 80).len(), 1);
+        assert_eq!(audit("☁\u{fe0f} ", Shell::Cmd, 80).len(), 1);
+        assert_eq!(audit("line\n12345", Shell::Zsh, 5).len(), 1);
+        assert_eq!(audit("12345\n1234", Shell::Zsh, 5), Vec::<String>::new());
+    }
+}
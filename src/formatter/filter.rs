@@ -0,0 +1,147 @@
+use std::borrow::Cow;
+use std::iter;
+
+use super::model::Filter;
+
+// Partially filled cells of a bar, in eighths
+const BAR_BLOCKS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+const BAR_EMPTY: char = '░';
+const SPARKLINE_BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Applies the filter to the value of a variable
+pub fn apply_filter(value: &str, filter: &Filter) -> Result<String, String> {
+    match filter.name.as_ref() {
+        "bar" => bar(value, &filter.args),
+        "sparkline" => sparkline(value, &filter.args),
+        name => Err(format!("Unknown filter `{name}`")),
+    }
+}
+
+/// Parses a number, ignoring a trailing `%`
+fn parse_number(value: &str) -> Result<f64, String> {
+    value
+        .trim()
+        .trim_end_matches('%')
+        .parse::<f64>()
+        .map_err(|_| format!("`{value}` is not a number"))
+}
+
+/// Renders the value as a bar that is `width` cells wide and completely filled at `max`.
+///
+/// Arguments: `bar(width = 10, max = 100)`
+fn bar(value: &str, args: &[Cow<str>]) -> Result<String, String> {
+    if args.len() > 2 {
+        return Err("`bar` takes at most 2 arguments".to_string());
+    }
+    let width = match args.first() {
+        Some(width) => width
+            .parse::<usize>()
+            .map_err(|_| format!("`{width}` is not a valid bar width"))?,
+        None => 10,
+    };
+    let max = match args.get(1) {
+        Some(max) => parse_number(max)?,
+        None => 100.0,
+    };
+
+    let value = parse_number(value)?;
+    let ratio = if max > 0.0 {
+        (value / max).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let eighths = (ratio * (width * 8) as f64).round() as usize;
+
+    let mut bar: String = iter::repeat(BAR_BLOCKS[7]).take(eighths / 8).collect();
+    if eighths % 8 > 0 {
+        bar.push(BAR_BLOCKS[eighths % 8 - 1]);
+    }
+    let filled = (eighths + 7) / 8;
+    bar.extend(iter::repeat(BAR_EMPTY).take(width - filled));
+    Ok(bar)
+}
+
+/// Renders a list of numbers, separated by commas or whitespace, as a sparkline
+/// that is scaled between the smallest and the largest number.
+fn sparkline(value: &str, args: &[Cow<str>]) -> Result<String, String> {
+    if !args.is_empty() {
+        return Err("`sparkline` takes no arguments".to_string());
+    }
+
+    let values = value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|value| !value.is_empty())
+        .map(parse_number)
+        .collect::<Result<Vec<_>, _>>()?;
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    Ok(values
+        .iter()
+        .map(|value| {
+            let level = if max > min {
+                ((value - min) / (max - min) * 7.0).round() as usize
+            } else {
+                0
+            };
+            SPARKLINE_BLOCKS[level]
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter<'a>(name: &'a str, args: &[&'a str]) -> Filter<'a> {
+        Filter {
+            name: name.into(),
+            args: args.iter().map(|&arg| arg.into()).collect(),
+        }
+    }
+
+    #[test]
+    fn bar_widths_and_partial_cells() {
+        assert_eq!(
+            apply_filter("50%", &filter("bar", &[])).unwrap(),
+            "█████░░░░░"
+        );
+        assert_eq!(apply_filter("0", &filter("bar", &["4"])).unwrap(), "░░░░");
+        assert_eq!(apply_filter("100", &filter("bar", &["4"])).unwrap(), "████");
+        assert_eq!(apply_filter("30", &filter("bar", &["4"])).unwrap(), "█▎░░");
+        assert_eq!(apply_filter("150", &filter("bar", &["3"])).unwrap(), "███");
+        assert_eq!(
+            apply_filter("2.5", &filter("bar", &["2", "5"])).unwrap(),
+            "█░"
+        );
+    }
+
+    #[test]
+    fn bar_invalid_input() {
+        assert!(apply_filter("abc", &filter("bar", &[])).is_err());
+        assert!(apply_filter("50", &filter("bar", &["wide"])).is_err());
+        assert!(apply_filter("50", &filter("bar", &["1", "2", "3"])).is_err());
+    }
+
+    #[test]
+    fn sparkline_scales_values() {
+        assert_eq!(
+            apply_filter("1 2 3 4 5 6 7 8", &filter("sparkline", &[])).unwrap(),
+            "▁▂▃▄▅▆▇█"
+        );
+        assert_eq!(
+            apply_filter("10,40, 20", &filter("sparkline", &[])).unwrap(),
+            "▁█▃"
+        );
+        assert_eq!(
+            apply_filter("3 3", &filter("sparkline", &[])).unwrap(),
+            "▁▁"
+        );
+        assert!(apply_filter("1 x", &filter("sparkline", &[])).is_err());
+    }
+
+    #[test]
+    fn unknown_filter() {
+        assert!(apply_filter("1", &filter("upper", &[])).is_err());
+    }
+}
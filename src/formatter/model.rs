@@ -17,10 +17,18 @@ pub struct TextGroup<'a> {
     pub style: Vec<StyleElement<'a>>,
 }
 
+/// A function applied to the value of a variable, e.g. `bar(10)`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Filter<'a> {
+    pub name: Cow<'a, str>,
+    pub args: Vec<Cow<'a, str>>,
+}
+
 #[derive(Clone)]
 pub enum FormatElement<'a> {
     Text(Cow<'a, str>),
     Variable(Cow<'a, str>),
+    FilteredVariable(Cow<'a, str>, Vec<Filter<'a>>),
     TextGroup(TextGroup<'a>),
     Conditional(Vec<FormatElement<'a>>),
 }
@@ -34,7 +42,7 @@ pub enum StyleElement<'a> {
 impl<'a> VariableHolder<Cow<'a, str>> for FormatElement<'a> {
     fn get_variables(&self) -> BTreeSet<Cow<'a, str>> {
         match self {
-            FormatElement::Variable(var) => {
+            FormatElement::Variable(var) | FormatElement::FilteredVariable(var, _) => {
                 let mut variables = BTreeSet::new();
                 variables.insert(var.clone());
                 variables
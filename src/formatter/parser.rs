@@ -13,7 +13,7 @@ struct IdentParser;
 fn parse_value(value: Pair<Rule>) -> FormatElement {
     match value.as_rule() {
         Rule::text => FormatElement::Text(parse_text(value).into()),
-        Rule::variable => FormatElement::Variable(parse_variable(value).into()),
+        Rule::variable => parse_variable_element(value),
         Rule::textgroup => FormatElement::TextGroup(parse_textgroup(value)),
         Rule::conditional => {
             FormatElement::Conditional(parse_format(value.into_inner().next().unwrap()))
@@ -37,6 +37,28 @@ fn parse_variable(variable: Pair<Rule>) -> &str {
     variable.into_inner().next().unwrap().as_str()
 }
 
+fn parse_variable_element(variable: Pair<Rule>) -> FormatElement {
+    let mut inner_rules = variable.into_inner();
+    let name = inner_rules.next().unwrap().as_str();
+    let filters: Vec<Filter> = inner_rules.map(parse_filter).collect();
+
+    if filters.is_empty() {
+        FormatElement::Variable(name.into())
+    } else {
+        FormatElement::FilteredVariable(name.trim().into(), filters)
+    }
+}
+
+fn parse_filter(filter: Pair<Rule>) -> Filter {
+    let mut inner_rules = filter.into_inner();
+    let name = inner_rules.next().unwrap().as_str();
+
+    Filter {
+        name: name.into(),
+        args: inner_rules.map(|arg| arg.as_str().into()).collect(),
+    }
+}
+
 fn parse_text(text: Pair<Rule>) -> String {
     text.into_inner()
         .flat_map(|pair| pair.as_str().chars())
@@ -9,7 +9,9 @@ use std::fmt;
 use crate::config::parse_style_string;
 use crate::context::{Context, Shell};
 use crate::segment::Segment;
+use crate::trace;
 
+use super::filter::apply_filter;
 use super::model::*;
 use super::parser::{parse, Rule};
 
@@ -117,11 +119,15 @@ impl<'a> StringFormatter<'a> {
         T: Into<Cow<'a, str>>,
         M: Fn(&str) -> Option<Result<T, StringFormatterError>> + Sync,
     {
+        // Variables are mapped on other threads, which would lose track of the module
+        let module = trace::current_module();
         self.variables
             .par_iter_mut()
             .filter(|(_, value)| value.is_none())
             .for_each(|(key, value)| {
-                *value = mapper(key).map(|var| var.map(|var| VariableValue::Plain(var.into())));
+                trace::in_module(module.as_deref(), || {
+                    *value = mapper(key).map(|var| var.map(|var| VariableValue::Plain(var.into())));
+                });
             });
         self
     }
@@ -138,12 +144,15 @@ impl<'a> StringFormatter<'a> {
         T: Into<Cow<'a, str>>,
         M: Fn(&str) -> Option<Result<T, StringFormatterError>> + Sync,
     {
+        let module = trace::current_module();
         self.variables
             .par_iter_mut()
             .filter(|(_, value)| value.is_none())
             .for_each(|(key, value)| {
-                *value = mapper(key)
-                    .map(|var| var.map(|var| VariableValue::NoEscapingPlain(var.into())));
+                trace::in_module(module.as_deref(), || {
+                    *value = mapper(key)
+                        .map(|var| var.map(|var| VariableValue::NoEscapingPlain(var.into())));
+                });
             });
         self
     }
@@ -201,11 +210,14 @@ impl<'a> StringFormatter<'a> {
     where
         M: Fn(&str) -> Option<Result<Vec<Segment>, StringFormatterError>> + Sync,
     {
+        let module = trace::current_module();
         self.variables
             .par_iter_mut()
             .filter(|(_, value)| value.is_none())
             .for_each(|(key, value)| {
-                *value = mapper(key).map(|var| var.map(VariableValue::Styled));
+                trace::in_module(module.as_deref(), || {
+                    *value = mapper(key).map(|var| var.map(VariableValue::Styled));
+                });
             });
         self
     }
@@ -219,11 +231,14 @@ impl<'a> StringFormatter<'a> {
         T: Into<Cow<'a, str>>,
         M: Fn(&str) -> Option<Result<T, StringFormatterError>> + Sync,
     {
+        let module = trace::current_module();
         self.style_variables
             .par_iter_mut()
             .filter(|(_, value)| value.is_none())
             .for_each(|(key, value)| {
-                *value = mapper(key).map(|var| var.map(std::convert::Into::into));
+                trace::in_module(module.as_deref(), || {
+                    *value = mapper(key).map(|var| var.map(std::convert::Into::into));
+                });
             });
         self
     }
@@ -346,6 +361,49 @@ impl<'a> StringFormatter<'a> {
                                 }
                             })
                             .unwrap_or_else(|| Ok(Vec::new())),
+                        FormatElement::FilteredVariable(name, filters) => variables
+                            .get(name.as_ref())
+                            .expect("Uncached variable found")
+                            .as_ref()
+                            .map(|value| {
+                                let text: String = match value.clone()? {
+                                    VariableValue::Plain(text)
+                                    | VariableValue::NoEscapingPlain(text) => text.into_owned(),
+                                    VariableValue::Styled(segments) => {
+                                        segments.iter().map(Segment::value).collect()
+                                    }
+                                    VariableValue::Meta(format) => {
+                                        let formatter = StringFormatter {
+                                            format,
+                                            variables: clone_without_meta(variables),
+                                            style_variables: style_variables.clone(),
+                                        };
+                                        formatter
+                                            .parse(None, context)?
+                                            .iter()
+                                            .map(Segment::value)
+                                            .collect()
+                                    }
+                                };
+                                if text.is_empty() {
+                                    return Ok(Vec::new());
+                                }
+
+                                let text = filters
+                                    .iter()
+                                    .try_fold(text, |text, filter| apply_filter(&text, filter))?;
+                                Ok(Segment::from_text(
+                                    style,
+                                    shell_prompt_escape(
+                                        text,
+                                        match context {
+                                            None => Shell::Unknown,
+                                            Some(c) => c.shell,
+                                        },
+                                    ),
+                                ))
+                            })
+                            .unwrap_or_else(|| Ok(Vec::new())),
                         FormatElement::Conditional(format) => {
                             // Show the conditional format string if all the variables inside are not
                             // none or empty string.
@@ -402,8 +460,14 @@ impl<'a> StringFormatter<'a> {
             Ok(results?.into_iter().flatten().collect())
         }
 
+        let format = if context.map_or(false, Context::is_compact) {
+            compact_format(self.format)
+        } else {
+            self.format
+        };
+
         parse_format(
-            self.format,
+            format,
             default_style,
             &self.variables,
             &self.style_variables,
@@ -424,6 +488,38 @@ impl<'a> StyleVariableHolder<String> for StringFormatter<'a> {
     }
 }
 
+/// Reduces a format string that contains `$symbol` to the symbol, keeping its styles and spacing.
+/// Format strings without a symbol are left unchanged.
+fn compact_format(format: Vec<FormatElement>) -> Vec<FormatElement> {
+    fn retain_symbol(format: Vec<FormatElement>) -> Vec<FormatElement> {
+        format
+            .into_iter()
+            .filter_map(|el| match el {
+                FormatElement::Text(text) if text.trim().is_empty() => {
+                    Some(FormatElement::Text(text))
+                }
+                FormatElement::Variable(name) if name == "symbol" => {
+                    Some(FormatElement::Variable(name))
+                }
+                FormatElement::TextGroup(textgroup) => Some(FormatElement::TextGroup(TextGroup {
+                    format: retain_symbol(textgroup.format),
+                    style: textgroup.style,
+                })),
+                FormatElement::Conditional(format) => {
+                    Some(FormatElement::Conditional(retain_symbol(format)))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    if format.get_variables().contains("symbol") {
+        retain_symbol(format)
+    } else {
+        format
+    }
+}
+
 fn clone_without_meta<'a>(variables: &VariableMapType<'a>) -> VariableMapType<'a> {
     variables
         .iter()
@@ -699,6 +795,83 @@ mod tests {
         match_next!(result_iter, " shouldn't", None);
     }
 
+    #[test]
+    fn test_compact() {
+        const FORMAT_STR: &str = "via [$symbol($version )]($style) ";
+        let context = crate::test::default_context().set_config(toml::toml! {
+            compact = true
+        });
+
+        let formatter = StringFormatter::new(FORMAT_STR)
+            .unwrap()
+            .map_meta(|var, _| match var {
+                "symbol" => Some("S"),
+                _ => None,
+            })
+            .map_style(|var| match var {
+                "style" => Some(Ok("red")),
+                _ => None,
+            })
+            .map(|var| match var {
+                "version" => Some(Ok("v1.0.0")),
+                _ => None,
+            });
+        let result = formatter.parse(None, Some(&context)).unwrap();
+        let mut result_iter = result.iter();
+        match_next!(result_iter, "S", Some(Color::Red.normal()));
+        match_next!(result_iter, " ", None);
+        assert!(result_iter.next().is_none());
+    }
+
+    #[test]
+    fn test_compact_without_symbol() {
+        const FORMAT_STR: &str = "in $path";
+        let context = crate::test::default_context().set_config(toml::toml! {
+            compact = true
+        });
+
+        let formatter = StringFormatter::new(FORMAT_STR)
+            .unwrap()
+            .map(|var| match var {
+                "path" => Some(Ok("~")),
+                _ => None,
+            });
+        let result = formatter.parse(None, Some(&context)).unwrap();
+        let mut result_iter = result.iter();
+        match_next!(result_iter, "in ", None);
+        match_next!(result_iter, "~", None);
+    }
+
+    #[test]
+    fn test_filtered_variable() {
+        const FORMAT_STR: &str = "[${percentage | bar(4)}](red) ${percentage|bar}";
+
+        let formatter = StringFormatter::new(FORMAT_STR)
+            .unwrap()
+            .map(|var| match var {
+                "percentage" => Some(Ok("50%")),
+                _ => None,
+            });
+        let result = formatter.parse(None, None).unwrap();
+        let mut result_iter = result.iter();
+        match_next!(result_iter, "██░░", Some(Color::Red.normal()));
+        match_next!(result_iter, " ", None);
+        match_next!(result_iter, "█████░░░░░", None);
+    }
+
+    #[test]
+    fn test_filtered_variable_error() {
+        const FORMAT_STR: &str = "${value | bar(4)}";
+
+        let formatter = StringFormatter::new(FORMAT_STR)
+            .unwrap()
+            .map(|var| match var {
+                "value" => Some(Ok("high")),
+                _ => None,
+            });
+        assert!(formatter.parse(None, None).is_err());
+    }
+
     #[test]
     fn test_empty() {
         const FORMAT_STR: &str = "(@$empty)";
@@ -1,3 +1,7 @@
+use crate::config::ModuleConfig;
+use crate::configs::character::CharacterConfig;
+use crate::context::{Context, Shell};
+use crate::instant_prompt;
 use crate::utils::create_command;
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
@@ -211,8 +215,33 @@ pub fn init_main(shell_name: &str) -> io::Result<()> {
     let starship_path = StarshipPath::init()?;
 
     match shell_name {
-        "bash" => print_script(BASH_INIT, &starship_path.sprint_posix()?),
-        "zsh" => print_script(ZSH_INIT, &starship_path.sprint_posix()?),
+        "bash" => {
+            let context = Context::default();
+            let vi_mode_strings =
+                CharacterConfig::try_load(context.config.get_module_config("character"))
+                    .bash_vi_mode_strings;
+            let script = BASH_INIT
+                .replace(
+                    "::INSTANT_PROMPT::",
+                    &instant_prompt_path(&context, &Shell::Bash),
+                )
+                .replace("::CONTINUATION_FORMAT::", continuation_format_set(&context))
+                .replace(
+                    "::VI_MODE_STRINGS::",
+                    if vi_mode_strings { "1" } else { "" },
+                );
+            print_script(&script, &starship_path.sprint_posix()?);
+        }
+        "zsh" => {
+            let context = Context::default();
+            let script = ZSH_INIT
+                .replace(
+                    "::INSTANT_PROMPT::",
+                    &instant_prompt_path(&context, &Shell::Zsh),
+                )
+                .replace("::CONTINUATION_FORMAT::", continuation_format_set(&context));
+            print_script(&script, &starship_path.sprint_posix()?);
+        }
         "fish" => print_script(FISH_INIT, &starship_path.sprint_posix()?),
         "powershell" => print_script(PWSH_INIT, &starship_path.sprint_pwsh()?),
         "ion" => print_script(ION_INIT, &starship_path.sprint()?),
@@ -230,6 +259,28 @@ pub fn init_main(shell_name: &str) -> io::Result<()> {
     Ok(())
 }
 
+/// The quoted path of the prompt to show on startup, or an empty string if `instant_prompt` is
+/// disabled
+fn instant_prompt_path(context: &Context, shell: &Shell) -> String {
+    let path = context
+        .root_config
+        .instant_prompt
+        .then(|| instant_prompt::path(shell))
+        .flatten();
+    let path = path.as_deref().and_then(Path::to_str).unwrap_or_default();
+    shell_words::quote(path).into_owned()
+}
+
+/// `true` if `continuation_format` is set, so the continuation prompt has to be rendered for every
+/// line, or `false` if the prompt rendered at init can be used for all of them
+fn continuation_format_set(context: &Context) -> &'static str {
+    if context.root_config.continuation_format.is_empty() {
+        "false"
+    } else {
+        "true"
+    }
+}
+
 fn print_script(script: &str, path: &str) {
     let script = script.replace("::STARSHIP::", path);
     print!("{script}");
@@ -15,6 +15,67 @@
 # A way to set '$?', since bash does not allow assigning to '$?' directly
 function _starship_set_return() { return "${1:-0}"; }
 
+# Sets STARSHIP_CPU_TIMES to the user and system CPU time, in clock ticks, used by the finished
+# child processes of the shell. The times are read from /proc, because running `times` in a
+# subshell only reports the times of the subshell, so they are unknown on macOS.
+_starship_get_cpu_times() {
+    local stat
+    [[ -r /proc/$$/stat ]] && read -r -a stat < "/proc/$$/stat" || return 1
+    STARSHIP_CPU_TIMES=("${stat[15]}" "${stat[16]}")
+}
+
+# Clears the instant prompt, moving up over its lines, including the ones that wrapped
+_starship_clear_instant_prompt() {
+    local line width rows=0
+    local escape=$'\e''(\[[0-9;?]*[@-~]|\][^'$'\a\e'']*('$'\a''|'$'\e''\\))'
+    while IFS= read -r line; do
+        # Escape sequences and the markers around them don't take up any space
+        line=${line//[$'\001\002']}
+        while [[ $line =~ $escape ]]; do
+            line=${line/"${BASH_REMATCH[0]}"}
+        done
+        width=${#line}
+        (( rows += width ? (width - 1) / COLUMNS + 1 : 1 ))
+    done <<< "$STARSHIP_INSTANT_PROMPT"
+    printf '\r'
+    (( rows > 1 )) && printf '\e[%dA' "$(( rows - 1 ))"
+    printf '\e[J'
+}
+
+# Sets STARSHIP_MODE_STRINGS[$1] to the module as rendered for the keymap and status, in the
+# syntax of readline: quoted, with non-printing sequences between \1 and \2.
+_starship_render_mode_string() {
+    local rendered mode_string=""
+    rendered=$(::STARSHIP:: module character --keymap="$2" --status="$3")
+    rendered=${rendered//\\/\\\\}
+    rendered=${rendered//\"/\\\"}
+    while [[ $rendered =~ ^([^$'\e']*)$'\e'(\[[0-9\;]*m)(.*)$ ]]; do
+        mode_string+="${BASH_REMATCH[1]}\\1\\e${BASH_REMATCH[2]}\\2"
+        rendered=${BASH_REMATCH[3]}
+    done
+    STARSHIP_MODE_STRINGS[$1]=$mode_string$rendered
+}
+
+# Readline doesn't tell bash which vi mode it's in, but shows a mode string at the start of the
+# last line of the prompt, so with `character.bash_vi_mode_strings` the `character` module is shown
+# there instead of in PS1. Rendering the mode strings for every prompt would take three more
+# processes, so they are only rendered again when the config file has changed.
+_starship_set_mode_strings() {
+    local config
+    IFS= read -r -d '' config 2>/dev/null < "${STARSHIP_CONFIG:-$HOME/.config/starship.toml}"
+    if [[ -z ${STARSHIP_MODE_STRINGS[*]} || $config != "$STARSHIP_MODE_STRINGS_CONFIG" ]]; then
+        STARSHIP_MODE_STRINGS_CONFIG=$config
+        _starship_render_mode_string 0 viins 0
+        _starship_render_mode_string 1 viins 1
+        _starship_render_mode_string 2 vicmd 0
+    fi
+    local failed=$(( STARSHIP_CMD_STATUS != 0 ))
+    bind 'set show-mode-in-prompt on'
+    bind 'set emacs-mode-string ""'
+    bind "set vi-ins-mode-string \"${STARSHIP_MODE_STRINGS[failed]}\""
+    bind "set vi-cmd-mode-string \"${STARSHIP_MODE_STRINGS[2]}\""
+}
+
 # Will be run before *every* command (even ones in pipes!)
 starship_preexec() {
     # Save previous command's last argument, otherwise it will be set to "starship_preexec"
@@ -24,6 +85,8 @@ starship_preexec() {
     if [ "$STARSHIP_PREEXEC_READY" = "true" ]; then
         STARSHIP_PREEXEC_READY=false
         STARSHIP_START_TIME=$(::STARSHIP:: time)
+        STARSHIP_CMD_ID=$STARSHIP_START_TIME
+        _starship_get_cpu_times && STARSHIP_START_CPU_TIMES=("${STARSHIP_CPU_TIMES[@]}")
     fi
 
     : "$PREV_LAST_ARG"
@@ -37,12 +100,30 @@ starship_precmd() {
         STARSHIP_PIPE_STATUS=(${BP_PIPESTATUS[@]})
     fi
 
+    # Measure the CPU time of the command before the commands below add to it
+    local STARSHIP_USER_TICKS STARSHIP_SYS_TICKS
+    if [[ $STARSHIP_START_CPU_TIMES ]] && _starship_get_cpu_times; then
+        STARSHIP_USER_TICKS=$(( STARSHIP_CPU_TIMES[0] - STARSHIP_START_CPU_TIMES[0] ))
+        STARSHIP_SYS_TICKS=$(( STARSHIP_CPU_TIMES[1] - STARSHIP_START_CPU_TIMES[1] ))
+    fi
+    unset STARSHIP_START_CPU_TIMES
+
     local NUM_JOBS=0
     # Evaluate the number of jobs before running the preserved prompt command, so that tools
     # like z/autojump, which background certain jobs, do not cause spurious background jobs
     # to be displayed by starship. Also avoids forking to run `wc`, slightly improving perf.
     for job in $(jobs -p); do [[ $job ]] && ((NUM_JOBS++)); done
 
+    # Collect the commands of running and stopped jobs from lines like `[1]+  Running  sleep 9 &`
+    local STARSHIP_JOB_NAMES=""
+    local job_pattern='^\[[0-9]+\][-+ ] +(Running|Stopped( \([^)]*\))?) +(.*)$'
+    while IFS= read -r job; do
+        [[ $job =~ $job_pattern ]] && STARSHIP_JOB_NAMES+="${BASH_REMATCH[3]}"$'\n'
+    done <<< "$(jobs)"
+
+    # The first continuation line is the second line of the command
+    STARSHIP_CONTINUATION_LINE=1
+
     # Run the bash precmd function, if it's set. If not set, evaluates to no-op
     "${starship_precmd_user_func-:}"
 
@@ -52,16 +133,28 @@ starship_precmd() {
 
     eval "$_PRESERVED_PROMPT_COMMAND"
 
+    local STARSHIP_KEYMAP=emacs
+    if [[ $STARSHIP_VI_MODE_STRINGS && :$SHELLOPTS: == *:vi:* ]]; then
+        _starship_set_mode_strings
+        STARSHIP_KEYMAP=vi-mode-string
+    fi
+
     # Prepare the timer data, if needed.
     if [[ $STARSHIP_START_TIME ]]; then
         STARSHIP_END_TIME=$(::STARSHIP:: time)
         STARSHIP_DURATION=$((STARSHIP_END_TIME - STARSHIP_START_TIME))
-        PS1="$(::STARSHIP:: prompt --terminal-width="$COLUMNS" --status=$STARSHIP_CMD_STATUS --pipestatus="${STARSHIP_PIPE_STATUS[*]}" --jobs="$NUM_JOBS" --cmd-duration=$STARSHIP_DURATION)"
+        PS1="$(::STARSHIP:: prompt --terminal-width="$COLUMNS" --keymap="$STARSHIP_KEYMAP" --status=$STARSHIP_CMD_STATUS --pipestatus="${STARSHIP_PIPE_STATUS[*]}" --jobs="$NUM_JOBS" --job-names="$STARSHIP_JOB_NAMES" --cmd-duration=$STARSHIP_DURATION --cmd-user-ticks="$STARSHIP_USER_TICKS" --cmd-sys-ticks="$STARSHIP_SYS_TICKS" --cmd-id="$STARSHIP_CMD_ID")"
         unset STARSHIP_START_TIME
     else
-        PS1="$(::STARSHIP:: prompt --terminal-width="$COLUMNS" --status=$STARSHIP_CMD_STATUS --pipestatus="${STARSHIP_PIPE_STATUS[*]}" --jobs="$NUM_JOBS")"
+        PS1="$(::STARSHIP:: prompt --terminal-width="$COLUMNS" --keymap="$STARSHIP_KEYMAP" --status=$STARSHIP_CMD_STATUS --pipestatus="${STARSHIP_PIPE_STATUS[*]}" --jobs="$NUM_JOBS" --job-names="$STARSHIP_JOB_NAMES")"
     fi
     STARSHIP_PREEXEC_READY=true  # Signal that we can safely restart the timer
+
+    # Clear the instant prompt, so that readline draws the real one in its place
+    if [[ ${STARSHIP_INSTANT_PROMPT+set} ]]; then
+        _starship_clear_instant_prompt
+        unset STARSHIP_INSTANT_PROMPT
+    fi
 }
 
 # If the user appears to be using https://github.com/rcaloras/bash-preexec,
@@ -111,6 +204,27 @@ STARSHIP_SESSION_KEY="$RANDOM$RANDOM$RANDOM$RANDOM$RANDOM"; # Random generates a
 STARSHIP_SESSION_KEY="${STARSHIP_SESSION_KEY}0000000000000000" # Pad it to 16+ chars.
 export STARSHIP_SESSION_KEY=${STARSHIP_SESSION_KEY:0:16}; # Trim to 16-digits if excess.
 
-# Set the continuation prompt
-PS2="$(::STARSHIP:: prompt --continuation)"
+# Show the `character` module in the vi mode strings of readline, if `character.bash_vi_mode_strings`
+# is set
+STARSHIP_VI_MODE_STRINGS=::VI_MODE_STRINGS::
 
+# Set the continuation prompt. With `continuation_format`, it is rendered for every line, counting
+# its lines in the subscript of an unset array.
+if ::CONTINUATION_FORMAT::; then
+    PS2='${_starship_continuation_line[STARSHIP_CONTINUATION_LINE+=1]-}$(::STARSHIP:: prompt --continuation --line-number="$STARSHIP_CONTINUATION_LINE")'
+else
+    PS2="$(::STARSHIP:: prompt --continuation)"
+fi
+
+# Show the last rendered prompt right away, while the shell finishes starting and the first real
+# prompt is rendered. Expanding it needs the `@P` operator of bash 4.4. Anything printed after
+# this, e.g. by commands below `starship init` in ~/.bashrc, isn't cleared.
+STARSHIP_INSTANT_PROMPT=::INSTANT_PROMPT::
+if [[ $STARSHIP_INSTANT_PROMPT && -r $STARSHIP_INSTANT_PROMPT ]] \
+    && (( BASH_VERSINFO[0] * 100 + BASH_VERSINFO[1] >= 404 )); then
+    STARSHIP_INSTANT_PROMPT=$(<"$STARSHIP_INSTANT_PROMPT")
+    STARSHIP_INSTANT_PROMPT=${STARSHIP_INSTANT_PROMPT@P}
+    printf '%s' "$STARSHIP_INSTANT_PROMPT"
+else
+    unset STARSHIP_INSTANT_PROMPT
+fi
@@ -10,6 +10,7 @@ function fish_prompt
     # Account for changes in variable name between v2.7 and v3.0
     set STARSHIP_DURATION "$CMD_DURATION$cmd_duration"
     set STARSHIP_JOBS (count (jobs -p))
+    set STARSHIP_JOB_NAMES (jobs --command)
     if test "$TRANSIENT" = "1"
         # Clear from cursor to end of screen as `commandline -f repaint` does not do this
         # See https://github.com/fish-shell/fish-shell/issues/8418
@@ -20,7 +21,7 @@ function fish_prompt
             printf "\e[1;32m❯\e[0m "
         end
     else
-        ::STARSHIP:: prompt --terminal-width="$COLUMNS" --status=$STARSHIP_CMD_STATUS --pipestatus="$STARSHIP_CMD_PIPESTATUS" --keymap=$STARSHIP_KEYMAP --cmd-duration=$STARSHIP_DURATION --jobs=$STARSHIP_JOBS
+        ::STARSHIP:: prompt --terminal-width="$COLUMNS" --status=$STARSHIP_CMD_STATUS --pipestatus="$STARSHIP_CMD_PIPESTATUS" --keymap=$STARSHIP_KEYMAP --cmd-duration=$STARSHIP_DURATION --cmd-user-ticks=$STARSHIP_USER_TICKS --cmd-sys-ticks=$STARSHIP_SYS_TICKS --cmd-id=$STARSHIP_CMD_ID --jobs=$STARSHIP_JOBS --job-names=$STARSHIP_JOB_NAMES
     end
 end
 
@@ -36,6 +37,7 @@ function fish_right_prompt
     # Account for changes in variable name between v2.7 and v3.0
     set STARSHIP_DURATION "$CMD_DURATION$cmd_duration"
     set STARSHIP_JOBS (count (jobs -p))
+    set STARSHIP_JOB_NAMES (jobs --command)
     if test "$TRANSIENT" = "1"
         if type -q starship_transient_rprompt_func
             starship_transient_rprompt_func
@@ -43,7 +45,32 @@ function fish_right_prompt
             printf ""
         end
     else
-        ::STARSHIP:: prompt --right --terminal-width="$COLUMNS" --status=$STARSHIP_CMD_STATUS --pipestatus="$STARSHIP_CMD_PIPESTATUS" --keymap=$STARSHIP_KEYMAP --cmd-duration=$STARSHIP_DURATION --jobs=$STARSHIP_JOBS
+        ::STARSHIP:: prompt --right --terminal-width="$COLUMNS" --status=$STARSHIP_CMD_STATUS --pipestatus="$STARSHIP_CMD_PIPESTATUS" --keymap=$STARSHIP_KEYMAP --cmd-duration=$STARSHIP_DURATION --cmd-user-ticks=$STARSHIP_USER_TICKS --cmd-sys-ticks=$STARSHIP_SYS_TICKS --cmd-id=$STARSHIP_CMD_ID --jobs=$STARSHIP_JOBS --job-names=$STARSHIP_JOB_NAMES
+    end
+end
+
+# Prints the user and system CPU time, in clock ticks, used by the finished child processes of the shell
+function __starship_get_cpu_times
+    test -r /proc/$fish_pid/stat; or return 1
+    read --local --array stat </proc/$fish_pid/stat
+    echo $stat[16] $stat[17]
+end
+
+function __starship_start_cpu_times --on-event fish_preexec
+    set -g STARSHIP_START_CPU_TIMES (__starship_get_cpu_times | string split ' ')
+end
+
+# Identify each command by the number of commands that ran before it
+function __starship_cmd_id --on-event fish_preexec
+    set -g STARSHIP_CMD_ID $status_generation
+end
+
+function __starship_cpu_times --on-event fish_postexec
+    set -e STARSHIP_USER_TICKS STARSHIP_SYS_TICKS
+    set -l cpu_times (__starship_get_cpu_times | string split ' ')
+    if set -q STARSHIP_START_CPU_TIMES[2] cpu_times[2]
+        set -g STARSHIP_USER_TICKS (math "$cpu_times[1] - $STARSHIP_START_CPU_TIMES[1]")
+        set -g STARSHIP_SYS_TICKS (math "$cpu_times[2] - $STARSHIP_START_CPU_TIMES[2]")
     end
 end
 
@@ -112,7 +112,7 @@ $null = New-Module starship {
         } catch {}
 
         # @ makes sure the result is an array even if single or no values are returned
-        $jobs = @(Get-Job | Where-Object { $_.State -eq 'Running' }).Count
+        $jobs = @(Get-Job | Where-Object { $_.State -eq 'Running' })
 
         $cwd = Get-Cwd
         $arguments = @(
@@ -120,7 +120,8 @@ $null = New-Module starship {
             "--path=$($cwd.Path)",
             "--logical-path=$($cwd.LogicalPath)",
             "--terminal-width=$($Host.UI.RawUI.WindowSize.Width)",
-            "--jobs=$($jobs)"
+            "--jobs=$($jobs.Count)",
+            "--job-names=$($jobs.Command -join "`n")"
         )
 
         # We start from the premise that the command executed correctly, which covers also the fresh console.
@@ -137,6 +138,7 @@ $null = New-Module starship {
             $duration = [math]::Round(($lastCmd.EndExecutionTime - $lastCmd.StartExecutionTime).TotalMilliseconds)
 
             $arguments += "--cmd-duration=$($duration)"
+            $arguments += "--cmd-id=$($lastCmd.Id)"
         }
 
         $arguments += "--status=$($lastExitCodeForPrompt)"
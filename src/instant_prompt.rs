@@ -0,0 +1,78 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::context::{Context, Shell, Target};
+use crate::logger::get_log_dir;
+
+/// The file with the last prompt of the shell, which its init script shows on startup while the
+/// first prompt is rendered. Only the bash and zsh init scripts show it.
+pub fn path(shell: &Shell) -> Option<PathBuf> {
+    let name = match shell {
+        Shell::Bash => "instant_prompt_bash",
+        Shell::Zsh => "instant_prompt_zsh",
+        _ => return None,
+    };
+    Some(get_log_dir().join(name))
+}
+
+/// The file the rendered prompt should be saved to, if `instant_prompt` is enabled
+pub fn path_for(context: &Context) -> Option<PathBuf> {
+    if !context.root_config.instant_prompt || context.target != Target::Main {
+        return None;
+    }
+    path(&context.shell)
+}
+
+/// Saves the rendered prompt, for the next shell to show on startup
+pub fn save(path: &Path, prompt: &str) {
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+
+    // Write to a temporary file first, so that a starting shell never reads half a prompt
+    let temp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+    let result = fs::write(&temp_path, prompt).and_then(|_| fs::rename(&temp_path, path));
+    if let Err(error) = result {
+        log::debug!("Unable to write instant prompt {:?}: {}", path, error);
+        let _ = fs::remove_file(&temp_path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::default_context;
+    use std::io;
+
+    #[test]
+    fn saved_only_when_enabled() {
+        let mut context = default_context().set_config(toml::toml! {
+            instant_prompt = true
+        });
+        context.shell = Shell::Zsh;
+        assert!(path_for(&context).is_some());
+
+        context.target = Target::Right;
+        assert_eq!(path_for(&context), None);
+
+        context.target = Target::Main;
+        context.shell = Shell::Fish;
+        assert_eq!(path_for(&context), None);
+
+        let mut context = default_context();
+        context.shell = Shell::Zsh;
+        assert_eq!(path_for(&context), None);
+    }
+
+    #[test]
+    fn save_replaces_prompt() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("instant_prompt_zsh");
+
+        save(&path, "first > ");
+        save(&path, "\nsecond > ");
+        assert_eq!(fs::read_to_string(&path)?, "\nsecond > ");
+        assert_eq!(fs::read_dir(dir.path())?.count(), 1);
+        dir.close()
+    }
+}
@@ -8,19 +8,27 @@ shadow!(shadow);
 // Lib is present to allow for benchmarking
 pub mod bug_report;
 pub mod config;
+mod config_validation;
 pub mod configs;
 pub mod configure;
 pub mod context;
 pub mod context_env;
+mod contrast;
+pub mod copy_context;
+mod escape_audit;
 pub mod formatter;
 pub mod init;
+mod instant_prompt;
 pub mod logger;
 pub mod module;
 mod modules;
 pub mod print;
 mod segment;
 mod serde_utils;
+pub mod session_state;
+pub mod snapshot;
+mod trace;
 mod utils;
 
-#[cfg(test)]
-mod test;
+#[cfg(any(test, feature = "testing"))]
+pub mod test;
@@ -1,3 +1,4 @@
+use crate::configs::{LogFormat, LogLevel, LoggingConfig};
 use crate::utils;
 use log::{Level, LevelFilter, Metadata, Record};
 use nu_ansi_term::Color;
@@ -12,11 +13,100 @@ use std::{
     sync::{Mutex, RwLock},
 };
 
+// Settings from the `[logging]` table of the configuration, once it has been loaded
+static CONFIGURED: RwLock<Option<(LogFilter, LogFormat)>> = RwLock::new(None);
+
 pub struct StarshipLogger {
     log_file: OnceCell<Mutex<File>>,
     log_file_path: PathBuf,
     log_file_content: RwLock<HashSet<String>>,
-    log_level: Level,
+    // Set by `STARSHIP_LOG`, takes precedence over the configuration
+    log_filter: Option<LogFilter>,
+    log_format: Option<LogFormat>,
+}
+
+/// The minimum log level of starship, with overrides for single modules
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogFilter {
+    level: Level,
+    modules: Vec<(String, Level)>,
+}
+
+impl LogFilter {
+    pub fn new(level: Level) -> Self {
+        Self {
+            level,
+            modules: Vec::new(),
+        }
+    }
+
+    /// Parses a list of levels like `warn,git_status=debug,container=trace`,
+    /// where entries without a module name set the level of everything else
+    pub fn parse(spec: &str) -> Self {
+        let mut filter = Self::new(Level::Warn);
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            match entry.split_once('=') {
+                Some((module, level)) => filter
+                    .modules
+                    .push((module.trim().to_string(), parse_level(level))),
+                None => filter.level = parse_level(entry),
+            }
+        }
+        filter
+    }
+
+    /// Returns the minimum level of messages logged from the given module path
+    fn level(&self, target: &str) -> Level {
+        let name = target
+            .strip_prefix("starship::modules::")
+            .or_else(|| target.strip_prefix("starship::"))
+            .unwrap_or(target);
+        let name = name.split("::").next().unwrap_or_default();
+        self.modules
+            .iter()
+            .rev()
+            .find(|(module, _)| module == name)
+            .map_or(self.level, |(_, level)| *level)
+    }
+}
+
+fn parse_level(level: &str) -> Level {
+    match level.trim().to_ascii_lowercase().as_str() {
+        "trace" => Level::Trace,
+        "debug" => Level::Debug,
+        "info" => Level::Info,
+        "warn" => Level::Warn,
+        "error" => Level::Error,
+        _ => Level::Warn,
+    }
+}
+
+impl From<LogLevel> for Level {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Trace => Level::Trace,
+            LogLevel::Debug => Level::Debug,
+            LogLevel::Info => Level::Info,
+            LogLevel::Warn => Level::Warn,
+            LogLevel::Error => Level::Error,
+        }
+    }
+}
+
+/// Applies the `[logging]` table of the configuration.
+/// Log levels set with `STARSHIP_LOG` take precedence over the ones of the configuration.
+pub fn configure(config: &LoggingConfig) {
+    let mut filter = LogFilter::new(config.level.into());
+    filter.modules.extend(
+        config
+            .modules
+            .iter()
+            .map(|(module, level)| (module.clone(), (*level).into())),
+    );
+
+    if let Ok(mut configured) = CONFIGURED.write() {
+        *configured = Some((filter, config.format));
+    }
 }
 
 /// Returns the path to the log directory.
@@ -37,12 +127,12 @@ pub fn cleanup_log_files<P: AsRef<Path>>(path: P) {
     let log_dir = path.as_ref();
     let Ok(log_files) = fs::read_dir(log_dir) else {
         // Avoid noisily handling errors in this cleanup function.
-        return
+        return;
     };
 
     for file in log_files {
         // Skip files that can't be read.
-        let Ok (file) = file else {
+        let Ok(file) = file else {
             continue;
         };
 
@@ -103,16 +193,10 @@ impl Default for StarshipLogger {
             ),
             log_file: OnceCell::new(),
             log_file_path: session_log_file,
-            log_level: env::var("STARSHIP_LOG")
-                .map(|level| match level.to_ascii_lowercase().as_str() {
-                    "trace" => Level::Trace,
-                    "debug" => Level::Debug,
-                    "info" => Level::Info,
-                    "warn" => Level::Warn,
-                    "error" => Level::Error,
-                    _ => Level::Warn,
-                })
-                .unwrap_or_else(|_| Level::Warn),
+            log_filter: env::var("STARSHIP_LOG")
+                .ok()
+                .map(|spec| LogFilter::parse(&spec)),
+            log_format: None,
         }
     }
 }
@@ -120,7 +204,39 @@ impl Default for StarshipLogger {
 impl StarshipLogger {
     /// Override the minimum log level
     pub fn set_log_level(&mut self, level: log::Level) {
-        self.log_level = level;
+        self.log_filter = Some(LogFilter::new(level));
+    }
+
+    /// Override the minimum log levels of starship and single modules
+    pub fn set_log_filter(&mut self, filter: LogFilter) {
+        self.log_filter = Some(filter);
+    }
+
+    /// Override the format of the log file
+    pub fn set_log_format(&mut self, format: LogFormat) {
+        self.log_format = Some(format);
+    }
+
+    /// Returns the minimum level of messages logged from the given module path
+    fn log_level(&self, target: &str) -> Level {
+        if let Some(filter) = &self.log_filter {
+            return filter.level(target);
+        }
+        CONFIGURED
+            .read()
+            .ok()
+            .and_then(|configured| configured.as_ref().map(|(filter, _)| filter.level(target)))
+            .unwrap_or(Level::Warn)
+    }
+
+    fn log_format(&self) -> LogFormat {
+        self.log_format.unwrap_or_else(|| {
+            CONFIGURED
+                .read()
+                .ok()
+                .and_then(|configured| configured.as_ref().map(|(_, format)| *format))
+                .unwrap_or_default()
+        })
     }
 
     /// Override the log level path
@@ -138,7 +254,7 @@ impl StarshipLogger {
 
 impl log::Log for StarshipLogger {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        metadata.level() <= self.log_level
+        metadata.level() <= self.log_level(metadata.target())
     }
 
     fn log(&self, record: &Record) {
@@ -147,12 +263,20 @@ impl log::Log for StarshipLogger {
             return;
         }
 
-        let to_print = format!(
-            "[{}] - ({}): {}",
-            record.level(),
-            record.module_path().unwrap_or_default(),
-            record.args()
-        );
+        let to_print = match self.log_format() {
+            LogFormat::Text => format!(
+                "[{}] - ({}): {}",
+                record.level(),
+                record.module_path().unwrap_or_default(),
+                record.args()
+            ),
+            LogFormat::Json => serde_json::json!({
+                "level": record.level().as_str(),
+                "module": record.module_path().unwrap_or_default(),
+                "message": record.args().to_string(),
+            })
+            .to_string(),
+        };
 
         // A log message is only printed or written to the log file,
         // if it's not already in the log file or has been printed in this session.
@@ -173,7 +297,7 @@ impl log::Log for StarshipLogger {
 
         // Write warning messages to the log file
         // If log level is error, only write error messages to the log file
-        if record.level() <= cmp::min(Level::Warn, self.log_level) {
+        if record.level() <= cmp::min(Level::Warn, self.log_level(record.target())) {
             let log_file = match self.log_file.get_or_try_init(|| {
                 OpenOptions::new()
                     .create(true)
@@ -352,6 +476,62 @@ mod test {
         log_dir.close()
     }
 
+    #[test]
+    fn test_module_log_levels() {
+        let filter = LogFilter::parse("error, git_status=debug,context=trace");
+
+        assert_eq!(filter.level("starship::modules::git_status"), Level::Debug);
+        assert_eq!(
+            filter.level("starship::modules::git_status::tests"),
+            Level::Debug
+        );
+        assert_eq!(filter.level("starship::context"), Level::Trace);
+        assert_eq!(filter.level("starship::modules::container"), Level::Error);
+        assert_eq!(LogFilter::parse("").level("starship::print"), Level::Warn);
+
+        let mut logger = StarshipLogger::default();
+        logger.set_log_filter(filter);
+        let metadata = |target| {
+            Metadata::builder()
+                .level(Level::Debug)
+                .target(target)
+                .build()
+        };
+        assert!(logger.enabled(&metadata("starship::modules::git_status")));
+        assert!(!logger.enabled(&metadata("starship::modules::git_branch")));
+    }
+
+    #[test]
+    fn test_json_log_format() -> io::Result<()> {
+        let log_dir = tempfile::tempdir()?;
+        let log_file = log_dir.path().join("test.log");
+
+        let mut logger = StarshipLogger::default();
+        logger.set_log_file_path(log_file.clone());
+        logger.set_log_level(Level::Warn);
+        logger.set_log_format(LogFormat::Json);
+
+        logger.log(
+            &Record::builder()
+                .level(Level::Warn)
+                .module_path(Some("starship::modules::git_status"))
+                .args(format_args!("warn \"quoted\""))
+                .build(),
+        );
+
+        logger.flush();
+        drop(logger);
+
+        let content = read_file(log_file)?;
+
+        assert_eq!(
+            content,
+            "{\"level\":\"WARN\",\"message\":\"warn \\\"quoted\\\"\",\"module\":\"starship::modules::git_status\"}\n"
+        );
+
+        log_dir.close()
+    }
+
     #[test]
     #[cfg(unix)]
     fn test_cleanup() -> io::Result<()> {
@@ -38,15 +38,33 @@ enum Commands {
         shell: CompletionShell,
     },
     /// Edit the starship configuration
+    #[clap(disable_help_subcommand = true)]
     Config {
+        #[clap(subcommand)]
+        command: Option<ConfigCommands>,
         /// Configuration key to edit
         #[clap(requires = "value")]
         name: Option<String>,
         /// Value to place into that key
         value: Option<String>,
     },
+    /// Copies the current directory, git branch, kubernetes context and exit code
+    /// to the clipboard using OSC 52
+    CopyContext {
+        /// Print the context to stdout instead of copying it
+        #[clap(long)]
+        print: bool,
+        #[clap(flatten)]
+        properties: Properties,
+    },
     /// Explains the currently showing modules
-    Explain(Properties),
+    Explain {
+        /// Also list the external commands run, files read and network connections made by each module
+        #[clap(long)]
+        trace_commands: bool,
+        #[clap(flatten)]
+        properties: Properties,
+    },
     ///  Prints the shell function used to execute starship
     Init {
         shell: String,
@@ -75,6 +93,12 @@ enum Commands {
         /// List out all preset names
         #[clap(short, long)]
         list: bool,
+        /// Merge the preset into your configuration, keeping the settings you already have
+        #[clap(long, conflicts_with_all = ["list", "output", "diff"])]
+        apply: bool,
+        /// Show what applying the preset would change in your configuration
+        #[clap(long, conflicts_with_all = ["list", "output"])]
+        diff: bool,
     },
     /// Prints the computed starship configuration
     PrintConfig {
@@ -100,6 +124,18 @@ enum Commands {
     },
     /// Generate random session key
     Session,
+    /// Renders the prompt into a shareable snapshot with its styles preserved
+    #[clap(visible_alias = "print-prompt")]
+    Snapshot {
+        /// The format of the snapshot
+        #[clap(long, value_enum, default_value = "ansi")]
+        format: snapshot::SnapshotFormat,
+        /// Output the snapshot to a file instead of stdout
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+        #[clap(flatten)]
+        properties: Properties,
+    },
     /// Prints time in milliseconds
     #[clap(hide = true)]
     Time,
@@ -108,16 +144,27 @@ enum Commands {
     /// Toggle a given starship module
     Toggle {
         /// The name of the module to be toggled
-        name: String,
+        #[clap(required_unless_present("compact"))]
+        name: Option<String>,
         /// The key of the config to be toggled
         #[clap(default_value = "disabled")]
         value: String,
+        /// Toggle the compact mode, where modules only show their symbol
+        #[clap(long, conflicts_with("name"))]
+        compact: bool,
     },
     #[cfg(feature = "config-schema")]
     /// Generate a schema for the starship configuration as JSON-schema
     ConfigSchema,
 }
 
+#[derive(Subcommand, Debug)]
+enum ConfigCommands {
+    /// Checks the configuration for unknown keys, values of the wrong type and invalid format
+    /// strings
+    Validate,
+}
+
 fn main() {
     // Configure the current terminal on windows to support ANSI escape sequences.
     #[cfg(windows)]
@@ -125,9 +172,10 @@ fn main() {
     logger::init();
     init_global_threadpool();
 
-    // Delete old log files
+    // Delete old log files and the state of closed sessions
     rayon::spawn(|| {
         let log_dir = logger::get_log_dir();
+        session_state::cleanup_stale_sessions(log_dir.join("sessions"));
         logger::cleanup_log_files(log_dir);
     });
 
@@ -206,10 +254,27 @@ fn main() {
                 print::module(&module_name, properties);
             }
         }
-        Commands::Preset { name, list, output } => print::preset_command(name, output, list),
-        Commands::Config { name, value } => {
+        Commands::Preset {
+            name,
+            list,
+            output,
+            apply,
+            diff,
+        } => match name {
+            Some(name) if apply || diff => {
+                configure::apply_preset(&Context::default(), name.0, diff)
+            }
+            name => print::preset_command(name, output, list),
+        },
+        Commands::Config {
+            command,
+            name,
+            value,
+        } => {
             let context = Context::default();
-            if let Some(name) = name {
+            if let Some(ConfigCommands::Validate) = command {
+                configure::validate_configuration(&context)
+            } else if let Some(name) = name {
                 if let Some(value) = value {
                     configure::update_configuration(&context, &name, &value)
                 }
@@ -221,8 +286,17 @@ fn main() {
         Commands::PrintConfig { default, name } => {
             configure::print_configuration(&Context::default(), default, &name);
         }
-        Commands::Toggle { name, value } => {
-            configure::toggle_configuration(&Context::default(), &name, &value)
+        Commands::Toggle {
+            name,
+            value,
+            compact,
+        } => {
+            let context = Context::default();
+            if compact {
+                configure::toggle_compact(&context);
+            } else if let Some(name) = name {
+                configure::toggle_configuration(&context, &name, &value);
+            }
         }
         Commands::BugReport => bug_report::create(),
         Commands::Time => {
@@ -234,7 +308,10 @@ fn main() {
                 None => println!("{}", -1),
             }
         }
-        Commands::Explain(props) => print::explain(props),
+        Commands::Explain {
+            trace_commands,
+            properties,
+        } => print::explain(properties, trace_commands),
         Commands::Timings(props) => print::timings(props),
         Commands::Completions { shell } => generate(
             shell,
@@ -250,6 +327,14 @@ fn main() {
                 .map(char::from)
                 .collect::<String>()
         ),
+        Commands::CopyContext { print, properties } => {
+            copy_context::copy_context(properties, print)
+        }
+        Commands::Snapshot {
+            format,
+            output,
+            properties,
+        } => snapshot::snapshot(properties, format, output),
         #[cfg(feature = "config-schema")]
         Commands::ConfigSchema => print::print_schema(),
     }
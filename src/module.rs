@@ -13,11 +13,15 @@ pub const ALL_MODULES: &[&str] = &[
     "azure",
     #[cfg(feature = "battery")]
     "battery",
+    "bazel",
+    "buck2",
     "buf",
     "bun",
     "c",
     "character",
+    "clipboard",
     "cmake",
+    "cmd_counter",
     "cmd_duration",
     "cobol",
     "conda",
@@ -26,7 +30,10 @@ pub const ALL_MODULES: &[&str] = &[
     "daml",
     "dart",
     "deno",
+    "dev_env",
     "directory",
+    "disk_usage",
+    "do_not_disturb",
     "docker_context",
     "dotnet",
     "elixir",
@@ -39,6 +46,7 @@ pub const ALL_MODULES: &[&str] = &[
     "git_branch",
     "git_commit",
     "git_metrics",
+    "git_signing",
     "git_state",
     "git_status",
     "golang",
@@ -52,13 +60,17 @@ pub const ALL_MODULES: &[&str] = &[
     "java",
     "jobs",
     "julia",
+    "keyboard_layout",
     "kotlin",
     "kubernetes",
     "line_break",
+    "load_average",
     "localip",
     "lua",
     "memory_usage",
     "meson",
+    "multiplexer",
+    "network",
     "nim",
     "nix_shell",
     "nodejs",
@@ -70,6 +82,7 @@ pub const ALL_MODULES: &[&str] = &[
     "perl",
     "php",
     "pijul_channel",
+    "public_ip",
     "pulumi",
     "purescript",
     "python",
@@ -84,15 +97,18 @@ pub const ALL_MODULES: &[&str] = &[
     "singularity",
     "solidity",
     "spack",
+    "ssh_agent",
     "status",
     "sudo",
     "swift",
     "terraform",
     "time",
+    "tool_versions",
     "username",
     "vagrant",
     "vcsh",
     "vlang",
+    "volume",
     "zig",
 ];
 
@@ -222,12 +238,12 @@ where
     if chunks.is_empty() {
         current
     } else {
-        let fill_size = term_width
-            .and_then(|tw| if tw > used { Some(tw - used) } else { None })
-            .map(|remaining| remaining / chunks.len());
+        let remaining = term_width.and_then(|tw| if tw > used { Some(tw - used) } else { None });
+        let fill_sizes = fill_widths(remaining, chunks.iter().map(|(_, fill)| *fill));
         chunks
             .into_iter()
-            .flat_map(|(strs, fill)| {
+            .zip(fill_sizes)
+            .flat_map(|((strs, fill), fill_size)| {
                 strs.into_iter()
                     .chain(std::iter::once(fill.ansi_string(fill_size)))
             })
@@ -236,6 +252,60 @@ where
     }
 }
 
+/// Splits the remaining space of a line between the fill segments, in proportion to their
+/// weights. Fills whose share is outside of their bounds are clamped, and the rest of the space
+/// is split again between the other fills.
+fn fill_widths<'a>(
+    remaining: Option<usize>,
+    fills: impl Iterator<Item = &'a FillSegment>,
+) -> Vec<Option<usize>> {
+    let fills = fills.collect::<Vec<_>>();
+    let Some(remaining) = remaining else {
+        return fills
+            .iter()
+            .map(|fill| (fill.min_width > 0).then_some(fill.min_width))
+            .collect();
+    };
+
+    let mut widths: Vec<Option<usize>> = vec![None; fills.len()];
+    loop {
+        let available = remaining.saturating_sub(widths.iter().flatten().sum());
+        let total_weight: usize = fills
+            .iter()
+            .zip(&widths)
+            .filter(|(_, width)| width.is_none())
+            .map(|(fill, _)| fill.weight)
+            .sum();
+        let share = |fill: &FillSegment| {
+            (available * fill.weight)
+                .checked_div(total_weight)
+                .unwrap_or_default()
+        };
+
+        let mut clamped = false;
+        for (fill, width) in fills.iter().zip(widths.iter_mut()) {
+            if width.is_some() {
+                continue;
+            }
+            let share = share(fill);
+            if share < fill.min_width {
+                *width = Some(fill.min_width);
+                clamped = true;
+            } else if fill.max_width.map_or(false, |max| share > max) {
+                *width = fill.max_width;
+                clamped = true;
+            }
+        }
+
+        if !clamped {
+            for (fill, width) in fills.iter().zip(widths.iter_mut()) {
+                width.get_or_insert_with(|| share(fill));
+            }
+            return widths;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -306,4 +376,54 @@ mod tests {
 
         assert!(!module.is_empty());
     }
+
+    fn fill_sizes(
+        remaining: Option<usize>,
+        fills: &[(usize, usize, Option<usize>)],
+    ) -> Vec<Option<usize>> {
+        let fills = fills
+            .iter()
+            .map(|(weight, min_width, max_width)| {
+                match Segment::fill(None, ".").with_fill_width(*weight, *min_width, *max_width) {
+                    Segment::Fill(fill) => fill,
+                    _ => unreachable!(),
+                }
+            })
+            .collect::<Vec<_>>();
+        fill_widths(remaining, fills.iter())
+    }
+
+    #[test]
+    fn test_fill_widths_by_weight() {
+        assert_eq!(
+            fill_sizes(Some(40), &[(1, 0, None), (1, 0, None)]),
+            vec![Some(20), Some(20)]
+        );
+        assert_eq!(
+            fill_sizes(Some(40), &[(1, 0, None), (3, 0, None)]),
+            vec![Some(10), Some(30)]
+        );
+        assert_eq!(
+            fill_sizes(Some(40), &[(0, 0, None), (1, 0, None)]),
+            vec![Some(0), Some(40)]
+        );
+    }
+
+    #[test]
+    fn test_fill_widths_clamped() {
+        // The space a clamped fill doesn't take is split between the others
+        assert_eq!(
+            fill_sizes(Some(40), &[(1, 0, Some(4)), (1, 0, None), (1, 0, None)]),
+            vec![Some(4), Some(18), Some(18)]
+        );
+        assert_eq!(
+            fill_sizes(Some(40), &[(1, 30, None), (1, 0, None)]),
+            vec![Some(30), Some(10)]
+        );
+        // Without space left, fills only keep their minimum width
+        assert_eq!(
+            fill_sizes(None, &[(1, 3, None), (1, 0, None)]),
+            vec![Some(3), None]
+        );
+    }
 }
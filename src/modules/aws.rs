@@ -1,10 +1,12 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use chrono::DateTime;
 use ini::Ini;
 use once_cell::unsync::OnceCell;
+use sha1::{Digest, Sha1};
 
 use super::{Context, Module, ModuleConfig};
 
@@ -145,6 +147,93 @@ fn get_credentials_duration(
     Some(expiration_date.timestamp() - chrono::Local::now().timestamp())
 }
 
+/// Parses the expiration dates of the AWS CLI caches, which older versions wrote with a `UTC`
+/// suffix instead of an offset
+fn parse_expiration(expiration: &str) -> Option<i64> {
+    let expiration = match expiration.strip_suffix("UTC") {
+        Some(expiration) => format!("{expiration}Z"),
+        None => expiration.to_string(),
+    };
+    DateTime::parse_from_rfc3339(&expiration)
+        .ok()
+        .map(|expiration| expiration.timestamp())
+}
+
+/// Reads the JSON files of a cache directory of the AWS CLI
+fn read_cache_files(dir: &Path) -> impl Iterator<Item = serde_json::Value> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().extension().map_or(false, |ext| ext == "json"))
+        .filter_map(|entry| crate::utils::read_file(entry.path()).ok())
+        .filter_map(|contents| serde_json::from_str(&contents).ok())
+}
+
+/// Returns the seconds until the SSO token of the profile expires.
+///
+/// The AWS CLI caches the token in a file named after the SHA-1 hash of the `sso_session` of
+/// the profile or, for legacy profiles, of its `sso_start_url`.
+fn get_sso_token_duration(
+    context: &Context,
+    aws_profile: Option<&Profile>,
+    aws_config: &AwsConfigFile,
+) -> Option<i64> {
+    let config = get_config(context, aws_config)?;
+    let section = get_profile_config(config, aws_profile)?;
+    let cache_key = section
+        .get("sso_session")
+        .or_else(|| section.get("sso_start_url"))?;
+
+    let mut hasher = Sha1::new();
+    hasher.update(cache_key.as_bytes());
+    let mut path = context.get_home()?;
+    path.push(".aws/sso/cache");
+    path.push(format!(
+        "{}.json",
+        crate::utils::encode_to_hex(&hasher.finalize())
+    ));
+
+    let token: serde_json::Value =
+        serde_json::from_str(&crate::utils::read_file(path).ok()?).ok()?;
+    let expiration = parse_expiration(token["expiresAt"].as_str()?)?;
+    Some(expiration - chrono::Local::now().timestamp())
+}
+
+/// Returns the seconds until the latest cached credentials of the role that the profile assumes
+/// expire.
+///
+/// The files of the AWS CLI cache are named after a hash of all the arguments of the
+/// `AssumeRole` call, so the credentials are found by the ARN of the role in their contents.
+fn get_assumed_role_duration(
+    context: &Context,
+    aws_profile: Option<&Profile>,
+    aws_config: &AwsConfigFile,
+) -> Option<i64> {
+    let config = get_config(context, aws_config)?;
+    let section = get_profile_config(config, aws_profile)?;
+    // e.g. `arn:aws:iam::123456789012:role/path/name`
+    let role_arn = section.get("role_arn")?;
+    let (account, role) = role_arn
+        .split(':')
+        .nth(4)
+        .zip(role_arn.rsplit('/').next())?;
+
+    let mut dir = context.get_home()?;
+    dir.push(".aws/cli/cache");
+    let expiration = read_cache_files(&dir)
+        .filter(|credentials| {
+            // e.g. `arn:aws:sts::123456789012:assumed-role/name/session`
+            let arn = credentials["AssumedRoleUser"]["Arn"].as_str().unwrap_or("");
+            arn.split(':').nth(4) == Some(account) && arn.split('/').nth(1) == Some(role)
+        })
+        .filter_map(|credentials| {
+            parse_expiration(credentials["Credentials"]["Expiration"].as_str()?)
+        })
+        .max()?;
+    Some(expiration - chrono::Local::now().timestamp())
+}
+
 fn alias_name(name: Option<String>, aliases: &HashMap<String, &str>) -> Option<String> {
     name.as_ref()
         .and_then(|n| aliases.get(n))
@@ -251,15 +340,33 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
-    let duration = {
-        get_credentials_duration(context, aws_profile.as_ref(), &aws_creds).map(|duration| {
-            if duration > 0 {
-                render_time((duration * 1000) as u128, false)
-            } else {
-                config.expiration_symbol.to_string()
-            }
-        })
+    let render_duration = |duration: i64| {
+        if duration > 0 {
+            render_time((duration * 1000) as u128, false)
+        } else {
+            config.expiration_symbol.to_string()
+        }
+    };
+
+    let credentials_duration = get_credentials_duration(context, aws_profile.as_ref(), &aws_creds);
+    let duration = credentials_duration.map(render_duration);
+
+    // Whatever expires first forces the next login
+    let expiration_remaining = [
+        credentials_duration,
+        get_sso_token_duration(context, aws_profile.as_ref(), &aws_config),
+        get_assumed_role_duration(context, aws_profile.as_ref(), &aws_config),
+    ]
+    .into_iter()
+    .flatten()
+    .min();
+    let style = match expiration_remaining {
+        Some(remaining) if remaining < config.expiration_warning_threshold => {
+            config.expiration_warning_style
+        }
+        _ => config.style,
     };
+    let expiration_remaining = expiration_remaining.map(render_duration);
 
     let mapped_region = alias_name(aws_region, &config.region_aliases);
 
@@ -272,13 +379,14 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
+                "style" => Some(Ok(style)),
                 _ => None,
             })
             .map(|variable| match variable {
                 "profile" => mapped_profile.as_ref().map(Ok),
                 "region" => mapped_region.as_ref().map(Ok),
                 "duration" => duration.as_ref().map(Ok),
+                "expiration_remaining" => expiration_remaining.as_ref().map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -287,7 +395,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `aws`: \n{}", error);
+            context.report_module_error("aws", &error);
             return None;
         }
     });
@@ -299,7 +407,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 mod tests {
     use crate::test::ModuleRenderer;
     use nu_ansi_term::Color;
-    use std::fs::{create_dir, File};
+    use std::fs::{self, create_dir, create_dir_all, File};
     use std::io::{self, Write};
 
     #[test]
@@ -809,9 +917,10 @@ aws_secret_access_key=dummy
                 now.to_rfc3339_opts(SecondsFormat::Secs, true),
             )
             .collect();
+        // Expired credentials are shown with the warning style
         let expected = Some(format!(
             "on {}",
-            Color::Yellow
+            Color::Red
                 .bold()
                 .paint(format!("☁️  astronauts (ap-northeast-2) [{symbol}] "))
         ));
@@ -1190,4 +1299,94 @@ source_profile = starship
         assert_eq!(expected, actual);
         dir.close()
     }
+
+    #[test]
+    fn sso_token_expiration() -> io::Result<()> {
+        use sha1::{Digest, Sha1};
+
+        let dir = tempfile::tempdir()?;
+        create_dir_all(dir.path().join(".aws/sso/cache"))?;
+        fs::write(
+            dir.path().join(".aws/config"),
+            "[profile astronauts]
+sso_session = crew
+[sso-session crew]
+sso_start_url = https://crew.awsapps.com/start
+",
+        )?;
+
+        let expires_at = (chrono::Utc::now() + chrono::Duration::hours(2))
+            .format("%Y-%m-%dT%H:%M:%SUTC")
+            .to_string();
+        let mut hasher = Sha1::new();
+        hasher.update(b"crew");
+        fs::write(
+            dir.path().join(format!(
+                ".aws/sso/cache/{}.json",
+                crate::utils::encode_to_hex(&hasher.finalize())
+            )),
+            format!(
+                r#"{{"startUrl": "https://crew.awsapps.com/start", "expiresAt": "{expires_at}"}}"#
+            ),
+        )?;
+
+        let actual = ModuleRenderer::new("aws")
+            .config(toml::toml! {
+                [aws]
+                format = "$expiration_remaining"
+            })
+            .env("HOME", dir.path().to_string_lossy())
+            .env("AWS_PROFILE", "astronauts")
+            .collect();
+
+        let possible_values = ["2h", "1h59m59s", "1h59m58s"].map(|d| Some(d.to_string()));
+        assert!(
+            possible_values.contains(&actual),
+            "time is not in range: {actual:?}"
+        );
+        dir.close()
+    }
+
+    #[test]
+    fn assumed_role_expiration_warning() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        create_dir_all(dir.path().join(".aws/cli/cache"))?;
+        fs::write(
+            dir.path().join(".aws/config"),
+            "[profile astronauts]
+role_arn = arn:aws:iam::123456789012:role/crew/pilot
+source_profile = default
+",
+        )?;
+
+        let credentials = |role: &str, seconds: i64| {
+            let expiration = (chrono::Utc::now() + chrono::Duration::seconds(seconds))
+                .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+            format!(
+                r#"{{"Credentials": {{"Expiration": "{expiration}"}}, "AssumedRoleUser": {{"Arn": "arn:aws:sts::123456789012:assumed-role/{role}/session"}}}}"#
+            )
+        };
+        let cache_dir = dir.path().join(".aws/cli/cache");
+        fs::write(cache_dir.join("a.json"), credentials("pilot", 120))?;
+        fs::write(cache_dir.join("b.json"), credentials("engineer", -3600))?;
+
+        let render = |threshold: i64| {
+            ModuleRenderer::new("aws")
+                .config(toml::toml! {
+                    [aws]
+                    format = "[$profile]($style)"
+                    force_display = true
+                    expiration_warning_threshold = threshold
+                })
+                .env("HOME", dir.path().to_string_lossy())
+                .env("AWS_PROFILE", "astronauts")
+                .collect()
+        };
+
+        let expected = Some(Color::Red.bold().paint("astronauts").to_string());
+        assert_eq!(expected, render(300));
+        let expected = Some(Color::Yellow.bold().paint("astronauts").to_string());
+        assert_eq!(expected, render(60));
+        dir.close()
+    }
 }
@@ -1,11 +1,13 @@
+use ini::Ini;
 use serde::{Deserialize, Serialize};
-use std::fs;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 use super::{Context, Module, ModuleConfig};
 
 use crate::configs::azure::AzureConfig;
 use crate::formatter::StringFormatter;
+use crate::utils;
 
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -23,9 +25,33 @@ struct User {
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 struct Subscription {
+    #[serde(default)]
+    id: String,
     name: String,
     user: User,
     is_default: bool,
+    #[serde(default)]
+    tenant_id: String,
+    #[serde(default)]
+    tenant_default_domain: Option<String>,
+    #[serde(default)]
+    tenant_display_name: Option<String>,
+}
+
+impl Subscription {
+    /// The display name of the tenant, which older versions of the CLI don't store
+    fn tenant(&self) -> &str {
+        self.tenant_display_name
+            .as_deref()
+            .or(self.tenant_default_domain.as_deref())
+            .filter(|tenant| !tenant.is_empty())
+            .unwrap_or(&self.tenant_id)
+    }
+}
+
+/// Looks up the value for a subscription or tenant by its name or its ID
+fn get_by_name_or_id<'a>(map: &HashMap<String, &'a str>, name: &str, id: &str) -> Option<&'a str> {
+    map.get(name).or_else(|| map.get(id)).copied()
 }
 
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
@@ -44,6 +70,12 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     }
 
     let subscription = subscription.unwrap();
+    let style = get_by_name_or_id(
+        &config.subscription_styles,
+        &subscription.name,
+        &subscription.id,
+    )
+    .unwrap_or(config.style);
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -52,16 +84,25 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
+                "style" => Some(Ok(style)),
                 _ => None,
             })
             .map(|variable| match variable {
-                "subscription" => Some(Ok(config
-                    .subscription_aliases
-                    .get(&subscription.name)
-                    .copied()
-                    .unwrap_or(&subscription.name))),
+                "subscription" => Some(Ok(get_by_name_or_id(
+                    &config.subscription_aliases,
+                    &subscription.name,
+                    &subscription.id,
+                )
+                .unwrap_or(&subscription.name))),
+                "subscription_id" => Some(Ok(subscription.id.as_str())),
                 "username" => Some(Ok(&subscription.user.name)),
+                "tenant" => Some(Ok(get_by_name_or_id(
+                    &config.tenant_aliases,
+                    subscription.tenant(),
+                    &subscription.tenant_id,
+                )
+                .unwrap_or(subscription.tenant()))),
+                "tenant_id" => Some(Ok(subscription.tenant_id.as_str())),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -70,7 +111,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `azure`:\n{}", error);
+            context.report_module_error("azure", &error);
             return None;
         }
     });
@@ -79,18 +120,33 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 }
 
 fn get_azure_profile_info(context: &Context) -> Option<Subscription> {
-    let mut config_path = get_config_file_location(context)?;
-    config_path.push("azureProfile.json");
-
-    let azure_profile = load_azure_profile(&config_path)?;
-    azure_profile
-        .subscriptions
-        .into_iter()
-        .find(|s| s.is_default)
+    let config_dir = get_config_file_location(context)?;
+
+    let azure_profile = load_azure_profile(&config_dir.join("azureProfile.json"))?;
+    let active_id = get_cloud_subscription_id(&config_dir);
+    let mut subscriptions = azure_profile.subscriptions.into_iter();
+    match active_id {
+        Some(id) => subscriptions.find(|s| s.id.eq_ignore_ascii_case(&id)),
+        None => subscriptions.find(|s| s.is_default),
+    }
+}
+
+/// Returns the subscription that `az account set` selected for the active cloud.
+///
+/// The CLI stores it in `clouds.config` in addition to flagging it as default in
+/// `azureProfile.json`, which other tools may still have to catch up with.
+fn get_cloud_subscription_id(config_dir: &Path) -> Option<String> {
+    let cloud = Ini::load_from_file(config_dir.join("config"))
+        .ok()
+        .and_then(|config| Some(config.section(Some("cloud"))?.get("name")?.to_string()))
+        .unwrap_or_else(|| "AzureCloud".to_string());
+    let clouds = Ini::load_from_file(config_dir.join("clouds.config")).ok()?;
+    let id = clouds.section(Some(cloud))?.get("subscription")?;
+    (!id.is_empty()).then(|| id.to_string())
 }
 
 fn load_azure_profile(config_path: &PathBuf) -> Option<AzureProfile> {
-    let json_data = fs::read_to_string(config_path).ok()?;
+    let json_data = utils::read_file(config_path).ok()?;
     let sanitized_json_data = json_data.strip_prefix('\u{feff}').unwrap_or(&json_data);
     if let Ok(azure_profile) = serde_json::from_str::<AzureProfile>(sanitized_json_data) {
         Some(azure_profile)
@@ -792,4 +848,94 @@ mod tests {
         bom_file.sync_all()?;
         Ok(bom_file_path)
     }
+
+    const MULTI_TENANT_PROFILE: &str = r#"{
+        "installationId": "3deacd2a-b9db-77e1-aa42-23e2f8dfffc3",
+        "subscriptions": [
+          {
+            "id": "f568c543-d12e-de0b-3d85-69843598b565",
+            "name": "Production",
+            "state": "Enabled",
+            "user": {
+              "name": "user@domain.com",
+              "type": "user"
+            },
+            "isDefault": false,
+            "tenantId": "0e8a15ec-b0f5-d355-7062-8ece54c59aee",
+            "tenantDefaultDomain": "contoso.onmicrosoft.com",
+            "tenantDisplayName": "Contoso",
+            "environmentName": "AzureCloud",
+            "managedByTenants": []
+          },
+          {
+            "id": "f3935dc9-92b5-9a93-da7b-42c325d86939",
+            "name": "Development",
+            "state": "Enabled",
+            "user": {
+              "name": "user@domain.com",
+              "type": "user"
+            },
+            "isDefault": true,
+            "tenantId": "f0273a19-7779-e40a-00a1-53b8331b3bb6",
+            "environmentName": "AzureCloud",
+            "managedByTenants": []
+          }
+        ]
+      }
+    "#;
+
+    #[test]
+    fn tenant_and_subscription_id() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        generate_test_config(&dir, MULTI_TENANT_PROFILE)?;
+
+        let actual = ModuleRenderer::new("azure")
+            .config(toml::toml! {
+                [azure]
+                format = "$subscription $subscription_id $tenant $tenant_id"
+                disabled = false
+                [azure.tenant_aliases]
+                "f0273a19-7779-e40a-00a1-53b8331b3bb6" = "Fabrikam"
+            })
+            .env("AZURE_CONFIG_DIR", dir.path().to_string_lossy())
+            .collect();
+        let expected = Some(
+            "Development f3935dc9-92b5-9a93-da7b-42c325d86939 Fabrikam f0273a19-7779-e40a-00a1-53b8331b3bb6"
+                .to_string(),
+        );
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn subscription_from_cloud_config_with_style() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        generate_test_config(&dir, MULTI_TENANT_PROFILE)?;
+        save_string_to_file(
+            &dir,
+            "[cloud]\nname = AzureCloud\n".to_string(),
+            String::from("config"),
+        )?;
+        save_string_to_file(
+            &dir,
+            "[AzureCloud]\nsubscription = f568c543-d12e-de0b-3d85-69843598b565\n".to_string(),
+            String::from("clouds.config"),
+        )?;
+
+        let actual = ModuleRenderer::new("azure")
+            .config(toml::toml! {
+                [azure]
+                format = "[$subscription@$tenant]($style)"
+                disabled = false
+                [azure.subscription_aliases]
+                "f568c543-d12e-de0b-3d85-69843598b565" = "prod"
+                [azure.subscription_styles]
+                "Production" = "bold red"
+            })
+            .env("AZURE_CONFIG_DIR", dir.path().to_string_lossy())
+            .collect();
+        let expected = Some(Color::Red.bold().paint("prod@Contoso").to_string());
+        assert_eq!(actual, expected);
+        dir.close()
+    }
 }
@@ -1,33 +1,66 @@
 use super::{Context, Module, ModuleConfig};
-use crate::configs::battery::BatteryConfig;
+use crate::configs::battery::{BatteryConfig, BatteryDisplayConfig};
 #[cfg(test)]
 use mockall::automock;
 use starship_battery as battery;
 
 use crate::formatter::StringFormatter;
+use crate::segment::Segment;
+use crate::utils::render_time;
 
 /// Creates a module for the battery percentage and charging state
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
-    let battery_status = get_battery_status(context)?;
-    let BatteryStatus { state, percentage } = battery_status;
+    let batteries = context.battery_info_provider.get_batteries_info();
 
     let mut module = context.new_module("battery");
     let config: BatteryConfig = BatteryConfig::try_load(module.config);
 
-    // Parse config under `display`.
-    // Select the first style that match the threshold,
-    // if all thresholds are lower do not display battery module.
-    let display_style = config
-        .display
-        .iter()
-        .find(|display_style| percentage <= display_style.threshold as f32)?;
+    let statuses: Vec<BatteryStatus> = if config.per_battery {
+        batteries
+            .iter()
+            .filter_map(|battery| get_battery_status(std::slice::from_ref(battery)))
+            .collect()
+    } else {
+        get_battery_status(&batteries).into_iter().collect()
+    };
+
+    let mut segments = Vec::new();
+    for status in &statuses {
+        // Parse config under `display`.
+        // Select the first style that match the threshold,
+        // if all thresholds are lower do not display the battery.
+        let Some(display_style) = config
+            .display
+            .iter()
+            .find(|display_style| status.percentage <= display_style.threshold as f32)
+        else {
+            continue;
+        };
+
+        segments.extend(format_battery(context, &config, display_style, status)?);
+    }
+
+    if segments.is_empty() {
+        return None;
+    }
+
+    module.set_segments(segments);
+    Some(module)
+}
 
+/// Formats the status of a battery, or of all batteries combined
+fn format_battery(
+    context: &Context,
+    config: &BatteryConfig,
+    display_style: &BatteryDisplayConfig,
+    status: &BatteryStatus,
+) -> Option<Vec<Segment>> {
     // Parse the format string and build the module
     match StringFormatter::new(config.format) {
         Ok(formatter) => {
             let formatter = formatter
                 .map_meta(|variable, _| match variable {
-                    "symbol" => match state {
+                    "symbol" => match status.state {
                         battery::State::Full => Some(config.full_symbol),
                         battery::State::Charging => display_style
                             .charging_symbol
@@ -45,34 +78,64 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                     _ => None,
                 })
                 .map(|variable| match variable {
-                    "percentage" => Some(Ok(format!("{}%", percentage.round()))),
+                    "percentage" => Some(Ok(format!("{}%", status.percentage.round()))),
+                    "time_remaining" => status.time_remaining.map(|seconds| {
+                        // Round to whole minutes, the estimate isn't more precise than that
+                        let minutes = ((seconds + 30) / 60).max(1);
+                        Ok(render_time(u128::from(minutes) * 60_000, false))
+                    }),
+                    "power_draw" => {
+                        (status.power_draw > 0.0).then(|| Ok(format!("{:.1}W", status.power_draw)))
+                    }
                     _ => None,
                 });
 
             match formatter.parse(None, Some(context)) {
-                Ok(format_string) => {
-                    module.set_segments(format_string);
-                    Some(module)
-                }
+                Ok(format_string) => Some(format_string),
                 Err(e) => {
-                    log::warn!("Cannot parse `battery.format`: {}", e);
+                    context.report_module_error("battery", &e);
                     None
                 }
             }
         }
         Err(e) => {
-            log::warn!("Cannot load `battery.format`: {}", e);
+            context.report_module_error("battery", &e);
             None
         }
     }
 }
 
-fn get_battery_status(context: &Context) -> Option<BatteryStatus> {
-    let battery_info = context.battery_info_provider.get_battery_info()?;
+/// Combines the information of the given batteries into a single status
+fn get_battery_status(batteries: &[BatteryInfo]) -> Option<BatteryStatus> {
+    let battery_info = batteries.iter().fold(
+        BatteryInfo {
+            energy: 0.0,
+            energy_full: 0.0,
+            energy_rate: 0.0,
+            state: battery::State::Unknown,
+        },
+        |mut acc, x| {
+            acc.energy += x.energy;
+            acc.energy_full += x.energy_full;
+            acc.energy_rate += x.energy_rate.abs();
+            acc.state = merge_battery_states(acc.state, x.state);
+            acc
+        },
+    );
+
     if battery_info.energy_full != 0.0 {
+        let remaining_energy = match battery_info.state {
+            battery::State::Discharging => Some(battery_info.energy),
+            battery::State::Charging => Some(battery_info.energy_full - battery_info.energy),
+            _ => None,
+        };
         let battery = BatteryStatus {
             percentage: battery_info.energy / battery_info.energy_full * 100.0,
             state: battery_info.state,
+            power_draw: battery_info.energy_rate,
+            time_remaining: remaining_energy
+                .filter(|_| battery_info.energy_rate > 0.0)
+                .map(|energy| (energy.max(0.0) / battery_info.energy_rate) as u64),
         };
         log::debug!("Battery status: {:?}", battery);
         Some(battery)
@@ -103,9 +166,11 @@ fn merge_battery_states(state1: battery::State, state2: battery::State) -> batte
     }
 }
 
+/// Energy in joules and power in watts, as reported by a single battery
 pub struct BatteryInfo {
     energy: f32,
     energy_full: f32,
+    energy_rate: f32,
     state: battery::State,
 }
 
@@ -113,54 +178,49 @@ pub struct BatteryInfo {
 struct BatteryStatus {
     percentage: f32,
     state: battery::State,
+    /// The power in watts the batteries are charged or discharged with
+    power_draw: f32,
+    /// The estimated seconds until the batteries are empty or full
+    time_remaining: Option<u64>,
 }
 
 #[cfg_attr(test, automock)]
 pub trait BatteryInfoProvider {
-    fn get_battery_info(&self) -> Option<BatteryInfo>;
+    fn get_batteries_info(&self) -> Vec<BatteryInfo>;
 }
 
 pub struct BatteryInfoProviderImpl;
 
 impl BatteryInfoProvider for BatteryInfoProviderImpl {
-    fn get_battery_info(&self) -> Option<BatteryInfo> {
-        let battery_manager = battery::Manager::new().ok()?;
-        let batteries = battery_manager.batteries().ok()?;
-        Some(
-            batteries
-                .filter_map(|battery| match battery {
-                    Ok(battery) => {
-                        log::debug!("Battery found: {:?}", battery);
-                        Some(BatteryInfo {
-                            energy: battery.energy().value,
-                            energy_full: battery.energy_full().value,
-                            state: battery.state(),
-                        })
-                    }
-                    Err(e) => {
-                        let level = if cfg!(target_os = "linux") {
-                            log::Level::Info
-                        } else {
-                            log::Level::Warn
-                        };
-                        log::log!(level, "Unable to access battery information:\n{}", &e);
-                        None
-                    }
-                })
-                .fold(
-                    BatteryInfo {
-                        energy: 0.0,
-                        energy_full: 0.0,
-                        state: battery::State::Unknown,
-                    },
-                    |mut acc, x| {
-                        acc.energy += x.energy;
-                        acc.energy_full += x.energy_full;
-                        acc.state = merge_battery_states(acc.state, x.state);
-                        acc
-                    },
-                ),
-        )
+    fn get_batteries_info(&self) -> Vec<BatteryInfo> {
+        let Ok(battery_manager) = battery::Manager::new() else {
+            return Vec::new();
+        };
+        let Ok(batteries) = battery_manager.batteries() else {
+            return Vec::new();
+        };
+        batteries
+            .filter_map(|battery| match battery {
+                Ok(battery) => {
+                    log::debug!("Battery found: {:?}", battery);
+                    Some(BatteryInfo {
+                        energy: battery.energy().value,
+                        energy_full: battery.energy_full().value,
+                        energy_rate: battery.energy_rate().value,
+                        state: battery.state(),
+                    })
+                }
+                Err(e) => {
+                    let level = if cfg!(target_os = "linux") {
+                        log::Level::Info
+                    } else {
+                        log::Level::Warn
+                    };
+                    log::log!(level, "Unable to access battery information:\n{}", &e);
+                    None
+                }
+            })
+            .collect()
     }
 }
 
@@ -174,7 +234,9 @@ mod tests {
     fn no_battery_status() {
         let mut mock = MockBatteryInfoProvider::new();
 
-        mock.expect_get_battery_info().times(1).returning(|| None);
+        mock.expect_get_batteries_info()
+            .times(1)
+            .returning(Vec::new);
 
         let actual = ModuleRenderer::new("battery")
             .config(toml::toml! {
@@ -193,12 +255,13 @@ mod tests {
     fn ignores_zero_capacity_battery() {
         let mut mock = MockBatteryInfoProvider::new();
 
-        mock.expect_get_battery_info().times(1).returning(|| {
-            Some(BatteryInfo {
+        mock.expect_get_batteries_info().times(1).returning(|| {
+            vec![BatteryInfo {
                 energy: 0.0,
                 energy_full: 0.0,
+                energy_rate: 0.0,
                 state: battery::State::Full,
-            })
+            }]
         });
 
         let actual = ModuleRenderer::new("battery")
@@ -218,12 +281,13 @@ mod tests {
     fn battery_full() {
         let mut mock = MockBatteryInfoProvider::new();
 
-        mock.expect_get_battery_info().times(1).returning(|| {
-            Some(BatteryInfo {
+        mock.expect_get_batteries_info().times(1).returning(|| {
+            vec![BatteryInfo {
                 energy: 1000.0,
                 energy_full: 1000.0,
+                energy_rate: 0.0,
                 state: battery::State::Full,
-            })
+            }]
         });
 
         let actual = ModuleRenderer::new("battery")
@@ -243,12 +307,13 @@ mod tests {
     fn battery_charging() {
         let mut mock = MockBatteryInfoProvider::new();
 
-        mock.expect_get_battery_info().times(1).returning(|| {
-            Some(BatteryInfo {
+        mock.expect_get_batteries_info().times(1).returning(|| {
+            vec![BatteryInfo {
                 energy: 800.0,
                 energy_full: 1000.0,
+                energy_rate: 0.0,
                 state: battery::State::Charging,
-            })
+            }]
         });
 
         let actual = ModuleRenderer::new("battery")
@@ -268,12 +333,13 @@ mod tests {
     fn battery_discharging() {
         let mut mock = MockBatteryInfoProvider::new();
 
-        mock.expect_get_battery_info().times(1).returning(|| {
-            Some(BatteryInfo {
+        mock.expect_get_batteries_info().times(1).returning(|| {
+            vec![BatteryInfo {
                 energy: 800.0,
                 energy_full: 1000.0,
+                energy_rate: 0.0,
                 state: battery::State::Discharging,
-            })
+            }]
         });
 
         let actual = ModuleRenderer::new("battery")
@@ -293,12 +359,13 @@ mod tests {
     fn battery_unknown() {
         let mut mock = MockBatteryInfoProvider::new();
 
-        mock.expect_get_battery_info().times(1).returning(|| {
-            Some(BatteryInfo {
+        mock.expect_get_batteries_info().times(1).returning(|| {
+            vec![BatteryInfo {
                 energy: 0.0,
                 energy_full: 1.0,
+                energy_rate: 0.0,
                 state: battery::State::Unknown,
-            })
+            }]
         });
 
         let actual = ModuleRenderer::new("battery")
@@ -318,12 +385,13 @@ mod tests {
     fn battery_empty() {
         let mut mock = MockBatteryInfoProvider::new();
 
-        mock.expect_get_battery_info().times(1).returning(|| {
-            Some(BatteryInfo {
+        mock.expect_get_batteries_info().times(1).returning(|| {
+            vec![BatteryInfo {
                 energy: 0.0,
                 energy_full: 1000.0,
+                energy_rate: 0.0,
                 state: battery::State::Empty,
-            })
+            }]
         });
 
         let actual = ModuleRenderer::new("battery")
@@ -343,12 +411,13 @@ mod tests {
     fn battery_hidden_when_percentage_above_threshold() {
         let mut mock = MockBatteryInfoProvider::new();
 
-        mock.expect_get_battery_info().times(1).returning(|| {
-            Some(BatteryInfo {
+        mock.expect_get_batteries_info().times(1).returning(|| {
+            vec![BatteryInfo {
                 energy: 600.0,
                 energy_full: 1000.0,
+                energy_rate: 0.0,
                 state: battery::State::Full,
-            })
+            }]
         });
 
         let actual = ModuleRenderer::new("battery")
@@ -368,12 +437,13 @@ mod tests {
     fn battery_uses_style() {
         let mut mock = MockBatteryInfoProvider::new();
 
-        mock.expect_get_battery_info().times(1).returning(|| {
-            Some(BatteryInfo {
+        mock.expect_get_batteries_info().times(1).returning(|| {
+            vec![BatteryInfo {
                 energy: 400.0,
                 energy_full: 1000.0,
+                energy_rate: 0.0,
                 state: battery::State::Discharging,
-            })
+            }]
         });
 
         let actual = ModuleRenderer::new("battery")
@@ -393,12 +463,13 @@ mod tests {
     fn battery_displayed_precision() {
         let mut mock = MockBatteryInfoProvider::new();
 
-        mock.expect_get_battery_info().times(1).returning(|| {
-            Some(BatteryInfo {
+        mock.expect_get_batteries_info().times(1).returning(|| {
+            vec![BatteryInfo {
                 energy: 129.87654,
                 energy_full: 1000.0,
+                energy_rate: 0.0,
                 state: battery::State::Discharging,
-            })
+            }]
         });
 
         let actual = ModuleRenderer::new("battery")
@@ -413,4 +484,173 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn multiple_batteries_combined() {
+        let mut mock = MockBatteryInfoProvider::new();
+
+        mock.expect_get_batteries_info().times(1).returning(|| {
+            vec![
+                BatteryInfo {
+                    energy: 900.0,
+                    energy_full: 1000.0,
+                    energy_rate: 0.0,
+                    state: battery::State::Unknown,
+                },
+                BatteryInfo {
+                    energy: 100.0,
+                    energy_full: 1000.0,
+                    energy_rate: 10.0,
+                    state: battery::State::Discharging,
+                },
+            ]
+        });
+
+        let actual = ModuleRenderer::new("battery")
+            .config(toml::toml! {
+                [battery]
+                format = "$percentage $time_remaining $power_draw"
+                [[battery.display]]
+                threshold = 100
+                style = ""
+            })
+            .battery_info_provider(&mock)
+            .collect();
+        let expected = Some(String::from("50% 2m 10.0W"));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn battery_percentage_bar() {
+        let mut mock = MockBatteryInfoProvider::new();
+
+        mock.expect_get_batteries_info().times(1).returning(|| {
+            vec![BatteryInfo {
+                energy: 60.0,
+                energy_full: 100.0,
+                energy_rate: 0.0,
+                state: battery::State::Discharging,
+            }]
+        });
+
+        let actual = ModuleRenderer::new("battery")
+            .config(toml::toml! {
+                [battery]
+                format = "${percentage | bar(5)} $percentage"
+                [[battery.display]]
+                threshold = 100
+                style = ""
+            })
+            .battery_info_provider(&mock)
+            .collect();
+        let expected = Some(String::from("███░░ 60%"));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn battery_time_until_full() {
+        let mut mock = MockBatteryInfoProvider::new();
+
+        mock.expect_get_batteries_info().times(1).returning(|| {
+            vec![BatteryInfo {
+                energy: 36_000.0,
+                energy_full: 180_000.0,
+                energy_rate: -20.0,
+                state: battery::State::Charging,
+            }]
+        });
+
+        let actual = ModuleRenderer::new("battery")
+            .config(toml::toml! {
+                [battery]
+                format = "$percentage( $time_remaining)"
+                [[battery.display]]
+                threshold = 100
+                style = ""
+            })
+            .battery_info_provider(&mock)
+            .collect();
+        let expected = Some(String::from("20% 2h"));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn battery_without_rate_hides_estimates() {
+        let mut mock = MockBatteryInfoProvider::new();
+
+        mock.expect_get_batteries_info().times(1).returning(|| {
+            vec![BatteryInfo {
+                energy: 500.0,
+                energy_full: 1000.0,
+                energy_rate: 0.0,
+                state: battery::State::Discharging,
+            }]
+        });
+
+        let actual = ModuleRenderer::new("battery")
+            .config(toml::toml! {
+                [battery]
+                format = "$percentage( $time_remaining)( $power_draw)"
+                [[battery.display]]
+                threshold = 100
+                style = ""
+            })
+            .battery_info_provider(&mock)
+            .collect();
+        let expected = Some(String::from("50%"));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn per_battery_display() {
+        let mut mock = MockBatteryInfoProvider::new();
+
+        mock.expect_get_batteries_info().times(1).returning(|| {
+            vec![
+                BatteryInfo {
+                    energy: 1000.0,
+                    energy_full: 1000.0,
+                    energy_rate: 0.0,
+                    state: battery::State::Full,
+                },
+                BatteryInfo {
+                    energy: 50.0,
+                    energy_full: 1000.0,
+                    energy_rate: 5.0,
+                    state: battery::State::Discharging,
+                },
+                BatteryInfo {
+                    energy: 0.0,
+                    energy_full: 0.0,
+                    energy_rate: 0.0,
+                    state: battery::State::Unknown,
+                },
+            ]
+        });
+
+        let actual = ModuleRenderer::new("battery")
+            .config(toml::toml! {
+                [battery]
+                per_battery = true
+                [[battery.display]]
+                threshold = 10
+                style = "bold red"
+                [[battery.display]]
+                threshold = 100
+                style = "green"
+            })
+            .battery_info_provider(&mock)
+            .collect();
+        let expected = Some(format!(
+            "{} {} ",
+            Color::Green.paint("󰁹 100%"),
+            Color::Red.bold().paint("󰂃 5%")
+        ));
+
+        assert_eq!(expected, actual);
+    }
 }
@@ -0,0 +1,228 @@
+use std::path::Path;
+
+use super::{Context, Module, ModuleConfig};
+use crate::configs::bazel::BazelConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+use crate::utils;
+
+/// Files that mark the root of a Bazel workspace
+const WORKSPACE_FILES: [&str; 4] = ["MODULE.bazel", "REPO.bazel", "WORKSPACE", "WORKSPACE.bazel"];
+
+/// Creates a module with the Bazel version pinned by the current workspace
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("bazel");
+    let config = BazelConfig::try_load(module.config);
+
+    let is_bazel_project = context
+        .try_begin_scan()?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_bazel_project && !config.recursive {
+        return None;
+    }
+    let workspace_root = find_workspace_root(&context.current_dir);
+    if !is_bazel_project && workspace_root.is_none() {
+        return None;
+    }
+
+    let version = context
+        .get_env("USE_BAZEL_VERSION")
+        .or_else(|| get_pinned_version(workspace_root?));
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => {
+                    let version = version.as_deref()?;
+                    // Bazelisk also accepts labels like `latest` or `last_green`
+                    if version.starts_with(|c: char| c.is_ascii_digit()) {
+                        VersionFormatter::format_module_version(
+                            module.get_name(),
+                            version,
+                            config.version_format,
+                        )
+                    } else {
+                        Some(version.to_string())
+                    }
+                }
+                .map(Ok),
+                "mode" => get_mode(workspace_root?, version.as_deref()).map(Ok),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            context.report_module_error("bazel", &error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn find_workspace_root(current_dir: &Path) -> Option<&Path> {
+    current_dir
+        .ancestors()
+        .find(|dir| WORKSPACE_FILES.iter().any(|file| dir.join(file).is_file()))
+}
+
+/// Reads the version from `.bazelversion`, which is what Bazelisk uses to pick a Bazel release
+fn get_pinned_version(workspace_root: &Path) -> Option<String> {
+    let content = utils::read_file(workspace_root.join(".bazelversion")).ok()?;
+    let version = content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())?;
+    // Forks are pinned as `<fork>/<version>`
+    let version = version.rsplit('/').next().unwrap_or(version);
+    Some(version.to_string())
+}
+
+/// Returns `bzlmod` if the workspace manages its dependencies with `MODULE.bazel`,
+/// or `workspace` if it uses the legacy `WORKSPACE` file
+fn get_mode(workspace_root: &Path, version: Option<&str>) -> Option<String> {
+    let bzlmod_flag = utils::read_file(workspace_root.join(".bazelrc"))
+        .ok()
+        .and_then(|bazelrc| parse_bzlmod_flag(&bazelrc));
+    // Bzlmod is enabled by default since Bazel 7
+    let bzlmod_enabled = bzlmod_flag.unwrap_or_else(|| {
+        version
+            .and_then(|version| version.split('.').next()?.parse::<u32>().ok())
+            .map_or(true, |major| major >= 7)
+    });
+
+    if bzlmod_enabled && workspace_root.join("MODULE.bazel").is_file() {
+        Some("bzlmod".to_string())
+    } else if ["WORKSPACE", "WORKSPACE.bazel"]
+        .iter()
+        .any(|file| workspace_root.join(file).is_file())
+    {
+        Some("workspace".to_string())
+    } else {
+        None
+    }
+}
+
+/// Finds the last `--enable_bzlmod` flag that applies to all builds of the workspace
+fn parse_bzlmod_flag(bazelrc: &str) -> Option<bool> {
+    bazelrc.lines().rev().find_map(|line| {
+        let line = line.split('#').next()?;
+        let mut words = line.split_whitespace();
+        if !matches!(words.next(), Some("common" | "build")) {
+            return None;
+        }
+        words.rev().find_map(|word| match word {
+            "--enable_bzlmod" | "--enable_bzlmod=true" | "--enable_bzlmod=1" => Some(true),
+            "--noenable_bzlmod" | "--enable_bzlmod=false" | "--enable_bzlmod=0" => Some(false),
+            _ => None,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
+    use std::fs::{self, File};
+    use std::io;
+
+    #[test]
+    fn folder_without_bazel_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = ModuleRenderer::new("bazel").path(dir.path()).collect();
+        assert_eq!(None, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_bzlmod_workspace() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("MODULE.bazel"))?.sync_all()?;
+        fs::write(dir.path().join(".bazelversion"), "7.1.0\n")?;
+
+        let actual = ModuleRenderer::new("bazel").path(dir.path()).collect();
+        let expected = Some(format!(
+            "via {}",
+            Color::Green.bold().paint("🌿 v7.1.0 (bzlmod) ")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_legacy_workspace() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("MODULE.bazel"))?.sync_all()?;
+        File::create(dir.path().join("WORKSPACE"))?.sync_all()?;
+        fs::write(dir.path().join(".bazelversion"), "6.4.0")?;
+        fs::write(
+            dir.path().join(".bazelrc"),
+            "build --enable_bzlmod\ncommon --noenable_bzlmod # not yet\n",
+        )?;
+
+        let actual = ModuleRenderer::new("bazel").path(dir.path()).collect();
+        let expected = Some(format!(
+            "via {}",
+            Color::Green.bold().paint("🌿 v6.4.0 (workspace) ")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn bazelisk_version_override() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("WORKSPACE.bazel"))?.sync_all()?;
+
+        let actual = ModuleRenderer::new("bazel")
+            .path(dir.path())
+            .env("USE_BAZEL_VERSION", "last_green")
+            .collect();
+        let expected = Some(format!(
+            "via {}",
+            Color::Green.bold().paint("🌿 last_green (workspace) ")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn subdirectory_of_workspace() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let package = dir.path().join("services/api");
+        fs::create_dir_all(&package)?;
+        File::create(dir.path().join("MODULE.bazel"))?.sync_all()?;
+        fs::write(dir.path().join(".bazelversion"), "8.0.0")?;
+
+        let actual = ModuleRenderer::new("bazel").path(&package).collect();
+        assert_eq!(None, actual);
+
+        let actual = ModuleRenderer::new("bazel")
+            .path(&package)
+            .config(toml::toml! {
+                [bazel]
+                recursive = true
+            })
+            .collect();
+        let expected = Some(format!(
+            "via {}",
+            Color::Green.bold().paint("🌿 v8.0.0 (bzlmod) ")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
@@ -0,0 +1,239 @@
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{Context, Module, ModuleConfig};
+use crate::configs::buck2::Buck2Config;
+use crate::formatter::{StringFormatter, VersionFormatter};
+use crate::utils;
+
+const VERSION_KEY: &str = "buck2_version";
+
+/// Creates a module with the buck2 version and the cell of the current directory
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("buck2");
+    let config = Buck2Config::try_load(module.config);
+
+    let is_buck2_project = context
+        .try_begin_scan()?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_buck2_project {
+        return None;
+    }
+
+    let project_root = find_project_root(&context.current_dir);
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => {
+                    let buck2_version = get_buck2_version(context, &config, project_root)?;
+                    VersionFormatter::format_module_version(
+                        module.get_name(),
+                        &buck2_version,
+                        config.version_format,
+                    )
+                    .map(Ok)
+                }
+                "cell" => get_cell_name(project_root?, &context.current_dir).map(Ok),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            context.report_module_error("buck2", &error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Finds the root of the buck2 project, which is marked by a `.buckroot` file,
+/// or otherwise is the outermost directory with a `.buckconfig`
+fn find_project_root(current_dir: &Path) -> Option<&Path> {
+    let mut root = None;
+    for dir in current_dir.ancestors() {
+        if dir.join(".buckroot").is_file() {
+            return Some(dir);
+        }
+        if dir.join(".buckconfig").is_file() {
+            root = Some(dir);
+        }
+    }
+    root
+}
+
+/// Returns the version of buck2, reusing the version found by an earlier prompt of
+/// this session in the same project while it is younger than `version_cache` seconds.
+fn get_buck2_version(
+    context: &Context,
+    config: &Buck2Config,
+    project_root: Option<&Path>,
+) -> Option<String> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    // Projects often pin their own buck2 binary, so the version is cached per project
+    let project = project_root.map_or_else(String::new, |root| root.to_string_lossy().into());
+
+    let cached = context.get_session_value(VERSION_KEY).and_then(|value| {
+        let mut parts = value.splitn(3, ' ');
+        let checked_at = parts.next()?.parse::<u64>().ok()?;
+        let version = parts.next()?.to_string();
+        let is_fresh = now.saturating_sub(checked_at) < config.version_cache;
+        (parts.next()? == project && is_fresh).then_some(version)
+    });
+    if cached.is_some() {
+        return cached;
+    }
+
+    let version = parse_buck2_version(&context.exec_cmd("buck2", &["--version"])?.stdout)?;
+    context.set_session_value(VERSION_KEY, &format!("{now} {version} {project}"));
+    Some(version)
+}
+
+/// Parses `buck2 --version` output, e.g. `buck2 2024-10-15 8e2c8d3a1d0d <build-id>`
+fn parse_buck2_version(output: &str) -> Option<String> {
+    let mut words = output.split_whitespace();
+    if words.next()? != "buck2" {
+        return None;
+    }
+    words.next().map(ToString::to_string)
+}
+
+/// Finds the cell that contains the current directory, from the `[cells]` section
+/// of the `.buckconfig` in the project root
+fn get_cell_name(project_root: &Path, current_dir: &Path) -> Option<String> {
+    let buckconfig = utils::read_file(project_root.join(".buckconfig")).ok()?;
+    let relative_dir = current_dir.strip_prefix(project_root).ok()?;
+
+    let mut section = "";
+    let mut cell: Option<(&str, &Path)> = None;
+    for line in buckconfig.lines().map(str::trim) {
+        if line.starts_with('#') || line.starts_with(';') || line.is_empty() {
+            continue;
+        }
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            section = name.trim();
+            continue;
+        }
+        // Older projects list their cells under `[repositories]`
+        if section != "cells" && section != "repositories" {
+            continue;
+        }
+        let Some((name, path)) = line.split_once('=') else {
+            continue;
+        };
+        let path = Path::new(path.trim().trim_start_matches("./"));
+        let path = if path == Path::new(".") {
+            Path::new("")
+        } else {
+            path
+        };
+        let is_closer = cell.map_or(true, |(_, cell_path)| {
+            path.components().count() > cell_path.components().count()
+        });
+        if relative_dir.starts_with(path) && is_closer {
+            cell = Some((name.trim(), path));
+        }
+    }
+    cell.map(|(name, _)| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
+    use std::fs::{self, File};
+    use std::io;
+
+    const BUCKCONFIG: &str = "\
+[cells]
+  root = .
+  prelude = prelude
+  toolchains = tools/toolchains
+
+[buildfile]
+  name = BUCK
+";
+
+    #[test]
+    fn folder_without_buck2_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = ModuleRenderer::new("buck2").path(dir.path()).collect();
+        assert_eq!(None, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_buckconfig() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join(".buckconfig"), BUCKCONFIG)?;
+
+        let actual = ModuleRenderer::new("buck2").path(dir.path()).collect();
+        let expected = Some(format!(
+            "via {}",
+            Color::Yellow.bold().paint("🦌 2024-10-15 in root ")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn package_in_nested_cell() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let package = dir.path().join("tools/toolchains/python");
+        fs::create_dir_all(&package)?;
+        fs::write(dir.path().join(".buckconfig"), BUCKCONFIG)?;
+        File::create(dir.path().join(".buckroot"))?.sync_all()?;
+        File::create(dir.path().join("tools/toolchains/.buckconfig"))?.sync_all()?;
+        File::create(package.join("BUCK"))?.sync_all()?;
+
+        let actual = ModuleRenderer::new("buck2").path(&package).collect();
+        let expected = Some(format!(
+            "via {}",
+            Color::Yellow.bold().paint("🦌 2024-10-15 in toolchains ")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn version_is_cached_for_the_session() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache_dir = tempfile::tempdir()?;
+        fs::write(dir.path().join(".buckconfig"), BUCKCONFIG)?;
+
+        let renderer = || {
+            ModuleRenderer::new("buck2")
+                .path(dir.path())
+                .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+                .env("STARSHIP_SESSION_KEY", "1234")
+        };
+        let expected = Some(format!(
+            "via {}",
+            Color::Yellow.bold().paint("🦌 2024-10-15 in root ")
+        ));
+        assert_eq!(renderer().collect(), expected);
+        // The version is only checked again once the cached one expires
+        assert_eq!(renderer().cmd("buck2 --version", None).collect(), expected);
+        cache_dir.close()?;
+        dir.close()
+    }
+}
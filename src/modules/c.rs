@@ -81,7 +81,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `c`:\n{}", error);
+            context.report_module_error("c", &error);
             return None;
         }
     });
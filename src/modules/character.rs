@@ -29,17 +29,25 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let keymap = props.keymap.as_str();
     let exit_success = exit_code == "0";
 
+    // Readline doesn't tell bash which vi mode it's in, so the init script shows this module
+    // in the mode strings of readline instead, which are redrawn when the mode changes.
+    if matches!(context.shell, Shell::Bash) && keymap == "vi-mode-string" {
+        return None;
+    }
+
     // Match shell "keymap" names to normalized vi modes
     // NOTE: in vi mode, fish reports normal mode as "default".
     // Unfortunately, this is also the name of the non-vi default mode.
     // We do some environment detection in src/init.rs to translate.
     // The result: in non-vi fish, keymap is always reported as "insert"
+    // NOTE: zsh has no keymaps for visual and replace mode, so the init script reports them
+    // based on the state of zle.
     let mode = match (&context.shell, keymap) {
-        (Shell::Fish, "default") | (Shell::Zsh, "vicmd") | (Shell::Cmd, "vi") => {
+        (Shell::Fish, "default") | (Shell::Zsh | Shell::Bash, "vicmd") | (Shell::Cmd, "vi") => {
             ShellEditMode::Normal
         }
-        (Shell::Fish, "visual") => ShellEditMode::Visual,
-        (Shell::Fish, "replace") => ShellEditMode::Replace,
+        (Shell::Fish | Shell::Zsh, "visual") => ShellEditMode::Visual,
+        (Shell::Fish | Shell::Zsh, "replace") => ShellEditMode::Replace,
         (Shell::Fish, "replace_one") => ShellEditMode::ReplaceOne,
         _ => ASSUMED_MODE,
     };
@@ -70,7 +78,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `character`:\n{}", error);
+            context.report_module_error("character", &error);
             return None;
         }
     });
@@ -145,6 +153,8 @@ mod test {
     fn zsh_keymap() {
         let expected_vicmd = Some(format!("{} ", Color::Green.bold().paint("❮")));
         let expected_specified = Some(format!("{} ", Color::Green.bold().paint("V")));
+        let expected_visual = Some(format!("{} ", Color::Yellow.bold().paint("❮")));
+        let expected_replace = Some(format!("{} ", Color::Purple.bold().paint("❮")));
         let expected_other = Some(format!("{} ", Color::Green.bold().paint("❯")));
 
         // zle keymap is vicmd
@@ -165,11 +175,52 @@ mod test {
             .collect();
         assert_eq!(expected_specified, actual);
 
-        // zle keymap is other
+        // zle is in visual mode
         let actual = ModuleRenderer::new("character")
             .shell(Shell::Zsh)
             .keymap("visual")
             .collect();
+        assert_eq!(expected_visual, actual);
+
+        // zle is in replace mode
+        let actual = ModuleRenderer::new("character")
+            .shell(Shell::Zsh)
+            .keymap("replace")
+            .collect();
+        assert_eq!(expected_replace, actual);
+
+        // zle keymap is other
+        let actual = ModuleRenderer::new("character")
+            .shell(Shell::Zsh)
+            .keymap("viopp")
+            .collect();
+        assert_eq!(expected_other, actual);
+    }
+
+    #[test]
+    fn bash_keymap() {
+        let expected_vicmd = Some(format!("{} ", Color::Green.bold().paint("❮")));
+        let expected_other = Some(format!("{} ", Color::Green.bold().paint("❯")));
+
+        // the mode string of readline for normal mode
+        let actual = ModuleRenderer::new("character")
+            .shell(Shell::Bash)
+            .keymap("vicmd")
+            .collect();
+        assert_eq!(expected_vicmd, actual);
+
+        // readline shows the module in its mode strings
+        let actual = ModuleRenderer::new("character")
+            .shell(Shell::Bash)
+            .keymap("vi-mode-string")
+            .collect();
+        assert_eq!(None, actual);
+
+        // readline is in emacs mode
+        let actual = ModuleRenderer::new("character")
+            .shell(Shell::Bash)
+            .keymap("emacs")
+            .collect();
         assert_eq!(expected_other, actual);
     }
 
@@ -0,0 +1,280 @@
+use systemstat::data::ByteSize;
+
+use super::utils::byte_size::display_bs;
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::clipboard::ClipboardConfig;
+use crate::formatter::StringFormatter;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ClipboardKind {
+    Text,
+    Image,
+    Files,
+}
+
+impl ClipboardKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Image => "image",
+            Self::Files => "files",
+        }
+    }
+}
+
+/// The type and size of the clipboard content, never the content itself
+#[derive(Debug, PartialEq, Eq)]
+struct ClipboardInfo {
+    kind: ClipboardKind,
+    size: Option<u64>,
+}
+
+/// Creates a module indicating the type and size of the clipboard content
+///
+/// Uses `osascript` on macOS, and `wl-paste` on Wayland or `xclip` on X11 elsewhere.
+/// The content is never read, only the types it is offered as, and its size where the
+/// clipboard reports it without the content (macOS).
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("clipboard");
+    let config = ClipboardConfig::try_load(module.config);
+
+    if config.disabled {
+        return None;
+    }
+
+    let info = get_clipboard_info(context)?;
+    let symbol = match info.kind {
+        ClipboardKind::Text => config.text_symbol,
+        ClipboardKind::Image => config.image_symbol,
+        ClipboardKind::Files => config.files_symbol,
+    };
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "type" => Some(Ok(info.kind.as_str().to_string())),
+                "size" => info.size.map(|size| Ok(display_bs(ByteSize(size)))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            context.report_module_error("clipboard", &error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+#[cfg(target_os = "macos")]
+fn get_clipboard_info(context: &Context) -> Option<ClipboardInfo> {
+    let output = context.exec_cmd("osascript", &["-e", "clipboard info"])?;
+    parse_clipboard_info(&output.stdout)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn get_clipboard_info(context: &Context) -> Option<ClipboardInfo> {
+    // Only the offered MIME types or X11 targets are listed, so neither the content nor its
+    // size is known here
+    let types = if context.get_env("WAYLAND_DISPLAY").is_some() {
+        context.exec_cmd("wl-paste", &["--list-types"])?
+    } else {
+        context.exec_cmd("xclip", &["-selection", "clipboard", "-t", "TARGETS", "-o"])?
+    };
+    let types = types.stdout.lines().map(str::trim).collect::<Vec<_>>();
+
+    Some(ClipboardInfo {
+        kind: classify_mime_types(&types)?,
+        size: None,
+    })
+}
+
+/// Determines the kind of clipboard content from the offered MIME types or X11 targets
+#[cfg(any(not(target_os = "macos"), test))]
+fn classify_mime_types(types: &[&str]) -> Option<ClipboardKind> {
+    if types.contains(&"text/uri-list") {
+        Some(ClipboardKind::Files)
+    } else if types.iter().any(|t| t.starts_with("image/")) {
+        Some(ClipboardKind::Image)
+    } else if types
+        .iter()
+        .any(|t| t.starts_with("text/plain") || matches!(*t, "UTF8_STRING" | "STRING" | "TEXT"))
+    {
+        Some(ClipboardKind::Text)
+    } else {
+        None
+    }
+}
+
+/// Parses the output of AppleScript's `clipboard info`,
+/// e.g. `«class PNGf», 10240, «class utf8», 35, string, 35`
+#[cfg(any(target_os = "macos", test))]
+fn parse_clipboard_info(output: &str) -> Option<ClipboardInfo> {
+    let entries = output.trim().split(", ").collect::<Vec<_>>();
+    let entries = entries
+        .chunks_exact(2)
+        .filter_map(|entry| Some((entry[0], entry[1].parse::<u64>().ok()?)))
+        .collect::<Vec<_>>();
+
+    let find = |classes: &[&str]| {
+        entries
+            .iter()
+            .find(|(class, _)| classes.contains(class))
+            .map(|(_, size)| *size)
+    };
+
+    let (kind, size) = if find(&["«class furl»"]).is_some() {
+        (ClipboardKind::Files, None)
+    } else if let Some(size) = find(&[
+        "«class PNGf»",
+        "«class TIFF»",
+        "«class JPEG»",
+        "«class GIFf»",
+        "«class 8BPS»",
+        "«class BMP »",
+    ]) {
+        (ClipboardKind::Image, Some(size))
+    } else if let Some(size) = find(&["«class utf8»", "string", "«class ut16»", "Unicode text"])
+    {
+        (ClipboardKind::Text, Some(size))
+    } else {
+        return None;
+    };
+
+    Some(ClipboardInfo { kind, size })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{output, ModuleRenderer};
+    use nu_ansi_term::Color;
+
+    #[test]
+    fn classify_types() {
+        assert_eq!(
+            classify_mime_types(&["TARGETS", "UTF8_STRING", "STRING"]),
+            Some(ClipboardKind::Text)
+        );
+        assert_eq!(
+            classify_mime_types(&["image/png", "text/plain"]),
+            Some(ClipboardKind::Image)
+        );
+        assert_eq!(
+            classify_mime_types(&[
+                "x-special/gnome-copied-files",
+                "text/uri-list",
+                "UTF8_STRING"
+            ]),
+            Some(ClipboardKind::Files)
+        );
+        assert_eq!(classify_mime_types(&["application/x-foo"]), None);
+        assert_eq!(classify_mime_types(&[]), None);
+    }
+
+    #[test]
+    fn parse_macos_clipboard_info() {
+        assert_eq!(
+            parse_clipboard_info("«class PNGf», 10240, «class utf8», 35, string, 35\n"),
+            Some(ClipboardInfo {
+                kind: ClipboardKind::Image,
+                size: Some(10240),
+            })
+        );
+        assert_eq!(
+            parse_clipboard_info("«class utf8», 35, «class ut16», 72, string, 35"),
+            Some(ClipboardInfo {
+                kind: ClipboardKind::Text,
+                size: Some(35),
+            })
+        );
+        assert_eq!(
+            parse_clipboard_info("«class furl», 61, «class utf8», 35").map(|info| info.kind),
+            Some(ClipboardKind::Files)
+        );
+        assert_eq!(parse_clipboard_info(""), None);
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let actual = ModuleRenderer::new("clipboard").collect();
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn x11_text() {
+        let actual = ModuleRenderer::new("clipboard")
+            .config(toml::toml! {
+                [clipboard]
+                disabled = false
+            })
+            .cmd(
+                "xclip -selection clipboard -t TARGETS -o",
+                output("TARGETS\nUTF8_STRING\nSTRING\n"),
+            )
+            .collect();
+        let expected = Some(format!("{} ", Color::Blue.bold().paint("📋 ")));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn wayland_files() {
+        let actual = ModuleRenderer::new("clipboard")
+            .config(toml::toml! {
+                [clipboard]
+                disabled = false
+            })
+            .env("WAYLAND_DISPLAY", "wayland-0")
+            .cmd(
+                "wl-paste --list-types",
+                output("text/uri-list\nx-special/gnome-copied-files\n"),
+            )
+            .collect();
+        let expected = Some(format!("{} ", Color::Blue.bold().paint("📁 ")));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn wayland_image_without_size() {
+        let actual = ModuleRenderer::new("clipboard")
+            .config(toml::toml! {
+                [clipboard]
+                disabled = false
+                format = "[$symbol$type( $size)]($style)"
+            })
+            .env("WAYLAND_DISPLAY", "wayland-0")
+            .cmd("wl-paste --list-types", output("image/png\n"))
+            .collect();
+        let expected = Some(format!("{}", Color::Blue.bold().paint("🖼️ image")));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn empty_clipboard() {
+        let actual = ModuleRenderer::new("clipboard")
+            .config(toml::toml! {
+                [clipboard]
+                disabled = false
+            })
+            .cmd("xclip -selection clipboard -t TARGETS -o", None)
+            .collect();
+        assert_eq!(actual, None);
+    }
+}
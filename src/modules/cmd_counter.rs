@@ -0,0 +1,121 @@
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::cmd_counter::CmdCounterConfig;
+use crate::formatter::StringFormatter;
+
+// Session values with the number of commands and the id of the last counted command
+const COUNT_KEY: &str = "cmd_counter_count";
+const LAST_ID_KEY: &str = "cmd_counter_last_id";
+
+/// Creates a module with the number of commands entered in the current shell session
+///
+/// The prompt is rendered more than once per command, e.g. for the right prompt or when it's
+/// redrawn, so a command is only counted the first time its id is seen.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("cmd_counter");
+    let config = CmdCounterConfig::try_load(module.config);
+
+    if config.disabled {
+        return None;
+    }
+
+    let mut count = context
+        .get_session_value(COUNT_KEY)
+        .and_then(|count| count.parse::<u64>().ok())
+        .unwrap_or_default();
+    let cmd_id = context
+        .properties
+        .cmd_id
+        .as_deref()
+        .filter(|id| !id.is_empty());
+    if let Some(cmd_id) = cmd_id {
+        if context.get_session_value(LAST_ID_KEY).as_deref() != Some(cmd_id) {
+            count += 1;
+            context.set_session_value(COUNT_KEY, &count.to_string());
+            context.set_session_value(LAST_ID_KEY, cmd_id);
+        }
+    }
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "count" => Some(Ok(count.to_string())),
+                // The number of the command that is about to be entered, like `\#` in bash
+                "number" => Some(Ok((count + 1).to_string())),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            context.report_module_error("cmd_counter", &error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Style;
+    use std::io;
+    use std::path::Path;
+
+    fn render(cache_dir: &Path, cmd_id: Option<&str>) -> Option<String> {
+        let renderer = ModuleRenderer::new("cmd_counter")
+            .config(toml::toml! {
+                [cmd_counter]
+                disabled = false
+                format = "$count $number"
+            })
+            .env("STARSHIP_CACHE", cache_dir.to_string_lossy())
+            .env("STARSHIP_SESSION_KEY", "session");
+        match cmd_id {
+            Some(cmd_id) => renderer.cmd_id(cmd_id).collect(),
+            None => renderer.collect(),
+        }
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let actual = ModuleRenderer::new("cmd_counter").collect();
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn starts_without_commands() {
+        let actual = ModuleRenderer::new("cmd_counter")
+            .config(toml::toml! {
+                [cmd_counter]
+                disabled = false
+            })
+            .collect();
+        let expected = Some(format!("{} ", Style::new().dimmed().paint("#1")));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn counts_each_command_once() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        assert_eq!(render(dir.path(), None), Some("0 1".to_string()));
+        assert_eq!(render(dir.path(), Some("100")), Some("1 2".to_string()));
+        // Redrawing the prompt of the same command doesn't count it again
+        assert_eq!(render(dir.path(), Some("100")), Some("1 2".to_string()));
+        assert_eq!(render(dir.path(), None), Some("1 2".to_string()));
+        assert_eq!(render(dir.path(), Some("250")), Some("2 3".to_string()));
+        dir.close()
+    }
+}
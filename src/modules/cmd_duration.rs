@@ -2,7 +2,7 @@ use super::{Context, Module, ModuleConfig};
 
 use crate::configs::cmd_duration::CmdDurationConfig;
 use crate::formatter::StringFormatter;
-use crate::utils::render_time;
+use crate::utils::{clock_ticks_per_second, render_time};
 
 /// Outputs the time it took the last command to execute
 ///
@@ -27,6 +27,14 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
+    // The CPU time is only known in shells that can measure it, and is passed in clock ticks
+    let ticks_per_second = clock_ticks_per_second();
+    let parse_time = |ticks: &Option<String>| {
+        Some(ticks.as_deref()?.parse::<u128>().ok()? * 1000 / ticks_per_second)
+    };
+    let user_time = parse_time(&context.properties.cmd_user_ticks);
+    let sys_time = parse_time(&context.properties.cmd_sys_ticks);
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_style(|variable| match variable {
@@ -35,6 +43,12 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             })
             .map(|variable| match variable {
                 "duration" => Some(Ok(render_time(elapsed, config.show_milliseconds))),
+                "user_time" => user_time
+                    .map(|time| render_time(time, config.show_milliseconds))
+                    .map(Ok),
+                "sys_time" => sys_time
+                    .map(|time| render_time(time, config.show_milliseconds))
+                    .map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -43,7 +57,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `cmd_duration`: \n{}", error);
+            context.report_module_error("cmd_duration", &error);
             return None;
         }
     });
@@ -188,4 +202,36 @@ mod tests {
         let expected = Some(format!("underwent {} ", Color::Yellow.bold().paint("5s")));
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn config_cpu_times() {
+        let actual = ModuleRenderer::new("cmd_duration")
+            .config(toml::toml! {
+                [cmd_duration]
+                format = "took [$duration( \\(user $user_time sys $sys_time\\))]($style) "
+            })
+            .cmd_duration(5000)
+            .cmd_cpu_times(3250, 40)
+            .collect();
+
+        let expected = Some(format!(
+            "took {} ",
+            Color::Yellow.bold().paint("5s (user 3s sys 40ms)")
+        ));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn config_cpu_times_unknown() {
+        let actual = ModuleRenderer::new("cmd_duration")
+            .config(toml::toml! {
+                [cmd_duration]
+                format = "took [$duration( \\(user $user_time sys $sys_time\\))]($style) "
+            })
+            .cmd_duration(5000)
+            .collect();
+
+        let expected = Some(format!("took {} ", Color::Yellow.bold().paint("5s")));
+        assert_eq!(expected, actual);
+    }
 }
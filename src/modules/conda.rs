@@ -2,7 +2,10 @@ use super::{Context, Module, ModuleConfig};
 
 use super::utils::directory::truncate;
 use crate::configs::conda::CondaConfig;
-use crate::formatter::StringFormatter;
+use crate::formatter::{StringFormatter, VersionFormatter};
+
+use std::fs;
+use std::path::Path;
 
 /// Creates a module with the current Conda environment
 ///
@@ -21,12 +24,15 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
+    // Environments created with `conda create -p [path]` are activated by their path
+    let is_prefix_env = conda_env.contains(['/', '\\']);
     let conda_env = truncate(&conda_env, config.truncation_length).unwrap_or(conda_env);
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|variable, _| match variable {
                 "symbol" => Some(config.symbol),
+                "prefix_symbol" => is_prefix_env.then_some(config.prefix_symbol),
                 _ => None,
             })
             .map_style(|variable| match variable {
@@ -34,7 +40,17 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "environment" => Some(Ok(conda_env.as_str())),
+                "environment" => Some(Ok(conda_env.clone())),
+                "python_version" => {
+                    let python_version = get_python_version(context)?;
+                    VersionFormatter::format_module_version(
+                        "conda",
+                        &python_version,
+                        config.version_format,
+                    )
+                    .map(Ok)
+                }
+                "tool" => Some(Ok(get_tool(context).to_string())),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -43,7 +59,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `conda`:\n{}", error);
+            context.report_module_error("conda", &error);
             return None;
         }
     });
@@ -51,10 +67,45 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Reads the version of the Python package that is installed in the environment, e.g. from
+/// `conda-meta/python-3.12.4-h5148396_1.json`, which is faster than running `python`
+fn get_python_version(context: &Context) -> Option<String> {
+    let prefix = context.get_env("CONDA_PREFIX")?;
+
+    fs::read_dir(Path::new(&prefix).join("conda-meta"))
+        .ok()?
+        .flatten()
+        .find_map(|entry| {
+            let file_name = entry.file_name();
+            let package = file_name.to_str()?.strip_suffix(".json")?;
+            let (name_version, _build) = package.rsplit_once('-')?;
+            let (name, version) = name_version.rsplit_once('-')?;
+            (name == "python").then(|| version.to_string())
+        })
+}
+
+/// Returns the tool that manages the environment. The shell hooks of mamba and micromamba
+/// set `MAMBA_EXE`, while conda's set `CONDA_EXE`.
+fn get_tool(context: &Context) -> &'static str {
+    let mamba_exe = context.get_env("MAMBA_EXE");
+    let mamba_name = mamba_exe
+        .as_deref()
+        .and_then(|exe| Path::new(exe).file_stem())
+        .and_then(|name| name.to_str());
+
+    match mamba_name {
+        Some("micromamba") => "micromamba",
+        Some(_) => "mamba",
+        None => "conda",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::test::ModuleRenderer;
     use nu_ansi_term::Color;
+    use std::fs;
+    use std::io;
 
     #[test]
     fn not_in_env() {
@@ -104,4 +155,56 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn prefix_env() {
+        let actual = ModuleRenderer::new("conda")
+            .env("CONDA_DEFAULT_ENV", "/home/user/projects/rocket/.conda")
+            .config(toml::toml! {
+                [conda]
+                format = "via [$symbol$prefix_symbol$environment]($style) "
+                truncation_length = 2
+            })
+            .collect();
+
+        let expected = Some(format!(
+            "via {} ",
+            Color::Green.bold().paint("🅒 📁 rocket/.conda")
+        ));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn env_python_version() -> io::Result<()> {
+        let prefix = tempfile::tempdir()?;
+        fs::create_dir(prefix.path().join("conda-meta"))?;
+        for package in [
+            "python_abi-3.12-4_cp312.json",
+            "python-3.12.4-h5148396_1.json",
+            "python-dateutil-2.9.0-pyhd8ed1ab_0.json",
+        ] {
+            fs::write(prefix.path().join("conda-meta").join(package), "{}")?;
+        }
+
+        let actual = ModuleRenderer::new("conda")
+            .env("CONDA_DEFAULT_ENV", "astronauts")
+            .env("CONDA_PREFIX", prefix.path().to_string_lossy())
+            .env("MAMBA_EXE", "/home/user/.local/bin/micromamba")
+            .config(toml::toml! {
+                [conda]
+                format = "via [$symbol$prefix_symbol$environment \\($python_version\\) with $tool]($style) "
+            })
+            .collect();
+
+        let expected = Some(format!(
+            "via {} ",
+            Color::Green
+                .bold()
+                .paint("🅒 astronauts (v3.12.4) with micromamba")
+        ));
+
+        assert_eq!(expected, actual);
+        prefix.close()
+    }
 }
@@ -97,7 +97,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `container`: \n{}", error);
+            context.report_module_error("container", &error);
             return None;
         }
     });
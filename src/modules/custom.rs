@@ -1,19 +1,44 @@
 use std::env;
 use std::fmt::{self, Debug};
+use std::fs;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use once_cell::sync::Lazy;
 use process_control::{ChildExt, Control, Output};
+use regex::Regex;
+use serde::Deserialize;
+use sha1::{Digest, Sha1};
 
 use super::{Context, Module, ModuleConfig};
 
 use crate::{
-    config::Either, configs::custom::CustomConfig, formatter::StringFormatter,
-    utils::create_command,
+    config::{parse_style_string, Either},
+    configs::custom::CustomConfig,
+    formatter::StringFormatter,
+    segment::Segment,
+    utils::{self, create_command},
 };
 
+/// A segment printed by a command with `output = "json"`
+#[derive(Debug, Deserialize)]
+struct JsonSegment {
+    text: String,
+    style: Option<String>,
+    symbol: Option<String>,
+}
+
+/// The output of a command with `output = "json"`, either a single segment whose style and
+/// symbol replace the configured ones, or a list of segments that are styled individually
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum JsonOutput {
+    Single(JsonSegment),
+    Multiple(Vec<JsonSegment>),
+}
+
 /// Creates a custom module with some configuration
 ///
 /// The relevant TOML config will set the files, extensions, and directories needed
@@ -48,6 +73,10 @@ pub fn module<'a>(name: &str, context: &'a Context) -> Option<Module<'a>> {
         .set_folders(&config.detect_folders)
         .is_match();
 
+    if !is_match {
+        is_match = has_matching_contents(&config, context);
+    }
+
     if !is_match {
         is_match = match config.when {
             Either::First(b) => b,
@@ -59,19 +88,49 @@ pub fn module<'a>(name: &str, context: &'a Context) -> Option<Module<'a>> {
         }
     }
 
+    let is_json = config.output == "json";
+    let json_output = Lazy::new(|| {
+        let output = get_output(name, context, &config)?;
+        serde_json::from_str::<JsonOutput>(&output)
+            .map_err(|error| log::warn!("Unable to parse JSON output {output:?}: {error}"))
+            .ok()
+    });
+    let json_single = || match is_json.then(|| &*json_output) {
+        Some(Some(JsonOutput::Single(segment))) => Some(segment),
+        _ => None,
+    };
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
-                "symbol" => Some(config.symbol),
+                "symbol" => json_single()
+                    .and_then(|segment| segment.symbol.as_deref())
+                    .or(Some(config.symbol)),
                 _ => None,
             })
             .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
+                "style" => Some(Ok(json_single()
+                    .and_then(|segment| segment.style.as_deref())
+                    .unwrap_or(config.style))),
+                _ => None,
+            })
+            .map_variables_to_segments(|variable| match variable {
+                "output" if is_json => match &*json_output {
+                    Some(JsonOutput::Multiple(segments)) => {
+                        let segments = render_json_segments(segments, context);
+                        (!segments.is_empty()).then_some(Ok(segments))
+                    }
+                    _ => None,
+                },
                 _ => None,
             })
             .map_no_escaping(|variable| match variable {
                 "output" => {
-                    let output = exec_command(config.command, context, &config)?;
+                    let output = match json_single() {
+                        Some(segment) => segment.text.clone(),
+                        None if is_json => return None,
+                        None => get_output(name, context, &config)?,
+                    };
                     let trimmed = output.trim();
 
                     if trimmed.is_empty() {
@@ -88,7 +147,8 @@ pub fn module<'a>(name: &str, context: &'a Context) -> Option<Module<'a>> {
     match parsed {
         Ok(segments) => module.set_segments(segments),
         Err(error) => {
-            log::warn!("Error in module `custom.{}`:\n{}", name, error);
+            context.report_module_error(&format!("custom.{name}"), &error);
+            return None;
         }
     };
     Some(module)
@@ -121,6 +181,47 @@ fn get_config<'a>(module_name: &str, context: &'a Context<'a>) -> Option<&'a tom
     None
 }
 
+/// Renders the segments of a JSON output, each with its own symbol and style
+fn render_json_segments(segments: &[JsonSegment], context: &Context) -> Vec<Segment> {
+    let mut rendered = Vec::new();
+    for segment in segments
+        .iter()
+        .filter(|segment| !segment.text.trim().is_empty())
+    {
+        if !rendered.is_empty() {
+            rendered.extend(Segment::from_text(None, " "));
+        }
+        let style = segment
+            .style
+            .as_deref()
+            .and_then(|style| parse_style_string(style, Some(context)));
+        let symbol = segment.symbol.as_deref().unwrap_or_default();
+        rendered.extend(Segment::from_text(
+            style,
+            format!("{symbol}{}", segment.text.trim()),
+        ));
+    }
+    rendered
+}
+
+/// Checks whether any of the `detect_contents` files in the current directory has
+/// contents matching its regex
+fn has_matching_contents(config: &CustomConfig, context: &Context) -> bool {
+    config.detect_contents.iter().any(|(file, pattern)| {
+        // Let `^` and `$` match at the start and end of each line
+        let re = match Regex::new(&format!("(?m){pattern}")) {
+            Ok(re) => re,
+            Err(error) => {
+                log::warn!("Invalid regex {:?} for {:?}: {}", pattern, file, error);
+                return false;
+            }
+        };
+        utils::read_file(context.current_dir.join(file))
+            .map(|contents| re.is_match(&contents))
+            .unwrap_or(false)
+    })
+}
+
 /// Return the invoking shell, using `shell` and fallbacking in order to `STARSHIP_SHELL` and "sh"/"cmd"
 fn get_shell<'a, 'b>(
     shell_args: &'b [&'a str],
@@ -200,8 +301,11 @@ fn shell_command(cmd: &str, config: &CustomConfig, context: &Context) -> Option<
     let mut output = child.controlled_with_output();
 
     if !config.ignore_timeout {
+        let timeout = config
+            .command_timeout
+            .unwrap_or(context.root_config.command_timeout);
         output = output
-            .time_limit(Duration::from_millis(context.root_config.command_timeout))
+            .time_limit(Duration::from_millis(timeout))
             .terminate_for_timeout()
     }
 
@@ -240,6 +344,91 @@ fn exec_when(cmd: &str, config: &CustomConfig, context: &Context) -> bool {
     }
 }
 
+/// Returns the output of the command of the module, reusing the output of an earlier
+/// prompt in the same directory while it is younger than `cache_duration` seconds
+fn get_output(name: &str, context: &Context, config: &CustomConfig) -> Option<String> {
+    if config.cache_duration == 0 {
+        return exec_command(config.command, context, config);
+    }
+
+    let cache_file = cache_file(name, context, config);
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    let cached = utils::read_file(&cache_file).ok().and_then(|content| {
+        let (timestamp, output) = content.split_once('\n')?;
+        let is_fresh = now.saturating_sub(timestamp.parse().ok()?) < config.cache_duration;
+        is_fresh.then(|| output.to_string())
+    });
+    if cached.is_some() {
+        return cached;
+    }
+
+    let output = exec_command(config.command, context, config)?;
+    let cache_dir = cache_file.parent()?;
+    remove_expired_cache_files(cache_dir, &cache_file_prefix(name), config.cache_duration);
+    let result = fs::create_dir_all(cache_dir)
+        .and_then(|_| fs::write(&cache_file, format!("{now}\n{output}")));
+    if let Err(error) = result {
+        log::debug!("Unable to write custom command cache {cache_file:?}: {error}");
+    }
+    Some(output)
+}
+
+/// The output depends on the command and the directory it runs in, so both are part of the key
+fn cache_file(name: &str, context: &Context, config: &CustomConfig) -> PathBuf {
+    let mut hasher = Sha1::new();
+    hasher.update(config.command.as_bytes());
+    hasher.update([0]);
+    hasher.update(context.current_dir.to_string_lossy().as_bytes());
+
+    utils::cache_path(context, "custom").join(format!(
+        "{}{}",
+        cache_file_prefix(name),
+        utils::encode_to_hex(&hasher.finalize())
+    ))
+}
+
+fn cache_file_prefix(name: &str) -> String {
+    format!(
+        "{}-",
+        name.replace(|c: char| !c.is_ascii_alphanumeric() && c != '-', "_")
+    )
+}
+
+/// Files of other modules are kept for a week, as their `cache_duration` isn't known here
+const MAX_CACHE_FILE_AGE: u64 = 60 * 60 * 24 * 7;
+
+/// Deletes the cached outputs of this module that are older than `cache_duration`, and those of
+/// any module that haven't been written to for a week, e.g. because it was removed from the config
+fn remove_expired_cache_files(cache_dir: &Path, prefix: &str, cache_duration: u64) {
+    let Ok(files) = fs::read_dir(cache_dir) else {
+        return;
+    };
+
+    for file in files.flatten() {
+        // Module names may contain `-`, but the hash after the prefix doesn't
+        let is_own = file
+            .file_name()
+            .to_string_lossy()
+            .strip_prefix(prefix)
+            .map_or(false, |hash| !hash.contains('-'));
+        let max_age = if is_own {
+            cache_duration
+        } else {
+            MAX_CACHE_FILE_AGE
+        };
+        let is_expired = file
+            .metadata()
+            .ok()
+            .filter(fs::Metadata::is_file)
+            .and_then(|metadata| metadata.modified().ok())
+            .and_then(|modified| modified.elapsed().ok())
+            .map_or(false, |elapsed| elapsed.as_secs() >= max_age);
+        if is_expired {
+            let _ = fs::remove_file(file.path());
+        }
+    }
+}
+
 /// Execute the given command, returning its output on success
 fn exec_command(cmd: &str, context: &Context, config: &CustomConfig) -> Option<String> {
     log::trace!("Running '{cmd}'");
@@ -299,7 +488,7 @@ mod tests {
     use super::*;
 
     use crate::test::{fixture_repo, FixtureProvider, ModuleRenderer};
-    use nu_ansi_term::Color;
+    use nu_ansi_term::{AnsiStrings, Color};
     use std::fs::File;
     use std::io;
 
@@ -411,6 +600,26 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn format_error_shows_badge() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        // Without rate limiting, no markers are written into the real cache directory
+        let actual = ModuleRenderer::new("custom.test")
+            .path(dir.path())
+            .config(toml::toml! {
+                show_module_errors = true
+                module_error_log_interval = 0
+                [custom.test]
+                format = "[$output"
+                command = "echo hello"
+                when = true
+            })
+            .collect();
+        let expected = Some(format!("{} ", Color::Red.bold().paint("⚠ custom.test")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
     #[test]
     fn cwd_command() -> io::Result<()> {
         let dir = tempfile::tempdir()?;
@@ -669,6 +878,80 @@ mod tests {
         dir.close()
     }
 
+    #[test]
+    fn module_command_timeout() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        let shell = if cfg!(windows) {
+            "powershell".to_owned()
+        } else {
+            "sh".to_owned()
+        };
+
+        let actual = ModuleRenderer::new("custom.test")
+            .path(dir.path())
+            .config(toml::toml! {
+                command_timeout = 100_000
+                [custom.test]
+                format = "test"
+                when = "sleep 3"
+                shell = shell
+                command_timeout = 100
+            })
+            .collect();
+        let expected = None;
+        assert_eq!(expected, actual);
+
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn cached_output() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache_dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("status"), "1")?;
+
+        let render = |cache_duration: i64| {
+            ModuleRenderer::new("custom.test")
+                .path(dir.path())
+                .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+                .config(toml::toml! {
+                    [custom.test]
+                    format = "$output"
+                    command = "cat status"
+                    shell = ["/bin/sh"]
+                    when = true
+                    cache_duration = cache_duration
+                })
+                .collect()
+        };
+
+        assert_eq!(render(60), Some("1".to_string()));
+        std::fs::write(dir.path().join("status"), "2")?;
+        assert_eq!(render(60), Some("1".to_string()));
+        assert_eq!(render(0), Some("2".to_string()));
+
+        cache_dir.close()?;
+        dir.close()
+    }
+
+    #[test]
+    fn expired_cache_files_are_removed() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let files = ["test-0123abcd", "test-other-0123abcd", "other-0123abcd"];
+        for file in files {
+            std::fs::write(dir.path().join(file), "0\noutput")?;
+        }
+
+        remove_expired_cache_files(dir.path(), &cache_file_prefix("test"), 0);
+
+        assert!(!dir.path().join("test-0123abcd").exists());
+        assert!(dir.path().join("test-other-0123abcd").exists());
+        assert!(dir.path().join("other-0123abcd").exists());
+        dir.close()
+    }
+
     #[test]
     fn config_aliases_work() -> std::io::Result<()> {
         let dir = tempfile::tempdir()?;
@@ -712,6 +995,97 @@ mod tests {
         dir.close()
     }
 
+    #[cfg(not(windows))]
+    fn render_json(output: &str) -> io::Result<Option<String>> {
+        let dir = tempfile::tempdir()?;
+        let cmd = format!("cat <<'EOF'\n{output}\nEOF");
+        let out = ModuleRenderer::new("custom.test")
+            .path(dir.path())
+            .config(toml::toml! {
+                [custom.test]
+                command = cmd
+                output = "json"
+                shell = ["/bin/sh"]
+                symbol = "S "
+                style = "blue"
+                when = true
+                ignore_timeout = true
+            })
+            .collect();
+        dir.close()?;
+        Ok(out)
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn json_output() -> io::Result<()> {
+        assert_eq!(
+            render_json(r#"{"text": "staging"}"#)?,
+            Some(format!("{}", Color::Blue.paint("S staging ")))
+        );
+        assert_eq!(
+            render_json(r#"{"text": "prod", "style": "bold red", "symbol": "⚠ "}"#)?,
+            Some(format!("{}", Color::Red.bold().paint("⚠ prod ")))
+        );
+        assert_eq!(
+            render_json(
+                r#"[{"text": "3", "symbol": "✔", "style": "green"}, {"text": "1", "symbol": "✘", "style": "red"}, {"text": ""}]"#
+            )?,
+            Some(
+                AnsiStrings(&[
+                    Color::Blue.paint("S "),
+                    Color::Green.paint("✔3"),
+                    Color::Blue.paint(" "),
+                    Color::Red.paint("✘1"),
+                    Color::Blue.paint(" "),
+                ])
+                .to_string()
+            )
+        );
+        assert_eq!(
+            render_json("not json")?,
+            Some(format!("{}", Color::Blue.paint("S ")))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn detect_contents() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(
+            dir.path().join("Makefile"),
+            "build:\n\tcargo build\ndeploy: build\n\t./deploy.sh\n",
+        )?;
+
+        let render = |pattern: &str| {
+            ModuleRenderer::new("custom.test")
+                .path(dir.path())
+                .config(
+                    toml::from_str(&format!(
+                        "[custom.test]\nformat = 'test'\ndetect_contents = {{ Makefile = '{pattern}' }}"
+                    ))
+                    .unwrap(),
+                )
+                .collect()
+        };
+
+        assert_eq!(render("^deploy:"), Some("test".to_string()));
+        assert_eq!(render("^release:"), None);
+        assert_eq!(render("deploy:("), None);
+
+        let actual = ModuleRenderer::new("custom.test")
+            .path(dir.path())
+            .config(toml::toml! {
+                [custom.test]
+                format = "test"
+                detect_contents = { "Cargo.toml" = "." }
+            })
+            .collect();
+        assert_eq!(actual, None);
+
+        dir.close()
+    }
+
     #[test]
     fn disabled() {
         let actual = ModuleRenderer::new("custom.test")
@@ -0,0 +1,184 @@
+use std::path::Path;
+
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::dev_env::DevEnvConfig;
+use crate::formatter::StringFormatter;
+use crate::utils;
+
+/// An activated environment of one of the Nix based development environment tools
+#[derive(Debug, PartialEq, Eq)]
+struct DevEnv {
+    tool: &'static str,
+    name: String,
+}
+
+/// Creates a module with the activated devbox, devenv or flox environment
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("dev_env");
+    let config = DevEnvConfig::try_load(module.config);
+
+    let dev_env = get_flox_env(context)
+        .or_else(|| get_devbox_env(context))
+        .or_else(|| get_devenv_env(context))?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => config
+                    .symbols
+                    .get(dev_env.tool)
+                    .copied()
+                    .or_else(|| DevEnvConfig::default().symbols.get(dev_env.tool).copied()),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "name" => Some(Ok(dev_env.name.as_str())),
+                "tool" => Some(Ok(dev_env.tool)),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            context.report_module_error("dev_env", &error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// flox lists the names of the activated environments for prompts, the most recent first
+fn get_flox_env(context: &Context) -> Option<DevEnv> {
+    let name = match context.get_env("FLOX_PROMPT_ENVIRONMENTS") {
+        Some(environments) => environments.split_whitespace().next()?.to_string(),
+        None => {
+            let project = context.get_env("FLOX_ENV_PROJECT")?;
+            let project = Path::new(&project);
+            get_json_name(&project.join(".flox").join("env.json")).or_else(|| dir_name(project))?
+        }
+    };
+    Some(DevEnv { tool: "flox", name })
+}
+
+fn get_devbox_env(context: &Context) -> Option<DevEnv> {
+    if context.get_env("DEVBOX_SHELL_ENABLED").as_deref() != Some("1") {
+        return None;
+    }
+    let project = context.get_env("DEVBOX_PROJECT_ROOT")?;
+    let project = Path::new(&project);
+    let name = get_json_name(&project.join("devbox.json")).or_else(|| dir_name(project))?;
+    Some(DevEnv {
+        tool: "devbox",
+        name,
+    })
+}
+
+fn get_devenv_env(context: &Context) -> Option<DevEnv> {
+    let project = context.get_env("DEVENV_ROOT")?;
+    let name = dir_name(Path::new(&project))?;
+    Some(DevEnv {
+        tool: "devenv",
+        name,
+    })
+}
+
+/// Reads the `name` field of a project file
+fn get_json_name(path: &Path) -> Option<String> {
+    let content = utils::read_file(path).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+    json.get("name")?
+        .as_str()
+        .filter(|name| !name.is_empty())
+        .map(ToString::to_string)
+}
+
+fn dir_name(dir: &Path) -> Option<String> {
+    Some(dir.file_name()?.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
+    use std::fs;
+    use std::io;
+
+    #[test]
+    fn no_env_variables() {
+        let actual = ModuleRenderer::new("dev_env").collect();
+        let expected = None;
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn devbox_shell() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("devbox.json"),
+            r#"{"name": "api-server", "packages": ["go@1.22"]}"#,
+        )?;
+
+        let actual = ModuleRenderer::new("dev_env")
+            .env("DEVBOX_SHELL_ENABLED", "1")
+            .env("DEVBOX_PROJECT_ROOT", dir.path().to_string_lossy())
+            .collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Cyan.bold().paint("📦 api-server")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn devenv_shell() {
+        let actual = ModuleRenderer::new("dev_env")
+            .env("DEVENV_ROOT", "/home/user/starship")
+            .config(toml::toml! {
+                [dev_env]
+                format = "[$tool:$name]($style)"
+            })
+            .collect();
+        let expected = Some(format!("{}", Color::Cyan.bold().paint("devenv:starship")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn flox_environment() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::create_dir(dir.path().join(".flox"))?;
+        fs::write(
+            dir.path().join(".flox/env.json"),
+            r#"{"name": "toolbox", "version": 1}"#,
+        )?;
+
+        let actual = ModuleRenderer::new("dev_env")
+            .env("FLOX_ENV_PROJECT", dir.path().to_string_lossy())
+            .env("DEVENV_ROOT", "/home/user/starship")
+            .config(toml::toml! {
+                [dev_env]
+                symbols = { flox = "flox " }
+            })
+            .collect();
+        let expected = Some(format!("via {} ", Color::Cyan.bold().paint("flox toolbox")));
+        assert_eq!(expected, actual);
+
+        let actual = ModuleRenderer::new("dev_env")
+            .env("FLOX_PROMPT_ENVIRONMENTS", "web owner/shared")
+            .collect();
+        let expected = Some(format!("via {} ", Color::Cyan.bold().paint("🌀 web")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
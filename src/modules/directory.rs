@@ -4,18 +4,23 @@ use super::utils::directory_nix as directory_utils;
 use super::utils::directory_win as directory_utils;
 use super::utils::path::PathExt as SPathExt;
 use indexmap::IndexMap;
+use once_cell::sync::Lazy;
 use path_slash::{PathBufExt, PathExt};
+use regex::Regex;
 use std::borrow::Cow;
 use std::iter::FromIterator;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 use super::{Context, Module};
 
 use super::utils::directory::truncate;
-use crate::config::ModuleConfig;
+use crate::config::{Either, ModuleConfig};
 use crate::configs::directory::DirectoryConfig;
 use crate::formatter::StringFormatter;
+use crate::utils::run_with_timeout;
 
 /// Creates a module with the current logical or physical directory
 ///
@@ -25,16 +30,33 @@ use crate::formatter::StringFormatter;
 /// - Paths beginning with the home directory or with a git repo right inside
 ///   the home directory will be contracted to `~`, or the set `HOME_SYMBOL`
 /// - Paths containing a git repo will contract to begin at the repo root
+/// - With `repo_relative`, paths inside a git repo are always shown in full
+///   from the repo root, prefixed with `repo_symbol`, and are never truncated
 ///
 /// **Substitution**
-/// Paths will undergo user-provided substitutions of substrings
+/// Paths will undergo user-provided substitutions of substrings, then of regex matches
 ///
 /// **Truncation**
 /// Paths will be limited in length to `3` path components by default.
+/// With `truncation_length = "auto"`, path components are shortened
+/// fish-style instead, only as far as needed to fit the width the other modules
+/// on its line leave.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("directory");
     let config: DirectoryConfig = DirectoryConfig::try_load(module.config);
 
+    let (truncation_length, fit_width) = match config.truncation_length {
+        Either::First(length) => (length, None),
+        Either::Second("auto") => (0, Some(context.module_width()).filter(|&width| width > 0)),
+        Either::Second(other) => {
+            log::warn!(
+                "Invalid `truncation_length` `{}` in module `directory`, expected a number or `auto`",
+                other
+            );
+            (3, None)
+        }
+    };
+
     let home_symbol = String::from(config.home_symbol);
     let home_dir = context
         .get_home()
@@ -52,11 +74,12 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     // Attempt repository path contraction (if we are in a git repository)
     // Otherwise use the logical path, automatically contracting
-    let repo = if config.truncate_to_repo || config.repo_root_style.is_some() {
-        context.get_repo().ok()
-    } else {
-        None
-    };
+    let repo =
+        if config.truncate_to_repo || config.repo_relative || config.repo_root_style.is_some() {
+            context.get_repo().ok()
+        } else {
+            None
+        };
     let dir_string = if config.truncate_to_repo {
         repo.and_then(|r| r.workdir.as_ref())
             .filter(|&root| root != &home_dir)
@@ -76,20 +99,23 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     // Apply path substitutions
     let dir_string = substitute_path(dir_string, &config.substitutions);
+    let dir_string = substitute_path_regex(dir_string, &config.regex_substitutions);
 
     // Truncate the dir string to the maximum number of path components
-    let dir_string =
-        if let Some(truncated) = truncate(&dir_string, config.truncation_length as usize) {
-            is_truncated = true;
-            truncated
-        } else {
-            dir_string
-        };
+    let dir_string = if let Some(truncated) = truncate(&dir_string, truncation_length as usize) {
+        is_truncated = true;
+        truncated
+    } else {
+        dir_string
+    };
 
     let prefix = if is_truncated {
         // Substitutions could have changed the prefix, so don't allow them and
         // fish-style path contraction together
-        if config.fish_style_pwd_dir_length > 0 && config.substitutions.is_empty() {
+        if config.fish_style_pwd_dir_length > 0
+            && config.substitutions.is_empty()
+            && config.regex_substitutions.is_empty()
+        {
             // If user is using fish style path, we need to add the segment first
             let contracted_home_dir = contract_path(display_dir, &home_dir, &home_symbol);
             to_fish_style(
@@ -104,15 +130,39 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         String::new()
     };
 
-    let path_vec = match &repo.and_then(|r| r.workdir.as_ref()) {
-        Some(repo_root) if config.repo_root_style.is_some() => {
+    let repo_relative_path = if config.repo_relative {
+        repo.and_then(|r| r.workdir.as_ref())
+            .and_then(|root| contract_repo_path(display_dir, root))
+            .map(|path| substitute_path(path, &config.substitutions))
+            .map(|path| substitute_path_regex(path, &config.regex_substitutions))
+    } else {
+        None
+    };
+
+    let is_repo_relative = repo_relative_path.is_some();
+    let path_vec = match (repo_relative_path, &repo.and_then(|r| r.workdir.as_ref())) {
+        (Some(repo_path), _) => {
+            if config.repo_root_style.is_some() {
+                let (root, after_repo_root) = match repo_path.split_once('/') {
+                    Some((root, rest)) => (root.to_string(), format!("/{rest}")),
+                    None => (repo_path, String::new()),
+                };
+                [config.repo_symbol.to_string(), root, after_repo_root]
+            } else {
+                [
+                    String::new(),
+                    String::new(),
+                    format!("{}{}", config.repo_symbol, repo_path),
+                ]
+            }
+        }
+        (None, Some(repo_root)) if config.repo_root_style.is_some() => {
             let contracted_path = contract_repo_path(display_dir, repo_root)?;
             let repo_path_vec: Vec<&str> = contracted_path.split('/').collect();
             let after_repo_root = contracted_path.replacen(repo_path_vec[0], "", 1);
             let num_segments_after_root = after_repo_root.split('/').count();
 
-            if config.truncation_length == 0
-                || ((num_segments_after_root - 1) as i64) < config.truncation_length
+            if truncation_length == 0 || ((num_segments_after_root - 1) as i64) < truncation_length
             {
                 let root = repo_path_vec[0];
                 let before = before_root_dir(&dir_string, &contracted_path);
@@ -124,13 +174,15 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         _ => [String::new(), String::new(), prefix + dir_string.as_str()],
     };
 
-    let path_vec = if config.use_os_path_sep {
-        path_vec.map(|i| convert_path_sep(&i))
-    } else {
-        path_vec
-    };
-
     let lock_symbol = String::from(config.read_only);
+    let remote_symbol = String::from(config.fs_remote);
+    // Stat the directory at most once, and only if it's needed by the format
+    let dir_status = Lazy::new(|| {
+        get_dir_status(
+            physical_dir,
+            Duration::from_millis(context.root_config.scan_timeout),
+        )
+    });
     let display_format = if path_vec[0].is_empty() && path_vec[1].is_empty() {
         config.format
     } else {
@@ -139,35 +191,66 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let repo_root_style = config.repo_root_style.unwrap_or(config.style);
     let before_repo_root_style = config.before_repo_root_style.unwrap_or(config.style);
 
-    let parsed = StringFormatter::new(display_format).and_then(|formatter| {
-        formatter
-            .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
-                "read_only_style" => Some(Ok(config.read_only_style)),
-                "repo_root_style" => Some(Ok(repo_root_style)),
-                "before_repo_root_style" => Some(Ok(before_repo_root_style)),
-                _ => None,
-            })
-            .map(|variable| match variable {
-                "path" => Some(Ok(&path_vec[2])),
-                "before_root_path" => Some(Ok(&path_vec[0])),
-                "repo_root" => Some(Ok(&path_vec[1])),
-                "read_only" => {
-                    if is_readonly_dir(physical_dir) {
-                        Some(Ok(&lock_symbol))
-                    } else {
-                        None
+    let render = |path_vec: &[String; 3]| {
+        let path_vec = if config.use_os_path_sep {
+            path_vec.clone().map(|i| convert_path_sep(&i))
+        } else {
+            path_vec.clone()
+        };
+        StringFormatter::new(display_format).and_then(|formatter| {
+            formatter
+                .map_style(|variable| match variable {
+                    "style" => Some(Ok(config.style)),
+                    "read_only_style" => Some(Ok(config.read_only_style)),
+                    "repo_root_style" => Some(Ok(repo_root_style)),
+                    "before_repo_root_style" => Some(Ok(before_repo_root_style)),
+                    _ => None,
+                })
+                .map(|variable| match variable {
+                    "path" => Some(Ok(&path_vec[2])),
+                    "before_root_path" => Some(Ok(&path_vec[0])),
+                    "repo_root" => Some(Ok(&path_vec[1])),
+                    "read_only" => {
+                        if dir_status.read_only {
+                            Some(Ok(&lock_symbol))
+                        } else {
+                            None
+                        }
                     }
-                }
-                _ => None,
-            })
-            .parse(None, Some(context))
-    });
+                    "fs_remote" => {
+                        if dir_status.remote {
+                            Some(Ok(&remote_symbol))
+                        } else {
+                            None
+                        }
+                    }
+                    _ => None,
+                })
+                .parse(None, Some(context))
+        })
+    };
+
+    // Repo-relative paths are never truncated
+    let path_vec = match fit_width {
+        Some(width) if !is_repo_relative => {
+            // The symbols and spacing around the path have to fit as well
+            let path_width = path_vec.iter().map(|part| part.width()).sum::<usize>();
+            let other_width = render(&path_vec).map_or(0, |segments| {
+                segments
+                    .iter()
+                    .map(|segment| segment.value().width())
+                    .sum::<usize>()
+                    .saturating_sub(path_width)
+            });
+            shorten_path_vec(path_vec, width.saturating_sub(other_width))
+        }
+        _ => path_vec,
+    };
 
-    module.set_segments(match parsed {
+    module.set_segments(match render(&path_vec) {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `directory`:\n{}", error);
+            context.report_module_error("directory", &error);
             return None;
         }
     });
@@ -193,6 +276,44 @@ fn remove_extended_path_prefix(path: String) -> String {
     path
 }
 
+/// Read-only and network filesystem status of a directory
+#[derive(Clone, Copy, Default)]
+struct DirStatus {
+    read_only: bool,
+    remote: bool,
+}
+
+/// Checks the status of a directory, returning the default status if the
+/// checks don't finish within `timeout`
+fn get_dir_status(path: &Path, timeout: Duration) -> DirStatus {
+    let thread_path = path.to_path_buf();
+    run_with_timeout(timeout, move || DirStatus {
+        read_only: is_readonly_dir(&thread_path),
+        remote: is_remote_dir(&thread_path),
+    })
+    .unwrap_or_else(|| {
+        log::debug!(
+            "Checking the status of directory '{:?}' timed out, it may be on a stale network mount",
+            path
+        );
+        DirStatus::default()
+    })
+}
+
+fn is_remote_dir(path: &Path) -> bool {
+    match directory_utils::is_network_fs(path) {
+        Ok(res) => res,
+        Err(e) => {
+            log::debug!(
+                "Failed to determine filesystem type of directory '{:?}': {}",
+                path,
+                e
+            );
+            false
+        }
+    }
+}
+
 fn is_readonly_dir(path: &Path) -> bool {
     match directory_utils::is_write_allowed(path) {
         Ok(res) => !res,
@@ -305,6 +426,26 @@ fn substitute_path(dir_string: String, substitutions: &IndexMap<String, &str>) -
     substituted_dir
 }
 
+/// Perform a list of regex substitutions on the path
+///
+/// Given a list of (pattern, replacement) pairs, this will replace all matches of each
+/// pattern, in order. The replacement can refer to capture groups, like `$1` or `${name}`.
+/// Invalid patterns are skipped.
+fn substitute_path_regex(dir_string: String, substitutions: &IndexMap<String, &str>) -> String {
+    let mut substituted_dir = dir_string;
+    for (pattern, replacement) in substitutions {
+        match Regex::new(pattern) {
+            Ok(regex) => {
+                substituted_dir = regex
+                    .replace_all(&substituted_dir, *replacement)
+                    .into_owned();
+            }
+            Err(error) => log::warn!("Invalid directory substitution {pattern:?}: {error}"),
+        }
+    }
+    substituted_dir
+}
+
 /// Takes part before contracted path and replaces it with fish style path
 ///
 /// Will take the first letter of each directory before the contracted path and
@@ -340,6 +481,41 @@ fn to_fish_style(pwd_dir_length: usize, dir_string: String, truncated_dir_string
         .join("/")
 }
 
+/// Shortens the path before the repo root, or else the whole path, to fit `width`
+fn shorten_path_vec(path_vec: [String; 3], width: usize) -> [String; 3] {
+    let [before_root, root, path] = path_vec;
+    if before_root.is_empty() && root.is_empty() {
+        let path = shorten_to_width(&path, width, true);
+        [before_root, root, path]
+    } else {
+        let remaining_width = width.saturating_sub(root.width() + path.width());
+        let before_root = shorten_to_width(&before_root, remaining_width, false);
+        [before_root, root, path]
+    }
+}
+
+/// Shortens path components fish-style, from left to right, until the path fits `max_width`
+///
+/// The last component is only shortened if `keep_last` is `false`. If the path
+/// doesn't fit even with all components shortened, the shortened path is returned.
+fn shorten_to_width(path: &str, max_width: usize, keep_last: bool) -> String {
+    let mut components = path.split('/').map(String::from).collect::<Vec<_>>();
+    let shortenable = if keep_last {
+        components.len().saturating_sub(1)
+    } else {
+        components.len()
+    };
+
+    for i in 0..shortenable {
+        if components.join("/").width() <= max_width {
+            break;
+        }
+        components[i] = to_fish_style(1, components[i].clone(), "");
+    }
+
+    components.join("/")
+}
+
 /// Convert the path separators in `path` to the OS specific path separators.
 fn convert_path_sep(path: &str) -> String {
     return PathBuf::from_slash(path).to_string_lossy().into_owned();
@@ -674,6 +850,38 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn regex_substitution_before_truncation() {
+        let actual = ModuleRenderer::new("directory")
+            .path(home_dir().unwrap().join("work/rocket/src/engine/fuel/pump"))
+            .config(toml::toml! {
+                [directory]
+                truncation_length = 4
+                [directory.regex_substitutions]
+                "^~/work/([^/]+)/src" = "⟨$1⟩"
+            })
+            .collect();
+        let expected = Some(format!(
+            "{} ",
+            Color::Cyan
+                .bold()
+                .paint(convert_path_sep("⟨rocket⟩/engine/fuel/pump"))
+        ));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn regex_substitutions_in_order() {
+        let mut substitutions = IndexMap::new();
+        substitutions.insert("/projects/([a-z]+)/".to_string(), "/${1}_x/");
+        substitutions.insert("[".to_string(), "invalid");
+        substitutions.insert("_x".to_string(), "!");
+
+        let output = substitute_path_regex("/projects/abc/def".to_string(), &substitutions);
+        assert_eq!(output, "/abc!/def");
+    }
+
     #[test]
     fn strange_substitution() {
         let strange_sub = "/\\/;,!";
@@ -1740,6 +1948,166 @@ mod tests {
         tmp_dir.close()
     }
 
+    #[test]
+    fn dir_status_of_local_directory() -> io::Result<()> {
+        let tmp_dir = TempDir::new()?;
+
+        let status = get_dir_status(tmp_dir.path(), Duration::from_secs(5));
+        assert!(!status.read_only);
+        assert!(!status.remote);
+        tmp_dir.close()
+    }
+
+    #[test]
+    fn fs_remote_hidden_for_local_directory() -> io::Result<()> {
+        let (tmp_dir, name) = make_known_tempdir(Path::new("/tmp"))?;
+
+        let actual = ModuleRenderer::new("directory")
+            .config(toml::toml! {
+                [directory]
+                format = "[$path]($style)[$fs_remote]($style) "
+            })
+            .path(tmp_dir.path())
+            .collect();
+        let expected = Some(format!(
+            "{} ",
+            Color::Cyan
+                .bold()
+                .paint(convert_path_sep(&format!("/tmp/{name}")))
+        ));
+        assert_eq!(expected, actual);
+        tmp_dir.close()
+    }
+
+    #[test]
+    fn shorten_to_width_only_as_needed() {
+        let path = "~/Projects/starship/src/modules";
+        assert_eq!(shorten_to_width(path, 40, true), path);
+        assert_eq!(shorten_to_width(path, 25, true), "~/P/starship/src/modules");
+        assert_eq!(shorten_to_width(path, 10, true), "~/P/s/s/modules");
+        assert_eq!(shorten_to_width("/above/", 3, false), "/a/");
+    }
+
+    #[test]
+    fn auto_truncation_fits_terminal_width() -> io::Result<()> {
+        let (tmp_dir, name) = make_known_tempdir(Path::new("/tmp"))?;
+        let dir = tmp_dir.path().join("thrusters/rocket");
+        fs::create_dir_all(&dir)?;
+
+        let actual = ModuleRenderer::new("directory")
+            .config(toml::toml! {
+                [directory]
+                truncation_length = "auto"
+            })
+            .path(&dir)
+            // The path and the space after it
+            .width(name.len() + 21)
+            .collect();
+        let expected = Some(format!(
+            "{} ",
+            Color::Cyan
+                .bold()
+                .paint(convert_path_sep(&format!("/t/{name}/thrusters/rocket")))
+        ));
+        assert_eq!(expected, actual);
+
+        let actual = ModuleRenderer::new("directory")
+            .config(toml::toml! {
+                [directory]
+                truncation_length = "auto"
+            })
+            .path(&dir)
+            .width(200)
+            .collect();
+        let expected = Some(format!(
+            "{} ",
+            Color::Cyan
+                .bold()
+                .paint(convert_path_sep(&format!("/tmp/{name}/thrusters/rocket")))
+        ));
+        assert_eq!(expected, actual);
+        tmp_dir.close()
+    }
+
+    #[test]
+    fn repo_relative_directory_in_git_repo() -> io::Result<()> {
+        let (tmp_dir, _) = make_known_tempdir(Path::new("/tmp"))?;
+        let repo_dir = tmp_dir.path().join("above").join("repo");
+        let dir = repo_dir.join("src/sub/deeper/path");
+        fs::create_dir_all(&dir)?;
+        init_repo(&repo_dir).unwrap();
+
+        let actual = ModuleRenderer::new("directory")
+            .config(toml::toml! {
+                [directory]
+                repo_relative = true
+                repo_symbol = "R "
+            })
+            .path(dir)
+            .collect();
+        let expected = Some(format!(
+            "{} ",
+            Color::Cyan
+                .bold()
+                .paint(convert_path_sep("R repo/src/sub/deeper/path"))
+        ));
+        assert_eq!(expected, actual);
+        tmp_dir.close()
+    }
+
+    #[test]
+    fn repo_relative_highlight_git_root_dir() -> io::Result<()> {
+        let (tmp_dir, _) = make_known_tempdir(Path::new("/tmp"))?;
+        let repo_dir = tmp_dir.path().join("above").join("repo");
+        let dir = repo_dir.join("src/sub/deeper/path");
+        fs::create_dir_all(&dir)?;
+        init_repo(&repo_dir).unwrap();
+
+        let actual = ModuleRenderer::new("directory")
+            .config(toml::toml! {
+                [directory]
+                repo_relative = true
+                repo_symbol = "R "
+                repo_root_style = "green"
+            })
+            .path(dir)
+            .collect();
+        let expected = Some(format!(
+            "{}{}repo{} ",
+            Color::Cyan.bold().paint("R "),
+            Color::Green.prefix(),
+            Color::Cyan
+                .bold()
+                .paint(convert_path_sep("/src/sub/deeper/path"))
+        ));
+        assert_eq!(expected, actual);
+        tmp_dir.close()
+    }
+
+    #[test]
+    fn repo_relative_outside_git_repo() -> io::Result<()> {
+        let (tmp_dir, name) = make_known_tempdir(Path::new("/tmp"))?;
+        let dir = tmp_dir.path().join("src");
+        fs::create_dir_all(&dir)?;
+
+        let actual = ModuleRenderer::new("directory")
+            .config(toml::toml! {
+                [directory]
+                repo_relative = true
+                repo_symbol = "R "
+            })
+            .path(dir)
+            .collect();
+        let expected = Some(format!(
+            "{} ",
+            Color::Cyan
+                .bold()
+                .paint(convert_path_sep(&format!("/tmp/{name}/src")))
+        ));
+        assert_eq!(expected, actual);
+        tmp_dir.close()
+    }
+
     // sample for invalid unicode from https://doc.rust-lang.org/std/ffi/struct.OsStr.html#method.to_string_lossy
     #[cfg(any(unix, target_os = "redox"))]
     fn invalid_path() -> PathBuf {
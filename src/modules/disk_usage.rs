@@ -0,0 +1,151 @@
+#[cfg(not(target_os = "windows"))]
+use super::utils::directory_nix as directory_utils;
+#[cfg(target_os = "windows")]
+use super::utils::directory_win as directory_utils;
+use std::time::Duration;
+use systemstat::data::ByteSize;
+
+use super::utils::byte_size::display_bs;
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::disk_usage::{DiskUsageConfig, DiskUsageDisplayConfig};
+use crate::formatter::StringFormatter;
+use crate::utils::run_with_timeout;
+
+/// Creates a module with the disk usage of the filesystem containing the current directory
+///
+/// The module is only shown if the used space reaches `threshold` percent.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("disk_usage");
+    let config = DiskUsageConfig::try_load(module.config);
+
+    if config.disabled {
+        return None;
+    }
+
+    // A stale network mount could block the prompt, see the `directory` module
+    let path = context.current_dir.clone();
+    let (total, available) = run_with_timeout(
+        Duration::from_millis(context.root_config.scan_timeout),
+        move || directory_utils::disk_space(&path),
+    )?
+    .map_err(|e| log::debug!("Failed to retrieve disk space: {}", e))
+    .ok()?;
+
+    if total == 0 {
+        return None;
+    }
+
+    let used = total.saturating_sub(available);
+    let used_pct = 100.0 * used as f64 / total as f64;
+
+    if (used_pct.round() as i64) < config.threshold {
+        return None;
+    }
+
+    let display = display_for(&config.display, used_pct);
+    let symbol = display.and_then(|d| d.symbol).unwrap_or(config.symbol);
+    let style = display.map_or(config.style, |d| d.style);
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "free" => Some(Ok(display_bs(ByteSize(available)))),
+                "used" => Some(Ok(display_bs(ByteSize(used)))),
+                "total" => Some(Ok(display_bs(ByteSize(total)))),
+                "used_pct" => Some(Ok(format!("{used_pct:.0}%"))),
+                "free_pct" => Some(Ok(format!("{:.0}%", 100.0 - used_pct))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            context.report_module_error("disk_usage", &error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Returns the display config with the highest threshold reached by `used_pct`
+fn display_for<'a, 'b>(
+    display: &'b [DiskUsageDisplayConfig<'a>],
+    used_pct: f64,
+) -> Option<&'b DiskUsageDisplayConfig<'a>> {
+    display
+        .iter()
+        .filter(|display| used_pct.round() as i64 >= display.threshold)
+        .max_by_key(|display| display.threshold)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::test::ModuleRenderer;
+    use std::io;
+
+    #[test]
+    fn display_for_highest_reached_threshold() {
+        let display = [
+            DiskUsageDisplayConfig {
+                threshold: 95,
+                style: "red",
+                symbol: Some("!! "),
+            },
+            DiskUsageDisplayConfig {
+                threshold: 85,
+                style: "yellow",
+                symbol: None,
+            },
+        ];
+
+        assert!(display_for(&display, 50.0).is_none());
+        assert_eq!(display_for(&display, 90.0).unwrap().style, "yellow");
+        assert_eq!(display_for(&display, 99.0).unwrap().style, "red");
+    }
+
+    #[test]
+    fn zero_threshold() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let output = ModuleRenderer::new("disk_usage")
+            .path(dir.path())
+            .config(toml::toml! {
+                [disk_usage]
+                disabled = false
+                threshold = 0
+            })
+            .collect();
+
+        assert!(output.is_some());
+        dir.close()
+    }
+
+    #[test]
+    fn impossible_threshold() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let output = ModuleRenderer::new("disk_usage")
+            .path(dir.path())
+            .config(toml::toml! {
+                [disk_usage]
+                disabled = false
+                threshold = 9999
+            })
+            .collect();
+
+        assert!(output.is_none());
+        dir.close()
+    }
+}
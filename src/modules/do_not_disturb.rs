@@ -0,0 +1,134 @@
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::do_not_disturb::DoNotDisturbConfig;
+use crate::formatter::StringFormatter;
+
+/// Creates a module that is shown while do-not-disturb or focus mode is active
+///
+/// Supports macOS Focus and GNOME's do-not-disturb setting.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("do_not_disturb");
+    let config = DoNotDisturbConfig::try_load(module.config);
+
+    if config.disabled {
+        return None;
+    }
+
+    if !is_do_not_disturb_active(context) {
+        return None;
+    }
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            context.report_module_error("do_not_disturb", &error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+#[cfg(target_os = "macos")]
+fn is_do_not_disturb_active(context: &Context) -> bool {
+    // macOS 12 and later show a Focus status item while a focus is active,
+    // older versions store the do-not-disturb state in the notification center
+    context
+        .exec_cmds_return_first(vec![
+            vec![
+                "defaults",
+                "read",
+                "com.apple.controlcenter",
+                "NSStatusItem Visible FocusModes",
+            ],
+            vec![
+                "defaults",
+                "-currentHost",
+                "read",
+                "com.apple.notificationcenterui",
+                "doNotDisturb",
+            ],
+        ])
+        .map_or(false, |output| output.stdout.trim() == "1")
+}
+
+#[cfg(not(target_os = "macos"))]
+fn is_do_not_disturb_active(context: &Context) -> bool {
+    // GNOME hides notification banners while do-not-disturb is on
+    context
+        .exec_cmd(
+            "gsettings",
+            &["get", "org.gnome.desktop.notifications", "show-banners"],
+        )
+        .map_or(false, |output| output.stdout.trim() == "false")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::{output, ModuleRenderer};
+    use nu_ansi_term::Color;
+
+    #[cfg(not(target_os = "macos"))]
+    const DND_COMMAND: &str = "gsettings get org.gnome.desktop.notifications show-banners";
+    #[cfg(not(target_os = "macos"))]
+    const ACTIVE: &str = "false\n";
+    #[cfg(not(target_os = "macos"))]
+    const INACTIVE: &str = "true\n";
+
+    #[cfg(target_os = "macos")]
+    const DND_COMMAND: &str =
+        "defaults read com.apple.controlcenter NSStatusItem Visible FocusModes";
+    #[cfg(target_os = "macos")]
+    const ACTIVE: &str = "1\n";
+    #[cfg(target_os = "macos")]
+    const INACTIVE: &str = "0\n";
+
+    #[test]
+    fn disabled_by_default() {
+        let actual = ModuleRenderer::new("do_not_disturb")
+            .cmd(DND_COMMAND, output(ACTIVE))
+            .collect();
+
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    fn active() {
+        let actual = ModuleRenderer::new("do_not_disturb")
+            .cmd(DND_COMMAND, output(ACTIVE))
+            .config(toml::toml! {
+                [do_not_disturb]
+                disabled = false
+            })
+            .collect();
+        let expected = Some(format!("{} ", Color::Yellow.bold().paint("🔕")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn inactive() {
+        let actual = ModuleRenderer::new("do_not_disturb")
+            .cmd(DND_COMMAND, output(INACTIVE))
+            .config(toml::toml! {
+                [do_not_disturb]
+                disabled = false
+            })
+            .collect();
+
+        assert_eq!(None, actual);
+    }
+}
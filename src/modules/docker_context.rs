@@ -1,5 +1,7 @@
 use std::path::PathBuf;
 
+use yaml_rust::YamlLoader;
+
 use super::{Context, Module, ModuleConfig};
 
 use crate::configs::docker_context::DockerContextConfig;
@@ -72,7 +74,9 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "context" => Some(Ok(ctx.as_str())),
+                "context" => Some(Ok(ctx.clone())),
+                "compose_project" => get_compose_project(context, &config).map(Ok),
+                "compose_profiles" => get_compose_profiles(context).map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -81,13 +85,72 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `docker_context`:\n{}", error);
+            context.report_module_error("docker_context", &error);
             return None;
         }
     });
     Some(module)
 }
 
+/// Returns the first Compose file of the project, from `COMPOSE_FILE` or else the first of the
+/// `compose_files` in the current directory
+fn get_compose_file(context: &Context, config: &DockerContextConfig) -> Option<PathBuf> {
+    if let Some(files) = context.get_env("COMPOSE_FILE") {
+        let separator = context
+            .get_env("COMPOSE_PATH_SEPARATOR")
+            .unwrap_or_else(|| if cfg!(windows) { ";" } else { ":" }.to_string());
+        let file = files.split(separator.as_str()).next()?;
+        return (!file.is_empty()).then(|| context.current_dir.join(file));
+    }
+
+    config
+        .compose_files
+        .iter()
+        .map(|file| context.current_dir.join(file))
+        .find(|file| file.is_file())
+}
+
+/// Determines the name of the Compose project the same way `docker compose` does: from
+/// `COMPOSE_PROJECT_NAME`, the top-level `name` of the Compose file, or the directory of the file
+fn get_compose_project(context: &Context, config: &DockerContextConfig) -> Option<String> {
+    if let Some(name) = context
+        .get_env("COMPOSE_PROJECT_NAME")
+        .filter(|name| !name.is_empty())
+    {
+        return Some(name);
+    }
+
+    let compose_file = get_compose_file(context, config)?;
+    let name = utils::read_file(&compose_file)
+        .ok()
+        .and_then(|contents| YamlLoader::load_from_str(&contents).ok())
+        .and_then(|docs| Some(docs.first()?["name"].as_str()?.to_string()));
+    if name.is_some() {
+        return name;
+    }
+
+    // Project names only consist of lowercase letters, digits, dashes and underscores,
+    // and start with a letter or a digit
+    let dir_name = compose_file.parent()?.file_name()?.to_str()?.to_lowercase();
+    let name: String = dir_name
+        .chars()
+        .filter(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '-' || *c == '_')
+        .skip_while(|c| !c.is_ascii_alphanumeric())
+        .collect();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Returns the Compose profiles enabled by `COMPOSE_PROFILES`
+fn get_compose_profiles(context: &Context) -> Option<String> {
+    let profiles = context.get_env("COMPOSE_PROFILES")?;
+    let profiles: Vec<&str> = profiles
+        .split(',')
+        .map(str::trim)
+        .filter(|profile| !profile.is_empty())
+        .collect();
+    (!profiles.is_empty()).then(|| profiles.join(","))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::test::ModuleRenderer;
@@ -423,4 +486,83 @@ mod tests {
 
         cfg_dir.close()
     }
+
+    fn render_compose(dir: &std::path::Path, env: &[(&str, &str)]) -> Option<String> {
+        env.iter()
+            .fold(
+                ModuleRenderer::new("docker_context")
+                    .env("DOCKER_CONTEXT", "starship")
+                    .path(dir),
+                |renderer, (key, value)| renderer.env(key, *value),
+            )
+            .config(toml::toml! {
+                [docker_context]
+                format = "$context( $compose_project)( \\[$compose_profiles\\])"
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compose_project_from_file() -> io::Result<()> {
+        let pwd = tempfile::tempdir()?;
+        let mut file = File::create(pwd.path().join("compose.yaml"))?;
+        file.write_all(b"name: shop\nservices:\n  web:\n    image: nginx\n")?;
+        file.sync_all()?;
+
+        assert_eq!(
+            render_compose(pwd.path(), &[]),
+            Some("starship shop".to_string())
+        );
+        assert_eq!(
+            render_compose(
+                pwd.path(),
+                &[
+                    ("COMPOSE_PROJECT_NAME", "shop-staging"),
+                    ("COMPOSE_PROFILES", "debug, tools")
+                ]
+            ),
+            Some("starship shop-staging [debug,tools]".to_string())
+        );
+        pwd.close()
+    }
+
+    #[test]
+    fn test_compose_project_from_directory() -> io::Result<()> {
+        let pwd = tempfile::tempdir()?;
+        let project_dir = pwd.path().join(".My Shop");
+        std::fs::create_dir(&project_dir)?;
+        File::create(project_dir.join("docker-compose.yml"))?.sync_all()?;
+
+        assert_eq!(
+            render_compose(&project_dir, &[]),
+            Some("starship myshop".to_string())
+        );
+        pwd.close()
+    }
+
+    #[test]
+    fn test_compose_file_from_env() -> io::Result<()> {
+        let pwd = tempfile::tempdir()?;
+        File::create(pwd.path().join("Dockerfile"))?.sync_all()?;
+        std::fs::create_dir(pwd.path().join("deploy"))?;
+        let mut file = File::create(pwd.path().join("deploy").join("prod.yml"))?;
+        file.write_all(b"name: shop-prod\n")?;
+        file.sync_all()?;
+
+        assert_eq!(
+            render_compose(pwd.path(), &[]),
+            Some("starship".to_string())
+        );
+        assert_eq!(
+            render_compose(
+                pwd.path(),
+                &[
+                    ("COMPOSE_FILE", "deploy/prod.yml:extra.yml"),
+                    ("COMPOSE_PATH_SEPARATOR", ":")
+                ]
+            ),
+            Some("starship shop-prod".to_string())
+        );
+        pwd.close()
+    }
 }
@@ -1,6 +1,8 @@
+use once_cell::sync::Lazy;
 use quick_xml::events::Event;
 use quick_xml::Reader;
 use std::ffi::OsStr;
+use std::fs;
 use std::iter::Iterator;
 use std::path::{Path, PathBuf};
 use std::str;
@@ -36,6 +38,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     }
 
     let dotnet_files = get_local_dotnet_files(context).ok()?;
+    let repo_root = context.get_repo().ok().and_then(|r| r.workdir.as_deref());
+    let global_json = Lazy::new(|| {
+        let path = find_global_json(&dotnet_files, &context.current_dir, repo_root)?;
+        utils::read_file(path).ok()
+    });
 
     // Internally, this module uses its own mechanism for version detection.
     // Typically it is twice as fast as running `dotnet --version`.
@@ -54,13 +61,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             .map(|variable| match variable {
                 "version" => {
                     let version = if enable_heuristic {
-                        let repo_root = context.get_repo().ok().and_then(|r| r.workdir.as_deref());
-                        estimate_dotnet_version(
-                            context,
-                            &dotnet_files,
-                            &context.current_dir,
-                            repo_root,
-                        )
+                        estimate_dotnet_version(context, &dotnet_files, global_json.as_deref())
                     } else {
                         get_version_from_cli(context)
                     };
@@ -71,7 +72,9 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                     )
                     .map(Ok)
                 }
-                "tfm" => find_current_tfm(&dotnet_files).map(Ok),
+                "tfm" => find_current_tfm(&dotnet_files, &context.current_dir).map(Ok),
+                "pinned_sdk" => get_pinned_sdk_version(global_json.as_deref()?).map(Ok),
+                "roll_forward" => get_sdk_roll_forward(global_json.as_deref()?).map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -80,7 +83,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `dotnet`:\n{}", error);
+            context.report_module_error("dotnet", &error);
             return None;
         }
     });
@@ -88,12 +91,19 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
-fn find_current_tfm(files: &[DotNetFile]) -> Option<String> {
+fn find_current_tfm(files: &[DotNetFile], current_dir: &Path) -> Option<String> {
     let get_file_of_type = |t: FileType| files.iter().find(|f| f.file_type == t);
 
     let relevant_file = get_file_of_type(FileType::ProjectFile)?;
 
-    get_tfm_from_project_file(relevant_file.path.as_path())
+    get_tfm_from_project_file(relevant_file.path.as_path()).or_else(|| {
+        // Like MSBuild, only the closest `Directory.Build.props` is imported
+        let props = current_dir
+            .ancestors()
+            .map(|dir| dir.join("Directory.Build.props"))
+            .find(|path| path.is_file())?;
+        get_tfm_from_project_file(&props)
+    })
 }
 
 fn get_tfm_from_project_file(path: &Path) -> Option<String> {
@@ -142,45 +152,49 @@ fn get_tfm_from_project_file(path: &Path) -> Option<String> {
 
 fn estimate_dotnet_version(
     context: &Context,
+    files: &[DotNetFile],
+    global_json: Option<&str>,
+) -> Option<String> {
+    if files.is_empty() {
+        return None;
+    }
+
+    // Without a global.json that pins a version, assume the latest SDK is in use.
+    global_json
+        .and_then(get_pinned_sdk_version)
+        .or_else(|| get_latest_sdk(context))
+}
+
+/// Finds the `global.json` that applies to the current directory.
+///
+/// A `global.json` in the current directory takes precedence. With this heuristic, we'll
+/// assume that a `global.json` won't be found in any directory above a solution file.
+/// Otherwise, if we see a dotnet project, we'll check a small number of neighboring
+/// directories to see if we can find a `global.json`.
+fn find_global_json(
     files: &[DotNetFile],
     current_dir: &Path,
     repo_root: Option<&Path>,
-) -> Option<String> {
+) -> Option<PathBuf> {
     let get_file_of_type = |t: FileType| files.iter().find(|f| f.file_type == t);
 
-    // It's important to check for a global.json or a solution file first,
-    // but otherwise we can take any relevant file. We'll take whichever is first.
-    let relevant_file = get_file_of_type(FileType::GlobalJson)
-        .or_else(|| get_file_of_type(FileType::SolutionFile))
-        .or_else(|| files.iter().next())?;
-
-    match relevant_file.file_type {
-        FileType::GlobalJson => get_pinned_sdk_version_from_file(relevant_file.path.as_path())
-            .or_else(|| get_latest_sdk_from_cli(context)),
-        FileType::SolutionFile => {
-            // With this heuristic, we'll assume that a "global.json" won't
-            // be found in any directory above the solution file.
-            get_latest_sdk_from_cli(context)
-        }
-        _ => {
-            // If we see a dotnet project, we'll check a small number of neighboring
-            // directories to see if we can find a global.json. Otherwise, assume the
-            // latest SDK is in use.
-            try_find_nearby_global_json(current_dir, repo_root)
-                .or_else(|| get_latest_sdk_from_cli(context))
-        }
+    if let Some(global_json) = get_file_of_type(FileType::GlobalJson) {
+        return Some(global_json.path.clone());
     }
+    if files.is_empty() || get_file_of_type(FileType::SolutionFile).is_some() {
+        return None;
+    }
+    try_find_nearby_global_json(current_dir, repo_root)
 }
 
 /// Looks for a `global.json` which may exist in one of the parent directories of the current path.
-/// If there is one present, and it contains valid version pinning information, then return that version.
 ///
 /// The following places are scanned:
 ///     - The parent of the current directory
 ///       (Unless there is a git repository, and the parent is above the root of that repository)
 ///     - The root of the git repository
 ///       (If there is one)
-fn try_find_nearby_global_json(current_dir: &Path, repo_root: Option<&Path>) -> Option<String> {
+fn try_find_nearby_global_json(current_dir: &Path, repo_root: Option<&Path>) -> Option<PathBuf> {
     let current_dir_is_repo_root = repo_root.map_or(false, |r| r == current_dir);
     let parent_dir = if current_dir_is_repo_root {
         // Don't scan the parent directory if it's above the root of a git repository
@@ -207,26 +221,13 @@ fn try_find_nearby_global_json(current_dir: &Path, repo_root: Option<&Path>) ->
         .find_map(|d| check_directory_for_global_json(d))
 }
 
-fn check_directory_for_global_json(path: &Path) -> Option<String> {
+fn check_directory_for_global_json(path: &Path) -> Option<PathBuf> {
     let global_json_path = path.join(GLOBAL_JSON_FILE);
     log::debug!(
         "Checking if global.json exists at: {}",
         &global_json_path.display()
     );
-    if global_json_path.exists() {
-        get_pinned_sdk_version_from_file(&global_json_path)
-    } else {
-        None
-    }
-}
-
-fn get_pinned_sdk_version_from_file(path: &Path) -> Option<String> {
-    let json_text = crate::utils::read_file(path).ok()?;
-    log::debug!(
-        "Checking if .NET SDK version is pinned in: {}",
-        path.display()
-    );
-    get_pinned_sdk_version(&json_text)
+    global_json_path.exists().then_some(global_json_path)
 }
 
 fn get_pinned_sdk_version(json: &str) -> Option<String> {
@@ -254,6 +255,13 @@ fn get_pinned_sdk_version(json: &str) -> Option<String> {
     }
 }
 
+fn get_sdk_roll_forward(json: &str) -> Option<String> {
+    let parsed_json: JValue = serde_json::from_str(json).ok()?;
+    let roll_forward = parsed_json.get("sdk")?.get("rollForward")?.as_str()?;
+
+    Some(roll_forward.to_string())
+}
+
 fn get_local_dotnet_files(context: &Context) -> Result<Vec<DotNetFile>, std::io::Error> {
     Ok(context
         .dir_contents()?
@@ -297,6 +305,32 @@ fn get_version_from_cli(context: &Context) -> Option<String> {
     Some(format!("v{}", version_output.stdout.trim()))
 }
 
+fn get_latest_sdk(context: &Context) -> Option<String> {
+    get_latest_sdk_from_install(context).or_else(|| get_latest_sdk_from_cli(context))
+}
+
+/// Finds the latest SDK in the `sdk` directory of the dotnet installation, which is a lot
+/// faster than running `dotnet --list-sdks`
+fn get_latest_sdk_from_install(context: &Context) -> Option<String> {
+    let dotnet_root = match context.get_env("DOTNET_ROOT") {
+        Some(dotnet_root) => PathBuf::from(dotnet_root),
+        None => {
+            let path = context.get_env_os("PATH");
+            let dotnet = which::which_in("dotnet", path, &context.current_dir).ok()?;
+            dunce::canonicalize(dotnet).ok()?.parent()?.to_path_buf()
+        }
+    };
+
+    let latest_sdk = fs::read_dir(dotnet_root.join("sdk"))
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.file_type().map_or(false, |t| t.is_dir()))
+        .filter_map(|entry| semver::Version::parse(entry.file_name().to_str()?).ok())
+        .max()?;
+    log::debug!("Found .NET SDK {} in {}", latest_sdk, dotnet_root.display());
+    Some(latest_sdk.to_string())
+}
+
 fn get_latest_sdk_from_cli(context: &Context) -> Option<String> {
     match context.exec_cmd("dotnet", &["--list-sdks"]) {
         Some(sdks_output) => {
@@ -553,6 +587,72 @@ mod tests {
         workspace.close()
     }
 
+    #[test]
+    fn shows_global_json_details() -> io::Result<()> {
+        let workspace = create_workspace(false)?;
+        let global_json = r#"{ "sdk": { "version": "8.0.100", "rollForward": "latestFeature" } }"#;
+        touch_path(&workspace, "global.json", Some(global_json))?;
+        touch_path(&workspace, "project/project.csproj", None)?;
+
+        let actual = ModuleRenderer::new("dotnet")
+            .path(workspace.path().join("project"))
+            .config(toml::toml! {
+                [dotnet]
+                format = "via [$symbol($pinned_sdk )(\\($roll_forward\\) )]($style)"
+            })
+            .collect();
+        let expected = Some(format!(
+            "via {}",
+            Color::Blue.bold().paint(".NET 8.0.100 (latestFeature) ")
+        ));
+        assert_eq!(actual, expected);
+        workspace.close()
+    }
+
+    #[test]
+    fn shows_latest_sdk_from_dotnet_root() -> io::Result<()> {
+        let workspace = create_workspace(false)?;
+        touch_path(&workspace, "project/project.csproj", None)?;
+        for sdk in ["6.0.400", "8.0.100", "7.0.302", "8.0.100-rc.2.23502.2"] {
+            fs::create_dir_all(workspace.path().join("dotnet").join("sdk").join(sdk))?;
+        }
+        touch_path(&workspace, "dotnet/sdk/9.0.100.txt", None)?;
+
+        let actual = ModuleRenderer::new("dotnet")
+            .path(workspace.path().join("project"))
+            .env(
+                "DOTNET_ROOT",
+                workspace.path().join("dotnet").to_string_lossy(),
+            )
+            .collect();
+        let expected = Some(format!(
+            "via {}",
+            Color::Blue.bold().paint(".NET v8.0.100 ")
+        ));
+        assert_eq!(actual, expected);
+        workspace.close()
+    }
+
+    #[test]
+    fn shows_tfm_from_directory_build_props() -> io::Result<()> {
+        let workspace = create_workspace(false)?;
+        let props = make_csproj_with_tfm("TargetFramework", "net8.0");
+        touch_path(&workspace, "Directory.Build.props", Some(&props))?;
+        touch_path(
+            &workspace,
+            "src/project/project.csproj",
+            Some("<Project />"),
+        )?;
+        expect_output(
+            &workspace.path().join("src/project"),
+            Some(format!(
+                "via {}",
+                Color::Blue.bold().paint(".NET v3.1.103 🎯 net8.0 ")
+            )),
+        );
+        workspace.close()
+    }
+
     fn create_workspace(is_repo: bool) -> io::Result<TempDir> {
         let repo_dir = tempfile::tempdir()?;
 
@@ -1,16 +1,17 @@
 use super::{Context, Module};
+use regex::Regex;
 use std::borrow::Cow;
 
 use crate::config::ModuleConfig;
 use crate::configs::env_var::EnvVarConfig;
 use crate::formatter::StringFormatter;
 
-/// Creates a module with the value of the chosen environment variable
+/// Creates a module with the value of the chosen environment variables
 ///
-/// Will display the environment variable's value if all of the following criteria are met:
+/// Will display the environment variables' values if all of the following criteria are met:
 ///     - `env_var.disabled` is absent or false
-///     - `env_var.variable` is defined
-///     - a variable named as the value of `env_var.variable` is defined
+///     - `env_var.variable`, `env_var.variables` or `env_var.match` is defined
+///     - one of the chosen variables is defined, or `env_var.default` is
 pub fn module<'a>(name: Option<&str>, context: &'a Context) -> Option<Module<'a>> {
     let toml_config = match name {
         Some(name) => context
@@ -37,38 +38,104 @@ pub fn module<'a>(name: Option<&str>, context: &'a Context) -> Option<Module<'a>
         return None;
     };
 
-    let variable_name = config.variable.or(name)?;
+    let values = get_values(name, context, &config)?;
+    let all = values
+        .iter()
+        .map(|(_, value)| value.as_str())
+        .collect::<Vec<_>>()
+        .join(config.separator);
+
+    // Matches of `match` are shown one after another, listed variables are shown together
+    let items = if config.pattern.is_some() {
+        values.iter().collect()
+    } else {
+        vec![&values[0]]
+    };
 
-    let env_value = context.get_env(variable_name);
-    let env_value = env_value.as_deref().or(config.default)?;
-    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
-        formatter
-            .map_meta(|var, _| match var {
-                "symbol" => Some(config.symbol),
-                _ => None,
-            })
-            .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
-                _ => None,
-            })
-            .map(|variable| match variable {
-                "env_value" => Some(Ok(env_value)),
-                _ => None,
-            })
-            .parse(None, Some(context))
-    });
-
-    module.set_segments(match parsed {
-        Ok(segments) => segments,
-        Err(error) => {
-            log::warn!("Error in module `env_var`:\n{}", error);
-            return None;
+    let mut segments = Vec::new();
+    for (variable_name, env_value) in items {
+        let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+            formatter
+                .map_meta(|var, _| match var {
+                    "symbol" => Some(config.symbol),
+                    _ => None,
+                })
+                .map_style(|variable| match variable {
+                    "style" => Some(Ok(config.style)),
+                    _ => None,
+                })
+                .map(|variable| match variable {
+                    "env_value" => Some(Ok(env_value.as_str())),
+                    "name" => Some(Ok(variable_name.as_str())),
+                    "all" => Some(Ok(all.as_str())),
+                    variable => values
+                        .iter()
+                        .find(|(name, _)| name == variable)
+                        .map(|(_, value)| Ok(value.as_str())),
+                })
+                .parse(None, Some(context))
+        });
+
+        match parsed {
+            Ok(parsed) => segments.extend(parsed),
+            Err(error) => {
+                context.report_module_error(&mod_name, &error);
+                return None;
+            }
         }
-    });
+    }
+    module.set_segments(segments);
 
     Some(module)
 }
 
+/// Returns the names and values of the variables to show, which are the variables whose names
+/// match `match`, the defined variables of `variables`, or the single variable of the module.
+///
+/// When none of the variables are defined, `default` is used instead.
+fn get_values(
+    name: Option<&str>,
+    context: &Context,
+    config: &EnvVarConfig,
+) -> Option<Vec<(String, String)>> {
+    let (default_name, mut values) = if let Some(pattern) = config.pattern {
+        let regex = match Regex::new(pattern) {
+            Ok(regex) => regex,
+            Err(error) => {
+                log::warn!("Invalid env_var match pattern {pattern:?}: {error}");
+                return None;
+            }
+        };
+        let mut values = context
+            .get_env_vars()
+            .into_iter()
+            .filter(|(name, _)| regex.is_match(name))
+            .collect::<Vec<_>>();
+        values.sort();
+        (pattern, values)
+    } else if !config.variables.is_empty() {
+        let values = config
+            .variables
+            .iter()
+            .filter_map(|name| Some((name.to_string(), context.get_env(name)?)))
+            .collect();
+        (config.variables[0], values)
+    } else {
+        let variable_name = config.variable.or(name)?;
+        let values = context
+            .get_env(variable_name)
+            .map(|value| (variable_name.to_string(), value))
+            .into_iter()
+            .collect();
+        (variable_name, values)
+    };
+
+    if values.is_empty() {
+        values.push((default_name.to_string(), config.default?.to_string()));
+    }
+    Some(values)
+}
+
 /// Filter `config` to only includes non-table values
 /// This filters the top-level table to only include its specific configuration
 fn filter_config(config: &toml::Value) -> Option<toml::Value> {
@@ -330,6 +397,93 @@ mod test {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn multiple_variables() {
+        let actual = ModuleRenderer::new("env_var.cluster")
+            .config(toml::toml! {
+                [env_var.cluster]
+                variables = ["CLUSTER", "NAMESPACE", "REGION"]
+                format = "[$CLUSTER/$NAMESPACE( in $REGION)]($style) "
+            })
+            .env("CLUSTER", "prod")
+            .env("NAMESPACE", "web")
+            .collect();
+        let expected = Some(format!("{} ", style().paint("prod/web")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn multiple_variables_joined() {
+        let actual = ModuleRenderer::new("env_var.cluster")
+            .config(toml::toml! {
+                [env_var.cluster]
+                variables = ["CLUSTER", "NAMESPACE", "REGION"]
+                separator = ":"
+                format = "$all"
+            })
+            .env("CLUSTER", "prod")
+            .env("REGION", "eu")
+            .collect();
+
+        assert_eq!(Some("prod:eu".to_string()), actual);
+    }
+
+    #[test]
+    fn multiple_undefined_variables() {
+        let actual = ModuleRenderer::new("env_var.cluster")
+            .config(toml::toml! {
+                [env_var.cluster]
+                variables = ["CLUSTER", "NAMESPACE"]
+            })
+            .collect();
+        assert_eq!(None, actual);
+
+        let actual = ModuleRenderer::new("env_var.cluster")
+            .config(toml::toml! {
+                [env_var.cluster]
+                variables = ["CLUSTER", "NAMESPACE"]
+                default = "none"
+                format = "$all"
+            })
+            .collect();
+        assert_eq!(Some("none".to_string()), actual);
+    }
+
+    #[test]
+    fn matching_variables() {
+        let actual = ModuleRenderer::new("env_var.myapp")
+            .config(toml::toml! {
+                [env_var.myapp]
+                match = "^MYAPP_"
+                format = "[$name=$env_value]($style) "
+            })
+            .env("MYAPP_PORT", "8080")
+            .env("MYAPP_HOST", "localhost")
+            .env("OTHER_MYAPP_HOST", "example.com")
+            .collect();
+        let expected = Some(format!(
+            "{} {} ",
+            style().paint("MYAPP_HOST=localhost"),
+            style().paint("MYAPP_PORT=8080")
+        ));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn invalid_match_pattern() {
+        let actual = ModuleRenderer::new("env_var.myapp")
+            .config(toml::toml! {
+                [env_var.myapp]
+                match = "^MYAPP_("
+            })
+            .env("MYAPP_PORT", "8080")
+            .collect();
+
+        assert_eq!(None, actual);
+    }
+
     fn style() -> Style {
         // default style
         Color::Black.bold().dimmed()
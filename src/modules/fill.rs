@@ -6,21 +6,62 @@ use crate::segment::Segment;
 
 /// Creates a module that fills the any extra space on the line.
 ///
-pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
-    let mut module = context.new_module("fill");
-    let config: FillConfig = FillConfig::try_load(module.config);
+/// Named fills, like `${fill.center}`, use the options of `[fill.center]` on top of
+/// the ones of `[fill]`.
+pub fn module<'a>(name: Option<&str>, context: &'a Context) -> Option<Module<'a>> {
+    let toml_config = get_config(name, context);
+    let config: FillConfig = FillConfig::try_load(toml_config.as_ref());
 
     if config.disabled {
         return None;
     }
 
+    let mod_name = match name {
+        Some(name) => format!("fill.{name}"),
+        None => "fill".to_owned(),
+    };
+    let mut module = Module::new(&mod_name, super::description("fill"), None);
+
     let style = parse_style_string(config.style, Some(context));
 
-    module.set_segments(vec![Segment::fill(style, config.symbol)]);
+    module.set_segments(vec![Segment::fill(style, config.symbol).with_fill_width(
+        config.weight,
+        config.min_width,
+        config.max_width,
+    )]);
 
     Some(module)
 }
 
+/// Merges the options of a named fill into the top-level options, without the named fill tables
+fn get_config(name: Option<&str>, context: &Context) -> Option<toml::Value> {
+    let mut table = context
+        .config
+        .get_module_config("fill")
+        .and_then(toml::Value::as_table)
+        .map(|table| {
+            table
+                .iter()
+                .filter(|(_, value)| !value.is_table())
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect::<toml::Table>()
+        })
+        .unwrap_or_default();
+
+    if let Some(named) = name
+        .and_then(|name| context.config.get_config(&["fill", name]))
+        .and_then(toml::Value::as_table)
+    {
+        table.extend(
+            named
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+    }
+
+    (!table.is_empty()).then_some(toml::Value::Table(table))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::test::ModuleRenderer;
@@ -65,4 +106,20 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn named_fill() {
+        let actual = ModuleRenderer::new("fill.center")
+            .config(toml::toml! {
+                [fill]
+                style = "bold green"
+                symbol = "-"
+                [fill.center]
+                symbol = "*"
+            })
+            .collect();
+        let expected = Some(format!("{}", Color::Green.bold().paint("*")));
+
+        assert_eq!(expected, actual);
+    }
 }
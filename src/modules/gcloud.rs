@@ -49,6 +49,49 @@ impl<'a> GcloudContext {
         let config = self.get_config()?;
         config.section(Some("compute"))?.get("region")
     }
+
+    pub fn get_impersonated_account(&'a self) -> Option<&'a str> {
+        let config = self.get_config()?;
+        config
+            .section(Some("auth"))?
+            .get("impersonate_service_account")
+    }
+}
+
+/// The identity and project of the application default credentials (ADC), which client
+/// libraries use instead of the active account of gcloud
+struct AdcCredentials {
+    account: Option<String>,
+    project: Option<String>,
+}
+
+fn get_adc_credentials(context: &Context, config_dir: &Path) -> Option<AdcCredentials> {
+    let path = context
+        .get_env("GOOGLE_APPLICATION_CREDENTIALS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| config_dir.join("application_default_credentials.json"));
+    let credentials: serde_json::Value =
+        serde_json::from_str(&utils::read_file(path).ok()?).ok()?;
+
+    let account = match credentials["type"].as_str()? {
+        "service_account" => credentials["client_email"].as_str(),
+        // e.g. `https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts/name@project.iam.gserviceaccount.com:generateAccessToken`
+        "impersonated_service_account" => credentials["service_account_impersonation_url"]
+            .as_str()
+            .and_then(|url| url.rsplit('/').next())
+            .and_then(|name| name.split(':').next()),
+        // Only recent versions of gcloud store the account of user credentials
+        _ => credentials["account"].as_str(),
+    };
+    let project = credentials["quota_project_id"]
+        .as_str()
+        .or_else(|| credentials["project_id"].as_str());
+
+    let non_empty = |value: Option<&str>| value.filter(|v| !v.is_empty()).map(String::from);
+    Some(AdcCredentials {
+        account: non_empty(account),
+        project: non_empty(project),
+    })
 }
 
 fn get_current_config(context: &Context) -> Option<(String, PathBuf)> {
@@ -91,6 +134,38 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let (config_name, config_path) = get_current_config(context)?;
     let gcloud_context = GcloudContext::new(&config_name, &config_path);
     let account: Lazy<Option<Account<'_>>, _> = Lazy::new(|| gcloud_context.get_account());
+    let impersonated_account: Lazy<Option<Cow<str>>, _> = Lazy::new(|| {
+        context
+            .get_env("CLOUDSDK_AUTH_IMPERSONATE_SERVICE_ACCOUNT")
+            .map(Cow::Owned)
+            .or_else(|| gcloud_context.get_impersonated_account().map(Cow::Borrowed))
+    });
+    let project: Lazy<Option<Cow<str>>, _> = Lazy::new(|| {
+        context
+            .get_env("CLOUDSDK_CORE_PROJECT")
+            .map(Cow::Owned)
+            .or_else(|| gcloud_context.get_project().map(Cow::Borrowed))
+    });
+    let adc: Lazy<Option<AdcCredentials>, _> =
+        Lazy::new(|| get_adc_credentials(context, &get_config_dir(context)?));
+
+    // The ADC differ when they are known to use another account or project than gcloud
+    let adc_mismatch = Lazy::new(|| {
+        let Some(adc) = adc.as_ref() else {
+            return false;
+        };
+        let gcloud_account = impersonated_account.as_deref().or_else(|| {
+            gcloud_context
+                .get_config()?
+                .section(Some("core"))?
+                .get("account")
+        });
+        let differs = |adc_value: Option<&str>, gcloud_value: Option<&str>| {
+            adc_value.zip(gcloud_value).map_or(false, |(a, b)| a != b)
+        };
+        differs(adc.account.as_deref(), gcloud_account)
+            || differs(adc.project.as_deref(), project.as_deref())
+    });
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -116,19 +191,32 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                     .map(|region| config.region_aliases.get(region).copied().unwrap_or(region))
                     .map(Cow::Borrowed)
                     .map(Ok),
-                "project" => context
-                    .get_env("CLOUDSDK_CORE_PROJECT")
-                    .map(Cow::Owned)
-                    .or_else(|| gcloud_context.get_project().map(Cow::Borrowed))
+                "project" => project
+                    .as_deref()
                     .map(|project| {
                         config
                             .project_aliases
-                            .get(project.as_ref())
+                            .get(project)
                             .copied()
-                            .map_or(project, Cow::Borrowed)
+                            .unwrap_or(project)
                     })
+                    .map(Cow::Borrowed)
                     .map(Ok),
                 "active" => Some(Ok(Cow::Borrowed(&gcloud_context.config_name))),
+                "impersonated" => impersonated_account.as_deref().map(Cow::Borrowed).map(Ok),
+                "adc_account" => adc
+                    .as_ref()
+                    .and_then(|adc| adc.account.as_deref())
+                    .map(Cow::Borrowed)
+                    .map(Ok),
+                "adc_project" => adc
+                    .as_ref()
+                    .and_then(|adc| adc.project.as_deref())
+                    .map(Cow::Borrowed)
+                    .map(Ok),
+                "adc_mismatch" => {
+                    (*adc_mismatch).then_some(Ok(Cow::Borrowed(config.adc_mismatch_symbol)))
+                }
                 _ => None,
             })
             .parse(None, Some(context))
@@ -137,7 +225,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::error!("Error in module `gcloud`: \n{}", error);
+            context.report_module_error("gcloud", &error);
             return None;
         }
     });
@@ -501,4 +589,115 @@ project = overridden
         assert_eq!(actual, expected);
         dir.close()
     }
+
+    fn write_config(dir: &std::path::Path, config: &[u8]) -> io::Result<()> {
+        File::create(dir.join("active_config"))?.write_all(b"default")?;
+        create_dir(dir.join("configurations"))?;
+        File::create(dir.join("configurations").join("config_default"))?.write_all(config)
+    }
+
+    #[test]
+    fn impersonated_account_set() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_config(
+            dir.path(),
+            b"\
+[core]
+account = foo@example.com
+
+[auth]
+impersonate_service_account = deployer@project.iam.gserviceaccount.com
+",
+        )?;
+
+        let actual = ModuleRenderer::new("gcloud")
+            .env("CLOUDSDK_CONFIG", dir.path().to_string_lossy())
+            .config(toml::toml! {
+                [gcloud]
+                format = "$account as $impersonated"
+            })
+            .collect();
+        let expected = Some("foo as deployer@project.iam.gserviceaccount.com".to_string());
+        assert_eq!(actual, expected);
+
+        let actual = ModuleRenderer::new("gcloud")
+            .env("CLOUDSDK_CONFIG", dir.path().to_string_lossy())
+            .env(
+                "CLOUDSDK_AUTH_IMPERSONATE_SERVICE_ACCOUNT",
+                "reader@project.iam.gserviceaccount.com",
+            )
+            .config(toml::toml! {
+                [gcloud]
+                format = "$impersonated"
+            })
+            .collect();
+        let expected = Some("reader@project.iam.gserviceaccount.com".to_string());
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn adc_with_other_project() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_config(
+            dir.path(),
+            b"\
+[core]
+account = foo@example.com
+project = abc
+",
+        )?;
+        File::create(dir.path().join("application_default_credentials.json"))?.write_all(
+            br#"{"type": "authorized_user", "account": "", "quota_project_id": "xyz"}"#,
+        )?;
+
+        let actual = ModuleRenderer::new("gcloud")
+            .env("CLOUDSDK_CONFIG", dir.path().to_string_lossy())
+            .config(toml::toml! {
+                [gcloud]
+                format = "$project( \\[$adc_account\\])( $adc_project)( $adc_mismatch)"
+            })
+            .collect();
+        let expected = Some("abc xyz ≠ADC".to_string());
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn adc_matching_impersonated_account() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_config(
+            dir.path(),
+            b"\
+[core]
+account = foo@example.com
+project = abc
+
+[auth]
+impersonate_service_account = deployer@abc.iam.gserviceaccount.com
+",
+        )?;
+        let credentials_path = dir.path().join("credentials.json");
+        File::create(&credentials_path)?.write_all(
+            br#"{
+                "type": "impersonated_service_account",
+                "service_account_impersonation_url": "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts/deployer@abc.iam.gserviceaccount.com:generateAccessToken"
+            }"#,
+        )?;
+
+        let actual = ModuleRenderer::new("gcloud")
+            .env("CLOUDSDK_CONFIG", dir.path().to_string_lossy())
+            .env(
+                "GOOGLE_APPLICATION_CREDENTIALS",
+                credentials_path.to_string_lossy(),
+            )
+            .config(toml::toml! {
+                [gcloud]
+                format = "$adc_account( $adc_mismatch)"
+            })
+            .collect();
+        let expected = Some("deployer@abc.iam.gserviceaccount.com".to_string());
+        assert_eq!(actual, expected);
+        dir.close()
+    }
 }
@@ -26,7 +26,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     let repo = context.get_repo().ok()?;
 
-    if config.only_attached && repo.open().head().ok()?.is_detached() {
+    if config.only_attached && repo.is_detached {
         return None;
     }
 
@@ -104,7 +104,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `git_branch`: \n{}", error);
+            context.report_module_error("git_branch", &error);
             return None;
         }
     });
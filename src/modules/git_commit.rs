@@ -13,11 +13,9 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let config: GitCommitConfig = GitCommitConfig::try_load(module.config);
 
     let repo = context.get_repo().ok()?;
-    let git_repo = repo.open();
-    let git_head = git_repo.head().ok()?;
+    let head_id = repo.head_id?;
 
-    let is_detached = git_head.is_detached();
-    if config.only_detached && !is_detached {
+    if config.only_detached && !repo.is_detached {
         return None;
     };
 
@@ -28,11 +26,14 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "hash" => Some(Ok(git_hash(context.get_repo().ok()?, &config)?)),
+                "hash" => Some(Ok(format!(
+                    "{}",
+                    head_id.to_hex_with_len(config.commit_hash_length)
+                ))),
                 "tag" if !config.tag_disabled => Some(Ok(format!(
                     "{}{}",
                     config.tag_symbol,
-                    git_tag(context.get_repo().ok()?, &config)?
+                    git_tag(repo, &config)?
                 ))),
                 _ => None,
             })
@@ -42,7 +43,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `git_commit`:\n{}", error);
+            context.report_module_error("git_commit", &error);
             return None;
         }
     });
@@ -66,16 +67,6 @@ fn git_tag(repo: &Repo, config: &GitCommitConfig) -> Option<String> {
     Some(formatter.name?.to_string())
 }
 
-fn git_hash(repo: &Repo, config: &GitCommitConfig) -> Option<String> {
-    let git_repo = repo.open();
-    let head_id = git_repo.head_id().ok()?;
-
-    Some(format!(
-        "{}",
-        head_id.to_hex_with_len(config.commit_hash_length)
-    ))
-}
-
 #[cfg(test)]
 mod tests {
     use nu_ansi_term::Color;
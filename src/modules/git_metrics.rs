@@ -1,8 +1,9 @@
+use once_cell::sync::Lazy;
 use regex::Regex;
 use std::ffi::OsStr;
 
 use crate::{
-    config::ModuleConfig, configs::git_metrics::GitMetricsConfig,
+    config::ModuleConfig, configs::git_metrics::GitMetricsConfig, context::Repo,
     formatter::string_formatter::StringFormatterError, formatter::StringFormatter, module::Module,
 };
 
@@ -21,25 +22,10 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     };
 
     let repo = context.get_repo().ok()?;
-    let repo_root = repo.workdir.as_ref()?;
-
-    let mut args = vec![
-        OsStr::new("--git-dir"),
-        repo.path.as_os_str(),
-        OsStr::new("--work-tree"),
-        repo_root.as_os_str(),
-        OsStr::new("--no-optional-locks"),
-        OsStr::new("diff"),
-        OsStr::new("--shortstat"),
-    ];
-
-    if config.ignore_submodules {
-        args.push(OsStr::new("--ignore-submodules"));
-    }
 
-    let diff = context.exec_cmd("git", &args)?.stdout;
-
-    let stats = GitDiff::parse(&diff);
+    // Only run the diffs that are actually referenced in the format string
+    let unstaged = Lazy::new(|| get_diff(context, repo, &config, false));
+    let staged = Lazy::new(|| get_diff(context, repo, &config, true));
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -49,8 +35,18 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "added" => GitDiff::get_variable(config.only_nonzero_diffs, stats.added),
-                "deleted" => GitDiff::get_variable(config.only_nonzero_diffs, stats.deleted),
+                "added" | "unstaged_added" => {
+                    GitDiff::get_variable(config.only_nonzero_diffs, &unstaged.as_ref()?.added)
+                }
+                "deleted" | "unstaged_deleted" => {
+                    GitDiff::get_variable(config.only_nonzero_diffs, &unstaged.as_ref()?.deleted)
+                }
+                "staged_added" => {
+                    GitDiff::get_variable(config.only_nonzero_diffs, &staged.as_ref()?.added)
+                }
+                "staged_deleted" => {
+                    GitDiff::get_variable(config.only_nonzero_diffs, &staged.as_ref()?.deleted)
+                }
                 _ => None,
             })
             .parse(None, Some(context))
@@ -59,7 +55,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `git_metrics`:\n{}", error);
+            context.report_module_error("git_metrics", &error);
             return None;
         }
     });
@@ -67,24 +63,68 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Runs `git diff --shortstat` for either the staged or the unstaged changes
+fn get_diff(
+    context: &Context,
+    repo: &Repo,
+    config: &GitMetricsConfig,
+    staged: bool,
+) -> Option<GitDiff> {
+    let repo_root = repo.workdir.as_ref()?;
+
+    let mut args = vec![
+        OsStr::new("--git-dir"),
+        repo.path.as_os_str(),
+        OsStr::new("--work-tree"),
+        repo_root.as_os_str(),
+        OsStr::new("--no-optional-locks"),
+        OsStr::new("diff"),
+        OsStr::new("--shortstat"),
+    ];
+
+    if staged {
+        args.push(OsStr::new("--cached"));
+    }
+
+    if config.ignore_submodules {
+        args.push(OsStr::new("--ignore-submodules"));
+    }
+
+    // Exclude pathspecs are relative to the repository root, regardless of the current directory
+    let excludes = config
+        .ignore_paths
+        .iter()
+        .map(|path| format!(":(top,exclude){path}"))
+        .collect::<Vec<_>>();
+    if !excludes.is_empty() {
+        args.push(OsStr::new("--"));
+        args.push(OsStr::new(":/"));
+        args.extend(excludes.iter().map(OsStr::new));
+    }
+
+    let diff = context.exec_cmd("git", &args)?.stdout;
+
+    Some(GitDiff::parse(&diff))
+}
+
 /// Represents the parsed output from a git diff.
-struct GitDiff<'a> {
-    added: &'a str,
-    deleted: &'a str,
+struct GitDiff {
+    added: String,
+    deleted: String,
 }
 
-impl<'a> GitDiff<'a> {
+impl GitDiff {
     /// Returns the first capture group given a regular expression and a string.
     /// If it fails to get the capture group it will return "0".
-    fn get_matched_str(diff: &'a str, re: &Regex) -> &'a str {
+    fn get_matched_str(diff: &str, re: &Regex) -> String {
         match re.captures(diff) {
-            Some(caps) => caps.get(1).unwrap().as_str(),
-            _ => "0",
+            Some(caps) => caps.get(1).unwrap().as_str().to_string(),
+            _ => "0".to_string(),
         }
     }
 
     /// Parses the result of 'git diff --shortstat' as a `GitDiff` struct.
-    pub fn parse(diff: &'a str) -> Self {
+    pub fn parse(diff: &str) -> Self {
         let added_re = Regex::new(r"(\d+) \w+\(\+\)").unwrap();
         let deleted_re = Regex::new(r"(\d+) \w+\(\-\)").unwrap();
 
@@ -256,6 +296,72 @@ mod tests {
         repo_dir.close()
     }
 
+    #[test]
+    fn shows_staged_and_unstaged_changes_separately() -> io::Result<()> {
+        let repo_dir = create_repo_with_commit()?;
+        let path = repo_dir.path();
+
+        let file_path = path.join("the_file");
+        write_file(&file_path, "First Line\nSecond Line\nThird Line\nStaged\n")?;
+        run_git_cmd(["add", "the_file"], Some(path), true)?;
+        write_file(
+            &file_path,
+            "First Line\nThird Line\nStaged\nUnstaged 1\nUnstaged 2\n",
+        )?;
+
+        let actual = ModuleRenderer::new("git_metrics")
+            .config(toml::toml! {
+                [git_metrics]
+                disabled = false
+                format = "$staged_added/$staged_deleted $unstaged_added/$unstaged_deleted"
+            })
+            .path(path)
+            .collect();
+
+        let expected = Some(String::from("1/ 2/1"));
+
+        assert_eq!(expected, actual);
+        repo_dir.close()
+    }
+
+    #[test]
+    fn shows_nothing_for_ignored_paths() -> io::Result<()> {
+        let repo_dir = create_repo_with_commit()?;
+        let path = repo_dir.path();
+
+        let file_path = path.join("the_file");
+        write_file(file_path, "\nSecond Line\n\nModified\nAdded\n")?;
+
+        let actual = ModuleRenderer::new("git_metrics")
+            .config(toml::toml! {
+                [git_metrics]
+                disabled = false
+                ignore_paths = ["the_*"]
+            })
+            .path(path)
+            .collect();
+
+        assert_eq!(None, actual);
+
+        let actual = ModuleRenderer::new("git_metrics")
+            .config(toml::toml! {
+                [git_metrics]
+                disabled = false
+                ignore_paths = ["other_file"]
+            })
+            .path(path)
+            .collect();
+
+        let expected = Some(format!(
+            "{} {} ",
+            Color::Green.bold().paint("+4"),
+            Color::Red.bold().paint("-2")
+        ));
+
+        assert_eq!(expected, actual);
+        repo_dir.close()
+    }
+
     fn render_metrics(path: &Path) -> Option<String> {
         ModuleRenderer::new("git_metrics")
             .config(toml::toml! {
@@ -0,0 +1,388 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::git_signing::GitSigningConfig;
+use crate::context::Repo;
+use crate::formatter::StringFormatter;
+use crate::utils;
+
+/// The settings that decide which key git signs with, and with which program
+const SIGNING_SETTINGS: &str = r"^(user\.(signingkey|email)|commit\.gpgsign|gpg\.(format|program|openpgp\.program|x509\.program))$";
+
+/// Creates a module with the key git signs commits with
+///
+/// The key is shown in `unavailable_style` if signing with it would fail, because the secret key
+/// isn't in the keyring, or the SSH key is neither on disk nor in the agent.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("git_signing");
+    let config = GitSigningConfig::try_load(module.config);
+
+    if config.disabled {
+        return None;
+    }
+
+    let repo = context.get_repo().ok()?;
+    let settings = get_settings(context, repo);
+
+    let signing = settings.get("commit.gpgsign").map_or(false, |value| {
+        matches!(value.as_str(), "true" | "yes" | "on" | "1")
+    });
+    let signing_key = settings.get("user.signingkey").map(String::as_str);
+    if !signing && (signing_key.is_none() || config.only_when_signing) {
+        return None;
+    }
+
+    let format = settings.get("gpg.format").map_or("openpgp", String::as_str);
+    let (key, available) = match format {
+        "ssh" => match signing_key {
+            Some(key) => (ssh_key_name(key), is_ssh_key_available(context, key)),
+            None => (String::new(), false),
+        },
+        _ => {
+            // Without a signing key, gpg picks a key matching the committer's email
+            let key = signing_key
+                .or_else(|| settings.get("user.email").map(String::as_str))
+                .unwrap_or_default();
+            let program = if format == "x509" {
+                settings
+                    .get("gpg.x509.program")
+                    .map_or("gpgsm", String::as_str)
+            } else {
+                settings
+                    .get("gpg.openpgp.program")
+                    .or_else(|| settings.get("gpg.program"))
+                    .map_or("gpg", String::as_str)
+            };
+            let available = !key.is_empty()
+                && context
+                    .exec_cmd(program, &["--list-secret-keys", key])
+                    .is_some();
+            (key.to_string(), available)
+        }
+    };
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" if available => Some(Ok(config.style)),
+                "style" => Some(Ok(config.unavailable_style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "key" => Some(Ok(key.as_str())),
+                "format" => Some(Ok(format)),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            context.report_module_error("git_signing", &error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Reads the signing settings with `git config`, because they're usually in the global config,
+/// which isn't loaded when the repository is opened
+fn get_settings(context: &Context, repo: &Repo) -> HashMap<String, String> {
+    let git_dir = repo.path.to_string_lossy();
+    let output = context.exec_cmd(
+        "git",
+        &[
+            "--git-dir",
+            &git_dir,
+            "config",
+            "--get-regexp",
+            SIGNING_SETTINGS,
+        ],
+    );
+
+    // `git config` fails if none of the settings are set
+    output
+        .map(|output| {
+            output
+                .stdout
+                .lines()
+                .filter_map(|line| line.split_once(' '))
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The public key of a `user.signingkey` that holds the key itself rather than its path. Like git,
+/// this is a key prefixed with `key::`, or one that starts with its `ssh-` or `ecdsa-` type.
+fn literal_ssh_key(key: &str) -> Option<&str> {
+    key.strip_prefix("key::")
+        .or_else(|| (key.starts_with("ssh-") || key.starts_with("ecdsa-")).then_some(key))
+}
+
+/// A short name of the SSH key, which is either a path or a literal public key
+fn ssh_key_name(key: &str) -> String {
+    match literal_ssh_key(key) {
+        // The comment of the key, or else its type
+        Some(public_key) => {
+            let mut parts = public_key.split_whitespace();
+            let key_type = parts.next().unwrap_or_default();
+            parts.nth(1).unwrap_or(key_type).to_string()
+        }
+        None => Path::new(key).file_name().map_or_else(
+            || key.to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        ),
+    }
+}
+
+/// Checks if the SSH key can be used, either because its private key is on disk or because it's
+/// loaded in the agent
+fn is_ssh_key_available(context: &Context, key: &str) -> bool {
+    let public_key = match literal_ssh_key(key) {
+        Some(public_key) => public_key.to_string(),
+        None => {
+            let path = expand_home(context, key);
+            let Ok(content) = utils::read_file(&path) else {
+                return false;
+            };
+            // The setting may point to the private key itself
+            if content.contains("PRIVATE KEY") {
+                return true;
+            }
+            if path.extension().map_or(false, |ext| ext == "pub")
+                && path.with_extension("").is_file()
+            {
+                return true;
+            }
+            content
+        }
+    };
+
+    // Keys are compared by their type and data, as the comment may differ
+    fn key_data(line: &str) -> Vec<&str> {
+        line.split_whitespace().take(2).collect()
+    }
+    let wanted = key_data(&public_key);
+    context
+        .exec_cmd("ssh-add", &["-L"])
+        .map_or(false, |output| {
+            output.stdout.lines().any(|line| key_data(line) == wanted)
+        })
+}
+
+fn expand_home(context: &Context, path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), context.get_home()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{output, ModuleRenderer};
+    use crate::utils::create_command;
+    use nu_ansi_term::Color;
+    use std::fs;
+    use std::io;
+
+    fn create_repo() -> io::Result<tempfile::TempDir> {
+        let dir = tempfile::tempdir()?;
+        create_command("git")?
+            .args(["init", "--quiet"])
+            .current_dir(dir.path())
+            .output()?;
+        Ok(dir)
+    }
+
+    fn git_config(dir: &Path) -> String {
+        format!(
+            "git --git-dir {} config --get-regexp {SIGNING_SETTINGS}",
+            dir.join(".git").to_string_lossy()
+        )
+    }
+
+    fn renderer<'a>(dir: &Path, git_config: &'a str, settings: &str) -> ModuleRenderer<'a> {
+        ModuleRenderer::new("git_signing")
+            .path(dir)
+            .config(toml::toml! {
+                [git_signing]
+                disabled = false
+            })
+            .cmd(git_config, output(settings))
+    }
+
+    #[test]
+    fn not_in_repo() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = ModuleRenderer::new("git_signing")
+            .path(dir.path())
+            .config(toml::toml! {
+                [git_signing]
+                disabled = false
+            })
+            .collect();
+        assert_eq!(actual, None);
+        dir.close()
+    }
+
+    #[test]
+    fn no_signing_key() -> io::Result<()> {
+        let dir = create_repo()?;
+        let git_config = git_config(dir.path());
+        let actual = renderer(dir.path(), &git_config, "user.email me@example.com\n").collect();
+        assert_eq!(actual, None);
+        dir.close()
+    }
+
+    #[test]
+    fn gpg_key_available() -> io::Result<()> {
+        let dir = create_repo()?;
+        let git_config = git_config(dir.path());
+        let actual = renderer(
+            dir.path(),
+            &git_config,
+            "user.signingkey 3AA5C34371567BD2\n",
+        )
+        .cmd("gpg --list-secret-keys 3AA5C34371567BD2", output("sec ..."))
+        .collect();
+        let expected = Some(format!(
+            "{} ",
+            Color::Green.bold().paint("🔏 3AA5C34371567BD2")
+        ));
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn gpg_key_missing() -> io::Result<()> {
+        let dir = create_repo()?;
+        let git_config = git_config(dir.path());
+        let actual = renderer(
+            dir.path(),
+            &git_config,
+            "user.signingkey 3AA5C34371567BD2\ngpg.program gpg2\n",
+        )
+        .cmd("gpg2 --list-secret-keys 3AA5C34371567BD2", None)
+        .collect();
+        let expected = Some(format!(
+            "{} ",
+            Color::Red.bold().paint("🔏 3AA5C34371567BD2")
+        ));
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn gpg_key_from_email_when_signing() -> io::Result<()> {
+        let dir = create_repo()?;
+        let git_config = git_config(dir.path());
+        let actual = renderer(
+            dir.path(),
+            &git_config,
+            "user.email me@example.com\ncommit.gpgsign true\n",
+        )
+        .cmd("gpg --list-secret-keys me@example.com", None)
+        .collect();
+        let expected = Some(format!("{} ", Color::Red.bold().paint("🔏 me@example.com")));
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn only_when_signing() -> io::Result<()> {
+        let dir = create_repo()?;
+        let git_config = git_config(dir.path());
+        let actual = renderer(
+            dir.path(),
+            &git_config,
+            "user.signingkey 3AA5C34371567BD2\n",
+        )
+        .config(toml::toml! {
+            [git_signing]
+            disabled = false
+            only_when_signing = true
+        })
+        .collect();
+        assert_eq!(actual, None);
+        dir.close()
+    }
+
+    #[test]
+    fn ssh_key_in_agent() -> io::Result<()> {
+        let dir = create_repo()?;
+        let git_config = git_config(dir.path());
+        let actual = renderer(
+            dir.path(),
+            &git_config,
+            "gpg.format ssh\nuser.signingkey key::ssh-ed25519 AAAAC3Nza me@laptop\n",
+        )
+        .cmd(
+            "ssh-add -L",
+            output("ssh-rsa AAAAB3Nza other\nssh-ed25519 AAAAC3Nza agent comment\n"),
+        )
+        .collect();
+        let expected = Some(format!("{} ", Color::Green.bold().paint("🔏 me@laptop")));
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn ssh_key_without_prefix() -> io::Result<()> {
+        let dir = create_repo()?;
+        let git_config = git_config(dir.path());
+        let actual = renderer(
+            dir.path(),
+            &git_config,
+            "gpg.format ssh\nuser.signingkey ecdsa-sha2-nistp256 AAAAE2Vj me@desktop\n",
+        )
+        .cmd(
+            "ssh-add -L",
+            output("ecdsa-sha2-nistp256 AAAAE2Vj agent comment\n"),
+        )
+        .collect();
+        let expected = Some(format!("{} ", Color::Green.bold().paint("🔏 me@desktop")));
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn ssh_key_file() -> io::Result<()> {
+        let dir = create_repo()?;
+        let git_config = git_config(dir.path());
+        let key_dir = tempfile::tempdir()?;
+        let public_key = key_dir.path().join("id_ed25519.pub");
+        fs::write(&public_key, "ssh-ed25519 AAAAC3Nza me@laptop\n")?;
+        let settings = format!(
+            "gpg.format ssh\nuser.signingkey {}\n",
+            public_key.to_string_lossy()
+        );
+
+        // Neither the private key nor the agent has it
+        let actual = renderer(dir.path(), &git_config, &settings)
+            .cmd("ssh-add -L", None)
+            .collect();
+        let expected = Some(format!("{} ", Color::Red.bold().paint("🔏 id_ed25519.pub")));
+        assert_eq!(actual, expected);
+
+        fs::write(key_dir.path().join("id_ed25519"), "")?;
+        let actual = renderer(dir.path(), &git_config, &settings).collect();
+        let expected = Some(format!(
+            "{} ",
+            Color::Green.bold().paint("🔏 id_ed25519.pub")
+        ));
+        assert_eq!(actual, expected);
+
+        key_dir.close()?;
+        dir.close()
+    }
+}
@@ -118,7 +118,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             }
         }
         Err(error) => {
-            log::warn!("Error in module `git_status`:\n{}", error);
+            context.report_module_error("git_status", &error);
             return None;
         }
     });
@@ -345,14 +345,17 @@ fn format_text<F>(
 where
     F: Fn(&str) -> Option<String> + Send + Sync,
 {
-    if let Ok(formatter) = StringFormatter::new(format_str) {
+    let parsed = StringFormatter::new(format_str).and_then(|formatter| {
         formatter
             .map(|variable| mapper(variable).map(Ok))
             .parse(None, Some(context))
-            .ok()
-    } else {
-        log::warn!("Error parsing format string `{}`", &config_path);
-        None
+    });
+    match parsed {
+        Ok(segments) => Some(segments),
+        Err(error) => {
+            context.report_module_error("git_status", &format!("`{config_path}`: {error}"));
+            None
+        }
     }
 }
 
@@ -3,12 +3,13 @@ use super::{Context, Module, ModuleConfig};
 use crate::configs::go::GoConfig;
 use crate::formatter::StringFormatter;
 use crate::formatter::VersionFormatter;
+use crate::utils;
 
 use once_cell::sync::Lazy;
 use regex::Regex;
-use semver::Version;
-use semver::VersionReq;
+use std::cmp::Ordering;
 use std::ops::Deref;
+use std::path::{Path, PathBuf};
 
 /// Creates a module with the current Go version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
@@ -66,6 +67,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
                     (!in_mod_range).then_some(Ok(mod_ver))
                 }
+                "workspace" => get_go_work_file(context)
+                    .as_deref()
+                    .and_then(Path::parent)
+                    .and_then(Path::file_name)
+                    .map(|name| Ok(name.to_string_lossy().to_string())),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -74,7 +80,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `golang`:\n{}", error);
+            context.report_module_error("golang", &error);
             return None;
         }
     });
@@ -99,9 +105,27 @@ fn parse_go_version(go_stdout: &str) -> Option<String> {
     Some(version.to_string())
 }
 
+/// Finds the file in the current directory or its parents, like the `go` command does
+fn find_upwards(context: &Context, file_name: &str) -> Option<PathBuf> {
+    context
+        .current_dir
+        .ancestors()
+        .map(|dir| dir.join(file_name))
+        .find(|path| path.is_file())
+}
+
+/// Returns the `go.work` file of the workspace that the current directory belongs to
+fn get_go_work_file(context: &Context) -> Option<PathBuf> {
+    match context.get_env("GOWORK").as_deref() {
+        Some("off") => None,
+        Some(path) if !path.is_empty() => Some(PathBuf::from(path)),
+        _ => find_upwards(context, "go.work"),
+    }
+}
+
 fn get_go_mod_version(context: &Context) -> Option<String> {
-    let mod_str = context.read_file_from_pwd("go.mod")?;
-    let re = Regex::new(r"(?:go\s)(\d+(\.\d+)+)").unwrap();
+    let mod_str = utils::read_file(find_upwards(context, "go.mod")?).ok()?;
+    let re = Regex::new(r"(?m)^\s*go\s+(\d+(\.\d+)+)").unwrap();
 
     if let Some(cap) = re.captures(&mod_str) {
         let mod_ver = cap.get(1)?.as_str();
@@ -111,18 +135,41 @@ fn get_go_mod_version(context: &Context) -> Option<String> {
     }
 }
 
+/// Compares Go versions like `1.21`, `1.21.0` and `1.21rc2`, where release candidates come
+/// before the release
+fn compare_go_versions(a: &str, b: &str) -> Option<Ordering> {
+    fn parse(version: &str) -> Option<([u64; 3], Option<&str>)> {
+        let (release, prerelease) = match version.find(|c: char| c.is_ascii_alphabetic()) {
+            Some(i) => (&version[..i], Some(&version[i..])),
+            None => (version, None),
+        };
+        let mut numbers = [0; 3];
+        for (i, number) in release.split('.').enumerate() {
+            *numbers.get_mut(i)? = number.parse().ok()?;
+        }
+        Some((numbers, prerelease))
+    }
+
+    let (a_numbers, a_prerelease) = parse(a)?;
+    let (b_numbers, b_prerelease) = parse(b)?;
+    let ordering = a_numbers
+        .cmp(&b_numbers)
+        .then_with(|| match (a_prerelease, b_prerelease) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(a), Some(b)) => a.cmp(b),
+        });
+    Some(ordering)
+}
+
+/// Checks that the installed Go is at least the version required by the go directive
 fn check_go_version(go_version: Option<&str>, mod_version: Option<&str>) -> bool {
     let (Some(go_version), Some(mod_version)) = (go_version, mod_version) else {
         return true;
     };
-    let Ok(r) = VersionReq::parse(mod_version) else {
-        return true;
-    };
-    let Ok(v) = Version::parse(go_version) else {
-        return true;
-    };
 
-    r.matches(&v)
+    compare_go_versions(go_version, mod_version).map_or(true, Ordering::is_ge)
 }
 
 #[cfg(test)]
@@ -304,4 +351,58 @@ mod tests {
         assert_eq!(expected, actual);
         dir.close()
     }
+
+    #[test]
+    fn test_check_go_version() {
+        assert!(check_go_version(Some("1.21.0"), Some("1.21")));
+        assert!(check_go_version(Some("1.22.1"), Some("1.21.5")));
+        assert!(check_go_version(Some("1.22rc1"), Some("1.21.5")));
+        assert!(!check_go_version(Some("1.21rc2"), Some("1.21.0")));
+        assert!(!check_go_version(Some("1.20"), Some("1.21")));
+        assert!(check_go_version(Some("devel"), Some("1.21")));
+        assert!(check_go_version(None, Some("1.21")));
+    }
+
+    #[test]
+    fn show_workspace_and_mod_version_from_parent() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let workspace_dir = dir.path().join("monorepo");
+        let module_dir = workspace_dir.join("services").join("api");
+        let package_dir = module_dir.join("handlers");
+        fs::create_dir_all(&package_dir)?;
+        fs::write(
+            workspace_dir.join("go.work"),
+            "go 1.21\n\nuse ./services/api\n",
+        )?;
+        fs::write(
+            module_dir.join("go.mod"),
+            "module example.com/api\n\ngo 1.21.4\n\ntoolchain go1.22.0\n",
+        )?;
+        File::create(package_dir.join("main.go"))?.sync_all()?;
+
+        let actual = ModuleRenderer::new("golang")
+            .path(&package_dir)
+            .config(toml::toml! {
+                [golang]
+                format = "via [$symbol($version )($mod_version )(in $workspace )]($style)"
+            })
+            .collect();
+        let expected = Some(format!(
+            "via {}",
+            Color::Red.bold().paint("🐹 v1.12.1 1.21.4 in monorepo ")
+        ));
+        assert_eq!(expected, actual);
+
+        let actual = ModuleRenderer::new("golang")
+            .path(&package_dir)
+            .env("GOWORK", "off")
+            .config(toml::toml! {
+                [golang]
+                format = "via [$symbol($workspace )]($style)"
+            })
+            .collect();
+        let expected = Some(format!("via {}", Color::Red.bold().paint("🐹 ")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
 }
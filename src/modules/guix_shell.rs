@@ -33,7 +33,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `guix_shell`:\n{}", error);
+            context.report_module_error("guix_shell", &error);
             return None;
         }
     });
@@ -1,10 +1,14 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use super::{Context, Module, ModuleConfig};
 
 use crate::configs::helm::HelmConfig;
 use crate::formatter::StringFormatter;
 use crate::formatter::VersionFormatter;
 
-/// Creates a module with the current Helm version
+const VERSION_KEY: &str = "helm_version";
+
+/// Creates a module with the current Helm version, and the name and version of the chart
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("helm");
     let config = HelmConfig::try_load(module.config);
@@ -20,6 +24,8 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
+    let chart = read_chart(context);
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
@@ -32,11 +38,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             })
             .map(|variable| match variable {
                 "version" => {
-                    let helm_version = parse_helm_version(
-                        &context
-                            .exec_cmd("helm", &["version", "--short", "--client"])?
-                            .stdout,
-                    )?;
+                    let helm_version = get_helm_version(context, &config)?;
                     VersionFormatter::format_module_version(
                         module.get_name(),
                         &helm_version,
@@ -44,6 +46,8 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                     )
                     .map(Ok)
                 }
+                "chart" => chart.as_ref().map(|(name, _)| Ok(name.clone())),
+                "chart_version" => chart.as_ref()?.1.clone().map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -52,7 +56,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `helm`:\n{}", error);
+            context.report_module_error("helm", &error);
             return None;
         }
     });
@@ -60,6 +64,44 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Reads the name and version of the chart from the `Chart.yaml` in the current directory
+fn read_chart(context: &Context) -> Option<(String, Option<String>)> {
+    let file_contents = context.read_file_from_pwd("Chart.yaml")?;
+    let chart_yaml = yaml_rust::YamlLoader::load_from_str(&file_contents).ok()?;
+    let chart = chart_yaml.first()?;
+    let name = chart["name"].as_str()?.to_string();
+    // Unquoted versions like `1.0` are parsed as numbers
+    let version = match &chart["version"] {
+        yaml_rust::Yaml::String(version) | yaml_rust::Yaml::Real(version) => Some(version.clone()),
+        yaml_rust::Yaml::Integer(version) => Some(version.to_string()),
+        _ => None,
+    };
+    Some((name, version))
+}
+
+/// Returns the version of the helm client, reusing the version found by an earlier prompt of
+/// this session while it is younger than `version_cache` seconds.
+fn get_helm_version(context: &Context, config: &HelmConfig) -> Option<String> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+
+    let cached = context.get_session_value(VERSION_KEY).and_then(|value| {
+        let (checked_at, version) = value.split_once(' ')?;
+        let is_fresh = now.saturating_sub(checked_at.parse().ok()?) < config.version_cache;
+        is_fresh.then(|| version.to_string())
+    });
+    if cached.is_some() {
+        return cached;
+    }
+
+    let version = parse_helm_version(
+        &context
+            .exec_cmd("helm", &["version", "--short", "--client"])?
+            .stdout,
+    )?;
+    context.set_session_value(VERSION_KEY, &format!("{now} {version}"));
+    Some(version)
+}
+
 fn parse_helm_version(helm_stdout: &str) -> Option<String> {
     // `helm version --short --client` output looks like this:
     // v3.1.1+gafe7058
@@ -84,7 +126,7 @@ mod tests {
     use super::*;
     use crate::test::ModuleRenderer;
     use nu_ansi_term::Color;
-    use std::fs::File;
+    use std::fs::{self, File};
     use std::io;
 
     #[test]
@@ -122,6 +164,69 @@ mod tests {
         dir.close()
     }
 
+    #[test]
+    fn folder_with_chart_name_and_version() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("Chart.yaml"),
+            "apiVersion: v2\nname: mychart\nversion: 0.1.0\n",
+        )?;
+
+        let actual = ModuleRenderer::new("helm").path(dir.path()).collect();
+
+        let expected = Some(format!(
+            "via {}",
+            Color::White.bold().paint("⎈ mychart@0.1.0 v3.1.1 ")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn chart_with_numeric_version() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("Chart.yaml"),
+            "name: mychart\nversion: 1.0\n",
+        )?;
+
+        let actual = ModuleRenderer::new("helm")
+            .path(dir.path())
+            .config(toml::toml! {
+                [helm]
+                format = "$chart $chart_version"
+            })
+            .collect();
+
+        assert_eq!(Some("mychart 1.0".to_string()), actual);
+        dir.close()
+    }
+
+    #[test]
+    fn version_is_cached_for_the_session() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache_dir = tempfile::tempdir()?;
+        File::create(dir.path().join("helmfile.yaml"))?.sync_all()?;
+
+        let renderer = || {
+            ModuleRenderer::new("helm")
+                .path(dir.path())
+                .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+                .env("STARSHIP_SESSION_KEY", "1234")
+        };
+        let expected = Some(format!("via {}", Color::White.bold().paint("⎈ v3.1.1 ")));
+        assert_eq!(renderer().collect(), expected);
+        // The version is only checked again once the cached one expires
+        assert_eq!(
+            renderer()
+                .cmd("helm version --short --client", None)
+                .collect(),
+            expected
+        );
+        cache_dir.close()?;
+        dir.close()
+    }
+
     #[test]
     fn test_parse_helm_version() {
         let helm_2 = "Client: v2.16.9+g8ad7037";
@@ -64,7 +64,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `hg_branch`:\n{}", error);
+            context.report_module_error("hg_branch", &error);
             return None;
         }
     });
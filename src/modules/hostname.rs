@@ -10,6 +10,11 @@ use crate::formatter::StringFormatter;
 /// Will display the hostname if all of the following criteria are met:
 ///     - hostname.disabled is absent or false
 ///     - `hostname.ssh_only` is false OR the user is currently connected as an SSH session (`$SSH_CONNECTION`)
+///
+/// The hosts an SSH session was opened from are read from `$LC_STARSHIP_JUMP_HOSTS`, which holds
+/// their names separated by spaces. Every host adds itself to the list, which reaches the next host
+/// if ssh is configured to forward `LC_*` variables (`SendEnv` and `AcceptEnv`), as some
+/// distributions like Debian do by default.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("hostname");
     let config: HostnameConfig = HostnameConfig::try_load(module.config);
@@ -41,6 +46,20 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         host.as_ref()
     };
 
+    // `$SSH_CONNECTION` is "client_ip client_port server_ip server_port"
+    let connection = ssh_connection.as_deref().map(|connection| {
+        let parts = connection.split_whitespace().collect::<Vec<_>>();
+        (parts.first().copied(), parts.get(3).copied())
+    });
+    let ssh_client_ip = connection.and_then(|(client_ip, _)| client_ip);
+    let ssh_port = connection.and_then(|(_, port)| port);
+
+    let jump_hosts = ssh_connection
+        .as_ref()
+        .and_then(|_| context.get_env("LC_STARSHIP_JUMP_HOSTS"))
+        .map(|jump_hosts| get_jump_hosts(&jump_hosts, host, config.jump_separator))
+        .unwrap_or_default();
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
@@ -59,6 +78,9 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             })
             .map(|variable| match variable {
                 "hostname" => Some(Ok(host)),
+                "jump_hosts" => Some(Ok(jump_hosts.as_str())),
+                "ssh_client_ip" => ssh_client_ip.map(Ok),
+                "ssh_port" => ssh_port.map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -67,7 +89,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `hostname`:\n{}", error);
+            context.report_module_error("hostname", &error);
             return None;
         }
     });
@@ -75,6 +97,19 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Formats the hosts before the current one, each followed by the separator
+fn get_jump_hosts(jump_hosts: &str, host: &str, separator: &str) -> String {
+    let mut hosts = jump_hosts.split_whitespace().collect::<Vec<_>>();
+    // The current host may have already added itself
+    while hosts.last() == Some(&host) {
+        hosts.pop();
+    }
+    hosts
+        .iter()
+        .map(|jump_host| format!("{jump_host}{separator}"))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::test::ModuleRenderer;
@@ -189,6 +224,56 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn ssh_connection_variables() {
+        let actual = ModuleRenderer::new("hostname")
+            .config(toml::toml! {
+                [hostname]
+                format = "$ssh_client_ip:$ssh_port"
+            })
+            .env("SSH_CONNECTION", "192.0.2.1 51234 192.0.2.2 2222")
+            .collect();
+        let expected = Some("192.0.2.1:2222".to_string());
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn jump_hosts() {
+        let hostname = get_hostname!();
+        let actual = ModuleRenderer::new("hostname")
+            .config(toml::toml! {
+                [hostname]
+                trim_at = ""
+            })
+            .env("SSH_CONNECTION", "something")
+            .env(
+                "LC_STARSHIP_JUMP_HOSTS",
+                format!("laptop bastion {hostname}"),
+            )
+            .collect();
+        let expected = Some(format!(
+            "{} in ",
+            style().paint(format!("🌐 laptop→bastion→{hostname}"))
+        ));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn jump_hosts_without_ssh() {
+        let actual = ModuleRenderer::new("hostname")
+            .config(toml::toml! {
+                [hostname]
+                ssh_only = false
+                format = "$jump_hosts"
+            })
+            .env("LC_STARSHIP_JUMP_HOSTS", "bastion")
+            .collect();
+
+        assert_eq!(None, actual);
+    }
+
     fn style() -> Style {
         Color::Green.bold().dimmed()
     }
@@ -1,13 +1,31 @@
 use super::{Context, Module, ModuleConfig};
 use crate::configs::java::JavaConfig;
 use crate::formatter::{StringFormatter, VersionFormatter};
-use crate::utils::get_command_string_output;
+use crate::utils::{self, get_command_string_output};
 use std::path::PathBuf;
 
+use once_cell::sync::Lazy;
 use regex::Regex;
 const JAVA_VERSION_PATTERN: &str =
     "(?:JRE.*\\(|OpenJ9 )(?P<version>\\d+(?:\\.\\d+){0,2}).*, built on";
 
+// Distributions and the names they are recognized by, in the order they are checked.
+// GraalVM comes first, as Oracle's GraalVM also names Oracle as its implementor.
+const VENDORS: &[(&str, &[&str])] = &[
+    ("GraalVM", &["graalvm"]),
+    ("Temurin", &["temurin", "adoptium", "adoptopenjdk"]),
+    ("Zulu", &["zulu", "azul"]),
+    ("Corretto", &["corretto", "amazon"]),
+    ("SapMachine", &["sapmachine", "sap se"]),
+    ("Liberica", &["liberica", "bellsoft"]),
+    ("Microsoft", &["microsoft"]),
+    (
+        "Semeru",
+        &["semeru", "openj9", "international business machines"],
+    ),
+    ("Oracle", &["oracle", "hotspot(tm)"]),
+];
+
 /// Creates a module with the current Java version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("java");
@@ -24,10 +42,17 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
+    let java_version_output = Lazy::new(|| get_java_version_output(context));
+    let vendor = Lazy::new(|| {
+        get_java_release_vendor(context)
+            .or_else(|| identify_vendor(java_version_output.as_deref()?))
+    });
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
                 "symbol" => Some(config.symbol),
+                "vendor_symbol" => config.vendor_symbols.get((*vendor)?).copied(),
                 _ => None,
             })
             .map_style(|variable| match variable {
@@ -36,7 +61,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             })
             .map(|variable| match variable {
                 "version" => {
-                    let java_version = get_java_version(context)?;
+                    let java_version = parse_java_version(java_version_output.as_deref()?)?;
                     VersionFormatter::format_module_version(
                         module.get_name(),
                         &java_version,
@@ -44,6 +69,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                     )
                     .map(Ok)
                 }
+                "vendor" => vendor.map(|vendor| Ok(vendor.to_string())),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -52,7 +78,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `java`:\n{}", error);
+            context.report_module_error("java", &error);
             return None;
         }
     });
@@ -60,7 +86,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
-fn get_java_version(context: &Context) -> Option<String> {
+fn get_java_version_output(context: &Context) -> Option<String> {
     let java_command = context
         .get_env("JAVA_HOME")
         .map(PathBuf::from)
@@ -74,9 +100,43 @@ fn get_java_version(context: &Context) -> Option<String> {
         .unwrap_or_else(|| String::from("java"));
 
     let output = context.exec_cmd(java_command, &["-Xinternalversion"])?;
-    let java_version_string = get_command_string_output(output);
+    Some(get_command_string_output(output))
+}
 
-    parse_java_version(&java_version_string)
+/// Reads the vendor from the `release` file of the JDK, which is more precise than the
+/// version output, e.g. for Temurin and Corretto
+fn get_java_release_vendor(context: &Context) -> Option<&'static str> {
+    let java_home = match context.get_env("JAVA_HOME") {
+        Some(java_home) => PathBuf::from(java_home),
+        // Follow the `java` on the path, e.g. `/usr/bin/java`, to the JDK it belongs to
+        None => {
+            let path = context.get_env_os("PATH");
+            let java = which::which_in("java", path, &context.current_dir).ok()?;
+            let java = dunce::canonicalize(java).ok()?;
+            java.parent()?.parent()?.to_path_buf()
+        }
+    };
+    let release = utils::read_file(java_home.join("release")).ok()?;
+
+    // e.g. `IMPLEMENTOR="Eclipse Adoptium"` and `IMPLEMENTOR_VERSION="Temurin-21.0.1+12"`
+    let implementor = release
+        .lines()
+        .filter(|line| {
+            ["IMPLEMENTOR=", "IMPLEMENTOR_VERSION=", "GRAALVM_VERSION="]
+                .iter()
+                .any(|key| line.starts_with(key))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    identify_vendor(&implementor)
+}
+
+fn identify_vendor(text: &str) -> Option<&'static str> {
+    let text = text.to_lowercase();
+    VENDORS
+        .iter()
+        .find(|(_, names)| names.iter().any(|name| text.contains(name)))
+        .map(|(vendor, _)| *vendor)
 }
 
 fn parse_java_version(java_version_string: &str) -> Option<String> {
@@ -312,4 +372,52 @@ mod tests {
         assert_eq!(expected, actual);
         dir.close()
     }
+
+    #[test]
+    fn test_identify_vendor() {
+        let graalvm = "OpenJDK 64-Bit GraalVM CE 19.2.0.1 (25.222-b08-jvmci-19.2-b02) for linux-amd64 JRE (8u222), built on Jul 19 2019 17:37:13 by \"buildslave\" with gcc 7.3.0";
+        let zulu = "OpenJDK 64-Bit Server VM (17.0.5+8-LTS) for bsd-amd64 JRE (17.0.5+8-LTS) (Zulu17.38+21-CA), built on Oct  7 2022 06:03:12 by \"zulu_re\" with clang 4.2.1 Compatible Apple LLVM 11.0.0 (clang-1100.0.33.17)";
+        let oracle = "Java HotSpot(TM) Client VM (25.65-b01) for linux-arm-vfp-hflt JRE (1.8.0_65-b17), built on Oct  6 2015 16:19:04 by \"java_re\" with gcc 4.7.2 20120910 (prerelease)";
+        let openjdk = "OpenJDK 64-Bit Server VM (12.0.2+10) for linux-amd64 JRE (12.0.2+10), built on Jul 18 2019 14:41:47 by \"jenkins\" with gcc 7.3.1 20180303 (Red Hat 7.3.1-5)";
+        assert_eq!(identify_vendor(graalvm), Some("GraalVM"));
+        assert_eq!(identify_vendor(zulu), Some("Zulu"));
+        assert_eq!(identify_vendor(oracle), Some("Oracle"));
+        assert_eq!(identify_vendor(openjdk), None);
+    }
+
+    #[test]
+    fn test_java_home_release_vendor() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("Main.java"))?.sync_all()?;
+        let java_home = tempfile::tempdir()?;
+        let java_bin = java_home.path().join("bin").join("java");
+        std::fs::write(
+            java_home.path().join("release"),
+            "IMPLEMENTOR=\"Oracle Corporation\"\nIMPLEMENTOR_VERSION=\"GraalVM CE 21+35.1\"\nJAVA_VERSION=\"21\"\n",
+        )?;
+
+        let actual = ModuleRenderer::new("java")
+            .env("JAVA_HOME", java_home.path().to_str().unwrap())
+            .cmd(
+                &format!("{} -Xinternalversion", java_bin.to_str().unwrap()),
+                Some(CommandOutput {
+                    stdout: "OpenJDK 64-Bit Server VM (21+35-jvmci-23.1-b15) for linux-amd64 JRE (21+35-jvmci-23.1-b15), built on Sep  5 2023 12:00:00 by \"graal\" with gcc 11.2.0".to_owned(),
+                    stderr: String::new(),
+                }),
+            )
+            .config(toml::toml! {
+                [java]
+                format = "via [$symbol$vendor_symbol($vendor )]($style)"
+                vendor_symbols = { GraalVM = "🐰 " }
+            })
+            .path(dir.path())
+            .collect();
+        let expected = Some(format!(
+            "via {}",
+            Color::Red.dimmed().paint("☕ 🐰 GraalVM ")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()?;
+        java_home.close()
+    }
 }
@@ -2,6 +2,7 @@ use super::{Context, Module, ModuleConfig};
 
 use crate::configs::jobs::JobsConfig;
 use crate::formatter::StringFormatter;
+use unicode_segmentation::UnicodeSegmentation;
 
 /// Creates a segment to show if there are any active jobs running
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
@@ -69,6 +70,17 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         }
     }
 
+    let names = props
+        .job_names
+        .iter()
+        .flatten()
+        // Shells may include the `&` that sent the job to the background
+        .map(|name| name.trim().trim_end_matches('&').trim_end())
+        .filter(|name| !name.is_empty())
+        .map(|name| truncate_name(name, &config))
+        .collect::<Vec<_>>()
+        .join(config.name_separator);
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
@@ -81,6 +93,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             })
             .map(|variable| match variable {
                 "number" => Some(Ok(module_number.clone())),
+                "names" => Some(Ok(names.clone())),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -89,7 +102,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `jobs`:\n{}", error);
+            context.report_module_error("jobs", &error);
             return None;
         }
     });
@@ -97,6 +110,17 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Shortens the name to `name_truncation_length` graphemes, followed by the truncation symbol
+fn truncate_name(name: &str, config: &JobsConfig) -> String {
+    let graphemes: Vec<&str> = name.graphemes(true).collect();
+    match usize::try_from(config.name_truncation_length) {
+        Ok(length) if length > 0 && graphemes.len() > length => {
+            graphemes[..length].concat() + config.truncation_symbol
+        }
+        _ => name.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::test::ModuleRenderer;
@@ -330,4 +354,37 @@ mod test {
         let expected = Some(format!("{} ", Color::Blue.bold().paint("✦1")));
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn job_names() {
+        let actual = ModuleRenderer::new("jobs")
+            .config(toml::toml! {
+                [jobs]
+                format = "$symbol$number $names"
+                name_truncation_length = 10
+            })
+            .jobs(2)
+            .job_names(&["npm run watch &", "vim", ""])
+            .collect();
+
+        let expected = Some("✦2 npm run wa…, vim".to_string());
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn job_names_without_truncation() {
+        let actual = ModuleRenderer::new("jobs")
+            .config(toml::toml! {
+                [jobs]
+                format = "$names"
+                name_truncation_length = 0
+                name_separator = " | "
+            })
+            .jobs(2)
+            .job_names(&["npm run watch", "vim"])
+            .collect();
+
+        let expected = Some("npm run watch | vim".to_string());
+        assert_eq!(expected, actual);
+    }
 }
@@ -0,0 +1,198 @@
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::keyboard_layout::KeyboardLayoutConfig;
+use crate::formatter::StringFormatter;
+
+/// Creates a module with the active keyboard layout or input method
+///
+/// On macOS the layout is read from the HIToolbox preferences. Elsewhere the
+/// active input method is queried from fcitx5, fcitx or IBus, in that order.
+///
+/// Their command line tools are used instead of the Text Input Sources API and D-Bus, which
+/// would need platform bindings. The preferences on macOS are only saved after a switch, so the
+/// layout may briefly be outdated, and plain XKB layouts without an input method aren't found.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("keyboard_layout");
+    let config = KeyboardLayoutConfig::try_load(module.config);
+
+    if config.disabled {
+        return None;
+    }
+
+    let layout_id = get_layout_id(context)?;
+    let layout = match config.layout_aliases.get(&layout_id) {
+        Some(alias) => (*alias).to_string(),
+        None => display_name(&layout_id).to_string(),
+    };
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "layout" => Some(Ok(layout.as_str())),
+                "layout_id" => Some(Ok(layout_id.as_str())),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            context.report_module_error("keyboard_layout", &error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+#[cfg(target_os = "macos")]
+fn get_layout_id(context: &Context) -> Option<String> {
+    let output = context.exec_cmd(
+        "defaults",
+        &[
+            "read",
+            "com.apple.HIToolbox",
+            "AppleCurrentKeyboardLayoutInputSourceID",
+        ],
+    )?;
+    non_empty(&output.stdout)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn get_layout_id(context: &Context) -> Option<String> {
+    [
+        ("fcitx5-remote", &["-n"][..]),
+        ("fcitx-remote", &["-n"][..]),
+        ("ibus", &["engine"][..]),
+    ]
+    .iter()
+    .find_map(|(cmd, args)| {
+        context
+            .exec_cmd(cmd, args)
+            .and_then(|o| non_empty(&o.stdout))
+    })
+}
+
+fn non_empty(output: &str) -> Option<String> {
+    Some(output.trim())
+        .filter(|id| !id.is_empty())
+        .map(String::from)
+}
+
+/// Turns a layout or input method id into a short name
+///
+/// - `xkb:us::eng` (IBus) becomes `us`
+/// - `keyboard-de-nodeadkeys` (fcitx) becomes `de-nodeadkeys`
+/// - `com.apple.keylayout.US` (macOS) becomes `US`
+fn display_name(layout_id: &str) -> &str {
+    if let Some(xkb) = layout_id.strip_prefix("xkb:") {
+        xkb.split(':').next().unwrap_or(xkb)
+    } else if let Some(keyboard) = layout_id.strip_prefix("keyboard-") {
+        keyboard
+    } else if layout_id.starts_with("com.apple.") {
+        layout_id.rsplit('.').next().unwrap_or(layout_id)
+    } else {
+        layout_id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::display_name;
+    use crate::test::{output, ModuleRenderer};
+    use nu_ansi_term::Color;
+
+    #[test]
+    fn display_names() {
+        assert_eq!(display_name("xkb:us::eng"), "us");
+        assert_eq!(display_name("keyboard-de-nodeadkeys"), "de-nodeadkeys");
+        assert_eq!(display_name("com.apple.keylayout.US"), "US");
+        assert_eq!(display_name("pinyin"), "pinyin");
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let actual = ModuleRenderer::new("keyboard_layout")
+            .cmd("ibus engine", output("xkb:us::eng\n"))
+            .collect();
+
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn ibus_engine() {
+        let actual = ModuleRenderer::new("keyboard_layout")
+            .cmd("fcitx5-remote -n", None)
+            .cmd("fcitx-remote -n", None)
+            .cmd("ibus engine", output("xkb:us::eng\n"))
+            .config(toml::toml! {
+                [keyboard_layout]
+                disabled = false
+            })
+            .collect();
+        let expected = Some(format!("{} ", Color::Purple.bold().paint("⌨️ us")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn fcitx_with_alias() {
+        let actual = ModuleRenderer::new("keyboard_layout")
+            .cmd("fcitx5-remote -n", output("keyboard-ru\n"))
+            .config(toml::toml! {
+                [keyboard_layout]
+                disabled = false
+                [keyboard_layout.layout_aliases]
+                "keyboard-ru" = "RU"
+            })
+            .collect();
+        let expected = Some(format!("{} ", Color::Purple.bold().paint("⌨️ RU")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn no_input_method() {
+        let actual = ModuleRenderer::new("keyboard_layout")
+            .cmd("fcitx5-remote -n", None)
+            .cmd("fcitx-remote -n", None)
+            .cmd("ibus engine", None)
+            .config(toml::toml! {
+                [keyboard_layout]
+                disabled = false
+            })
+            .collect();
+
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    #[cfg(target_os = "macos")]
+    fn macos_layout() {
+        let actual = ModuleRenderer::new("keyboard_layout")
+            .cmd(
+                "defaults read com.apple.HIToolbox AppleCurrentKeyboardLayoutInputSourceID",
+                output("com.apple.keylayout.German\n"),
+            )
+            .config(toml::toml! {
+                [keyboard_layout]
+                disabled = false
+            })
+            .collect();
+        let expected = Some(format!("{} ", Color::Purple.bold().paint("⌨️ German")));
+
+        assert_eq!(expected, actual);
+    }
+}
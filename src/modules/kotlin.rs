@@ -52,7 +52,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `kotlin`:\n{}", error);
+            context.report_module_error("kotlin", &error);
             return None;
         }
     });
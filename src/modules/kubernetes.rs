@@ -3,18 +3,54 @@ use yaml_rust::YamlLoader;
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::env;
+use std::fs;
+use std::net::{TcpStream, ToSocketAddrs};
 use std::path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use super::{Context, Module, ModuleConfig};
 
 use crate::configs::kubernetes::KubernetesConfig;
 use crate::formatter::StringFormatter;
+use crate::trace::{self, TraceKind};
 use crate::utils;
 
+const REACHABILITY_CACHE: &str = "kubernetes_reachability";
+
+/// Messages of `kubectl` for requests that the API server rejected because of the credentials
+const UNAUTHORIZED_ERRORS: &[&str] = &["401", "403", "Unauthorized", "Forbidden"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Reachability {
+    Reachable,
+    Unreachable,
+    Unauthorized,
+}
+
+impl Reachability {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Reachable => "reachable",
+            Self::Unreachable => "unreachable",
+            Self::Unauthorized => "unauthorized",
+        }
+    }
+
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "reachable" => Some(Self::Reachable),
+            "unreachable" => Some(Self::Unreachable),
+            "unauthorized" => Some(Self::Unauthorized),
+            _ => None,
+        }
+    }
+}
+
 struct KubeCtxComponents {
     user: Option<String>,
     namespace: Option<String>,
     cluster: Option<String>,
+    server: Option<String>,
 }
 
 fn get_kube_context(filename: path::PathBuf) -> Option<String> {
@@ -50,6 +86,14 @@ fn get_kube_ctx_component(filename: path::PathBuf, current_ctx: &str) -> Option<
             .find(|(_, name)| *name == current_ctx)
     });
 
+    let cluster = ctx_yaml.and_then(|(ctx, _)| ctx["context"]["cluster"].as_str());
+    let server = conf["clusters"].as_vec().and_then(|clusters| {
+        clusters
+            .iter()
+            .find(|c| cluster.is_some() && c["name"].as_str() == cluster)
+            .and_then(|c| c["cluster"]["server"].as_str())
+    });
+
     let ctx_components = KubeCtxComponents {
         user: ctx_yaml
             .and_then(|(ctx, _)| ctx["context"]["user"].as_str())
@@ -67,14 +111,18 @@ fn get_kube_ctx_component(filename: path::PathBuf, current_ctx: &str) -> Option<
                 }
                 Some(s.to_owned())
             }),
-        cluster: ctx_yaml
-            .and_then(|(ctx, _)| ctx["context"]["cluster"].as_str())
-            .and_then(|s| {
-                if s.is_empty() {
-                    return None;
-                }
-                Some(s.to_owned())
-            }),
+        cluster: cluster.and_then(|s| {
+            if s.is_empty() {
+                return None;
+            }
+            Some(s.to_owned())
+        }),
+        server: server.and_then(|s| {
+            if s.is_empty() {
+                return None;
+            }
+            Some(s.to_owned())
+        }),
     };
 
     Some(ctx_components)
@@ -106,6 +154,159 @@ fn get_alias<'a>(
     });
 }
 
+/// Guesses the provider of a managed cluster from the naming conventions of its tools, which
+/// name contexts and clusters like `arn:aws:eks:...`, `gke_...`, `k3d-...` or `minikube`, and
+/// from the domain of the API server.
+fn get_kube_provider(
+    kube_ctx: &str,
+    cluster: Option<&str>,
+    server: Option<&str>,
+) -> Option<&'static str> {
+    let names: Vec<&str> = [Some(kube_ctx), cluster].into_iter().flatten().collect();
+    let host = server
+        .map(|server| server.split("://").last().unwrap_or(server))
+        .and_then(|server| server.split(['/', ':']).next())
+        .unwrap_or_default();
+
+    if host.ends_with(".eks.amazonaws.com") || names.iter().any(|n| n.starts_with("arn:aws:eks:")) {
+        Some("eks")
+    } else if names.iter().any(|n| n.starts_with("gke_")) {
+        Some("gke")
+    } else if host.ends_with(".azmk8s.io") {
+        Some("aks")
+    } else if names.iter().any(|n| *n == "k3s" || n.starts_with("k3d-")) {
+        Some("k3s")
+    } else if names.contains(&"minikube") {
+        Some("minikube")
+    } else {
+        None
+    }
+}
+
+/// Checks whether anything accepts connections at the address of the API server
+fn is_server_listening(server: &str, timeout: Duration) -> bool {
+    let (scheme, address) = server.split_once("://").unwrap_or(("https", server));
+    let authority = address.split('/').next().unwrap_or(address);
+    let has_port = authority
+        .rsplit_once(':')
+        .map_or(false, |(_, port)| port.parse::<u16>().is_ok());
+    let address = if has_port {
+        authority.to_string()
+    } else {
+        let port = if scheme == "http" { 80 } else { 443 };
+        format!("{authority}:{port}")
+    };
+
+    // Resolving the address can block, so it counts towards the timeout as well
+    utils::run_with_timeout(timeout, move || {
+        let start = Instant::now();
+        let connected = address
+            .to_socket_addrs()
+            .ok()?
+            .next()
+            .map(|socket_address| TcpStream::connect_timeout(&socket_address, timeout).is_ok());
+        trace::record(
+            TraceKind::Network,
+            || format!("tcp {address}"),
+            start,
+            connected == Some(true),
+        );
+        connected
+    })
+    .flatten()
+    .unwrap_or(false)
+}
+
+/// Checks whether the cluster of the context can be used, reusing the result of an earlier
+/// check while it is younger than `reachability_cache` seconds.
+///
+/// A cluster whose API server accepts connections, but rejects the request of `kubectl` with
+/// 401 or 403, doesn't accept the credentials of the context, e.g. because its token has
+/// expired. If `kubectl` can't be run or doesn't finish within `reachability_timeout`, which
+/// happens with slow credential plugins, the reachability is unknown.
+fn get_reachability(
+    context: &Context,
+    config: &KubernetesConfig,
+    kube_ctx: &str,
+    server: Option<&str>,
+) -> Option<Reachability> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    let cache_file = utils::cache_path(context, REACHABILITY_CACHE);
+
+    let cached = utils::read_file(&cache_file).ok().and_then(|content| {
+        let mut parts = content.trim().splitn(3, ' ');
+        let checked_at = parts.next()?.parse::<u64>().ok()?;
+        let reachability = match parts.next()? {
+            "unknown" => None,
+            reachability => Some(Reachability::from_str(reachability)?),
+        };
+        let is_fresh = now.saturating_sub(checked_at) < config.reachability_cache;
+        (parts.next()? == kube_ctx && is_fresh).then_some(reachability)
+    });
+    if let Some(reachability) = cached {
+        return reachability;
+    }
+
+    let timeout = Duration::from_millis(config.reachability_timeout);
+    let reachability = if server.map_or(false, |server| !is_server_listening(server, timeout)) {
+        Some(Reachability::Unreachable)
+    } else {
+        let output = context.exec_cmd_with_timeout(
+            "kubectl",
+            &[
+                "--context",
+                kube_ctx,
+                "get",
+                "--raw",
+                "/readyz",
+                &format!("--request-timeout={}ms", config.reachability_timeout),
+            ],
+            timeout,
+        );
+        match output {
+            Some(Ok(_)) => Some(Reachability::Reachable),
+            Some(Err(output))
+                if UNAUTHORIZED_ERRORS
+                    .iter()
+                    .any(|error| output.stderr.contains(error)) =>
+            {
+                Some(Reachability::Unauthorized)
+            }
+            Some(Err(_)) => Some(Reachability::Unreachable),
+            None => None,
+        }
+    };
+
+    let content = format!(
+        "{now} {} {kube_ctx}",
+        reachability.map_or("unknown", Reachability::as_str)
+    );
+    if let Err(error) = fs::write(&cache_file, content) {
+        log::debug!(
+            "Unable to write kubernetes reachability cache {:?}: {}",
+            cache_file,
+            error
+        );
+    }
+    reachability
+}
+
+fn get_kube_config_paths(context: &Context) -> Option<String> {
+    let default_config_file = context.get_home()?.join(".kube").join("config");
+
+    Some(
+        context
+            .get_env("KUBECONFIG")
+            .unwrap_or(default_config_file.to_str()?.to_string()),
+    )
+}
+
+/// Returns the name of the current kubernetes context, without applying any aliases
+pub fn get_current_context(context: &Context) -> Option<String> {
+    let kube_cfg = get_kube_config_paths(context)?;
+    env::split_paths(&kube_cfg).find_map(get_kube_context)
+}
+
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("kubernetes");
     let config: KubernetesConfig = KubernetesConfig::try_load(module.config);
@@ -133,26 +334,52 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
-    let default_config_file = context.get_home()?.join(".kube").join("config");
-
-    let kube_cfg = context
-        .get_env("KUBECONFIG")
-        .unwrap_or(default_config_file.to_str()?.to_string());
-
+    let kube_cfg = get_kube_config_paths(context)?;
     let kube_ctx = env::split_paths(&kube_cfg).find_map(get_kube_context)?;
 
     let ctx_components: Vec<KubeCtxComponents> = env::split_paths(&kube_cfg)
         .filter_map(|filename| get_kube_ctx_component(filename, &kube_ctx))
         .collect();
 
+    let provider = get_kube_provider(
+        &kube_ctx,
+        ctx_components
+            .iter()
+            .find_map(|kube| kube.cluster.as_deref()),
+        ctx_components
+            .iter()
+            .find_map(|kube| kube.server.as_deref()),
+    );
+    let provider_config = provider.and_then(|provider| config.providers.get(provider));
+
+    let reachability = if config.check_reachability {
+        let server = ctx_components
+            .iter()
+            .find_map(|kube| kube.server.as_deref());
+        get_reachability(context, &config, &kube_ctx, server)
+    } else {
+        None
+    };
+    let style = match reachability {
+        Some(Reachability::Unreachable) => config.unreachable_style,
+        Some(Reachability::Unauthorized) => config.unauthorized_style,
+        _ => provider_config
+            .and_then(|provider| provider.style)
+            .unwrap_or(config.style),
+    };
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|variable, _| match variable {
-                "symbol" => Some(config.symbol),
+                "symbol" => Some(
+                    provider_config
+                        .and_then(|provider| provider.symbol)
+                        .unwrap_or(config.symbol),
+                ),
                 _ => None,
             })
             .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
+                "style" => Some(Ok(style)),
                 _ => None,
             })
             .map(|variable| match variable {
@@ -172,6 +399,12 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                     .iter()
                     .find_map(|kube| kube.cluster.as_deref())
                     .map(|cluster| Ok(Cow::Borrowed(cluster))),
+
+                "provider" => provider.map(|provider| Ok(Cow::Borrowed(provider))),
+
+                "reachability" => reachability
+                    .filter(|reachability| *reachability != Reachability::Reachable)
+                    .map(|reachability| Ok(Cow::Borrowed(reachability.as_str()))),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -180,7 +413,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `kubernetes`: \n{}", error);
+            context.report_module_error("kubernetes", &error);
             return None;
         }
     });
@@ -191,10 +424,13 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 #[cfg(test)]
 mod tests {
     use crate::test::ModuleRenderer;
+    use crate::utils::CommandOutput;
     use nu_ansi_term::Color;
     use std::env;
     use std::fs::{create_dir, File};
     use std::io::{self, Write};
+    use std::net::TcpListener;
+    use std::path::Path;
 
     #[test]
     fn test_none_when_disabled() -> io::Result<()> {
@@ -932,4 +1168,278 @@ users: []
         assert_eq!(expected, actual);
         dir.close()
     }
+
+    fn base_test_provider(kube_config: &str, config: toml::Table) -> io::Result<Option<String>> {
+        let dir = tempfile::tempdir()?;
+
+        let filename = dir.path().join("config");
+
+        let mut file = File::create(&filename)?;
+        file.write_all(kube_config.as_bytes())?;
+        file.sync_all()?;
+
+        let actual = ModuleRenderer::new("kubernetes")
+            .path(dir.path())
+            .env("KUBECONFIG", filename.to_string_lossy().as_ref())
+            .config(config)
+            .collect();
+
+        dir.close()?;
+        Ok(actual)
+    }
+
+    #[test]
+    fn test_provider_from_server() -> io::Result<()> {
+        let actual = base_test_provider(
+            "
+apiVersion: v1
+clusters:
+  - cluster:
+      server: https://0123456789ABCDEF.gr7.eu-west-1.eks.amazonaws.com
+    name: prod_cluster
+contexts:
+  - context:
+      cluster: prod_cluster
+      user: test_user
+    name: prod
+current-context: prod
+kind: Config
+preferences: {}
+users: []
+",
+            toml::toml! {
+                [kubernetes]
+                format = "[$symbol$context \\($provider\\)]($style)"
+                disabled = false
+                [kubernetes.providers.eks]
+                symbol = "☁ "
+                style = "red"
+            },
+        )?;
+
+        let expected = Some(Color::Red.paint("☁ prod (eks)").to_string());
+        assert_eq!(expected, actual);
+        Ok(())
+    }
+
+    #[test]
+    fn test_provider_from_names() -> io::Result<()> {
+        for (ctx_name, expected) in [
+            ("arn:aws:eks:eu-west-1:123456789012:cluster/prod", "eks"),
+            ("gke_project_europe-west4_cluster-1", "gke"),
+            ("k3d-dev", "k3s"),
+            ("minikube", "minikube"),
+        ] {
+            let actual = base_test_provider(
+                &format!(
+                    "
+apiVersion: v1
+clusters: []
+contexts: []
+current-context: {ctx_name}
+kind: Config
+preferences: {{}}
+users: []
+"
+                ),
+                toml::toml! {
+                    [kubernetes]
+                    format = "$provider"
+                    disabled = false
+                },
+            )?;
+            assert_eq!(Some(expected.to_string()), actual);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_provider_unknown() -> io::Result<()> {
+        let actual = base_test_provider(
+            "
+apiVersion: v1
+clusters:
+  - cluster:
+      server: https://10.0.0.1:6443
+    name: test_cluster
+contexts:
+  - context:
+      cluster: test_cluster
+    name: test_context
+current-context: test_context
+kind: Config
+preferences: {}
+users: []
+",
+            toml::toml! {
+                [kubernetes]
+                format = "[$symbol$context( \\($provider\\))]($style)"
+                disabled = false
+                [kubernetes.providers.eks]
+                symbol = "☁ "
+            },
+        )?;
+
+        let expected = Some(Color::Cyan.bold().paint("☸ test_context").to_string());
+        assert_eq!(expected, actual);
+        Ok(())
+    }
+
+    const KUBECTL: &str =
+        "kubectl --context test_context get --raw /readyz --request-timeout=500ms";
+
+    /// Renders the module with `kubectl` printing the output, failing with the stderr of `Err`,
+    /// or not running at all
+    fn base_test_reachability(
+        server: &str,
+        kubectl_output: Option<Result<CommandOutput, &str>>,
+        cache_dir: &Path,
+    ) -> io::Result<Option<String>> {
+        let dir = tempfile::tempdir()?;
+
+        let filename = dir.path().join("config");
+
+        let mut file = File::create(&filename)?;
+        file.write_all(
+            format!(
+                "
+apiVersion: v1
+clusters:
+  - cluster:
+      server: {server}
+    name: test_cluster
+contexts:
+  - context:
+      cluster: test_cluster
+    name: test_context
+current-context: test_context
+kind: Config
+preferences: {{}}
+users: []
+"
+            )
+            .as_bytes(),
+        )?;
+        file.sync_all()?;
+
+        let renderer = ModuleRenderer::new("kubernetes");
+        let renderer = match kubectl_output {
+            Some(Err(stderr)) => renderer.cmd_failure(KUBECTL, stderr),
+            output => renderer.cmd(KUBECTL, output.and_then(Result::ok)),
+        };
+        let actual = renderer
+            .path(dir.path())
+            .env("KUBECONFIG", filename.to_string_lossy().as_ref())
+            .env("STARSHIP_CACHE", cache_dir.to_string_lossy())
+            .config(toml::toml! {
+                [kubernetes]
+                format = "[$context( $reachability)]($style)"
+                disabled = false
+                check_reachability = true
+            })
+            .collect();
+
+        dir.close()?;
+        Ok(actual)
+    }
+
+    fn kubectl_ok() -> Option<Result<CommandOutput, &'static str>> {
+        Some(Ok(CommandOutput {
+            stdout: "ok".to_string(),
+            stderr: String::new(),
+        }))
+    }
+
+    #[test]
+    fn test_reachable_cluster() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let server = format!("https://{}", listener.local_addr()?);
+
+        let actual = base_test_reachability(&server, kubectl_ok(), cache_dir.path())?;
+        let expected = Some(Color::Cyan.bold().paint("test_context").to_string());
+        assert_eq!(expected, actual);
+        cache_dir.close()
+    }
+
+    #[test]
+    fn test_unauthorized_cluster() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let server = format!("https://{}", listener.local_addr()?);
+
+        let kubectl_output = Some(Err(
+            "error: You must be logged in to the server (the server has asked for the client to provide credentials)\nUnauthorized",
+        ));
+        let actual = base_test_reachability(&server, kubectl_output, cache_dir.path())?;
+        let expected = Some(
+            Color::Yellow
+                .bold()
+                .paint("test_context unauthorized")
+                .to_string(),
+        );
+        assert_eq!(expected, actual);
+        cache_dir.close()
+    }
+
+    #[test]
+    fn test_failing_kubectl_is_not_unauthorized() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let server = format!("https://{}", listener.local_addr()?);
+
+        let kubectl_output = Some(Err(
+            "error: the server is currently unable to handle the request",
+        ));
+        let actual = base_test_reachability(&server, kubectl_output, cache_dir.path())?;
+        let expected = Some(
+            Color::Red
+                .bold()
+                .paint("test_context unreachable")
+                .to_string(),
+        );
+        assert_eq!(expected, actual);
+        cache_dir.close()
+    }
+
+    #[test]
+    fn test_unknown_reachability_without_kubectl() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let server = format!("https://{}", listener.local_addr()?);
+
+        // kubectl isn't installed or timed out, e.g. waiting for a credential plugin
+        let actual = base_test_reachability(&server, None, cache_dir.path())?;
+        let expected = Some(Color::Cyan.bold().paint("test_context").to_string());
+        assert_eq!(expected, actual);
+
+        // This is cached as well, instead of running kubectl on every prompt
+        let actual = base_test_reachability(&server, kubectl_ok(), cache_dir.path())?;
+        assert_eq!(expected, actual);
+        cache_dir.close()
+    }
+
+    #[test]
+    fn test_unreachable_cluster_is_cached() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let server = format!("https://{}", listener.local_addr()?);
+        drop(listener);
+
+        let actual = base_test_reachability(&server, kubectl_ok(), cache_dir.path())?;
+        let expected = Some(
+            Color::Red
+                .bold()
+                .paint("test_context unreachable")
+                .to_string(),
+        );
+        assert_eq!(expected, actual);
+
+        // The cluster is only checked again once the cached result expires
+        let listener = TcpListener::bind(server.trim_start_matches("https://"))?;
+        let actual = base_test_reachability(&server, kubectl_ok(), cache_dir.path())?;
+        assert_eq!(expected, actual);
+        drop(listener);
+        cache_dir.close()
+    }
 }
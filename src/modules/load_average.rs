@@ -0,0 +1,112 @@
+use std::thread::available_parallelism;
+use systemstat::{Platform, System};
+
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::load_average::LoadAverageConfig;
+use crate::formatter::StringFormatter;
+
+/// Creates a module with the system load average over 1, 5 and 15 minutes
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("load_average");
+    let config = LoadAverageConfig::try_load(module.config);
+
+    // As we default to disabled=true, we have to check here after loading our config module,
+    // before it was only checking against whatever is in the config starship.toml
+    if config.disabled {
+        return None;
+    }
+
+    let load = match System::new().load_average() {
+        Ok(load) => load,
+        Err(e) => {
+            log::warn!("Failed to retrieve load average: {}", e);
+            return None;
+        }
+    };
+
+    let cores = if config.per_core {
+        available_parallelism().map_or(1, usize::from)
+    } else {
+        1
+    };
+    let [one, five, fifteen] = [load.one, load.five, load.fifteen].map(|l| normalize(l, cores));
+
+    if one < config.threshold {
+        return None;
+    }
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "one" => Some(Ok(format!("{one:.2}"))),
+                "five" => Some(Ok(format!("{five:.2}"))),
+                "fifteen" => Some(Ok(format!("{fifteen:.2}"))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            context.report_module_error("load_average", &error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+// Divide the load by the number of cores
+fn normalize(load: f32, cores: usize) -> f64 {
+    f64::from(load) / cores as f64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::test::ModuleRenderer;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize(2.0, 1), 2.0);
+        assert_eq!(normalize(2.0, 4), 0.5);
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn zero_threshold() {
+        let output = ModuleRenderer::new("load_average")
+            .config(toml::toml! {
+                [load_average]
+                disabled = false
+                threshold = 0.0
+            })
+            .collect();
+
+        assert!(output.is_some())
+    }
+
+    #[test]
+    fn impossible_threshold() {
+        let output = ModuleRenderer::new("load_average")
+            .config(toml::toml! {
+                [load_average]
+                disabled = false
+                threshold = 9999.0
+            })
+            .collect();
+
+        assert!(output.is_none())
+    }
+}
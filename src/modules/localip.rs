@@ -3,26 +3,130 @@ use super::{Context, Module};
 use crate::config::ModuleConfig;
 use crate::configs::localip::LocalipConfig;
 use crate::formatter::StringFormatter;
+use crate::trace::{self, TraceKind};
 
 use std::io::Error;
-use std::net::UdpSocket;
+use std::net::{IpAddr, UdpSocket};
+use std::time::Instant;
 
 fn get_local_ipv4() -> Result<String, Error> {
-    let socket = UdpSocket::bind("0.0.0.0:0")?;
-    socket.connect("192.0.2.0:80")?;
+    get_local_ip("0.0.0.0:0", "192.0.2.0:80")
+}
 
-    let addr = socket.local_addr()?;
+fn get_local_ipv6() -> Result<String, Error> {
+    get_local_ip("[::]:0", "[2001:db8::1]:80")
+}
 
-    Ok(addr.ip().to_string())
+/// Returns the address the system would send from to reach `remote`.
+/// Connecting a UDP socket only picks the route, nothing is sent.
+fn get_local_ip(local: &str, remote: &str) -> Result<String, Error> {
+    let start = Instant::now();
+    let result = UdpSocket::bind(local).and_then(|socket| {
+        socket.connect(remote)?;
+        socket.local_addr()
+    });
+    trace::record(
+        TraceKind::Network,
+        || format!("udp {remote}"),
+        start,
+        result.is_ok(),
+    );
+
+    Ok(result?.ip().to_string())
 }
 
-/// Creates a module with the ipv4 address of the local machine.
+/// Returns the name and address of every address assigned to a network interface
+#[cfg(not(windows))]
+fn get_interface_addresses() -> Option<Vec<(String, IpAddr)>> {
+    use nix::ifaddrs::getifaddrs;
+    use std::net::{SocketAddrV4, SocketAddrV6};
+
+    let addresses = match getifaddrs() {
+        Ok(addresses) => addresses,
+        Err(e) => {
+            log::warn!("unable to list network interfaces: {e}");
+            return None;
+        }
+    };
+
+    Some(
+        addresses
+            .filter_map(|ifaddr| {
+                let address = ifaddr.address?;
+                let ip = if let Some(addr) = address.as_sockaddr_in() {
+                    IpAddr::V4(*SocketAddrV4::from(*addr).ip())
+                } else {
+                    IpAddr::V6(*SocketAddrV6::from(*address.as_sockaddr_in6()?).ip())
+                };
+                Some((ifaddr.interface_name, ip))
+            })
+            .collect(),
+    )
+}
+
+#[cfg(windows)]
+fn get_interface_addresses() -> Option<Vec<(String, IpAddr)>> {
+    log::warn!("`localip.interface` and `localip.cidr` are not supported on Windows");
+    None
+}
+
+/// Parses a CIDR block like `10.0.0.0/8` or `fd00::/8` into its network address and prefix length
+fn parse_cidr(cidr: &str) -> Option<(IpAddr, u32)> {
+    let (network, prefix) = cidr.split_once('/')?;
+    let network: IpAddr = network.trim().parse().ok()?;
+    let prefix: u32 = prefix.trim().parse().ok()?;
+    let max_prefix = if network.is_ipv4() { 32 } else { 128 };
+
+    (prefix <= max_prefix).then_some((network, prefix))
+}
+
+/// Checks whether the address is part of the network given by `parse_cidr`
+fn cidr_contains((network, prefix): (IpAddr, u32), ip: &IpAddr) -> bool {
+    match (network, ip) {
+        (IpAddr::V4(network), IpAddr::V4(ip)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            u32::from(network) & mask == u32::from(*ip) & mask
+        }
+        (IpAddr::V6(network), IpAddr::V6(ip)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            u128::from(network) & mask == u128::from(*ip) & mask
+        }
+        _ => false,
+    }
+}
+
+/// Selects the addresses of the configured interface or CIDR block.
 ///
-/// The IP address is gathered from the local endpoint of an UDP socket
-/// connected to a reserved IPv4 remote address, which is an accurate and fast
+/// Loopback and IPv6 link-local addresses are skipped unless their interface was requested.
+fn select_interface_addresses(
+    addresses: Vec<(String, IpAddr)>,
+    interface: &str,
+    cidr: Option<(IpAddr, u32)>,
+) -> Vec<IpAddr> {
+    addresses
+        .into_iter()
+        .filter(|(name, ip)| {
+            if !interface.is_empty() {
+                return name == interface;
+            }
+            let is_link_local = matches!(ip, IpAddr::V6(ip) if ip.segments()[0] & 0xffc0 == 0xfe80);
+            !ip.is_loopback() && !is_link_local
+        })
+        .map(|(_, ip)| ip)
+        .filter(|ip| cidr.map_or(true, |cidr| cidr_contains(cidr, ip)))
+        .collect()
+}
+
+/// Creates a module with the ip address of the local machine.
+///
+/// By default, the IP address is gathered from the local endpoint of an UDP socket
+/// connected to a reserved remote address, which is an accurate and fast
 /// way, especially if there are multiple IP addresses available.
 /// There should be no actual packets send over the wire.
 ///
+/// If `localip.interface` or `localip.cidr` is set, the addresses of the network
+/// interfaces are listed instead. With `localip.cidr`, all matching addresses are shown.
+///
 /// Will display the ip if all of the following criteria are met:
 ///     - `localip.disabled` is false
 ///     - `localip.ssh_only` is false OR the user is currently connected as an SSH session (`$SSH_CONNECTION`)
@@ -41,18 +145,61 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
-    let localip = match get_local_ipv4() {
-        Ok(ip) => ip,
-        Err(e) => {
-            // ErrorKind::NetworkUnreachable is unstable
-            if cfg!(target_os = "linux") && e.raw_os_error() == Some(101) {
-                "NetworkUnreachable".to_string()
-            } else {
-                log::warn!("unable to determine local ipv4 address: {e}");
-                return None;
+    let (ipv4, ipv6) = if config.interface.is_empty() && config.cidr.is_empty() {
+        let ipv4 = match get_local_ipv4() {
+            Ok(ip) => vec![ip],
+            Err(e) => {
+                // ErrorKind::NetworkUnreachable is unstable
+                if cfg!(target_os = "linux") && e.raw_os_error() == Some(101) {
+                    vec!["NetworkUnreachable".to_string()]
+                } else {
+                    log::warn!("unable to determine local ipv4 address: {e}");
+                    Vec::new()
+                }
             }
+        };
+        let ipv6 = match get_local_ipv6() {
+            Ok(ip) => vec![ip],
+            Err(e) => {
+                log::debug!("unable to determine local ipv6 address: {e}");
+                Vec::new()
+            }
+        };
+        (ipv4, ipv6)
+    } else {
+        let cidr = if config.cidr.is_empty() {
+            None
+        } else if let Some(cidr) = parse_cidr(config.cidr) {
+            Some(cidr)
+        } else {
+            log::warn!("Invalid `localip.cidr`: {}", config.cidr);
+            return None;
+        };
+
+        let addresses =
+            select_interface_addresses(get_interface_addresses()?, config.interface, cidr);
+        let (ipv4, ipv6): (Vec<_>, Vec<_>) = addresses.iter().partition(|ip| ip.is_ipv4());
+        let to_strings = |ips: Vec<&IpAddr>| ips.iter().map(ToString::to_string).collect();
+        (to_strings(ipv4), to_strings(ipv6))
+    };
+
+    // Without a CIDR block only a single address is shown
+    let show_all = !config.cidr.is_empty();
+    let join = |ips: &[String]| {
+        if show_all {
+            Some(ips.join(" ")).filter(|ips| !ips.is_empty())
+        } else {
+            ips.first().cloned()
         }
     };
+    let localipv4 = join(&ipv4);
+    let localipv6 = join(&ipv6);
+    let localip = if config.prefer_ipv6 {
+        join(&[ipv6.as_slice(), ipv4.as_slice()].concat())
+    } else {
+        join(&[ipv4.as_slice(), ipv6.as_slice()].concat())
+    };
+    localip.as_ref()?;
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -61,7 +208,9 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "localipv4" => Some(Ok(&localip)),
+                "localip" => localip.as_ref().map(Ok),
+                "localipv4" => localipv4.as_ref().map(Ok),
+                "localipv6" => localipv6.as_ref().map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -70,7 +219,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `localip`:\n{}", error);
+            context.report_module_error("localip", &error);
             return None;
         }
     });
@@ -80,7 +229,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
 #[cfg(test)]
 mod tests {
-    use crate::modules::localip::get_local_ipv4;
+    use super::*;
     use crate::test::ModuleRenderer;
     use nu_ansi_term::{Color, Style};
 
@@ -166,4 +315,90 @@ mod tests {
     fn style() -> Style {
         Color::Yellow.bold()
     }
+
+    #[test]
+    fn parse_and_match_cidr() {
+        let cidr = parse_cidr("10.1.0.0/16").unwrap();
+        assert!(cidr_contains(cidr, &"10.1.2.3".parse().unwrap()));
+        assert!(!cidr_contains(cidr, &"10.2.0.1".parse().unwrap()));
+        assert!(!cidr_contains(cidr, &"fd00::1".parse().unwrap()));
+
+        let cidr = parse_cidr("fd00::/8").unwrap();
+        assert!(cidr_contains(cidr, &"fd12:3456::1".parse().unwrap()));
+        assert!(!cidr_contains(cidr, &"2001:db8::1".parse().unwrap()));
+
+        assert!(cidr_contains(
+            parse_cidr("0.0.0.0/0").unwrap(),
+            &"192.168.1.1".parse().unwrap()
+        ));
+        assert_eq!(parse_cidr("10.0.0.0/33"), None);
+        assert_eq!(parse_cidr("10.0.0.0"), None);
+        assert_eq!(parse_cidr("nonsense/8"), None);
+    }
+
+    #[test]
+    fn select_addresses() {
+        let addresses = || {
+            vec![
+                ("lo".to_string(), "127.0.0.1".parse().unwrap()),
+                ("eth0".to_string(), "192.168.1.20".parse().unwrap()),
+                ("eth0".to_string(), "fe80::1".parse().unwrap()),
+                ("eth0".to_string(), "2001:db8::20".parse().unwrap()),
+                ("wg0".to_string(), "10.8.0.2".parse().unwrap()),
+            ]
+        };
+        let ips = |ips: &[&str]| {
+            ips.iter()
+                .map(|ip| ip.parse().unwrap())
+                .collect::<Vec<IpAddr>>()
+        };
+
+        assert_eq!(
+            select_interface_addresses(addresses(), "", None),
+            ips(&["192.168.1.20", "2001:db8::20", "10.8.0.2"])
+        );
+        assert_eq!(
+            select_interface_addresses(addresses(), "eth0", None),
+            ips(&["192.168.1.20", "fe80::1", "2001:db8::20"])
+        );
+        assert_eq!(
+            select_interface_addresses(addresses(), "", parse_cidr("10.0.0.0/8")),
+            ips(&["10.8.0.2"])
+        );
+        assert_eq!(
+            select_interface_addresses(addresses(), "lo", parse_cidr("127.0.0.0/8")),
+            ips(&["127.0.0.1"])
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn loopback_interface() {
+        let actual = ModuleRenderer::new("localip")
+            .config(toml::toml! {
+                [localip]
+                ssh_only = false
+                disabled = false
+                interface = "lo"
+                cidr = "127.0.0.0/8"
+            })
+            .collect();
+        let expected = Some(format!("{} ", style().paint("127.0.0.1")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn invalid_cidr() {
+        let actual = ModuleRenderer::new("localip")
+            .config(toml::toml! {
+                [localip]
+                ssh_only = false
+                disabled = false
+                cidr = "10.0.0.0"
+            })
+            .collect();
+
+        assert_eq!(None, actual);
+    }
 }
@@ -1,31 +1,16 @@
+use std::path::Path;
+
 use systemstat::{
     data::{saturating_sub_bytes, ByteSize},
     Platform, System,
 };
 
+use super::utils::byte_size::display_bs;
 use super::{Context, Module, ModuleConfig};
 
 use crate::configs::memory_usage::MemoryConfig;
 use crate::formatter::StringFormatter;
-
-// Display a `ByteSize` in a human readable format.
-fn display_bs(bs: ByteSize) -> String {
-    let mut display_bytes = bs.to_string_as(true);
-    let mut keep = true;
-    // Skip decimals and the space before the byte unit.
-    display_bytes.retain(|c| match c {
-        ' ' => {
-            keep = true;
-            false
-        }
-        '.' => {
-            keep = false;
-            false
-        }
-        _ => keep,
-    });
-    display_bytes
-}
+use crate::utils;
 
 // Calculate the memory usage from total and free memory
 fn pct(total: ByteSize, free: ByteSize) -> f64 {
@@ -41,6 +26,45 @@ fn format_usage_total(total: ByteSize, free: ByteSize) -> String {
     )
 }
 
+/// Reads the memory limit and usage of the cgroup the process is running in,
+/// returned as total and free memory like the system values.
+///
+/// Supports both cgroup v2 (`memory.max`) and v1 (`memory/memory.limit_in_bytes`).
+/// Returns `None` if there is no limit or it exceeds the system memory, e.g. outside of containers.
+fn cgroup_memory(root: &Path, system_total: ByteSize) -> Option<(ByteSize, ByteSize)> {
+    let read = |file: &str| utils::read_file(root.join(file)).ok();
+    let read_bytes = |file: &str| read(file)?.trim().parse::<u64>().ok();
+    // Page cache that can be reclaimed is not counted as used, like `docker stats` does
+    let stat_value = |file: &str, key: &str| {
+        read(file)?.lines().find_map(|line| {
+            let (name, value) = line.split_once(' ')?;
+            (name == key).then(|| value.trim().parse::<u64>().ok())?
+        })
+    };
+
+    let (limit, usage, inactive) = if let Some(limit) = read("memory.max") {
+        (
+            limit.trim().parse::<u64>().ok()?,
+            read_bytes("memory.current")?,
+            stat_value("memory.stat", "inactive_file"),
+        )
+    } else {
+        (
+            read_bytes("memory/memory.limit_in_bytes")?,
+            read_bytes("memory/memory.usage_in_bytes")?,
+            stat_value("memory/memory.stat", "total_inactive_file"),
+        )
+    };
+
+    // cgroup v1 reports a huge number instead of "max" when unlimited
+    if limit == 0 || limit >= system_total.0 {
+        return None;
+    }
+
+    let used = usage.saturating_sub(inactive.unwrap_or(0)).min(limit);
+    Some((ByteSize(limit), ByteSize(limit - used)))
+}
+
 /// Creates a module with system memory usage information
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("memory_usage");
@@ -77,7 +101,14 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         }
     };
 
-    let used_pct = pct(memory.total, memory.free);
+    let (total, free) = if cfg!(target_os = "linux") {
+        cgroup_memory(Path::new("/sys/fs/cgroup"), memory.total)
+    } else {
+        None
+    }
+    .unwrap_or((memory.total, memory.free));
+
+    let used_pct = pct(total, free);
 
     if (used_pct.round() as i64) < config.threshold {
         return None;
@@ -94,7 +125,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "ram" => Some(Ok(format_usage_total(memory.total, memory.free))),
+                "ram" => Some(Ok(format_usage_total(total, free))),
                 "ram_pct" => Some(Ok(format!("{used_pct:.0}%"))),
                 "swap" => Some(Ok(format_usage_total(
                     swap.as_ref()?.total,
@@ -112,7 +143,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `memory_usage`:\n{}", error);
+            context.report_module_error("memory_usage", &error);
             return None;
         }
     });
@@ -125,6 +156,10 @@ mod test {
     use super::*;
 
     use crate::test::ModuleRenderer;
+    use std::fs;
+    use std::io;
+
+    const GIB: u64 = 1024 * 1024 * 1024;
 
     #[test]
     fn test_format_usage_total() {
@@ -161,6 +196,69 @@ mod test {
         assert_eq!(pct(ByteSize(1024 * 1024 * 1024), ByteSize(0)), 100.0);
     }
 
+    #[test]
+    fn cgroup_v2_limit() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("memory.max"), format!("{}\n", 2 * GIB))?;
+        fs::write(dir.path().join("memory.current"), format!("{}\n", GIB))?;
+        fs::write(
+            dir.path().join("memory.stat"),
+            format!("anon 1234\ninactive_file {}\nactive_file 0\n", GIB / 2),
+        )?;
+
+        assert_eq!(
+            cgroup_memory(dir.path(), ByteSize(16 * GIB)),
+            Some((ByteSize(2 * GIB), ByteSize(3 * GIB / 2)))
+        );
+        dir.close()
+    }
+
+    #[test]
+    fn cgroup_v2_unlimited() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("memory.max"), "max\n")?;
+        fs::write(dir.path().join("memory.current"), format!("{}\n", GIB))?;
+
+        assert_eq!(cgroup_memory(dir.path(), ByteSize(16 * GIB)), None);
+        dir.close()
+    }
+
+    #[test]
+    fn cgroup_v1_limit() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let memory = dir.path().join("memory");
+        fs::create_dir(&memory)?;
+        fs::write(
+            memory.join("memory.limit_in_bytes"),
+            format!("{}\n", 4 * GIB),
+        )?;
+        fs::write(
+            memory.join("memory.usage_in_bytes"),
+            format!("{}\n", 3 * GIB),
+        )?;
+
+        assert_eq!(
+            cgroup_memory(dir.path(), ByteSize(16 * GIB)),
+            Some((ByteSize(4 * GIB), ByteSize(GIB)))
+        );
+        dir.close()
+    }
+
+    #[test]
+    fn cgroup_v1_unlimited() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let memory = dir.path().join("memory");
+        fs::create_dir(&memory)?;
+        fs::write(
+            memory.join("memory.limit_in_bytes"),
+            "9223372036854771712\n",
+        )?;
+        fs::write(memory.join("memory.usage_in_bytes"), format!("{}\n", GIB))?;
+
+        assert_eq!(cgroup_memory(dir.path(), ByteSize(16 * GIB)), None);
+        dir.close()
+    }
+
     #[test]
     fn zero_threshold() {
         let output = ModuleRenderer::new("memory_usage")
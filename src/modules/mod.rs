@@ -1,11 +1,15 @@
 // While adding out new module add out module to src/module.rs ALL_MODULES const array also.
 mod aws;
 mod azure;
+mod bazel;
+mod buck2;
 mod buf;
 mod bun;
 mod c;
 mod character;
+mod clipboard;
 mod cmake;
+mod cmd_counter;
 mod cmd_duration;
 mod cobol;
 mod conda;
@@ -15,7 +19,10 @@ pub mod custom;
 mod daml;
 mod dart;
 mod deno;
+mod dev_env;
 mod directory;
+mod disk_usage;
+mod do_not_disturb;
 mod docker_context;
 mod dotnet;
 mod elixir;
@@ -29,6 +36,7 @@ mod gcloud;
 mod git_branch;
 mod git_commit;
 mod git_metrics;
+mod git_signing;
 mod git_state;
 mod git_status;
 mod golang;
@@ -42,13 +50,17 @@ mod hostname;
 mod java;
 mod jobs;
 mod julia;
+mod keyboard_layout;
 mod kotlin;
 mod kubernetes;
 mod line_break;
+mod load_average;
 mod localip;
 mod lua;
 mod memory_usage;
 mod meson;
+mod multiplexer;
+mod network;
 mod nim;
 mod nix_shell;
 mod nodejs;
@@ -60,6 +72,7 @@ mod package;
 mod perl;
 mod php;
 mod pijul_channel;
+mod public_ip;
 mod pulumi;
 mod purescript;
 mod python;
@@ -74,16 +87,19 @@ mod shlvl;
 mod singularity;
 mod solidity;
 mod spack;
+mod ssh_agent;
 mod status;
 mod sudo;
 mod swift;
 mod terraform;
 mod time;
+mod tool_versions;
 mod username;
 mod utils;
 mod vagrant;
 mod vcsh;
 mod vlang;
+mod volume;
 mod zig;
 
 #[cfg(feature = "battery")]
@@ -92,14 +108,18 @@ mod battery;
 #[cfg(feature = "battery")]
 pub use self::battery::{BatteryInfoProvider, BatteryInfoProviderImpl};
 
+pub use self::kubernetes::get_current_context as get_current_kube_context;
+
 use crate::config::ModuleConfig;
 use crate::context::{Context, Shell};
+use crate::formatter::StringFormatter;
 use crate::module::Module;
 use std::time::Instant;
 
 pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
     let start: Instant = Instant::now();
-    let mut m: Option<Module> = {
+    let mut m: Option<Module> = crate::trace::in_module(Some(module), || {
+        let _timeout = context.module_command_timeout(module);
         match module {
             // Keep these ordered alphabetically.
             // Default ordering is handled in configs/starship_root.rs
@@ -107,11 +127,15 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
             "azure" => azure::module(context),
             #[cfg(feature = "battery")]
             "battery" => battery::module(context),
+            "bazel" => bazel::module(context),
+            "buck2" => buck2::module(context),
             "buf" => buf::module(context),
             "bun" => bun::module(context),
             "c" => c::module(context),
             "character" => character::module(context),
+            "clipboard" => clipboard::module(context),
             "cmake" => cmake::module(context),
+            "cmd_counter" => cmd_counter::module(context),
             "cmd_duration" => cmd_duration::module(context),
             "cobol" => cobol::module(context),
             "conda" => conda::module(context),
@@ -119,7 +143,10 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
             "daml" => daml::module(context),
             "dart" => dart::module(context),
             "deno" => deno::module(context),
+            "dev_env" => dev_env::module(context),
             "directory" => directory::module(context),
+            "disk_usage" => disk_usage::module(context),
+            "do_not_disturb" => do_not_disturb::module(context),
             "docker_context" => docker_context::module(context),
             "dotnet" => dotnet::module(context),
             "elixir" => elixir::module(context),
@@ -127,12 +154,13 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
             "erlang" => erlang::module(context),
             "env_var" => env_var::module(None, context),
             "fennel" => fennel::module(context),
-            "fill" => fill::module(context),
+            "fill" => fill::module(None, context),
             "fossil_branch" => fossil_branch::module(context),
             "gcloud" => gcloud::module(context),
             "git_branch" => git_branch::module(context),
             "git_commit" => git_commit::module(context),
             "git_metrics" => git_metrics::module(context),
+            "git_signing" => git_signing::module(context),
             "git_state" => git_state::module(context),
             "git_status" => git_status::module(context),
             "golang" => golang::module(context),
@@ -146,13 +174,17 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
             "java" => java::module(context),
             "jobs" => jobs::module(context),
             "julia" => julia::module(context),
+            "keyboard_layout" => keyboard_layout::module(context),
             "kotlin" => kotlin::module(context),
             "kubernetes" => kubernetes::module(context),
             "line_break" => line_break::module(context),
+            "load_average" => load_average::module(context),
             "localip" => localip::module(context),
             "lua" => lua::module(context),
             "memory_usage" => memory_usage::module(context),
             "meson" => meson::module(context),
+            "multiplexer" => multiplexer::module(context),
+            "network" => network::module(context),
             "nim" => nim::module(context),
             "nix_shell" => nix_shell::module(context),
             "nodejs" => nodejs::module(context),
@@ -164,6 +196,7 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
             "perl" => perl::module(context),
             "php" => php::module(context),
             "pijul_channel" => pijul_channel::module(context),
+            "public_ip" => public_ip::module(context),
             "pulumi" => pulumi::module(context),
             "purescript" => purescript::module(context),
             "python" => python::module(context),
@@ -178,17 +211,21 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
             "singularity" => singularity::module(context),
             "solidity" => solidity::module(context),
             "spack" => spack::module(context),
+            "ssh_agent" => ssh_agent::module(context),
             "swift" => swift::module(context),
             "status" => status::module(context),
             "sudo" => sudo::module(context),
             "terraform" => terraform::module(context),
             "time" => time::module(context),
+            "tool_versions" => tool_versions::module(context),
             "crystal" => crystal::module(context),
             "username" => username::module(context),
             "vlang" => vlang::module(context),
             "vagrant" => vagrant::module(context),
             "vcsh" => vcsh::module(context),
+            "volume" => volume::module(context),
             "zig" => zig::module(context),
+            fill if fill.starts_with("fill.") => fill::module(fill.strip_prefix("fill."), context),
             env if env.starts_with("env_var.") => {
                 env_var::module(env.strip_prefix("env_var."), context)
             }
@@ -201,7 +238,11 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
                 None
             }
         }
-    };
+    });
+
+    if m.is_none() && context.root_config.show_module_errors && context.has_module_error(module) {
+        m = error_badge(module, context);
+    }
 
     let elapsed = start.elapsed();
     log::trace!("Took {:?} to compute module {:?}", elapsed, module);
@@ -215,18 +256,45 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
     m
 }
 
+/// Renders the badge shown instead of a module that failed because of an error
+fn error_badge<'a>(name: &str, context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module(name);
+    let parsed =
+        StringFormatter::new(&context.root_config.module_error_format).and_then(|formatter| {
+            formatter
+                .map(|variable| match variable {
+                    "module" => Some(Ok(name)),
+                    _ => None,
+                })
+                .parse(None, Some(context))
+        });
+
+    match parsed {
+        Ok(segments) => module.set_segments(segments),
+        Err(error) => {
+            log::warn!("Error in `module_error_format`:\n{}", error);
+            return None;
+        }
+    }
+    Some(module)
+}
+
 pub fn description(module: &str) -> &'static str {
     match module {
         "aws" => "The current AWS region and profile",
         "azure" => "The current Azure subscription",
         "battery" => "The current charge of the device's battery and its current charging status",
+        "bazel" => "The Bazel version pinned by the current workspace and whether it uses Bzlmod",
+        "buck2" => "The currently installed version of Buck2 and the cell of the current directory",
         "buf" => "The currently installed version of the Buf CLI",
         "bun" => "The currently installed version of the Bun",
         "c" => "Your C compiler type",
         "character" => {
             "A character (usually an arrow) beside where the text is entered in your terminal"
         }
+        "clipboard" => "The type and size of the current clipboard content",
         "cmake" => "The currently installed version of CMake",
+        "cmd_counter" => "The number of commands entered in the current shell session",
         "cmd_duration" => "How long the last command took to execute",
         "cobol" => "The currently installed version of COBOL/GNUCOBOL",
         "conda" => "The current conda environment, if $CONDA_DEFAULT_ENV is set",
@@ -235,7 +303,10 @@ pub fn description(module: &str) -> &'static str {
         "daml" => "The Daml SDK version of your project",
         "dart" => "The currently installed version of Dart",
         "deno" => "The currently installed version of Deno",
+        "dev_env" => "The activated devbox, devenv or flox environment",
         "directory" => "The current working directory",
+        "disk_usage" => "Disk usage of the filesystem containing the current directory",
+        "do_not_disturb" => "Shown while do-not-disturb or focus mode is active",
         "docker_context" => "The current docker context",
         "dotnet" => "The relevant version of the .NET Core SDK for the current directory",
         "elixir" => "The currently installed versions of Elixir and OTP",
@@ -248,6 +319,7 @@ pub fn description(module: &str) -> &'static str {
         "git_branch" => "The active branch of the repo in your current directory",
         "git_commit" => "The active commit (and tag if any) of the repo in your current directory",
         "git_metrics" => "The currently added/deleted lines in your repo",
+        "git_signing" => "The key git signs commits with, and whether it is available",
         "git_state" => "The current git operation, and it's progress",
         "git_status" => "Symbol representing the state of the repo",
         "golang" => "The currently installed version of Golang",
@@ -261,15 +333,19 @@ pub fn description(module: &str) -> &'static str {
         "java" => "The currently installed version of Java",
         "jobs" => "The current number of jobs running",
         "julia" => "The currently installed version of Julia",
+        "keyboard_layout" => "The active keyboard layout or input method",
         "kotlin" => "The currently installed version of Kotlin",
         "kubernetes" => "The current Kubernetes context name and, if set, the namespace",
         "line_break" => "Separates the prompt into two lines",
+        "load_average" => "The system load average over 1, 5 and 15 minutes",
         "localip" => "The currently assigned ipv4 address",
         "lua" => "The currently installed version of Lua",
         "memory_usage" => "Current system memory and swap usage",
         "meson" => {
             "The current Meson environment, if $MESON_DEVENV and $MESON_PROJECT_NAME are set"
         }
+        "multiplexer" => "The current tmux or zellij session",
+        "network" => "The active VPN interfaces",
         "nim" => "The currently installed version of Nim",
         "nix_shell" => "The nix-shell environment",
         "nodejs" => "The currently installed version of NodeJS",
@@ -281,6 +357,7 @@ pub fn description(module: &str) -> &'static str {
         "perl" => "The currently installed version of Perl",
         "php" => "The currently installed version of PHP",
         "pijul_channel" => "The current channel of the repo in the current directory",
+        "public_ip" => "The public IP address used to reach the internet",
         "pulumi" => "The current username, stack, and installed version of Pulumi",
         "purescript" => "The currently installed version of PureScript",
         "python" => "The currently installed version of Python",
@@ -295,15 +372,18 @@ pub fn description(module: &str) -> &'static str {
         "singularity" => "The currently used Singularity image",
         "solidity" => "The current installed version of Solidity",
         "spack" => "The current spack environment, if $SPACK_ENV is set",
+        "ssh_agent" => "The number of keys loaded in the SSH agent",
         "status" => "The status of the last command",
         "sudo" => "The sudo credentials are currently cached",
         "swift" => "The currently installed version of Swift",
         "terraform" => "The currently selected terraform workspace and version",
         "time" => "The current local time",
+        "tool_versions" => "The tool versions pinned by mise or asdf in the current directory",
         "username" => "The active user's username",
         "vagrant" => "The currently installed version of Vagrant",
         "vcsh" => "The currently active VCSH repository",
         "vlang" => "The currently installed version of V",
+        "volume" => "The output volume and mute state of the default audio device",
         "zig" => "The currently installed version of Zig",
         _ => "<no description>",
     }
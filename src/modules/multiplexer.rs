@@ -0,0 +1,159 @@
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::multiplexer::MultiplexerConfig;
+use crate::formatter::StringFormatter;
+
+#[derive(Debug, PartialEq, Eq)]
+struct MultiplexerSession {
+    multiplexer: &'static str,
+    session: String,
+    window: Option<String>,
+}
+
+/// Creates a module with the name of the current tmux or zellij session
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("multiplexer");
+    let config = MultiplexerConfig::try_load(module.config);
+
+    if config.disabled {
+        return None;
+    }
+
+    let session = get_tmux_session(context).or_else(|| get_zellij_session(context))?;
+    let symbol = match session.multiplexer {
+        "tmux" => config.tmux_symbol,
+        _ => config.zellij_symbol,
+    };
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "multiplexer" => Some(Ok(session.multiplexer)),
+                "session" => Some(Ok(session.session.as_str())),
+                "window" => session.window.as_deref().map(Ok),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            context.report_module_error("multiplexer", &error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// `$TMUX` only contains the socket path, so the names are queried from tmux itself
+fn get_tmux_session(context: &Context) -> Option<MultiplexerSession> {
+    context.get_env("TMUX")?;
+
+    let output = context.exec_cmd("tmux", &["display-message", "-p", "#S\t#W"])?;
+    let (session, window) = output.stdout.trim_end_matches('\n').split_once('\t')?;
+
+    Some(MultiplexerSession {
+        multiplexer: "tmux",
+        session: session.to_string(),
+        window: Some(window.to_string()).filter(|window| !window.is_empty()),
+    })
+}
+
+fn get_zellij_session(context: &Context) -> Option<MultiplexerSession> {
+    let session = context
+        .get_env("ZELLIJ_SESSION_NAME")
+        .filter(|session| !session.is_empty())?;
+
+    Some(MultiplexerSession {
+        multiplexer: "zellij",
+        session,
+        window: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::ModuleRenderer;
+    use crate::utils::CommandOutput;
+    use nu_ansi_term::Color;
+
+    #[test]
+    fn not_in_multiplexer() {
+        let actual = ModuleRenderer::new("multiplexer")
+            .config(toml::toml! {
+                [multiplexer]
+                disabled = false
+            })
+            .collect();
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let actual = ModuleRenderer::new("multiplexer")
+            .env("ZELLIJ_SESSION_NAME", "work")
+            .collect();
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn tmux_session() {
+        let actual = ModuleRenderer::new("multiplexer")
+            .config(toml::toml! {
+                [multiplexer]
+                disabled = false
+                format = "[$symbol$session:$window]($style) "
+            })
+            .env("TMUX", "/tmp/tmux-1000/default,1234,0")
+            .cmd(
+                "tmux display-message -p #S\t#W",
+                Some(CommandOutput {
+                    stdout: "work\tvim\n".to_owned(),
+                    stderr: String::new(),
+                }),
+            )
+            .collect();
+        let expected = Some(format!("{} ", Color::Cyan.bold().paint("tmux work:vim")));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn tmux_command_fails() {
+        let actual = ModuleRenderer::new("multiplexer")
+            .config(toml::toml! {
+                [multiplexer]
+                disabled = false
+            })
+            .env("TMUX", "/tmp/tmux-1000/default,1234,0")
+            .cmd("tmux display-message -p #S\t#W", None)
+            .collect();
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn zellij_session() {
+        let actual = ModuleRenderer::new("multiplexer")
+            .config(toml::toml! {
+                [multiplexer]
+                disabled = false
+                format = "[$symbol$session( $window)]($style) "
+            })
+            .env("ZELLIJ_SESSION_NAME", "tired-tiger")
+            .collect();
+        let expected = Some(format!(
+            "{} ",
+            Color::Cyan.bold().paint("zellij tired-tiger")
+        ));
+        assert_eq!(actual, expected);
+    }
+}
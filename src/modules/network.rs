@@ -0,0 +1,199 @@
+use regex::Regex;
+
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::network::NetworkConfig;
+use crate::formatter::StringFormatter;
+use crate::utils;
+
+/// Creates a module showing the active VPN interfaces
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("network");
+    let config = NetworkConfig::try_load(module.config);
+
+    if config.disabled {
+        return None;
+    }
+
+    let pattern = match Regex::new(config.interface_pattern) {
+        Ok(pattern) => pattern,
+        Err(error) => {
+            context.report_module_error(
+                "network",
+                &format!("Invalid `network.interface_pattern`:\n{error}"),
+            );
+            return None;
+        }
+    };
+
+    let interfaces = active_interfaces(context)?
+        .into_iter()
+        .filter(|interface| pattern.is_match(interface))
+        .collect::<Vec<_>>();
+    if interfaces.is_empty() {
+        return None;
+    }
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "interface" => Some(Ok(interfaces.join(","))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            context.report_module_error("network", &error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Returns the names of all network interfaces that are up
+#[cfg(target_os = "linux")]
+fn active_interfaces(_context: &Context) -> Option<Vec<String>> {
+    linux_active_interfaces(std::path::Path::new("/sys/class/net"))
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn active_interfaces(context: &Context) -> Option<Vec<String>> {
+    let output = context.exec_cmd("ifconfig", &[] as &[&str])?;
+    Some(parse_ifconfig(&output.stdout))
+}
+
+#[cfg(windows)]
+fn active_interfaces(_context: &Context) -> Option<Vec<String>> {
+    log::debug!("Detecting network interfaces is not supported on Windows");
+    None
+}
+
+/// Lists the interfaces in `/sys/class/net` that have the `IFF_UP` flag set
+#[cfg(any(target_os = "linux", test))]
+fn linux_active_interfaces(net_dir: &std::path::Path) -> Option<Vec<String>> {
+    const IFF_UP: u32 = 0x1;
+
+    let mut interfaces = std::fs::read_dir(net_dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            utils::read_file(entry.path().join("flags"))
+                .ok()
+                .and_then(|flags| {
+                    u32::from_str_radix(flags.trim().trim_start_matches("0x"), 16).ok()
+                })
+                .map_or(false, |flags| flags & IFF_UP != 0)
+        })
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    interfaces.sort();
+    Some(interfaces)
+}
+
+/// Parses the output of BSD `ifconfig`, returning the interfaces that are up and have an IPv4 address.
+///
+/// macOS always keeps a few `utun` interfaces up for system services, but those only
+/// have link-local IPv6 addresses.
+#[cfg(any(all(unix, not(target_os = "linux")), test))]
+fn parse_ifconfig(output: &str) -> Vec<String> {
+    let mut interfaces = Vec::new();
+    let mut current: Option<(&str, bool)> = None;
+
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            current = line.split_once(": flags=").map(|(name, flags)| {
+                let flags = flags.split(['<', '>']).nth(1).unwrap_or_default();
+                (name, flags.split(',').any(|flag| flag == "UP"))
+            });
+        } else if let Some((name, true)) = current {
+            if line.trim_start().starts_with("inet ") {
+                interfaces.push(name.to_string());
+                current = None;
+            }
+        }
+    }
+    interfaces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::ModuleRenderer;
+    use std::fs;
+    use std::io;
+
+    #[test]
+    fn disabled_by_default() {
+        let actual = ModuleRenderer::new("network").collect();
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn no_matching_interface() {
+        let actual = ModuleRenderer::new("network")
+            .config(toml::toml! {
+                [network]
+                disabled = false
+                interface_pattern = "^no-such-interface$"
+            })
+            .collect();
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        let actual = ModuleRenderer::new("network")
+            .config(toml::toml! {
+                [network]
+                disabled = false
+                interface_pattern = "(unclosed"
+            })
+            .collect();
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn linux_interfaces_up() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        for (name, flags) in [("lo", "0x9"), ("wg0", "0x91"), ("tun1", "0x1002")] {
+            fs::create_dir(dir.path().join(name))?;
+            fs::write(dir.path().join(name).join("flags"), format!("{flags}\n"))?;
+        }
+
+        assert_eq!(
+            linux_active_interfaces(dir.path()),
+            Some(vec!["lo".to_string(), "wg0".to_string()])
+        );
+        dir.close()
+    }
+
+    #[test]
+    fn ifconfig_interfaces_with_ipv4() {
+        let output = "\
+lo0: flags=8049<UP,LOOPBACK,RUNNING,MULTICAST> mtu 16384
+\tinet 127.0.0.1 netmask 0xff000000
+en0: flags=8863<UP,BROADCAST,SMART,RUNNING,SIMPLEX,MULTICAST> mtu 1500
+\tinet6 fe80::1%en0 prefixlen 64 secured scopeid 0x6
+\tinet 192.168.1.20 netmask 0xffffff00 broadcast 192.168.1.255
+utun0: flags=8051<UP,POINTOPOINT,RUNNING,MULTICAST> mtu 1380
+\tinet6 fe80::2%utun0 prefixlen 64 scopeid 0xe
+utun4: flags=8051<UP,POINTOPOINT,RUNNING,MULTICAST> mtu 1420
+\tinet 10.8.0.2 --> 10.8.0.1 netmask 0xffffffff
+en5: flags=8822<BROADCAST,SMART,SIMPLEX,MULTICAST> mtu 1500
+\tinet 10.0.0.3 netmask 0xffffff00
+";
+        assert_eq!(parse_ifconfig(output), vec!["lo0", "en0", "utun4"]);
+    }
+}
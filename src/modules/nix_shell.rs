@@ -1,7 +1,10 @@
+use std::path::Path;
+
 use super::{Context, Module, ModuleConfig};
 
 use crate::configs::nix_shell::NixShellConfig;
 use crate::formatter::StringFormatter;
+use crate::utils;
 
 enum NixShellType {
     Pure,
@@ -40,6 +43,96 @@ impl NixShellType {
     }
 }
 
+/// The flake and devShell of a `nix develop` or direnv `use flake` shell
+#[derive(Debug, PartialEq, Eq)]
+struct FlakeShell {
+    flake: String,
+    dev_shell: String,
+}
+
+impl FlakeShell {
+    fn detect(context: &Context) -> Option<Self> {
+        // direnv knows the flake reference that the shell was loaded from
+        if let Some(envrc) = context.get_env("DIRENV_FILE") {
+            let envrc = Path::new(&envrc);
+            if let Some(flake_ref) = utils::read_file(envrc)
+                .ok()
+                .and_then(|content| parse_use_flake(&content))
+            {
+                return Self::from_flake_ref(&flake_ref, envrc.parent()?);
+            }
+        }
+
+        // `nix develop` sets up the build environment of the devShell, but doesn't record
+        // the flake reference, so look for the flake that the current directory belongs to
+        context.get_env("NIX_BUILD_TOP")?;
+        let flake_dir = context
+            .current_dir
+            .ancestors()
+            .find(|dir| dir.join("flake.nix").is_file())?;
+        // `mkShell` names its derivation `nix-shell` unless a name is set
+        let dev_shell = context
+            .get_env("name")
+            .filter(|name| name != "nix-shell")
+            .unwrap_or_else(|| "default".to_string());
+        Some(FlakeShell {
+            flake: dir_name(flake_dir)?,
+            dev_shell,
+        })
+    }
+
+    /// Splits a flake reference like `.#rust` or `github:owner/repo#shell`
+    fn from_flake_ref(flake_ref: &str, base_dir: &Path) -> Option<Self> {
+        let (location, attribute) = flake_ref.split_once('#').unwrap_or((flake_ref, ""));
+        let location = location.strip_prefix("path:").unwrap_or(location);
+
+        let flake = if location.is_empty() || !location.contains(':') {
+            dir_name(&base_dir.join(location))?
+        } else {
+            let location = location.split('?').next()?.trim_end_matches('/');
+            location.rsplit(['/', ':']).next()?.to_string()
+        };
+        // `devShells.<system>.<name>` can also be written out in full
+        let dev_shell = match attribute.rsplit('.').next() {
+            Some("") | None => "default",
+            Some(name) => name,
+        };
+        Some(FlakeShell {
+            flake,
+            dev_shell: dev_shell.to_string(),
+        })
+    }
+}
+
+/// Returns the flake reference of the `use flake` line of an `.envrc`
+fn parse_use_flake(envrc: &str) -> Option<String> {
+    envrc.lines().find_map(|line| {
+        let args = line
+            .trim()
+            .strip_prefix("use flake")
+            .or_else(|| line.trim().strip_prefix("use_flake"))?;
+        if !args.is_empty() && !args.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let flake_ref = args
+            .split_whitespace()
+            .take_while(|arg| !arg.starts_with('#'))
+            .find(|arg| !arg.starts_with('-'))
+            .unwrap_or(".");
+        Some(
+            flake_ref
+                .trim_matches(|c| c == '"' || c == '\'')
+                .to_string(),
+        )
+    })
+}
+
+fn dir_name(dir: &Path) -> Option<String> {
+    // Resolve `.` and `..` so they are named after the directory they point to
+    let dir = dunce::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+    Some(dir.file_name()?.to_string_lossy().into_owned())
+}
+
 /// Creates a module showing if inside a nix-shell
 ///
 /// The module will use the `$IN_NIX_SHELL` and `$name` environment variable to
@@ -57,6 +150,9 @@ impl NixShellType {
 ///     - impure         // $name == "" in an impure nix-shell
 ///     - unknown (name) // $name == "name" in an unknown nix-shell
 ///     - unknown        // $name == "" in an unknown nix-shell
+///
+/// In a `nix develop` or direnv `use flake` shell, `$flake` and `$dev_shell` are the
+/// name of the flake and the devShell attribute that the shell was loaded from.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("nix_shell");
     let config: NixShellConfig = NixShellConfig::try_load(module.config);
@@ -68,6 +164,10 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         NixShellType::Impure => config.impure_msg,
         NixShellType::Unknown => config.unknown_msg,
     };
+    let flake_shell = match shell_type {
+        NixShellType::Unknown => None,
+        _ => FlakeShell::detect(context),
+    };
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -82,6 +182,8 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             })
             .map(|variable| match variable {
                 "name" => shell_name.as_ref().map(Ok),
+                "flake" => flake_shell.as_ref().map(|shell| Ok(&shell.flake)),
+                "dev_shell" => flake_shell.as_ref().map(|shell| Ok(&shell.dev_shell)),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -90,7 +192,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `nix_shell`:\n{}", error);
+            context.report_module_error("nix_shell", &error);
             return None;
         }
     });
@@ -100,8 +202,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::test::ModuleRenderer;
     use nu_ansi_term::Color;
+    use std::fs::{self, File};
+    use std::io;
 
     #[test]
     fn no_env_variables() {
@@ -169,6 +274,73 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn direnv_flake_shell() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let project = dir.path().join("starship");
+        fs::create_dir(&project)?;
+        let envrc = project.join(".envrc");
+        fs::write(&envrc, "watch_file shell.nix\nuse flake .#rust --impure\n")?;
+
+        let actual = ModuleRenderer::new("nix_shell")
+            .env("IN_NIX_SHELL", "impure")
+            .env("DIRENV_FILE", envrc.to_string_lossy())
+            .collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Blue.bold().paint("❄️  impure starship#rust")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn nix_develop_flake_shell() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let project = dir.path().join("starship");
+        fs::create_dir_all(project.join("src"))?;
+        File::create(project.join("flake.nix"))?.sync_all()?;
+
+        let actual = ModuleRenderer::new("nix_shell")
+            .path(project.join("src"))
+            .env("IN_NIX_SHELL", "impure")
+            .env("NIX_BUILD_TOP", "/tmp/nix-shell.abc")
+            .env("name", "nix-shell")
+            .collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Blue
+                .bold()
+                .paint("❄️  impure (nix-shell) starship#default")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn flake_refs() {
+        let base_dir = Path::new("/home/user/starship");
+        let flake = |flake_ref| {
+            FlakeShell::from_flake_ref(flake_ref, base_dir)
+                .map(|shell| format!("{}#{}", shell.flake, shell.dev_shell))
+        };
+
+        assert_eq!(flake("."), Some("starship#default".to_string()));
+        assert_eq!(flake("path:.#docs"), Some("starship#docs".to_string()));
+        assert_eq!(
+            flake("github:starship/dev-env#devShells.x86_64-linux.rust"),
+            Some("dev-env#rust".to_string())
+        );
+        assert_eq!(
+            parse_use_flake("use flake \"../shared#go\""),
+            Some("../shared#go".to_string())
+        );
+        assert_eq!(parse_use_flake("use_flake"), Some(".".to_string()));
+        assert_eq!(parse_use_flake("use nix"), None);
+    }
+
     #[test]
     fn new_nix_shell() {
         let actual = ModuleRenderer::new("nix_shell")
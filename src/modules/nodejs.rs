@@ -2,6 +2,7 @@ use super::{Context, Module, ModuleConfig};
 
 use crate::configs::nodejs::NodejsConfig;
 use crate::formatter::{StringFormatter, VersionFormatter};
+use crate::utils;
 
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -9,6 +10,17 @@ use semver::Version;
 use semver::VersionReq;
 use serde_json as json;
 use std::ops::Deref;
+use std::path::Path;
+
+// Lockfiles in order of precedence, e.g. pnpm and yarn may leave a `package-lock.json` behind
+const LOCKFILES: [(&str, &str); 6] = [
+    ("pnpm-lock.yaml", "pnpm"),
+    ("yarn.lock", "yarn"),
+    ("bun.lock", "bun"),
+    ("bun.lockb", "bun"),
+    ("package-lock.json", "npm"),
+    ("npm-shrinkwrap.json", "npm"),
+];
 
 /// Creates a module with the current Node.js version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
@@ -36,11 +48,25 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             .map(|cmd| cmd.stdout)
     });
     let engines_version = Lazy::new(|| get_engines_version(context));
+    let package_manager = Lazy::new(|| get_package_manager(context));
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
                 "symbol" => Some(config.symbol),
+                "package_manager_symbol" => {
+                    let (name, _) = package_manager.as_ref()?;
+                    config
+                        .package_manager_symbols
+                        .get(name)
+                        .copied()
+                        .or_else(|| {
+                            NodejsConfig::default()
+                                .package_manager_symbols
+                                .get(name)
+                                .copied()
+                        })
+                }
                 _ => None,
             })
             .map_style(|variable| match variable {
@@ -82,6 +108,10 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
                     (!in_engines_range).then_some(Ok(eng_ver))
                 }
+                "package_manager" => match package_manager.as_ref()? {
+                    (name, Some(version)) => Some(Ok(format!("{name}@{version}"))),
+                    (name, None) => Some(Ok(name.clone())),
+                },
                 _ => None,
             })
             .parse(None, Some(context))
@@ -90,7 +120,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `nodejs`:\n{}", error);
+            context.report_module_error("nodejs", &error);
             return None;
         }
     });
@@ -106,6 +136,50 @@ fn get_engines_version(context: &Context) -> Option<String> {
     Some(raw_version.to_string())
 }
 
+/// Returns the package manager of the project and its pinned version.
+///
+/// The `packageManager` field of `package.json` takes precedence over lockfiles. Both are
+/// looked up from the current directory up to the root of the repository, so that packages
+/// in a monorepo use the package manager of the workspace.
+fn get_package_manager(context: &Context) -> Option<(String, Option<String>)> {
+    let repo_root = context
+        .get_repo()
+        .ok()
+        .and_then(|repo| repo.workdir.as_deref());
+
+    for dir in context.current_dir.ancestors() {
+        if let Some(package_manager) = get_pinned_package_manager(dir) {
+            return Some(package_manager);
+        }
+        if let Some((_, name)) = LOCKFILES
+            .iter()
+            .find(|(lockfile, _)| dir.join(lockfile).is_file())
+        {
+            return Some((name.to_string(), None));
+        }
+        if repo_root.map_or(true, |root| root == dir) {
+            break;
+        }
+    }
+    None
+}
+
+/// Parses the `packageManager` field of `package.json`, e.g. `pnpm@8.15.0+sha256.abc`
+fn get_pinned_package_manager(dir: &Path) -> Option<(String, Option<String>)> {
+    let json_str = utils::read_file(dir.join("package.json")).ok()?;
+    let package_json: json::Value = json::from_str(&json_str).ok()?;
+    let package_manager = package_json.get("packageManager")?.as_str()?;
+
+    let (name, version) = match package_manager.split_once('@') {
+        Some((name, version)) => {
+            let version = version.split('+').next().unwrap_or_default();
+            (name, (!version.is_empty()).then(|| version.to_string()))
+        }
+        None => (package_manager, None),
+    };
+    Some((name.to_string(), version))
+}
+
 fn check_engines_version(nodejs_version: Option<&str>, engines_version: Option<&str>) -> bool {
     let (Some(nodejs_version), Some(engines_version)) = (nodejs_version, engines_version) else {
         return true;
@@ -133,6 +207,7 @@ fn check_engines_version(nodejs_version: Option<&str>, engines_version: Option<&
 #[cfg(test)]
 mod tests {
     use crate::test::ModuleRenderer;
+    use crate::utils::create_command;
     use nu_ansi_term::Color;
     use std::fs::{self, File};
     use std::io;
@@ -372,4 +447,73 @@ mod tests {
         assert_eq!(expected, actual);
         dir.close()
     }
+
+    #[test]
+    fn package_manager_from_lockfile() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("package.json"))?.sync_all()?;
+        File::create(dir.path().join("package-lock.json"))?.sync_all()?;
+        File::create(dir.path().join("yarn.lock"))?.sync_all()?;
+
+        let actual = ModuleRenderer::new("nodejs")
+            .path(dir.path())
+            .config(toml::toml! {
+                [nodejs]
+                format = "via [$package_manager_symbol$package_manager]($style)"
+            })
+            .collect();
+        let expected = Some(format!("via {}", Color::Green.bold().paint("🧶 yarn")));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn package_manager_from_workspace_root() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        create_command("git")?
+            .args(["init", "--quiet"])
+            .current_dir(dir.path())
+            .output()?;
+        let mut file = File::create(dir.path().join("package.json"))?;
+        file.write_all(b"{\"packageManager\": \"pnpm@8.15.0+sha256.abc123\"}")?;
+        file.sync_all()?;
+        let package_dir = dir.path().join("packages").join("app");
+        fs::create_dir_all(&package_dir)?;
+        File::create(package_dir.join("package.json"))?.sync_all()?;
+
+        let actual = ModuleRenderer::new("nodejs")
+            .path(&package_dir)
+            .config(toml::toml! {
+                [nodejs]
+                format = "via [$package_manager_symbol$package_manager]($style)"
+                package_manager_symbols = { npm = "N " }
+            })
+            .collect();
+        let expected = Some(format!(
+            "via {}",
+            Color::Green.bold().paint("🟧 pnpm@8.15.0")
+        ));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn no_package_manager() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("index.js"))?.sync_all()?;
+
+        let actual = ModuleRenderer::new("nodejs")
+            .path(dir.path())
+            .config(toml::toml! {
+                [nodejs]
+                format = "via [$symbol($package_manager )]($style)"
+            })
+            .collect();
+        let expected = Some(format!("via {}", Color::Green.bold().paint(" ")));
+
+        assert_eq!(expected, actual);
+        dir.close()
+    }
 }
@@ -79,7 +79,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `ocaml`: \n{}", error);
+            context.report_module_error("ocaml", &error);
             return None;
         }
     });
@@ -75,7 +75,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::error!("Error in module `openstack`: \n{}", error);
+            context.report_module_error("openstack", &error);
             return None;
         }
     });
@@ -2,6 +2,7 @@ use super::{Context, Module, ModuleConfig};
 
 use crate::configs::os::OSConfig;
 use crate::formatter::StringFormatter;
+use crate::utils::{self, context_path};
 
 /// Creates a module with the current operating system
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
@@ -12,11 +13,12 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
-    #[cfg(not(test))]
-    let os = os_info::get();
+    let os = context.os_info();
 
-    #[cfg(test)]
-    let os = os_info::Info::default();
+    let os_release = ["/etc/os-release", "/usr/lib/os-release"]
+        .iter()
+        .find_map(|path| utils::read_file(context_path(context, path)).ok())
+        .unwrap_or_default();
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -29,11 +31,18 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "codename" => get_codename(&os).map(Ok),
-                "edition" => get_edition(&os).map(Ok),
-                "name" => get_name(&os).map(Ok),
-                "type" => get_type(&os).map(Ok),
-                "version" => get_version(&os).map(Ok),
+                "build_id" => os_release_value(&os_release, "BUILD_ID")
+                    .or_else(|| get_windows_build(os))
+                    .map(Ok),
+                "codename" => os_release_value(&os_release, "VERSION_CODENAME")
+                    .or_else(|| get_codename(os))
+                    .map(Ok),
+                "edition" => get_edition(os).map(Ok),
+                "name" => get_name(os).map(Ok),
+                "type" => get_type(os).map(Ok),
+                "version" => os_release_value(&os_release, "VERSION_ID")
+                    .or_else(|| get_version(os))
+                    .map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -41,7 +50,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `os`:\n{}", error);
+            context.report_module_error("os", &error);
             return None;
         }
     });
@@ -80,6 +89,27 @@ fn get_version(os: &os_info::Info) -> Option<String> {
         .map(os_info::Version::to_string)
 }
 
+// The build number of Windows, which is the last part of its version, e.g. 22631 for 10.0.22631
+fn get_windows_build(os: &os_info::Info) -> Option<String> {
+    match (os.os_type(), os.version()) {
+        (os_info::Type::Windows, os_info::Version::Semantic(_, _, build)) => {
+            Some(build.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Returns the value of a key of an `os-release` file, without its quotes
+fn os_release_value(os_release: &str, key: &str) -> Option<String> {
+    os_release
+        .lines()
+        .filter_map(|line| line.trim().split_once('='))
+        .find(|(name, _)| *name == key)
+        .map(|(_, value)| value.trim_matches(|c| c == '"' || c == '\''))
+        .filter(|value| !value.is_empty())
+        .map(String::from)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,6 +156,36 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn os_release_variables() -> std::io::Result<()> {
+        let renderer = ModuleRenderer::new("os").config(toml::toml! {
+            [os]
+            disabled = false
+            format = "[$version( $codename)( \\($build_id\\))]($style)"
+        });
+        let etc = renderer.root_path().join("etc");
+        std::fs::create_dir_all(&etc)?;
+        std::fs::write(
+            etc.join("os-release"),
+            "NAME=\"Ubuntu\"\nVERSION_ID=\"24.04\"\nVERSION_CODENAME=noble\n# BUILD_ID=ignored\nBUILD_ID='20240423'\n",
+        )?;
+
+        let actual = renderer.collect();
+        let expected = Some(format!(
+            "{}",
+            Color::White.bold().paint("24.04 noble (20240423)")
+        ));
+
+        assert_eq!(actual, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn os_release_missing_values() {
+        assert_eq!(os_release_value("VERSION_ID=\"\"", "VERSION_ID"), None);
+        assert_eq!(os_release_value("VERSION=\"24.04\"", "VERSION_ID"), None);
+    }
+
     #[test]
     fn get_symbol_default() {
         let config = OSConfig::default();
@@ -1,6 +1,7 @@
 use super::{Context, Module, ModuleConfig};
 use crate::configs::package::PackageConfig;
 use crate::formatter::{StringFormatter, VersionFormatter};
+use crate::utils;
 
 use ini::Ini;
 use quick_xml::events::Event as QXEvent;
@@ -8,7 +9,7 @@ use quick_xml::Reader as QXReader;
 use regex::Regex;
 use serde_json as json;
 use std::fs;
-use std::io::Read;
+use std::path::Path;
 
 /// Creates a module with the current package version
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
@@ -36,7 +37,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `package`:\n{}", error);
+            context.report_module_error("package", &error);
             return None;
         }
     });
@@ -80,13 +81,148 @@ fn get_pep621_version(pyproject: &toml::Table) -> Option<&str> {
     pyproject.get("project")?.get("version")?.as_str()
 }
 
+/// Reads a version that is assigned in a Python file, e.g. `__version__ = "1.0.0"`
+fn get_python_file_version(
+    context: &Context,
+    path: &str,
+    variable: Option<&str>,
+) -> Option<String> {
+    let contents = utils::read_file(context.current_dir.join(path)).ok()?;
+    let variable = match variable {
+        Some(variable) => regex::escape(variable),
+        None => "__version__|VERSION|version".to_string(),
+    };
+    let re = Regex::new(&format!(
+        r#"(?m)^\s*(?:{variable})\s*(?::\s*\w+\s*)?=\s*['"](?P<version>[^'"]+)['"]"#
+    ))
+    .ok()?;
+
+    match re.captures(&contents) {
+        Some(caps) => Some(caps["version"].to_string()),
+        // Plain version files, e.g. `VERSION` or `version.txt`
+        None if !path.ends_with(".py") => contents
+            .lines()
+            .next()
+            .map(str::trim)
+            .filter(|version| !version.is_empty())
+            .map(str::to_string),
+        None => None,
+    }
+}
+
+/// Resolves a setuptools `attr:` directive, e.g. `package.module.__version__`
+fn get_python_attr_version(context: &Context, attr: &str) -> Option<String> {
+    let (module, variable) = attr.trim().rsplit_once('.')?;
+    let module_path = module.replace('.', "/");
+
+    ["", "src/"].iter().find_map(|prefix| {
+        get_python_file_version(
+            context,
+            &format!("{prefix}{module_path}/__init__.py"),
+            Some(variable),
+        )
+        .or_else(|| {
+            get_python_file_version(
+                context,
+                &format!("{prefix}{module_path}.py"),
+                Some(variable),
+            )
+        })
+    })
+}
+
+/// Reads the version from the metadata of a built or installed package
+fn get_python_metadata_version(context: &Context) -> Option<String> {
+    let re = Regex::new(r"(?m)^Version:\s*(?P<version>\S+)").unwrap();
+    let read_version = |path: &Path| {
+        let contents = utils::read_file(path).ok()?;
+        Some(re.captures(&contents)?["version"].to_string())
+    };
+
+    read_version(&context.current_dir.join("PKG-INFO")).or_else(|| {
+        ["", "src"].iter().find_map(|dir| {
+            fs::read_dir(context.current_dir.join(dir))
+                .ok()?
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().map_or(false, |ext| ext == "egg-info"))
+                .find_map(|path| read_version(&path.join("PKG-INFO")))
+        })
+    })
+}
+
+/// Resolves a version that is marked as `dynamic` in `pyproject.toml` with the metadata of
+/// the common build backends
+fn get_pyproject_dynamic_version(context: &Context, pyproject: &toml::Table) -> Option<String> {
+    let project = pyproject.get("project")?;
+    let is_dynamic = project
+        .get("dynamic")?
+        .as_array()?
+        .iter()
+        .any(|field| field.as_str() == Some("version"));
+    if !is_dynamic {
+        return None;
+    }
+
+    let tool = pyproject.get("tool");
+    let tool_str = |keys: &[&str]| {
+        keys.iter()
+            .try_fold(tool?, |value, key| value.get(key))?
+            .as_str()
+    };
+
+    // Hatch and PDM
+    let version_path = tool_str(&["hatch", "version", "path"])
+        .or_else(|| tool_str(&["pdm", "version", "path"]))
+        // setuptools-scm writes the version to a file
+        .or_else(|| tool_str(&["setuptools_scm", "version_file"]))
+        .or_else(|| tool_str(&["setuptools_scm", "write_to"]));
+    if let Some(version) =
+        version_path.and_then(|path| get_python_file_version(context, path, None))
+    {
+        return Some(version);
+    }
+
+    // setuptools
+    let setuptools_version =
+        tool.and_then(|tool| tool.get("setuptools")?.get("dynamic")?.get("version"));
+    let setuptools_file = setuptools_version
+        .and_then(|version| version.get("file"))
+        .and_then(|file| match file {
+            toml::Value::Array(files) => files.first()?.as_str(),
+            file => file.as_str(),
+        });
+    if let Some(version) = setuptools_file
+        .and_then(|path| get_python_file_version(context, path, None))
+        .or_else(|| {
+            let attr = setuptools_version?.get("attr")?.as_str()?;
+            get_python_attr_version(context, attr)
+        })
+    {
+        return Some(version);
+    }
+
+    // Flit reads `__version__` from the module that is named after the project
+    let module = project
+        .get("name")
+        .and_then(toml::Value::as_str)
+        .map(|name| name.replace(['-', '.'], "_"));
+    module
+        .and_then(|module| get_python_attr_version(context, &format!("{module}.__version__")))
+        .or_else(|| get_python_metadata_version(context))
+}
+
 fn get_pyproject_version(context: &Context, config: &PackageConfig) -> Option<String> {
     let file_contents = context.read_file_from_pwd("pyproject.toml")?;
     let pyproject_toml: toml::Table = toml::from_str(&file_contents).ok()?;
 
-    get_pep621_version(&pyproject_toml)
-        .or_else(|| get_poetry_version(&pyproject_toml))
-        .and_then(|raw_version| format_version(raw_version, config.version_format))
+    let raw_version =
+        match get_pep621_version(&pyproject_toml).or_else(|| get_poetry_version(&pyproject_toml)) {
+            Some(version) => version.to_string(),
+            None => get_pyproject_dynamic_version(context, &pyproject_toml)?,
+        };
+
+    format_version(&raw_version, config.version_format)
 }
 
 fn get_setup_cfg_version(context: &Context, config: &PackageConfig) -> Option<String> {
@@ -223,9 +359,10 @@ fn get_sbt_version(context: &Context, config: &PackageConfig) -> Option<String>
 }
 
 fn get_cargo_version(context: &Context, config: &PackageConfig) -> Option<String> {
-    let mut file_contents = context.read_file_from_pwd("Cargo.toml")?;
+    let file_contents = context.read_file_from_pwd("Cargo.toml")?;
 
-    let mut cargo_toml: toml::Table = toml::from_str(&file_contents).ok()?;
+    let cargo_toml: toml::Table = toml::from_str(&file_contents).ok()?;
+    let workspace_toml: toml::Table;
     let cargo_version = cargo_toml.get("package").and_then(|p| p.get("version"));
     let raw_version = if let Some(v) = cargo_version.and_then(toml::Value::as_str) {
         // regular version string
@@ -235,25 +372,20 @@ fn get_cargo_version(context: &Context, config: &PackageConfig) -> Option<String
         .and_then(toml::Value::as_bool)
         .unwrap_or_default()
     {
-        // workspace version string (`package.version.worspace = true`)
-        // need to read the Cargo.toml file from the workspace root
-        let mut version = None;
-        // discover the workspace root
-        for path in context.current_dir.ancestors().skip(1) {
-            // Assume the workspace root is the first ancestor that contains a Cargo.toml file
-            if let Ok(mut file) = fs::File::open(path.join("Cargo.toml")) {
-                file.read_to_string(&mut file_contents).ok()?;
-                cargo_toml = toml::from_str(&file_contents).ok()?;
-                // Read workspace.package.version
-                version = cargo_toml
-                    .get("workspace")?
-                    .get("package")?
-                    .get("version")?
-                    .as_str();
-                break;
-            }
-        }
-        version?
+        // workspace version string (`package.version.workspace = true`)
+        // need to read the Cargo.toml file from the workspace root, which is the
+        // first ancestor whose Cargo.toml contains a `workspace` table
+        workspace_toml = context.current_dir.ancestors().skip(1).find_map(|path| {
+            let contents = utils::read_file(path.join("Cargo.toml")).ok()?;
+            let toml: toml::Table = toml::from_str(&contents).ok()?;
+            toml.contains_key("workspace").then_some(toml)
+        })?;
+        // Read workspace.package.version
+        workspace_toml
+            .get("workspace")?
+            .get("package")?
+            .get("version")?
+            .as_str()?
     } else {
         // This might be a workspace file
         cargo_toml
@@ -310,6 +442,50 @@ fn get_dart_pub_version(context: &Context, config: &PackageConfig) -> Option<Str
     format_version(raw_version, config.version_format)
 }
 
+fn get_zig_version(context: &Context, config: &PackageConfig) -> Option<String> {
+    let file_contents = context.read_file_from_pwd("build.zig.zon")?;
+    let re = Regex::new(r#"^\.version\s*=\s*"(?P<version>[^"]*)""#).unwrap();
+
+    // Only the `.version` of the package itself, not those of its dependencies
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in file_contents.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            '.' if depth == 1 => {
+                if let Some(caps) = re.captures(&file_contents[i..]) {
+                    return format_version(&caps["version"], config.version_format);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn get_pixi_version(context: &Context, config: &PackageConfig) -> Option<String> {
+    let file_contents = context.read_file_from_pwd("pixi.toml")?;
+    let pixi_toml: toml::Table = toml::from_str(&file_contents).ok()?;
+
+    let raw_version = ["project", "workspace"]
+        .iter()
+        .find_map(|table| pixi_toml.get(*table)?.get("version")?.as_str())?;
+
+    format_version(raw_version, config.version_format)
+}
+
 fn get_version(context: &Context, config: &PackageConfig) -> Option<String> {
     let package_version_fn: Vec<fn(&Context, &PackageConfig) -> Option<String>> = vec![
         get_cargo_version,
@@ -330,6 +506,8 @@ fn get_version(context: &Context, config: &PackageConfig) -> Option<String> {
         get_sbt_version,
         get_daml_project_version,
         get_dart_pub_version,
+        get_zig_version,
+        get_pixi_version,
     ];
 
     package_version_fn.iter().find_map(|f| f(context, config))
@@ -483,6 +661,47 @@ mod tests {
         project_dir.close()
     }
 
+    #[test]
+    fn test_extract_cargo_version_ws_nested_member() -> io::Result<()> {
+        let ws_config_content = toml::toml! {
+            [workspace.package]
+            version = "0.1.0"
+        }
+        .to_string();
+
+        let member_config_content = toml::toml! {
+            [package]
+            version.workspace = true
+        }
+        .to_string();
+
+        let project_dir = create_project_dir()?;
+        fs::create_dir_all(project_dir.path().join("crates/member"))?;
+
+        // `crates` isn't the workspace root, as its Cargo.toml has no `workspace` table
+        fill_config(&project_dir, "Cargo.toml", Some(&ws_config_content))?;
+        fill_config(
+            &project_dir,
+            "crates/Cargo.toml",
+            Some(&member_config_content),
+        )?;
+        fill_config(
+            &project_dir,
+            "crates/member/Cargo.toml",
+            Some(&member_config_content),
+        )?;
+
+        let actual = ModuleRenderer::new("package")
+            .path(project_dir.path().join("crates/member"))
+            .collect();
+        let expected = Some(format!(
+            "is {} ",
+            Color::Fixed(208).bold().paint("📦 v0.1.0")
+        ));
+        assert_eq!(actual, expected);
+        project_dir.close()
+    }
+
     #[test]
     fn test_extract_nimble_package_version() -> io::Result<()> {
         let config_name = "test_project.nimble";
@@ -841,6 +1060,113 @@ license = "MIT"
         project_dir.close()
     }
 
+    #[test]
+    fn test_extract_pyproject_dynamic_version_hatch() -> io::Result<()> {
+        let config_content = toml::toml! {
+            [project]
+            name = "starship"
+            dynamic = ["version"]
+
+            [tool.hatch.version]
+            path = "src/starship/__about__.py"
+        }
+        .to_string();
+
+        let project_dir = create_project_dir()?;
+        fs::create_dir_all(project_dir.path().join("src/starship"))?;
+        fill_config(&project_dir, "pyproject.toml", Some(&config_content))?;
+        fill_config(
+            &project_dir,
+            "src/starship/__about__.py",
+            Some("__version__ = \"0.1.0\"\n"),
+        )?;
+        expect_output(&project_dir, Some("v0.1.0"), None);
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_pyproject_dynamic_version_setuptools() -> io::Result<()> {
+        let config_content = toml::toml! {
+            [project]
+            name = "starship"
+            dynamic = ["version"]
+
+            [tool.setuptools.dynamic]
+            version = { attr = "starship.meta.VERSION" }
+        }
+        .to_string();
+
+        let project_dir = create_project_dir()?;
+        fs::create_dir_all(project_dir.path().join("starship"))?;
+        fill_config(&project_dir, "pyproject.toml", Some(&config_content))?;
+        fill_config(
+            &project_dir,
+            "starship/meta.py",
+            Some("VERSION: str = '0.2.0'\n"),
+        )?;
+        expect_output(&project_dir, Some("v0.2.0"), None);
+
+        let config_content = toml::toml! {
+            [project]
+            name = "starship"
+            dynamic = ["version"]
+
+            [tool.setuptools.dynamic]
+            version = { file = "VERSION" }
+        }
+        .to_string();
+        fill_config(&project_dir, "pyproject.toml", Some(&config_content))?;
+        fill_config(&project_dir, "VERSION", Some("0.3.0\n"))?;
+        expect_output(&project_dir, Some("v0.3.0"), None);
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_pyproject_dynamic_version_flit() -> io::Result<()> {
+        let config_content = toml::toml! {
+            [project]
+            name = "star-ship"
+            dynamic = ["version", "description"]
+        }
+        .to_string();
+
+        let project_dir = create_project_dir()?;
+        fs::create_dir_all(project_dir.path().join("src/star_ship"))?;
+        fill_config(&project_dir, "pyproject.toml", Some(&config_content))?;
+        fill_config(
+            &project_dir,
+            "src/star_ship/__init__.py",
+            Some("\"\"\"A prompt\"\"\"\n\n__version__ = \"0.1.0\"\n"),
+        )?;
+        expect_output(&project_dir, Some("v0.1.0"), None);
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_pyproject_dynamic_version_metadata() -> io::Result<()> {
+        let config_content = toml::toml! {
+            [project]
+            name = "starship"
+            dynamic = ["version"]
+
+            [tool.setuptools_scm]
+        }
+        .to_string();
+
+        let project_dir = create_project_dir()?;
+        fs::create_dir_all(project_dir.path().join("src/starship.egg-info"))?;
+        fill_config(&project_dir, "pyproject.toml", Some(&config_content))?;
+        expect_output(&project_dir, None, None);
+
+        fill_config(
+            &project_dir,
+            "src/starship.egg-info/PKG-INFO",
+            Some("Metadata-Version: 2.1\nName: starship\nVersion: 0.1.dev3\n"),
+        )?;
+        expect_output(&project_dir, Some("v0.1.dev3"), None);
+        project_dir.close()
+    }
+
     #[test]
     fn test_extract_setup_cfg_version() -> io::Result<()> {
         let config_name = "setup.cfg";
@@ -1402,6 +1728,44 @@ environment:
         project_dir.close()
     }
 
+    #[test]
+    fn test_extract_zig_version() -> io::Result<()> {
+        let config_name = "build.zig.zon";
+        let config_content = r#".{
+    .name = "starship",
+    .dependencies = .{
+        .example = .{
+            .url = "https://example.com/{.version = \"9.9.9\"}.tar.gz",
+            .version = "9.9.9",
+        },
+    },
+    .version = "0.1.0",
+    .paths = .{""},
+}
+"#;
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(config_content))?;
+        expect_output(&project_dir, Some("v0.1.0"), None);
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_pixi_version() -> io::Result<()> {
+        let config_name = "pixi.toml";
+        let config_content = toml::toml! {
+            [workspace]
+            name = "starship"
+            version = "0.1.0"
+            channels = ["conda-forge"]
+        }
+        .to_string();
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(&config_content))?;
+        expect_output(&project_dir, Some("v0.1.0"), None);
+        project_dir.close()
+    }
+
     fn create_project_dir() -> io::Result<TempDir> {
         tempfile::tempdir()
     }
@@ -53,7 +53,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `pijul_channel`:\n{}", error);
+            context.report_module_error("pijul_channel", &error);
             return None;
         }
     });
@@ -0,0 +1,206 @@
+use std::fs;
+use std::net::IpAddr;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::public_ip::PublicIpConfig;
+use crate::formatter::StringFormatter;
+use crate::trace::{self, TraceKind};
+use crate::utils;
+
+/// Creates a module with the public IP address, as seen by the service at `url`
+///
+/// The address is fetched with `curl` and cached for `cache_duration` seconds. If it can't be
+/// fetched within the command timeout, the last known address is shown instead, or nothing if
+/// there is none.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("public_ip");
+    let config = PublicIpConfig::try_load(module.config);
+
+    if config.disabled {
+        return None;
+    }
+
+    let ip = get_cached_ip(context, &config)?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "ip" => Some(Ok(&ip)),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            context.report_module_error("public_ip", &error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn get_cached_ip(context: &Context, config: &PublicIpConfig) -> Option<String> {
+    let cache_file = utils::cache_path(context, "public_ip");
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?;
+    let cached = utils::read_file(&cache_file)
+        .ok()
+        .and_then(|content| parse_cache(&content, config.url));
+
+    if let Some((timestamp, ip)) = &cached {
+        let age = now.saturating_sub(Duration::from_secs(*timestamp));
+        if age.as_secs() < config.cache_duration {
+            return ip.clone();
+        }
+    }
+
+    // When the address can't be fetched, the stale one (or its absence) is kept for another
+    // `cache_duration`, so that an unreachable service doesn't delay every prompt by the timeout
+    let ip = get_ip(context, config.url).or_else(|| {
+        log::debug!("Unable to fetch the public IP from {}", config.url);
+        cached.and_then(|(_, ip)| ip)
+    });
+    let content = format!(
+        "{} {} {}",
+        now.as_secs(),
+        ip.as_deref().unwrap_or_default(),
+        config.url
+    );
+    if let Err(error) = fs::write(&cache_file, content) {
+        log::debug!(
+            "Unable to write public IP cache {:?}: {}",
+            cache_file,
+            error
+        );
+    }
+    ip
+}
+
+/// Parses the cache, which holds the time the address was fetched, the address (empty if it
+/// couldn't be fetched), and the url
+fn parse_cache(content: &str, url: &str) -> Option<(u64, Option<String>)> {
+    let mut parts = content.trim().splitn(3, ' ');
+    let timestamp = parts.next()?.parse().ok()?;
+    let ip = Some(parts.next()?)
+        .filter(|ip| !ip.is_empty())
+        .map(ToString::to_string);
+    (parts.next()? == url).then_some((timestamp, ip))
+}
+
+fn get_ip(context: &Context, url: &str) -> Option<String> {
+    // Let curl give up on its own as well, instead of only being killed at the timeout
+    let max_time = context.command_timeout().as_secs_f64().to_string();
+    let start = Instant::now();
+    let output = context.exec_cmd("curl", &["-fsS", "--max-time", &max_time, url]);
+    // The command is traced as well, but this makes the request stand out
+    trace::record(
+        TraceKind::Network,
+        || url.to_string(),
+        start,
+        output.is_some(),
+    );
+    let output = output?;
+    let ip = output.stdout.trim();
+    // Anything else, like a captive portal page, isn't shown
+    ip.parse::<IpAddr>().ok().map(|_| ip.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{output, ModuleRenderer};
+    use crate::utils::CommandOutput;
+    use nu_ansi_term::Color;
+    use std::io;
+
+    const CURL: &str = "curl -fsS --max-time 0.5 https://api.ipify.org";
+
+    fn render(cache_dir: &std::path::Path, curl_output: Option<CommandOutput>) -> Option<String> {
+        ModuleRenderer::new("public_ip")
+            .env("STARSHIP_CACHE", cache_dir.to_string_lossy())
+            .config(toml::toml! {
+                [public_ip]
+                disabled = false
+            })
+            .cmd(CURL, curl_output)
+            .collect()
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let actual = ModuleRenderer::new("public_ip").collect();
+        assert_eq!(actual, None);
+    }
+
+    #[test]
+    fn fetches_and_caches_ip() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        let expected = Some(format!("{} ", Color::Blue.bold().paint("🌐 203.0.113.7")));
+        assert_eq!(render(dir.path(), output("203.0.113.7\n")), expected);
+        // The cached address is used without running curl again
+        assert_eq!(render(dir.path(), None), expected);
+        dir.close()
+    }
+
+    #[test]
+    fn stale_ip_when_fetch_fails() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("public_ip"),
+            "0 2001:db8::1 https://api.ipify.org",
+        )?;
+
+        let expected = Some(format!("{} ", Color::Blue.bold().paint("🌐 2001:db8::1")));
+        assert_eq!(render(dir.path(), None), expected);
+        dir.close()
+    }
+
+    #[test]
+    fn failed_fetch_is_cached() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        assert_eq!(render(dir.path(), None), None);
+        // curl isn't run again until the cache expires
+        assert_eq!(render(dir.path(), output("203.0.113.7\n")), None);
+        dir.close()
+    }
+
+    #[test]
+    fn ignores_invalid_response() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        assert_eq!(render(dir.path(), output("<html>Log in</html>")), None);
+        dir.close()
+    }
+
+    #[test]
+    fn cache_of_other_url_is_ignored() {
+        assert_eq!(
+            parse_cache("1 203.0.113.7 https://ifconfig.me", "https://api.ipify.org"),
+            None
+        );
+        assert_eq!(
+            parse_cache(
+                "1 203.0.113.7 https://api.ipify.org\n",
+                "https://api.ipify.org"
+            ),
+            Some((1, Some("203.0.113.7".to_string())))
+        );
+        assert_eq!(
+            parse_cache("1  https://api.ipify.org", "https://api.ipify.org"),
+            Some((1, None))
+        );
+    }
+}
@@ -75,7 +75,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             Some(module)
         }
         Err(e) => {
-            log::warn!("Error in module `pulumi`:\n{}", e);
+            context.report_module_error("pulumi", &e);
             None
         }
     }
@@ -164,22 +164,21 @@ fn stack_name(project_file: &Path, context: &Context) -> Option<String> {
 
 /// Calculates the path of the workspace settings file for a given pulumi stack.
 fn get_pulumi_workspace(context: &Context, name: &str, project_file: &Path) -> Option<PathBuf> {
-    let project_file = if cfg!(test) {
-        // Because this depends on the absolute path of the file, it changes in
-        // each test run. We thus mock it.
-        "test".to_string()
-    } else {
-        let mut hasher = Sha1::new();
-        hasher.update(project_file.to_str()?.as_bytes());
-        crate::utils::encode_to_hex(&hasher.finalize())
-    };
-    let unique_file_name = format!("{name}-{project_file}-workspace.json");
+    let unique_file_name = format!("{name}-{}-workspace.json", project_hash(project_file)?);
     let mut path = pulumi_home_dir(context)?;
     path.push("workspaces");
     path.push(unique_file_name);
     Some(path)
 }
 
+/// Hashes the absolute path of the project file, which tells workspaces of projects with the same
+/// name apart
+fn project_hash(project_file: &Path) -> Option<String> {
+    let mut hasher = Sha1::new();
+    hasher.update(project_file.to_str()?.as_bytes());
+    Some(crate::utils::encode_to_hex(&hasher.finalize()))
+}
+
 /// Get the Pulumi home directory. We first check `PULUMI_HOME`. If that isn't
 /// set, we return `$HOME/.pulumi`.
 fn pulumi_home_dir(context: &Context) -> Option<PathBuf> {
@@ -283,10 +282,13 @@ mod tests {
         context.env.insert("HOME", "/home/sweet/home".to_string());
         let name = "foobar";
         let project_file = PathBuf::from("/hello/Pulumi.yaml");
+        let expected = format!(
+            "/home/sweet/home/.pulumi/workspaces/foobar-{}-workspace.json",
+            project_hash(&project_file).unwrap()
+        );
         assert_eq!(
             get_pulumi_workspace(&context, name, &project_file),
-            Some("/home/sweet/home/.pulumi/workspaces/foobar-test-workspace.json")
-                .map(PathBuf::from)
+            Some(PathBuf::from(expected))
         );
     }
 
@@ -323,7 +325,10 @@ mod tests {
 
         let workspace_path = root.join(".pulumi").join("workspaces");
         std::fs::create_dir_all(&workspace_path)?;
-        let workspace_path = &workspace_path.join("starship-test-workspace.json");
+        let workspace_path = &workspace_path.join(format!(
+            "starship-{}-workspace.json",
+            project_hash(&root.join("Pulumi.yml")).unwrap()
+        ));
         let mut workspace = File::create(workspace_path)?;
         serde_json::to_writer_pretty(
             &mut workspace,
@@ -390,7 +395,10 @@ mod tests {
 
         let workspace_path = root.join(".pulumi").join("workspaces");
         std::fs::create_dir_all(&workspace_path)?;
-        let workspace_path = &workspace_path.join("starship-test-workspace.json");
+        let workspace_path = &workspace_path.join(format!(
+            "starship-{}-workspace.json",
+            project_hash(&root.join("Pulumi.yml")).unwrap()
+        ));
         let mut workspace = File::create(workspace_path)?;
         serde_json::to_writer_pretty(
             &mut workspace,
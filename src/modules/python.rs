@@ -1,5 +1,6 @@
 use ini::Ini;
-use std::path::Path;
+use once_cell::sync::Lazy;
+use std::path::{Path, PathBuf};
 
 use super::{Context, Module, ModuleConfig};
 use crate::configs::python::PythonConfig;
@@ -20,11 +21,19 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         .is_match();
 
     let is_venv = context.get_env("VIRTUAL_ENV").is_some();
+    let uv_venv = get_uv_venv(context);
 
-    if !is_py_project && !is_venv {
+    if !is_py_project && !is_venv && uv_venv.is_none() {
         return None;
     };
 
+    let is_uv_project = Lazy::new(|| {
+        uv_venv.is_some()
+            || context
+                .try_begin_scan()
+                .map_or(false, |scan| scan.set_files(&["uv.lock"]).is_match())
+    });
+
     let pyenv_prefix = if config.pyenv_version_name {
         config.pyenv_prefix
     } else {
@@ -35,6 +44,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         formatter
             .map_meta(|var, _| match var {
                 "symbol" => Some(config.symbol),
+                "uv_symbol" => uv_venv.as_ref().map(|_| config.uv_symbol),
                 _ => None,
             })
             .map_style(|variable| match variable {
@@ -46,7 +56,15 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                     if config.pyenv_version_name {
                         return get_pyenv_version(context).map(Ok);
                     }
-                    let python_version = get_python_version(context, &config)?;
+                    let uv_python_version = if *is_uv_project {
+                        get_uv_python_version(context, uv_venv.as_ref())
+                    } else {
+                        None
+                    };
+                    let python_version = match uv_python_version {
+                        Some(version) => version,
+                        None => get_python_version(context, &config)?,
+                    };
                     VersionFormatter::format_module_version(
                         module.get_name(),
                         &python_version,
@@ -59,6 +77,12 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                     virtual_env.as_ref().map(|e| Ok(e.trim().to_string()))
                 }
                 "pyenv_prefix" => Some(Ok(pyenv_prefix.to_string())),
+                "uv_version" => {
+                    if !*is_uv_project {
+                        return None;
+                    }
+                    get_uv_version(context, uv_venv.as_ref()).map(Ok)
+                }
                 _ => None,
             })
             .parse(None, Some(context))
@@ -67,7 +91,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `python`:\n{}", error);
+            context.report_module_error("python", &error);
             return None;
         }
     });
@@ -112,6 +136,53 @@ fn parse_python_version(python_version_string: &str) -> Option<String> {
     Some(version.to_string())
 }
 
+/// Returns the virtual environment if it was created by uv. That's either the active
+/// environment or, as uv doesn't need environments to be activated, the `.venv` of the project.
+fn get_uv_venv(context: &Context) -> Option<Ini> {
+    let venv_path = context
+        .get_env("VIRTUAL_ENV")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| context.current_dir.join(".venv"));
+    let venv_cfg = Ini::load_from_file(venv_path.join("pyvenv.cfg")).ok()?;
+
+    venv_cfg
+        .general_section()
+        .contains_key("uv")
+        .then_some(venv_cfg)
+}
+
+/// Resolves the Python version of a uv project without running `python`, from the
+/// environment of the project or else from the version that the project requests
+fn get_uv_python_version(context: &Context, uv_venv: Option<&Ini>) -> Option<String> {
+    uv_venv
+        .and_then(|venv_cfg| {
+            let section = venv_cfg.general_section();
+            section
+                .get("version_info")
+                .or_else(|| section.get("version"))
+        })
+        .map(|version| version.trim().to_string())
+        .or_else(|| {
+            let python_version = context.read_file_from_pwd(".python-version")?;
+            python_version
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(str::to_string)
+        })
+}
+
+fn get_uv_version(context: &Context, uv_venv: Option<&Ini>) -> Option<String> {
+    uv_venv
+        .and_then(|venv_cfg| venv_cfg.general_section().get("uv"))
+        .map(|version| version.trim().to_string())
+        .or_else(|| {
+            // `uv 0.4.18 (7b55e9790 2024-10-01)`
+            let output = context.exec_cmd("uv", &["--version"])?;
+            Some(output.stdout.split_whitespace().nth(1)?.to_string())
+        })
+}
+
 fn get_python_virtual_env(context: &Context) -> Option<String> {
     context.get_env("VIRTUAL_ENV").and_then(|venv| {
         get_prompt_from_venv(Path::new(&venv)).or_else(|| {
@@ -424,6 +495,93 @@ prompt = '(foo)'
         dir.close()
     }
 
+    #[test]
+    fn with_uv_venv() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        create_dir_all(dir.path().join(".venv"))?;
+        let mut venv_cfg = File::create(dir.path().join(".venv").join("pyvenv.cfg"))?;
+        venv_cfg.write_all(
+            br#"
+home = /usr/bin
+implementation = CPython
+uv = 0.4.17
+version_info = 3.12.6
+include-system-site-packages = false
+prompt = my-project
+        "#,
+        )?;
+        venv_cfg.sync_all()?;
+
+        // uv doesn't need the environment of the project to be activated
+        let actual = ModuleRenderer::new("python")
+            .path(dir.path())
+            .config(toml::toml! {
+                [python]
+                format = "via [$symbol$uv_symbol($version )(uv $uv_version )]($style)"
+            })
+            .collect();
+        let expected = Some(format!(
+            "via {}",
+            Color::Yellow.bold().paint("🐍 uv v3.12.6 uv 0.4.17 ")
+        ));
+        assert_eq!(actual, expected);
+
+        let actual = ModuleRenderer::new("python")
+            .path(dir.path())
+            .env("VIRTUAL_ENV", dir.path().join(".venv").to_str().unwrap())
+            .collect();
+        let expected = Some(format!(
+            "via {}",
+            Color::Yellow.bold().paint("🐍 v3.12.6 (my-project) ")
+        ));
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_uv_lock() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("uv.lock"))?.sync_all()?;
+        let mut python_version = File::create(dir.path().join(".python-version"))?;
+        python_version.write_all(b"3.11\n")?;
+        python_version.sync_all()?;
+
+        let actual = ModuleRenderer::new("python")
+            .path(dir.path())
+            .config(toml::toml! {
+                [python]
+                format = "via [$symbol$uv_symbol($version )(uv $uv_version )]($style)"
+            })
+            .collect();
+        let expected = Some(format!(
+            "via {}",
+            Color::Yellow.bold().paint("🐍 v3.11 uv 0.4.18 ")
+        ));
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn plain_venv_is_not_uv() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        create_dir_all(dir.path().join(".venv"))?;
+        let mut venv_cfg = File::create(dir.path().join(".venv").join("pyvenv.cfg"))?;
+        venv_cfg.write_all(b"home = /usr/bin\nversion = 3.12.6\n")?;
+        venv_cfg.sync_all()?;
+
+        let actual = ModuleRenderer::new("python")
+            .path(dir.path())
+            .env("VIRTUAL_ENV", dir.path().join(".venv").to_str().unwrap())
+            .config(toml::toml! {
+                [python]
+                format = "via [$symbol$uv_symbol($version )(uv $uv_version )]($style)"
+            })
+            .collect();
+        let expected = Some(format!("via {}", Color::Yellow.bold().paint("🐍 v3.8.0 ")));
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
     fn check_python2_renders(dir: &tempfile::TempDir, starship_config: Option<toml::Table>) {
         let config = starship_config.unwrap_or(toml::toml! {
             [python]
@@ -1,4 +1,3 @@
-use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Output;
 
@@ -9,7 +8,7 @@ use super::{Context, Module, ModuleConfig};
 
 use crate::configs::rust::RustConfig;
 use crate::formatter::{StringFormatter, VersionFormatter};
-use crate::utils::create_command;
+use crate::utils::{self, create_command};
 use home::rustup_home;
 
 use once_cell::sync::OnceCell;
@@ -93,7 +92,16 @@ impl RustToolingEnvironmentInfo {
     fn get_rustup_rustc_version(&self, context: &Context) -> &RustupRunRustcVersionOutcome {
         self.rustup_rustc_output.get_or_init(|| {
             let out = if let Some(toolchain) = self.get_env_toolchain_override(context) {
-                // First try running ~/.rustup/toolchains/<toolchain>/bin/rustc --version
+                // First try reading the version from the manifest of the installed toolchain
+                if let Some(version) = rustup_home().ok().and_then(|rustup_folder| {
+                    let host_triple = get_default_host_triple(context, self);
+                    read_toolchain_manifest_version(&rustup_folder, toolchain, host_triple)
+                }) {
+                    log::debug!("Rustup toolchain manifest version is {:?}", version);
+                    return RustupRunRustcVersionOutcome::RustcVersion(version);
+                }
+
+                // Then try running ~/.rustup/toolchains/<toolchain>/bin/rustc --version
                 rustup_home()
                     .map(|rustup_folder| {
                         rustup_folder
@@ -187,6 +195,8 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "version" => get_module_version(context, &config, &rust_env_info).map(Ok),
                 "numver" => get_module_numeric_version(context, &config, &rust_env_info).map(Ok),
                 "toolchain" => get_toolchain_version(context, &config, &rust_env_info).map(Ok),
+                "toolchain_channel" => get_toolchain_channel(context, &rust_env_info).map(Ok),
+                "target" => get_build_target(context, &rust_env_info).map(Ok),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -195,7 +205,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `rust`:\n{}", error);
+            context.report_module_error("rust", &error);
             return None;
         }
     });
@@ -251,14 +261,7 @@ fn get_toolchain_version(
 ) -> Option<String> {
     type Outcome = RustupRunRustcVersionOutcome;
 
-    let settings_host_triple = rust_env_info
-        .get_rustup_settings(context)
-        .default_host_triple();
-    let default_host_triple = if settings_host_triple.is_none() {
-        guess_host_triple()
-    } else {
-        settings_host_triple
-    };
+    let default_host_triple = get_default_host_triple(context, rust_env_info);
 
     match rust_env_info.get_rustup_rustc_version(context) {
         Outcome::RustcVersion(_) | Outcome::ToolchainNotInstalled(_) => {
@@ -277,6 +280,152 @@ fn get_toolchain_version(
     }
 }
 
+fn get_default_host_triple<'a>(
+    context: &Context,
+    rust_env_info: &'a RustToolingEnvironmentInfo,
+) -> Option<&'a str> {
+    let settings_host_triple = rust_env_info
+        .get_rustup_settings(context)
+        .default_host_triple();
+    if settings_host_triple.is_none() {
+        guess_host_triple()
+    } else {
+        settings_host_triple
+    }
+}
+
+/// Gets the release channel of the active toolchain, e.g. `stable`, `nightly` or `1.75.0`
+fn get_toolchain_channel(
+    context: &Context,
+    rust_env_info: &RustToolingEnvironmentInfo,
+) -> Option<String> {
+    let default_host_triple = get_default_host_triple(context, rust_env_info);
+    let toolchain = match rust_env_info.get_env_toolchain_override(context) {
+        Some(toolchain) => format_toolchain(toolchain, default_host_triple),
+        None => {
+            let (_numver, toolchain) = rust_env_info.get_rustc_verbose_version(context)?;
+            format_toolchain(toolchain, default_host_triple)
+        }
+    };
+
+    Some(format_toolchain_channel(&toolchain))
+}
+
+fn format_toolchain_channel(toolchain: &str) -> String {
+    // Dated toolchains like `nightly-2024-01-01` belong to their channel
+    ["stable", "beta", "nightly"]
+        .into_iter()
+        .find(|channel| toolchain == *channel || toolchain.starts_with(&format!("{channel}-")))
+        .unwrap_or(toolchain)
+        .to_owned()
+}
+
+/// Gets the target that cargo builds for, if it isn't the host.
+///
+/// Like cargo, this looks at `$CARGO_BUILD_TARGET`, then at the `build.target` of the
+/// `.cargo/config.toml` files in `.` or parent directories, then at `$CARGO_HOME/config.toml`.
+fn get_build_target(
+    context: &Context,
+    rust_env_info: &RustToolingEnvironmentInfo,
+) -> Option<String> {
+    #[derive(Deserialize)]
+    struct CargoConfig {
+        build: Option<BuildSection>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum BuildTarget {
+        Single(String),
+        Multiple(Vec<String>),
+    }
+
+    #[derive(Deserialize)]
+    struct BuildSection {
+        target: Option<BuildTarget>,
+    }
+
+    fn read_target(cargo_dir: &Path) -> Option<String> {
+        let contents = utils::read_file(cargo_dir.join("config.toml"))
+            .or_else(|_| utils::read_file(cargo_dir.join("config")))
+            .ok()?;
+        match toml::from_str::<CargoConfig>(&contents)
+            .ok()?
+            .build?
+            .target?
+        {
+            BuildTarget::Single(target) => Some(target),
+            BuildTarget::Multiple(targets) => Some(targets.join(", ")),
+        }
+    }
+
+    let cargo_home = context
+        .get_env("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| Some(context.get_home()?.join(".cargo")));
+    let target = context
+        .get_env("CARGO_BUILD_TARGET")
+        .or_else(|| {
+            context
+                .current_dir
+                .ancestors()
+                .find_map(|dir| read_target(&dir.join(".cargo")))
+        })
+        .or_else(|| read_target(&cargo_home?))?;
+
+    // Custom targets are given as the path of their target specification
+    let target = if target.ends_with(".json") {
+        Path::new(&target).file_stem()?.to_str()?.to_owned()
+    } else {
+        target
+    };
+
+    let default_host_triple = get_default_host_triple(context, rust_env_info);
+    (Some(target.as_str()) != default_host_triple).then_some(target)
+}
+
+/// Reads the version of `rustc` from the channel manifest that rustup keeps for each
+/// installed toolchain, which avoids running `rustc`
+fn read_toolchain_manifest_version(
+    rustup_folder: &Path,
+    toolchain: &str,
+    default_host_triple: Option<&str>,
+) -> Option<String> {
+    #[derive(Deserialize)]
+    struct ChannelManifest {
+        pkg: HashMap<String, ManifestPackage>,
+    }
+
+    #[derive(Deserialize)]
+    struct ManifestPackage {
+        version: String,
+    }
+
+    let toolchains = rustup_folder.join("toolchains");
+    // Toolchains are installed with the host triple, but may be referred to without it
+    let toolchain_dir = [Some(toolchain.to_owned())]
+        .into_iter()
+        .chain([default_host_triple.map(|triple| format!("{toolchain}-{triple}"))])
+        .flatten()
+        .map(|name| toolchains.join(name))
+        .find(|dir| dir.is_dir())?;
+    let manifest = utils::read_file(
+        toolchain_dir
+            .join("lib")
+            .join("rustlib")
+            .join("multirust-channel-manifest.toml"),
+    )
+    .ok()?;
+
+    // e.g. `1.75.0 (82e1608df 2023-12-21)`
+    let version = toml::from_str::<ChannelManifest>(&manifest)
+        .ok()?
+        .pkg
+        .remove("rustc")?
+        .version;
+    Some(format!("rustc {version}"))
+}
+
 fn env_rustup_toolchain(context: &Context) -> Option<String> {
     log::trace!("Searching for rustup toolchain in environment.");
     let val = context.get_env("RUSTUP_TOOLCHAIN")?;
@@ -314,7 +463,7 @@ fn find_rust_toolchain_file(context: &Context) -> Option<String> {
     }
 
     fn read_channel(path: &Path, only_toml: bool) -> Option<String> {
-        let contents = fs::read_to_string(path).ok()?;
+        let contents = utils::read_file(path).ok()?;
 
         match contents.lines().count() {
             0 => None,
@@ -461,7 +610,7 @@ fn strip_dos_path(path: PathBuf) -> PathBuf {
 impl RustupSettings {
     fn load(_context: &Context) -> Option<Self> {
         let path = rustup_home().ok()?.join("settings.toml");
-        Self::from_toml_str(&fs::read_to_string(path).ok()?)
+        Self::from_toml_str(&utils::read_file(path).ok()?)
     }
 
     fn from_toml_str(toml_str: &str) -> Option<Self> {
@@ -500,7 +649,10 @@ impl RustupSettings {
 #[cfg(test)]
 mod tests {
     use crate::context::{Shell, Target};
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
     use once_cell::sync::Lazy;
+    use std::fs::{self, File};
     use std::io;
     use std::process::{ExitStatus, Output};
 
@@ -899,6 +1051,119 @@ version = "12"
         dir.close()
     }
 
+    #[test]
+    fn test_read_toolchain_manifest_version() -> io::Result<()> {
+        let rustup_dir = tempfile::tempdir()?;
+        let rustlib_dir = rustup_dir
+            .path()
+            .join("toolchains")
+            .join("stable-x86_64-unknown-linux-gnu")
+            .join("lib")
+            .join("rustlib");
+        fs::create_dir_all(&rustlib_dir)?;
+        fs::write(
+            rustlib_dir.join("multirust-channel-manifest.toml"),
+            r#"
+date = "2023-12-28"
+manifest-version = "2"
+
+[pkg.cargo]
+version = "1.75.0 (1d8b05cdd 2023-11-20)"
+
+[pkg.rustc]
+version = "1.75.0 (82e1608df 2023-12-21)"
+"#,
+        )?;
+
+        for toolchain in ["stable", "stable-x86_64-unknown-linux-gnu"] {
+            assert_eq!(
+                read_toolchain_manifest_version(
+                    rustup_dir.path(),
+                    toolchain,
+                    Some("x86_64-unknown-linux-gnu")
+                ),
+                Some("rustc 1.75.0 (82e1608df 2023-12-21)".to_owned())
+            );
+        }
+        assert_eq!(
+            read_toolchain_manifest_version(rustup_dir.path(), "stable", None),
+            None
+        );
+        assert_eq!(
+            read_toolchain_manifest_version(
+                rustup_dir.path(),
+                "nightly",
+                Some("x86_64-unknown-linux-gnu")
+            ),
+            None
+        );
+        rustup_dir.close()
+    }
+
+    #[test]
+    fn test_format_toolchain_channel() {
+        assert_eq!(format_toolchain_channel("stable"), "stable");
+        assert_eq!(format_toolchain_channel("nightly-2024-01-01"), "nightly");
+        assert_eq!(format_toolchain_channel("beta-2024-01-01"), "beta");
+        assert_eq!(format_toolchain_channel("1.75.0"), "1.75.0");
+        assert_eq!(format_toolchain_channel("stage1"), "stage1");
+        assert_eq!(format_toolchain_channel("stablish"), "stablish");
+    }
+
+    #[test]
+    fn test_build_target() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let child_dir_path = dir.path().join("child");
+        fs::create_dir_all(dir.path().join(".cargo"))?;
+        fs::create_dir(&child_dir_path)?;
+        File::create(child_dir_path.join("main.rs"))?.sync_all()?;
+        fs::write(
+            dir.path().join(".cargo").join("config.toml"),
+            "[build]\ntarget = \"thumbv7em-none-eabihf\"\n",
+        )?;
+
+        let config = toml::toml! {
+            [rust]
+            format = "via [$symbol($target )]($style)"
+        };
+        let actual = ModuleRenderer::new("rust")
+            .path(&child_dir_path)
+            .env(
+                "CARGO_HOME",
+                dir.path().join("cargo_home").to_string_lossy(),
+            )
+            .config(config.clone())
+            .collect();
+        let expected = Some(format!(
+            "via {}",
+            Color::Red.bold().paint("🦀 thumbv7em-none-eabihf ")
+        ));
+        assert_eq!(actual, expected);
+
+        // The environment takes precedence over config files
+        let actual = ModuleRenderer::new("rust")
+            .path(&child_dir_path)
+            .env("CARGO_BUILD_TARGET", "targets/riscv32-custom.json")
+            .config(config.clone())
+            .collect();
+        let expected = Some(format!(
+            "via {}",
+            Color::Red.bold().paint("🦀 riscv32-custom ")
+        ));
+        assert_eq!(actual, expected);
+
+        // The host isn't shown
+        let host_triple = guess_host_triple().unwrap_or_default();
+        let actual = ModuleRenderer::new("rust")
+            .path(&child_dir_path)
+            .env("CARGO_BUILD_TARGET", host_triple)
+            .config(config)
+            .collect();
+        let expected = Some(format!("via {}", Color::Red.bold().paint("🦀 ")));
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
     #[test]
     fn test_format_rustc_version_verbose() {
         macro_rules! test {
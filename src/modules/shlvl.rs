@@ -7,6 +7,9 @@ use std::borrow::Cow;
 use std::convert::TryInto;
 
 const SHLVL_ENV_VAR: &str = "SHLVL";
+// The terminal value with the SHLVL of the first shell in the terminal
+#[cfg(unix)]
+const BASELINE_KEY: &str = "shlvl_baseline";
 
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let shlvl = context.get_env(SHLVL_ENV_VAR)?.parse::<i64>().ok()?;
@@ -14,9 +17,19 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("shlvl");
     let config: ShLvlConfig = ShLvlConfig::try_load(module.config);
 
+    if config.disabled {
+        return None;
+    }
+
+    let shlvl = if config.auto_baseline {
+        shlvl - get_baseline(context, shlvl).unwrap_or(0)
+    } else {
+        shlvl
+    };
+
     // As we default to disabled=true, we have to check here after loading our config module,
     // before it was only checking against whatever is in the config starship.toml
-    if config.disabled || shlvl < config.threshold {
+    if shlvl < config.threshold {
         return None;
     }
 
@@ -53,7 +66,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `shlvl`:\n{}", error);
+            context.report_module_error("shlvl", &error);
             return None;
         }
     });
@@ -61,6 +74,30 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Returns the SHLVL of the first shell that showed a prompt in the current terminal.
+///
+/// If the SHLVL is lower than the stored baseline, the terminal's session id must have been
+/// reused, so the baseline is recorded again.
+#[cfg(unix)]
+fn get_baseline(context: &Context, shlvl: i64) -> Option<i64> {
+    let baseline = context
+        .get_terminal_value(BASELINE_KEY)
+        .and_then(|baseline| baseline.parse::<i64>().ok())
+        .filter(|baseline| *baseline <= shlvl);
+    if baseline.is_some() {
+        return baseline;
+    }
+
+    context.set_terminal_value(BASELINE_KEY, &shlvl.to_string());
+    Some(shlvl)
+}
+
+#[cfg(not(unix))]
+fn get_baseline(_context: &Context, _shlvl: i64) -> Option<i64> {
+    log::debug!("shlvl: auto_baseline is not supported on this platform");
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use nu_ansi_term::{Color, Style};
@@ -219,4 +256,30 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    #[cfg(unix)]
+    fn auto_baseline() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let render = |shlvl: &str| {
+            ModuleRenderer::new("shlvl")
+                .config(toml::toml! {
+                    [shlvl]
+                    format = "$shlvl"
+                    threshold = 0
+                    auto_baseline = true
+                    disabled = false
+                })
+                .env("STARSHIP_CACHE", dir.path().to_string_lossy())
+                .env(SHLVL_ENV_VAR, shlvl)
+                .collect()
+        };
+
+        assert_eq!(render("3"), Some("0".to_string()));
+        assert_eq!(render("5"), Some("2".to_string()));
+        // A lower SHLVL than the baseline records a new one
+        assert_eq!(render("2"), Some("0".to_string()));
+        assert_eq!(render("3"), Some("1".to_string()));
+        dir.close()
+    }
 }
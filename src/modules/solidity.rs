@@ -46,7 +46,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module 'solidity'\n {}", error);
+            context.report_module_error("solidity", &error);
             return None;
         }
     });
@@ -0,0 +1,257 @@
+use std::path::Path;
+use std::time::Duration;
+
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::ssh_agent::SshAgentConfig;
+use crate::formatter::StringFormatter;
+
+/// Creates a module with the number of keys loaded in the SSH agent at `SSH_AUTH_SOCK`
+///
+/// The module is hidden when no agent can be reached. A forwarded agent is shown in
+/// `forwarded_style`.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("ssh_agent");
+    let config = SshAgentConfig::try_load(module.config);
+
+    if config.disabled {
+        return None;
+    }
+
+    let socket = context.get_env_os("SSH_AUTH_SOCK")?;
+    let keys = count_keys(Path::new(&socket), context.command_timeout())?.to_string();
+    let forwarded =
+        context.get_env("SSH_CONNECTION").is_some() && is_forwarded_socket(Path::new(&socket));
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" if forwarded => Some(Ok(config.forwarded_style)),
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "keys" => Some(Ok(keys.as_str())),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            context.report_module_error("ssh_agent", &error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Whether the socket is at a path like `/tmp/ssh-XXXXXXXXXX/agent.<pid>`, where sshd creates the
+/// sockets of forwarded agents
+fn is_forwarded_socket(socket: &Path) -> bool {
+    let file_name = socket.file_name().and_then(|name| name.to_str());
+    let dir_name = socket
+        .parent()
+        .and_then(Path::file_name)
+        .and_then(|name| name.to_str());
+    match (
+        dir_name,
+        file_name.and_then(|name| name.strip_prefix("agent.")),
+    ) {
+        (Some(dir_name), Some(pid)) => {
+            dir_name.starts_with("ssh-")
+                && !pid.is_empty()
+                && pid.bytes().all(|byte| byte.is_ascii_digit())
+        }
+        _ => false,
+    }
+}
+
+/// Message types of the SSH agent protocol
+const SSH_AGENTC_REQUEST_IDENTITIES: u8 = 11;
+const SSH_AGENT_IDENTITIES_ANSWER: u8 = 12;
+
+/// The largest answer that is read, like the limit of OpenSSH's `ssh-add`
+#[cfg(unix)]
+const MAX_ANSWER_LENGTH: u32 = 256 * 1024;
+
+/// Asks the agent for its keys, and returns how many there are
+#[cfg(unix)]
+fn count_keys(socket: &Path, timeout: Duration) -> Option<u32> {
+    use crate::trace::{self, TraceKind};
+    use std::io::{Read, Write};
+    use std::os::unix::net::UnixStream;
+    use std::time::Instant;
+
+    let start = Instant::now();
+    let stream = UnixStream::connect(socket);
+    trace::record(
+        TraceKind::Network,
+        || format!("unix {}", socket.to_string_lossy()),
+        start,
+        stream.is_ok(),
+    );
+    let mut stream = stream
+        .map_err(|error| log::debug!("Unable to connect to SSH agent {:?}: {}", socket, error))
+        .ok()?;
+    stream.set_read_timeout(Some(timeout)).ok()?;
+    stream.set_write_timeout(Some(timeout)).ok()?;
+
+    // Messages are prefixed with their length, and the request has no content besides its type
+    stream
+        .write_all(&[0, 0, 0, 1, SSH_AGENTC_REQUEST_IDENTITIES])
+        .ok()?;
+
+    let mut length = [0; 4];
+    stream.read_exact(&mut length).ok()?;
+    let length = u32::from_be_bytes(length);
+    // The answer holds the public keys, which are small, so anything larger is a broken agent
+    if !(1..=MAX_ANSWER_LENGTH).contains(&length) {
+        log::debug!("Invalid length of SSH agent answer: {}", length);
+        return None;
+    }
+    let mut answer = vec![0; length as usize];
+    stream.read_exact(&mut answer).ok()?;
+
+    match answer.as_slice() {
+        [SSH_AGENT_IDENTITIES_ANSWER, a, b, c, d, ..] => Some(u32::from_be_bytes([*a, *b, *c, *d])),
+        [message_type, ..] => {
+            log::debug!("Unexpected answer of SSH agent: {}", message_type);
+            None
+        }
+        [] => None,
+    }
+}
+
+/// The agent's named pipe on Windows isn't supported
+#[cfg(not(unix))]
+fn count_keys(_socket: &Path, _timeout: Duration) -> Option<u32> {
+    None
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
+    use std::io::{self, Read, Write};
+    use std::os::unix::net::UnixListener;
+    use std::path::PathBuf;
+    use std::thread;
+
+    /// Starts an agent that answers a single request with the given message
+    fn start_agent(dir: &Path, answer: Vec<u8>) -> io::Result<PathBuf> {
+        start_agent_at(dir.join("agent.sock"), answer)
+    }
+
+    fn start_agent_at(socket: PathBuf, answer: Vec<u8>) -> io::Result<PathBuf> {
+        let listener = UnixListener::bind(&socket)?;
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0; 5];
+            stream.read_exact(&mut request).unwrap();
+            assert_eq!(request[4], SSH_AGENTC_REQUEST_IDENTITIES);
+            stream.write_all(&answer).unwrap();
+        });
+        Ok(socket)
+    }
+
+    /// An answer listing `keys` keys, each made up of an empty key blob and comment
+    fn identities_answer(keys: u32) -> Vec<u8> {
+        let mut content = vec![SSH_AGENT_IDENTITIES_ANSWER];
+        content.extend(keys.to_be_bytes());
+        for _ in 0..keys {
+            content.extend([0; 8]);
+        }
+        let mut answer = (content.len() as u32).to_be_bytes().to_vec();
+        answer.extend(content);
+        answer
+    }
+
+    fn render(socket: &Path, ssh_connection: Option<&str>) -> Option<String> {
+        let mut renderer = ModuleRenderer::new("ssh_agent")
+            .env("SSH_AUTH_SOCK", socket.to_string_lossy())
+            .config(toml::toml! {
+                [ssh_agent]
+                disabled = false
+            });
+        if let Some(ssh_connection) = ssh_connection {
+            renderer = renderer.env("SSH_CONNECTION", ssh_connection);
+        }
+        renderer.collect()
+    }
+
+    #[test]
+    fn no_agent() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render(&dir.path().join("agent.sock"), None);
+        assert_eq!(actual, None);
+        dir.close()
+    }
+
+    #[test]
+    fn local_agent() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let socket = start_agent(dir.path(), identities_answer(2))?;
+        let actual = render(&socket, None);
+        let expected = Some(format!("{} ", Color::Green.bold().paint("🔑 2")));
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn forwarded_agent() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let socket_dir = dir.path().join("ssh-Xa1b2C3d4e");
+        std::fs::create_dir(&socket_dir)?;
+        let socket = start_agent_at(socket_dir.join("agent.4242"), identities_answer(0))?;
+        let actual = render(&socket, Some("192.0.2.1 51234 192.0.2.2 22"));
+        let expected = Some(format!("{} ", Color::Yellow.bold().paint("🔑 0")));
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn local_agent_in_ssh_session() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        // e.g. gpg-agent, which isn't forwarded even though the shell runs in an SSH session
+        let socket = start_agent_at(dir.path().join("S.gpg-agent.ssh"), identities_answer(1))?;
+        let actual = render(&socket, Some("192.0.2.1 51234 192.0.2.2 22"));
+        let expected = Some(format!("{} ", Color::Green.bold().paint("🔑 1")));
+        assert_eq!(actual, expected);
+        dir.close()
+    }
+
+    #[test]
+    fn forwarded_socket_paths() {
+        assert!(is_forwarded_socket(Path::new(
+            "/tmp/ssh-Xa1b2C3d4e/agent.4242"
+        )));
+        assert!(!is_forwarded_socket(Path::new(
+            "/tmp/ssh-Xa1b2C3d4e/agent."
+        )));
+        assert!(!is_forwarded_socket(Path::new(
+            "/tmp/ssh-Xa1b2C3d4e/agent.sock"
+        )));
+        assert!(!is_forwarded_socket(Path::new(
+            "/run/user/1000/gnupg/S.gpg-agent.ssh"
+        )));
+        assert!(!is_forwarded_socket(Path::new("agent.4242")));
+    }
+
+    #[test]
+    fn agent_failure() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        // SSH_AGENT_FAILURE, which has no content
+        let socket = start_agent(dir.path(), vec![0, 0, 0, 1, 5])?;
+        let actual = render(&socket, None);
+        assert_eq!(actual, None);
+        dir.close()
+    }
+}
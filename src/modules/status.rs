@@ -90,8 +90,8 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     module.set_segments(match parsed {
         Ok(segments) => segments,
-        Err(_error) => {
-            log::warn!("Error parsing format string in `status.format`");
+        Err(error) => {
+            context.report_module_error("status", &error);
             return None;
         }
     });
@@ -132,9 +132,21 @@ fn format_exit_code<'a>(
         false => None,
     };
 
+    // Entries in `status.map` override the symbol and style of a single exit code
+    let mapping = config
+        .map
+        .iter()
+        .find(|(code, _)| code.trim().parse::<ExitCode>() == Ok(exit_code_int))
+        .map(|(_, mapping)| mapping);
+    let mapped_symbol = mapping.and_then(|mapping| mapping.symbol);
+    let style = mapping
+        .and_then(|mapping| mapping.style)
+        .unwrap_or(config.style);
+
     StringFormatter::new(format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
+                "symbol" if mapped_symbol.is_some() => mapped_symbol,
                 "symbol" => match exit_code_int {
                     0 => Some(config.success_symbol),
                     126 if config.map_symbol => Some(config.not_executable_symbol),
@@ -153,7 +165,7 @@ fn format_exit_code<'a>(
                 _ => None,
             })
             .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
+                "style" => Some(Ok(style)),
                 _ => None,
             })
             .map(|variable| match variable {
@@ -227,6 +239,15 @@ fn status_signal_name(signal: SignalNumber) -> Option<&'static str> {
         20 => Some("TSTP"),   // 128 + 20
         21 => Some("TTIN"),   // 128 + 21
         22 => Some("TTOU"),   // 128 + 22
+        23 => Some("URG"),    // 128 + 23
+        24 => Some("XCPU"),   // 128 + 24
+        25 => Some("XFSZ"),   // 128 + 25
+        26 => Some("VTALRM"), // 128 + 26
+        27 => Some("PROF"),   // 128 + 27
+        28 => Some("WINCH"),  // 128 + 28
+        29 => Some("IO"),     // 128 + 29
+        30 => Some("PWR"),    // 128 + 30
+        31 => Some("SYS"),    // 128 + 31
         _ => None,
     }
 }
@@ -489,6 +510,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn mapped_exit_codes() {
+        let config = toml::toml! {
+            [status]
+            format = "[$symbol$signal_name]($style)"
+            symbol = "🔴"
+            map_symbol = true
+            disabled = false
+            [status.map.130]
+            symbol = "✋"
+            style = "dimmed"
+            [status.map.137]
+            symbol = "💀"
+        };
+
+        let actual = ModuleRenderer::new("status")
+            .config(config.clone())
+            .status(130)
+            .collect();
+        let expected = Some(Style::new().dimmed().paint("✋INT").to_string());
+        assert_eq!(expected, actual);
+
+        let actual = ModuleRenderer::new("status")
+            .config(config.clone())
+            .status(137)
+            .collect();
+        let expected = Some(Color::Red.bold().paint("💀KILL").to_string());
+        assert_eq!(expected, actual);
+
+        let actual = ModuleRenderer::new("status")
+            .config(config)
+            .status(139)
+            .collect();
+        let expected = Some(Color::Red.bold().paint("⚡SEGV").to_string());
+        assert_eq!(expected, actual);
+    }
+
     #[test]
     fn special_symbols_no_signals() {
         let exit_values = [1, 126, 127, 130, 131];
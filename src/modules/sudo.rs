@@ -1,9 +1,13 @@
 use std::env;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use super::{Context, Module, ModuleConfig};
 
 use crate::configs::sudo::SudoConfig;
-use crate::formatter::StringFormatter;
+use crate::formatter::{StringFormatter, VariableHolder};
+
+// The session value with the time of the last successful check, in seconds since the epoch
+const CHECKED_AT_KEY: &str = "sudo_checked_at";
 
 /// Creates a module with sudo credential cache status
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
@@ -18,13 +22,20 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
-    let is_sudo_cached = context.exec_cmd("sudo", &["-n", "true"]).is_some();
+    let formatter = StringFormatter::new(config.format);
+    let shows_time_remaining = formatter.as_ref().map_or(false, |formatter| {
+        formatter.get_variables().contains("time_remaining")
+    });
 
-    if !is_sudo_cached {
+    let time_remaining = if shows_time_remaining {
+        Some(get_time_remaining(context, &config)?)
+    } else if is_sudo_cached(context) {
+        None
+    } else {
         return None;
-    }
+    };
 
-    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+    let parsed = formatter.and_then(|formatter| {
         formatter
             .map_meta(|variable, _| match variable {
                 "symbol" => Some(config.symbol),
@@ -34,13 +45,19 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "style" => Some(Ok(config.style)),
                 _ => None,
             })
+            .map(|variable| match variable {
+                // Round up, so the credentials are still valid for at least the shown time
+                "time_remaining" => time_remaining
+                    .map(|remaining| Ok(format!("{}m", (remaining.as_secs() + 59) / 60))),
+                _ => None,
+            })
             .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `sudo`:\n{}", error);
+            context.report_module_error("sudo", &error);
             return None;
         }
     });
@@ -48,15 +65,62 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Checks for cached credentials with `sudo -Nnv`, which unlike `sudo -n true` doesn't refresh
+/// them. Older versions of sudo don't know `-N`, so they are checked with `sudo -n true`.
+fn is_sudo_cached(context: &Context) -> bool {
+    match context.exec_cmd_with_timeout("sudo", &["-Nnv"], context.command_timeout()) {
+        Some(Ok(_)) => true,
+        Some(Err(output))
+            if output.stderr.contains("invalid option")
+                || output.stderr.contains("illegal option") =>
+        {
+            context.exec_cmd("sudo", &["-n", "true"]).is_some()
+        }
+        _ => false,
+    }
+}
+
+/// Estimates how long the cached sudo credentials stay valid.
+///
+/// The credentials are checked on every prompt, so removing them with `sudo -k` is noticed. The
+/// estimate counts down from the first successful check. Running sudo again refreshes the
+/// credentials without resetting the estimate, so it is a lower bound of the time remaining.
+fn get_time_remaining(context: &Context, config: &SudoConfig) -> Option<Duration> {
+    if !is_sudo_cached(context) {
+        context.remove_session_value(CHECKED_AT_KEY);
+        return None;
+    }
+
+    let timeout = Duration::from_secs(config.timestamp_timeout * 60);
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?;
+
+    let elapsed = context
+        .get_session_value(CHECKED_AT_KEY)
+        .and_then(|timestamp| timestamp.parse().ok())
+        .map(|timestamp| now.saturating_sub(Duration::from_secs(timestamp)))
+        .filter(|elapsed| *elapsed < timeout);
+    if let Some(elapsed) = elapsed {
+        return Some(timeout - elapsed);
+    }
+
+    context.set_session_value(CHECKED_AT_KEY, &now.as_secs().to_string());
+    Some(timeout)
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{test::ModuleRenderer, utils::CommandOutput};
+    use crate::test::{output, ModuleRenderer};
+    use crate::utils::CommandOutput;
     use nu_ansi_term::Color;
+    use std::fs;
+    use std::io;
+    use std::path::Path;
+    use std::time::{SystemTime, UNIX_EPOCH};
 
     #[test]
     fn test_sudo_not_cached() {
         let actual = ModuleRenderer::new("sudo")
-            .cmd("sudo -n true", None)
+            .cmd("sudo -Nnv", None)
             .config(toml::toml! {
                 [sudo]
                 disabled = false
@@ -72,7 +136,7 @@ mod tests {
     fn test_sudo_cached() {
         let actual = ModuleRenderer::new("sudo")
             .cmd(
-                "sudo -n true",
+                "sudo -Nnv",
                 Some(CommandOutput {
                     stdout: String::new(),
                     stderr: String::new(),
@@ -89,12 +153,122 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn test_sudo_without_no_update_option() {
+        let actual = ModuleRenderer::new("sudo")
+            .cmd_failure("sudo -Nnv", "sudo: invalid option -- 'N'")
+            .cmd("sudo -n true", output(""))
+            .config(toml::toml! {
+                [sudo]
+                disabled = false
+                allow_windows = true
+            })
+            .collect();
+        let expected = Some(format!("{}", Color::Blue.bold().paint("as 🧙 ")));
+
+        assert_eq!(expected, actual);
+    }
+
+    fn timestamp_secs_ago(secs: u64) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - secs
+    }
+
+    fn render_time_remaining(cache_dir: &Path, cached: bool) -> Option<String> {
+        ModuleRenderer::new("sudo")
+            .cmd(
+                "sudo -Nnv",
+                cached.then(|| CommandOutput {
+                    stdout: String::new(),
+                    stderr: String::new(),
+                }),
+            )
+            .env("STARSHIP_CACHE", cache_dir.to_string_lossy())
+            .env("STARSHIP_SESSION_KEY", "session")
+            .config(toml::toml! {
+                [sudo]
+                disabled = false
+                allow_windows = true
+                format = "$symbol$time_remaining"
+                symbol = "sudo "
+            })
+            .collect()
+    }
+
+    fn write_checked_at(cache_dir: &Path, session: &str, secs_ago: u64) -> io::Result<()> {
+        fs::create_dir_all(cache_dir.join("sessions"))?;
+        fs::write(
+            cache_dir.join("sessions").join(format!("{session}.json")),
+            format!(
+                r#"{{"sudo_checked_at":"{}"}}"#,
+                timestamp_secs_ago(secs_ago)
+            ),
+        )
+    }
+
+    #[test]
+    fn test_time_remaining_after_check() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        let actual = render_time_remaining(dir.path(), true);
+        assert_eq!(Some("sudo 15m".to_string()), actual);
+        let state = fs::read_to_string(dir.path().join("sessions").join("session.json"))?;
+        assert!(state.contains("sudo_checked_at"));
+        dir.close()
+    }
+
+    #[test]
+    fn test_time_remaining_from_cache() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_checked_at(dir.path(), "session", 4 * 60)?;
+
+        let actual = render_time_remaining(dir.path(), true);
+        assert_eq!(Some("sudo 11m".to_string()), actual);
+        dir.close()
+    }
+
+    #[test]
+    fn test_time_remaining_after_sudo_k() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_checked_at(dir.path(), "session", 4 * 60)?;
+
+        let actual = render_time_remaining(dir.path(), false);
+        assert_eq!(None, actual);
+        let state = fs::read_to_string(dir.path().join("sessions").join("session.json"))?;
+        assert!(!state.contains("sudo_checked_at"));
+        dir.close()
+    }
+
+    #[test]
+    fn test_time_remaining_estimate_ran_out() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_checked_at(dir.path(), "session", 16 * 60)?;
+
+        // The credentials were refreshed by running sudo, so the estimate starts over
+        let actual = render_time_remaining(dir.path(), true);
+        assert_eq!(Some("sudo 15m".to_string()), actual);
+        dir.close()
+    }
+
+    #[test]
+    fn test_time_remaining_other_session() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_checked_at(dir.path(), "other", 4 * 60)?;
+
+        let actual = render_time_remaining(dir.path(), true);
+        assert_eq!(Some("sudo 15m".to_string()), actual);
+        dir.close()
+    }
+
     #[test]
     #[cfg(windows)]
     fn test_allow_windows_disabled_blocks_windows() {
         let actual = ModuleRenderer::new("sudo")
             .cmd(
-                "sudo -n true",
+                "sudo -Nnv",
                 Some(CommandOutput {
                     stdout: String::new(),
                     stderr: String::new(),
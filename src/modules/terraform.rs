@@ -8,7 +8,22 @@ use crate::formatter::VersionFormatter;
 use std::io;
 use std::path::PathBuf;
 
-/// Creates a module with the current Terraform version and workspace
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Tool {
+    Terraform,
+    OpenTofu,
+}
+
+impl Tool {
+    fn binary(self) -> &'static str {
+        match self {
+            Self::Terraform => "terraform",
+            Self::OpenTofu => "tofu",
+        }
+    }
+}
+
+/// Creates a module with the current Terraform or OpenTofu version and workspace
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("terraform");
     let config: TerraformConfig = TerraformConfig::try_load(module.config);
@@ -24,10 +39,15 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
+    let tool = get_tool(context);
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|variable, _| match variable {
-                "symbol" => Some(config.symbol),
+                "symbol" => Some(match tool {
+                    Tool::Terraform => config.symbol,
+                    Tool::OpenTofu => config.tofu_symbol,
+                }),
                 _ => None,
             })
             .map_style(|variable| match variable {
@@ -37,7 +57,10 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             .map(|variable| match variable {
                 "version" => {
                     let terraform_version = parse_terraform_version(
-                        context.exec_cmd("terraform", &["version"])?.stdout.as_str(),
+                        context
+                            .exec_cmd(tool.binary(), &["version"])?
+                            .stdout
+                            .as_str(),
                     )?;
                     VersionFormatter::format_module_version(
                         module.get_name(),
@@ -47,6 +70,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 }
                 .map(Ok),
                 "workspace" => get_terraform_workspace(context).map(Ok),
+                "tool" => Some(Ok(tool.binary().to_string())),
                 _ => None,
             })
             .parse(None, Some(context))
@@ -55,7 +79,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `terraform`:\n{}", error);
+            context.report_module_error("terraform", &error);
             return None;
         }
     });
@@ -63,6 +87,48 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Determines whether the project is managed with Terraform or with OpenTofu.
+///
+/// Both use the same files and `TF_` variables, but they download providers from different
+/// registries, which shows in the data directory and the lock file.
+fn get_tool(context: &Context) -> Tool {
+    if context.has_env_prefix("TOFU_") {
+        return Tool::OpenTofu;
+    }
+
+    let datadir = get_terraform_datadir(context);
+    let providers = datadir.join("providers");
+    if providers.join("registry.opentofu.org").is_dir() {
+        return Tool::OpenTofu;
+    }
+    if providers.join("registry.terraform.io").is_dir() {
+        return Tool::Terraform;
+    }
+    if let Ok(lock_file) = utils::read_file(context.current_dir.join(".terraform.lock.hcl")) {
+        if lock_file.contains("\"registry.opentofu.org/") {
+            return Tool::OpenTofu;
+        }
+        if lock_file.contains("\"registry.terraform.io/") {
+            return Tool::Terraform;
+        }
+    }
+
+    // Without any trace of either, use the one that is installed
+    let is_installed =
+        |binary| which::which_in(binary, context.get_env_os("PATH"), &context.current_dir).is_ok();
+    if !is_installed("terraform") && is_installed("tofu") {
+        return Tool::OpenTofu;
+    }
+    Tool::Terraform
+}
+
+fn get_terraform_datadir(context: &Context) -> PathBuf {
+    match context.get_env("TF_DATA_DIR") {
+        Some(s) => PathBuf::from(s),
+        None => context.current_dir.join(".terraform"),
+    }
+}
+
 // Determines the currently selected workspace (see https://github.com/hashicorp/terraform/blob/master/command/meta.go for the original implementation)
 fn get_terraform_workspace(context: &Context) -> Option<String> {
     // Workspace can be explicitly overwritten by an env var
@@ -72,10 +138,7 @@ fn get_terraform_workspace(context: &Context) -> Option<String> {
     }
 
     // Data directory containing current workspace can be overwritten by an env var
-    let datadir = match context.get_env("TF_DATA_DIR") {
-        Some(s) => PathBuf::from(s),
-        None => context.current_dir.join(".terraform"),
-    };
+    let datadir = get_terraform_datadir(context);
     match utils::read_file(datadir.join("environment")) {
         Err(ref e) if e.kind() == io::ErrorKind::NotFound => Some("default".to_string()),
         Ok(s) => Some(s),
@@ -87,10 +150,12 @@ fn parse_terraform_version(version: &str) -> Option<String> {
     // `terraform version` output looks like this
     // Terraform v0.12.14
     // With potential extra output if it detects you are not running the latest version
+    // `tofu version` prints `OpenTofu v1.6.0` instead
     let version = version
         .lines()
         .next()?
         .trim_start_matches("Terraform ")
+        .trim_start_matches("OpenTofu ")
         .trim()
         .trim_start_matches('v');
 
@@ -101,6 +166,7 @@ fn parse_terraform_version(version: &str) -> Option<String> {
 mod tests {
     use super::*;
     use crate::test::ModuleRenderer;
+    use crate::utils::CommandOutput;
     use nu_ansi_term::Color;
     use std::fs::{self, File};
     use std::io::{self, Write};
@@ -140,6 +206,12 @@ is 0.12.14. You can update by downloading from www.terraform.io/downloads.html
         assert_eq!(parse_terraform_version(input), Some("0.12.13".to_string()));
     }
 
+    #[test]
+    fn test_parse_tofu_version() {
+        let input = "OpenTofu v1.6.0\non linux_amd64";
+        assert_eq!(parse_terraform_version(input), Some("1.6.0".to_string()));
+    }
+
     #[test]
     fn folder_with_dotterraform_with_version_no_environment() -> io::Result<()> {
         let dir = tempfile::tempdir()?;
@@ -289,4 +361,69 @@ is 0.12.14. You can update by downloading from www.terraform.io/downloads.html
         assert_eq!(expected, actual);
         dir.close()
     }
+
+    fn render_tool(dir: &std::path::Path, env: Option<(&str, &str)>) -> Option<String> {
+        let renderer = ModuleRenderer::new("terraform")
+            .path(dir)
+            .cmd(
+                "tofu version",
+                Some(CommandOutput {
+                    stdout: String::from("OpenTofu v1.6.0\non linux_amd64\n"),
+                    stderr: String::default(),
+                }),
+            )
+            .config(toml::toml! {
+                [terraform]
+                format = "$symbol$tool $version"
+            });
+        match env {
+            Some((key, value)) => renderer.env(key, value).collect(),
+            None => renderer.collect(),
+        }
+    }
+
+    #[test]
+    fn tofu_detected_from_providers() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let providers = dir.path().join(".terraform").join("providers");
+        fs::create_dir_all(providers.join("registry.opentofu.org"))?;
+
+        let expected = Some("🟨 tofu v1.6.0".to_string());
+        assert_eq!(expected, render_tool(dir.path(), None));
+
+        fs::remove_dir(providers.join("registry.opentofu.org"))?;
+        fs::create_dir(providers.join("registry.terraform.io"))?;
+        let expected = Some("💠 terraform v0.12.14".to_string());
+        assert_eq!(expected, render_tool(dir.path(), None));
+        dir.close()
+    }
+
+    #[test]
+    fn tofu_detected_from_lock_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("main.tf"))?.sync_all()?;
+        let mut file = File::create(dir.path().join(".terraform.lock.hcl"))?;
+        file.write_all(
+            b"provider \"registry.opentofu.org/hashicorp/aws\" {\n  version = \"5.31.0\"\n}\n",
+        )?;
+        file.sync_all()?;
+
+        let expected = Some("🟨 tofu v1.6.0".to_string());
+        assert_eq!(expected, render_tool(dir.path(), None));
+        dir.close()
+    }
+
+    #[test]
+    fn tofu_detected_from_env() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("main.tf"))?.sync_all()?;
+
+        let expected = Some("💠 terraform v0.12.14".to_string());
+        assert_eq!(expected, render_tool(dir.path(), None));
+
+        let expected = Some("🟨 tofu v1.6.0".to_string());
+        let actual = render_tool(dir.path(), Some(("TOFU_CLI_CONFIG_FILE", "tofurc")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
 }
@@ -1,8 +1,13 @@
 use chrono::{DateTime, FixedOffset, Local, NaiveTime, Utc};
 
+#[cfg(not(target_os = "windows"))]
+use super::utils::timezone;
 use super::{Context, Module, ModuleConfig};
 use crate::configs::time::TimeConfig;
+#[cfg(not(target_os = "windows"))]
+use crate::formatter::string_formatter::shell_prompt_escape;
 use crate::formatter::StringFormatter;
+use crate::segment::Segment;
 
 /// Outputs the current time
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
@@ -51,7 +56,13 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "time" => Some(Ok(&formatted_time_string)),
+                "time" => Some(Ok(formatted_time_string.clone())),
+                _ => None,
+            })
+            .map_variables_to_segments(|variable| match variable {
+                "time_zones" => {
+                    render_time_zones(context, &config, time_format, Utc::now()).map(Ok)
+                }
                 _ => None,
             })
             .parse(None, Some(context))
@@ -60,7 +71,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `time`: \n{}", error);
+            context.report_module_error("time", &error);
             return None;
         }
     });
@@ -68,6 +79,73 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Renders the time in each of the configured time zones, skipping the zones that can't be found.
+/// Returns `None` if none of them could be rendered.
+#[cfg(not(target_os = "windows"))]
+fn render_time_zones(
+    context: &Context,
+    config: &TimeConfig,
+    time_format: &str,
+    utc_time: DateTime<Utc>,
+) -> Option<Vec<Segment>> {
+    let time_zones = config
+        .time_zones
+        .iter()
+        .filter_map(|&name| {
+            let local_time_type =
+                timezone::TimeZone::load(context, name)?.local_time_type(utc_time);
+            let offset = FixedOffset::east_opt(local_time_type.utc_offset)?;
+            let time = format_time_fixed_offset(time_format, utc_time.with_timezone(&offset));
+            let label = config
+                .time_zone_labels
+                .get(name)
+                .copied()
+                .unwrap_or(&local_time_type.abbreviation);
+
+            let parsed = StringFormatter::new(config.time_zone_format).and_then(|formatter| {
+                formatter
+                    .map(|variable| match variable {
+                        "label" => Some(Ok(label)),
+                        "time" => Some(Ok(time.as_str())),
+                        "zone" => Some(Ok(name)),
+                        _ => None,
+                    })
+                    .parse(None, Some(context))
+            });
+            match parsed {
+                Ok(segments) => Some(segments),
+                Err(error) => {
+                    context.report_module_error(
+                        "time",
+                        &format!("Error in `time.time_zone_format`: {error}"),
+                    );
+                    None
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let separator = Segment::from_text(
+        None,
+        shell_prompt_escape(config.time_zone_separator, context.shell),
+    );
+    (!time_zones.is_empty()).then(|| time_zones.join(separator.as_slice()))
+}
+
+/// Windows has no tz database to read the time zones from
+#[cfg(target_os = "windows")]
+fn render_time_zones(
+    _context: &Context,
+    config: &TimeConfig,
+    _time_format: &str,
+    _utc_time: DateTime<Utc>,
+) -> Option<Vec<Segment>> {
+    if !config.time_zones.is_empty() {
+        log::debug!("`time.time_zones` is not supported on Windows");
+    }
+    None
+}
+
 fn create_offset_time_string(
     utc_time: DateTime<Utc>,
     utc_time_offset_str: &str,
@@ -81,7 +159,7 @@ fn create_offset_time_string(
     if utc_time_offset_in_hours < 24_f32 && utc_time_offset_in_hours > -24_f32 {
         let utc_offset_in_seconds: i32 = (utc_time_offset_in_hours * 3600_f32) as i32;
         let Some(timezone_offset) = FixedOffset::east_opt(utc_offset_in_seconds) else {
-            return Err("Invalid offset")
+            return Err("Invalid offset");
         };
         log::trace!("Target timezone offset is {}", timezone_offset);
 
@@ -157,6 +235,7 @@ mod tests {
     use super::*;
     use crate::test::ModuleRenderer;
     use chrono::offset::TimeZone;
+    use nu_ansi_term::Style;
 
     const FMT_12: &str = "%r";
     const FMT_24: &str = "%T";
@@ -507,4 +586,102 @@ mod tests {
         assert!(actual.starts_with(&col_prefix));
         assert!(actual.ends_with(&col_suffix));
     }
+
+    /// Writes a time zone file without transitions and with a single local time type
+    #[cfg(not(target_os = "windows"))]
+    fn write_zone(dir: &std::path::Path, name: &str, utc_offset: i32, abbreviation: &str) {
+        let mut data = b"TZif".to_vec();
+        data.extend([0; 16]);
+        for count in [0, 0, 0, 0, 1, abbreviation.len() as u32 + 1] {
+            data.extend(count.to_be_bytes());
+        }
+        data.extend(utc_offset.to_be_bytes());
+        data.extend([0, 0]);
+        data.extend(abbreviation.bytes());
+        data.push(0);
+
+        let path = dir.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, data).unwrap();
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn config_time_zones() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_zone(dir.path(), "Asia/Kolkata", 19800, "IST");
+        write_zone(dir.path(), "America/Sao_Paulo", -3 * 3600, "-03");
+
+        let actual = ModuleRenderer::new("time")
+            .config(toml::toml! {
+                [time]
+                disabled = false
+                format = "$time_zones"
+                time_format = "%z"
+                time_zones = ["Asia/Kolkata", "Europe/Nowhere", "America/Sao_Paulo"]
+                time_zone_separator = " | "
+                [time.time_zone_labels]
+                "America/Sao_Paulo" = "SP"
+            })
+            .env("TZDIR", dir.path().to_string_lossy())
+            .collect();
+
+        let expected = Some("IST +0530 | SP -0300".to_string());
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn config_time_zone_styles() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_zone(dir.path(), "Asia/Kolkata", 19800, "IST");
+        write_zone(dir.path(), "America/Sao_Paulo", -3 * 3600, "-03");
+
+        let actual = ModuleRenderer::new("time")
+            .config(toml::toml! {
+                [time]
+                disabled = false
+                format = "[$time_zones](dimmed)"
+                time_format = "%z"
+                time_zones = ["Asia/Kolkata", "America/Sao_Paulo"]
+                time_zone_format = "[$label](bold) $time"
+                time_zone_separator = ", "
+            })
+            .env("TZDIR", dir.path().to_string_lossy())
+            .collect();
+
+        // Each zone keeps its own styles and the rest uses the style around `$time_zones`
+        let expected = Some(format!(
+            "{}{}{}{}",
+            Style::new().bold().paint("IST"),
+            Style::new().dimmed().paint(" +0530, "),
+            Style::new().bold().paint("-03"),
+            Style::new().dimmed().paint(" -0300"),
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn config_time_zone_invalid_name() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_zone(dir.path(), "UTC", 0, "UTC");
+
+        let actual = ModuleRenderer::new("time")
+            .config(toml::toml! {
+                [time]
+                disabled = false
+                format = "zones(: $time_zones)"
+                time_zones = ["../UTC"]
+            })
+            .env("TZDIR", dir.path().join("zoneinfo").to_string_lossy())
+            .collect();
+
+        // Without any zone to show, the optional group is hidden
+        let expected = Some("zones".to_string());
+        assert_eq!(expected, actual);
+        dir.close()
+    }
 }
@@ -0,0 +1,240 @@
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::tool_versions::ToolVersionsConfig;
+use crate::formatter::StringFormatter;
+
+// Later files override the versions of earlier ones
+const MISE_FILES: [&str; 4] = [
+    "mise.toml",
+    ".mise.toml",
+    "mise.local.toml",
+    ".mise.local.toml",
+];
+
+/// Creates a module with the tool versions pinned by mise or asdf in the current directory
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("tool_versions");
+    let config = ToolVersionsConfig::try_load(module.config);
+
+    let is_tool_versions_project = context
+        .try_begin_scan()?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_tool_versions_project {
+        return None;
+    }
+
+    let mut tools = context
+        .read_file_from_pwd(".tool-versions")
+        .map(|content| parse_tool_versions(&content))
+        .unwrap_or_default();
+    let mut has_mise_file = false;
+    for file in MISE_FILES {
+        if let Some(content) = context.read_file_from_pwd(file) {
+            has_mise_file = true;
+            for (name, version) in parse_mise_toml(&content) {
+                match tools.iter_mut().find(|(tool, _)| *tool == name) {
+                    Some(tool) => tool.1 = version,
+                    None => tools.push((name, version)),
+                }
+            }
+        }
+    }
+    // mise reads `.tool-versions` as well, so only a shell that mise hasn't been activated in
+    // is assumed to use asdf
+    let manager = if has_mise_file || context.get_env("MISE_SHELL").is_some() {
+        "mise"
+    } else {
+        "asdf"
+    };
+
+    let versions = tools
+        .iter()
+        .filter(|(name, _)| config.tools.is_empty() || config.tools.contains(&name.as_str()))
+        .map(|(name, version)| format!("{name}@{version}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if versions.is_empty() {
+        return None;
+    }
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "manager" => Some(Ok(manager)),
+                "versions" => Some(Ok(versions.as_str())),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            context.report_module_error("tool_versions", &error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Parses an asdf `.tool-versions` file, keeping the first version of each tool
+fn parse_tool_versions(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.split('#').next()?;
+            let mut words = line.split_whitespace();
+            Some((words.next()?.to_string(), words.next()?.to_string()))
+        })
+        .collect()
+}
+
+/// Parses the `[tools]` table of a mise config, keeping the first version of each tool
+fn parse_mise_toml(content: &str) -> Vec<(String, String)> {
+    let Ok(config) = content.parse::<toml::Table>() else {
+        log::debug!("Unable to parse mise config");
+        return Vec::new();
+    };
+    let Some(tools) = config.get("tools").and_then(toml::Value::as_table) else {
+        return Vec::new();
+    };
+
+    tools
+        .iter()
+        .filter_map(|(name, value)| {
+            // A version is a string, a list of versions, or a table with options
+            let version = match value {
+                toml::Value::Array(versions) => versions.first()?,
+                toml::Value::Table(options) => options.get("version")?,
+                version => version,
+            };
+            let version = match version {
+                toml::Value::Table(options) => options.get("version")?.as_str()?,
+                version => version.as_str()?,
+            };
+            Some((name.clone(), version.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test::ModuleRenderer;
+    use nu_ansi_term::Color;
+    use std::fs;
+    use std::io;
+
+    #[test]
+    fn folder_without_tool_versions() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = ModuleRenderer::new("tool_versions")
+            .path(dir.path())
+            .collect();
+        assert_eq!(None, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn asdf_tool_versions() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join(".tool-versions"),
+            "# runtimes\nnodejs 22.1.0 20.12.2\npython 3.12.3\n",
+        )?;
+
+        let actual = ModuleRenderer::new("tool_versions")
+            .path(dir.path())
+            .collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Purple
+                .bold()
+                .paint("🛠️ asdf nodejs@22.1.0 python@3.12.3")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn mise_config_overrides_tool_versions() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join(".tool-versions"),
+            "python 3.11\nterraform 1.8.0\n",
+        )?;
+        fs::write(
+            dir.path().join(".mise.toml"),
+            r#"
+[env]
+NODE_ENV = "development"
+
+[tools]
+node = "22"
+python = ["3.12", "3.11"]
+go = { version = "1.22", os = ["linux"] }
+"#,
+        )?;
+        fs::write(
+            dir.path().join("mise.local.toml"),
+            "[tools]\nnode = \"lts\"\n",
+        )?;
+
+        let actual = ModuleRenderer::new("tool_versions")
+            .path(dir.path())
+            .collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Purple
+                .bold()
+                .paint("🛠️ mise python@3.12 terraform@1.8.0 node@lts go@1.22")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn only_allowed_tools() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join(".tool-versions"),
+            "nodejs 22.1.0\nshellcheck 0.10.0\n",
+        )?;
+
+        let actual = ModuleRenderer::new("tool_versions")
+            .path(dir.path())
+            .env("MISE_SHELL", "zsh")
+            .config(toml::toml! {
+                [tool_versions]
+                tools = ["nodejs", "python"]
+            })
+            .collect();
+        let expected = Some(format!(
+            "via {} ",
+            Color::Purple.bold().paint("🛠️ mise nodejs@22.1.0")
+        ));
+        assert_eq!(expected, actual);
+
+        let actual = ModuleRenderer::new("tool_versions")
+            .path(dir.path())
+            .config(toml::toml! {
+                [tool_versions]
+                tools = ["python"]
+            })
+            .collect();
+        assert_eq!(None, actual);
+        dir.close()
+    }
+}
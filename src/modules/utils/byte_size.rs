@@ -0,0 +1,20 @@
+use systemstat::data::ByteSize;
+
+// Display a `ByteSize` in a human readable format.
+pub fn display_bs(bs: ByteSize) -> String {
+    let mut display_bytes = bs.to_string_as(true);
+    let mut keep = true;
+    // Skip decimals and the space before the byte unit.
+    display_bytes.retain(|c| match c {
+        ' ' => {
+            keep = true;
+            false
+        }
+        '.' => {
+            keep = false;
+            false
+        }
+        _ => keep,
+    });
+    display_bytes
+}
@@ -35,6 +35,79 @@ pub fn is_write_allowed(folder_path: &Path) -> Result<bool, String> {
     }
 }
 
+/// Checks if `folder_path` is on a network filesystem such as NFS, SMB or SSHFS.
+///
+/// Note that all FUSE filesystems are considered remote on Linux, since their
+/// type can't be told apart without reading the mount table.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn is_network_fs(folder_path: &Path) -> Result<bool, String> {
+    // Magic numbers of network filesystems, see `man 2 statfs`
+    const NETWORK_FS_MAGIC: &[u32] = &[
+        0x6969,      // NFS
+        0x517b,      // SMB
+        0xff53_4d42, // CIFS
+        0xfe53_4d42, // SMB2
+        0x5346_414f, // AFS
+        0x7375_7245, // CODA
+        0x564c,      // NCP
+        0x0102_1997, // 9P
+        0x00c3_6400, // CEPH
+        0x6573_5546, // FUSE
+    ];
+
+    let stat = nix::sys::statfs::statfs(folder_path)
+        .map_err(|e| format!("Unable to statfs() directory: {e:?}"))?;
+    Ok(NETWORK_FS_MAGIC.contains(&(stat.filesystem_type().0 as u32)))
+}
+
+/// Checks if `folder_path` is on a network filesystem such as NFS, SMB or SSHFS.
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "openbsd"
+))]
+pub fn is_network_fs(folder_path: &Path) -> Result<bool, String> {
+    const NETWORK_FS_TYPES: &[&str] = &[
+        "nfs", "smbfs", "cifs", "afpfs", "webdav", "afs", "macfuse", "osxfuse", "fusefs",
+    ];
+
+    let stat = nix::sys::statfs::statfs(folder_path)
+        .map_err(|e| format!("Unable to statfs() directory: {e:?}"))?;
+    let fs_type = stat.filesystem_type_name();
+    Ok(NETWORK_FS_TYPES
+        .iter()
+        .any(|network_fs| fs_type.starts_with(network_fs)))
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "dragonfly",
+    target_os = "openbsd"
+)))]
+pub fn is_network_fs(_folder_path: &Path) -> Result<bool, String> {
+    Ok(false)
+}
+
+/// Returns the total and available space in bytes of the filesystem containing `folder_path`.
+///
+/// The available space is what is available to unprivileged users.
+#[allow(clippy::useless_conversion)] // On some platforms it is not u64
+pub fn disk_space(folder_path: &Path) -> Result<(u64, u64), String> {
+    let stat = nix::sys::statvfs::statvfs(folder_path)
+        .map_err(|e| format!("Unable to statvfs() directory: {e:?}"))?;
+    let fragment_size = u64::from(stat.fragment_size());
+    Ok((
+        u64::from(stat.blocks()) * fragment_size,
+        u64::from(stat.blocks_available()) * fragment_size,
+    ))
+}
+
 #[cfg(all(unix, not(any(target_os = "macos", target_os = "ios"))))]
 fn get_supplementary_groups() -> Vec<u32> {
     match nix::unistd::getgroups() {
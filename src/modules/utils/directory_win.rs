@@ -11,12 +11,48 @@ use windows::{
             TOKEN_IMPERSONATE, TOKEN_QUERY, TOKEN_READ_CONTROL,
         },
         Storage::FileSystem::{
-            FILE_ALL_ACCESS, FILE_GENERIC_EXECUTE, FILE_GENERIC_READ, FILE_GENERIC_WRITE,
+            GetDiskFreeSpaceExW, FILE_ALL_ACCESS, FILE_GENERIC_EXECUTE, FILE_GENERIC_READ,
+            FILE_GENERIC_WRITE,
         },
         System::Threading::{GetCurrentProcess, OpenProcessToken},
         UI::Shell::PathIsNetworkPathW,
     },
 };
+
+/// Checks if `folder_path` is a network path, i.e. a UNC path or a mapped network drive
+pub fn is_network_fs(folder_path: &Path) -> std::result::Result<bool, String> {
+    let wpath_vec: Vec<u16> = folder_path.as_os_str().encode_wide().chain([0]).collect();
+    let wpath = PCWSTR(wpath_vec.as_ptr());
+
+    Ok(unsafe { PathIsNetworkPathW(wpath) }.as_bool())
+}
+
+/// Returns the total and available space in bytes of the volume containing `folder_path`.
+///
+/// The available space is what is available to the current user, taking quotas into account.
+pub fn disk_space(folder_path: &Path) -> std::result::Result<(u64, u64), String> {
+    let wpath_vec: Vec<u16> = folder_path.as_os_str().encode_wide().chain([0]).collect();
+    let wpath = PCWSTR(wpath_vec.as_ptr());
+
+    let mut available = 0;
+    let mut total = 0;
+    let rc = unsafe {
+        GetDiskFreeSpaceExW(
+            wpath,
+            Some(&mut available as *mut u64),
+            Some(&mut total as *mut u64),
+            None,
+        )
+    };
+    if !rc.as_bool() {
+        return Err(format!(
+            "GetDiskFreeSpaceExW failed to retrieve disk space: {:?}",
+            std::io::Error::last_os_error()
+        ));
+    }
+    Ok((total, available))
+}
+
 /// Checks if the current user has write access right to the `folder_path`
 ///
 /// First, the function extracts DACL from the given directory and then calls `AccessCheck` against
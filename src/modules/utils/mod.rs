@@ -1,3 +1,5 @@
+pub mod byte_size;
+
 pub mod directory;
 
 #[cfg(target_os = "windows")]
@@ -8,4 +10,7 @@ pub mod directory_nix;
 
 pub mod path;
 
+#[cfg(not(target_os = "windows"))]
+pub mod timezone;
+
 pub mod truncate;
@@ -0,0 +1,576 @@
+use std::path::{Component, Path, PathBuf};
+use std::time::Instant;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc, Weekday};
+
+use crate::context::Context;
+use crate::trace::{self, TraceKind};
+
+// Where the tz database is commonly installed, if `TZDIR` isn't set
+const ZONEINFO_DIRS: [&str; 3] = [
+    "/usr/share/zoneinfo",
+    "/usr/lib/zoneinfo",
+    "/usr/share/lib/zoneinfo",
+];
+
+/// A local time type of a time zone, e.g. CEST
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalTimeType {
+    /// Seconds east of UTC
+    pub utc_offset: i32,
+    pub abbreviation: String,
+}
+
+/// A time zone from the tz database, like `Europe/Berlin`
+#[derive(Debug, PartialEq, Eq)]
+pub struct TimeZone {
+    transitions: Vec<(i64, usize)>,
+    types: Vec<LocalTimeType>,
+    /// The rule for the times after the last transition
+    rule: Option<PosixRule>,
+}
+
+impl TimeZone {
+    /// Loads the time zone with the given name from the tz database of the system
+    pub fn load(context: &Context, name: &str) -> Option<Self> {
+        let name = Path::new(name);
+        if !name
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            log::warn!("Invalid time zone name {:?}", name);
+            return None;
+        }
+
+        let dirs = match context.get_env("TZDIR") {
+            Some(dir) => vec![PathBuf::from(dir)],
+            None => ZONEINFO_DIRS.iter().map(PathBuf::from).collect(),
+        };
+        let data = dirs.iter().find_map(|dir| {
+            let path = dir.join(name);
+            let start = Instant::now();
+            let data = std::fs::read(&path);
+            trace::record(
+                TraceKind::FileRead,
+                || path.to_string_lossy().into_owned(),
+                start,
+                data.is_ok(),
+            );
+            data.ok()
+        });
+        let Some(data) = data else {
+            log::warn!("Time zone {:?} was not found in {:?}", name, dirs);
+            return None;
+        };
+
+        let zone = Self::from_tzif(&data);
+        if zone.is_none() {
+            log::warn!("Unable to parse the time zone file of {:?}", name);
+        }
+        zone
+    }
+
+    /// Parses a time zone file in the TZif format, see RFC 8536
+    pub fn from_tzif(data: &[u8]) -> Option<Self> {
+        let header = TzifHeader::parse(data)?;
+        if header.version == 0 {
+            return header.parse_block(data.get(44..)?, 4).map(|(zone, _)| zone);
+        }
+
+        // Newer versions repeat the data with 64-bit times, followed by a rule for the future
+        let data = data.get(44 + header.block_len(4)..)?;
+        let header = TzifHeader::parse(data)?;
+        let (mut zone, rest) = header.parse_block(data.get(44..)?, 8)?;
+        let footer = std::str::from_utf8(rest).ok()?;
+        let footer = footer.strip_prefix('\n')?.split('\n').next()?;
+        if !footer.is_empty() {
+            zone.rule = Some(PosixRule::parse(footer)?);
+        }
+        Some(zone)
+    }
+
+    /// Returns the local time type of the zone at the given time
+    pub fn local_time_type(&self, time: DateTime<Utc>) -> LocalTimeType {
+        let timestamp = time.timestamp();
+        let index = self
+            .transitions
+            .partition_point(|&(transition, _)| transition <= timestamp);
+
+        match (index, &self.rule) {
+            (index, Some(rule)) if index == self.transitions.len() => rule.local_time_type(time),
+            (0, _) => self.types[0].clone(),
+            (index, _) => self.types[self.transitions[index - 1].1].clone(),
+        }
+    }
+}
+
+struct TzifHeader {
+    version: u8,
+    isutcnt: usize,
+    isstdcnt: usize,
+    leapcnt: usize,
+    timecnt: usize,
+    typecnt: usize,
+    charcnt: usize,
+}
+
+impl TzifHeader {
+    fn parse(data: &[u8]) -> Option<Self> {
+        if data.get(..4)? != b"TZif" {
+            return None;
+        }
+        let version = match *data.get(4)? {
+            0 => 0,
+            version @ b'2'..=b'4' => version - b'0',
+            _ => return None,
+        };
+        let count = |index: usize| {
+            let start = 20 + index * 4;
+            Some(read_int(data.get(start..start + 4)?) as usize)
+        };
+        Some(TzifHeader {
+            version,
+            isutcnt: count(0)?,
+            isstdcnt: count(1)?,
+            leapcnt: count(2)?,
+            timecnt: count(3)?,
+            typecnt: count(4)?,
+            charcnt: count(5)?,
+        })
+    }
+
+    /// The length of the data block that follows the header
+    fn block_len(&self, time_size: usize) -> usize {
+        self.timecnt * (time_size + 1)
+            + self.typecnt * 6
+            + self.charcnt
+            + self.leapcnt * (time_size + 4)
+            + self.isstdcnt
+            + self.isutcnt
+    }
+
+    /// Parses the data block and returns the rest of the data
+    fn parse_block<'a>(&self, data: &'a [u8], time_size: usize) -> Option<(TimeZone, &'a [u8])> {
+        let times = data.get(..self.timecnt * time_size)?;
+        let indices = data.get(times.len()..times.len() + self.timecnt)?;
+        let types_start = times.len() + indices.len();
+        let types = data.get(types_start..types_start + self.typecnt * 6)?;
+        let chars_start = types_start + types.len();
+        let chars = data.get(chars_start..chars_start + self.charcnt)?;
+
+        let types = types
+            .chunks(6)
+            .map(|info| {
+                let abbreviation = chars.get(info[5] as usize..)?;
+                let end = abbreviation.iter().position(|&c| c == 0)?;
+                Some(LocalTimeType {
+                    utc_offset: read_int(&info[..4]) as i32,
+                    abbreviation: String::from_utf8_lossy(&abbreviation[..end]).into_owned(),
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        let transitions = times
+            .chunks(time_size)
+            .map(read_int)
+            .zip(indices.iter().map(|&index| index as usize))
+            .collect::<Vec<_>>();
+        if types.is_empty() || transitions.iter().any(|&(_, index)| index >= types.len()) {
+            return None;
+        }
+
+        let zone = TimeZone {
+            transitions,
+            types,
+            rule: None,
+        };
+        Some((zone, data.get(self.block_len(time_size)..)?))
+    }
+}
+
+/// Reads a signed big-endian integer of 4 or 8 bytes
+fn read_int(bytes: &[u8]) -> i64 {
+    match bytes.len() {
+        4 => i64::from(i32::from_be_bytes(bytes.try_into().unwrap())),
+        _ => i64::from_be_bytes(bytes.try_into().unwrap()),
+    }
+}
+
+/// A time zone rule in the format of the POSIX `TZ` variable, like `CET-1CEST,M3.5.0,M10.5.0/3`
+#[derive(Debug, PartialEq, Eq)]
+struct PosixRule {
+    standard: LocalTimeType,
+    daylight: Option<(LocalTimeType, DaylightRule)>,
+}
+
+/// The dates and local times at which daylight saving time starts and ends
+#[derive(Debug, PartialEq, Eq)]
+struct DaylightRule {
+    start: (RuleDate, i64),
+    end: (RuleDate, i64),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum RuleDate {
+    /// `Jn`: the day of the year from 1 to 365, where February 29 is never counted
+    Julian(u32),
+    /// `n`: the day of the year from 0 to 365, counting February 29
+    Ordinal(u32),
+    /// `Mm.w.d`: the day `d` (0 is Sunday) of week `w` of month `m`, where week 5 is the last one
+    MonthWeekDay(u32, u32, u32),
+}
+
+impl PosixRule {
+    fn parse(rule: &str) -> Option<Self> {
+        let (standard, rest) = parse_local_time_type(rule, None)?;
+        if rest.is_empty() {
+            return Some(PosixRule {
+                standard,
+                daylight: None,
+            });
+        }
+
+        let (daylight, rest) = parse_local_time_type(rest, Some(standard.utc_offset + 3600))?;
+        // Without dates, the rules of the United States are used
+        let rest = if rest.is_empty() {
+            ",M3.2.0,M11.1.0"
+        } else {
+            rest
+        };
+        let mut dates = rest.strip_prefix(',')?.split(',');
+        let start = parse_rule_date(dates.next()?)?;
+        let end = parse_rule_date(dates.next()?)?;
+        if dates.next().is_some() {
+            return None;
+        }
+
+        Some(PosixRule {
+            standard,
+            daylight: Some((daylight, DaylightRule { start, end })),
+        })
+    }
+
+    fn local_time_type(&self, time: DateTime<Utc>) -> LocalTimeType {
+        let Some((daylight, rule)) = &self.daylight else {
+            return self.standard.clone();
+        };
+
+        let timestamp = time.timestamp();
+        let year = (time + Duration::seconds(self.standard.utc_offset.into())).year();
+        let transition = |(date, local_time): &(RuleDate, i64), utc_offset: i32| {
+            date.to_date(year).map(|date| {
+                date.and_hms_opt(0, 0, 0).unwrap().timestamp() + local_time - i64::from(utc_offset)
+            })
+        };
+        let (Some(start), Some(end)) = (
+            transition(&rule.start, self.standard.utc_offset),
+            transition(&rule.end, daylight.utc_offset),
+        ) else {
+            return self.standard.clone();
+        };
+
+        // In the southern hemisphere, daylight saving time lasts over the new year
+        let is_daylight = if start < end {
+            start <= timestamp && timestamp < end
+        } else {
+            timestamp < end || start <= timestamp
+        };
+        if is_daylight {
+            daylight.clone()
+        } else {
+            self.standard.clone()
+        }
+    }
+}
+
+impl RuleDate {
+    fn to_date(&self, year: i32) -> Option<NaiveDate> {
+        match *self {
+            RuleDate::Julian(day) => {
+                let date = NaiveDate::from_yo_opt(year, day)?;
+                let is_leap_year = NaiveDate::from_ymd_opt(year, 2, 29).is_some();
+                if is_leap_year && day >= 60 {
+                    date.succ_opt()
+                } else {
+                    Some(date)
+                }
+            }
+            RuleDate::Ordinal(day) => NaiveDate::from_yo_opt(year, day + 1),
+            RuleDate::MonthWeekDay(month, week, weekday) => {
+                let weekday = [
+                    Weekday::Sun,
+                    Weekday::Mon,
+                    Weekday::Tue,
+                    Weekday::Wed,
+                    Weekday::Thu,
+                    Weekday::Fri,
+                    Weekday::Sat,
+                ][weekday as usize];
+                NaiveDate::from_weekday_of_month_opt(year, month, weekday, week as u8)
+                    .or_else(|| NaiveDate::from_weekday_of_month_opt(year, month, weekday, 4))
+            }
+        }
+    }
+}
+
+/// Parses the abbreviation and offset of a local time type, like `CEST-2` or `<+0330>-3:30`.
+/// The offset is optional if a default is given.
+fn parse_local_time_type(rule: &str, default_offset: Option<i32>) -> Option<(LocalTimeType, &str)> {
+    let (abbreviation, rest) = match rule.strip_prefix('<') {
+        Some(rest) => rest.split_once('>')?,
+        None => {
+            let end = rule
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(rule.len());
+            rule.split_at(end)
+        }
+    };
+    if abbreviation.len() < 3 {
+        return None;
+    }
+
+    let (utc_offset, rest) = match (parse_duration(rest), default_offset) {
+        // POSIX offsets are positive west of UTC
+        (Some((offset, rest)), _) => (i32::try_from(-offset).ok()?, rest),
+        (None, Some(offset)) => (offset, rest),
+        (None, None) => return None,
+    };
+    let local_time_type = LocalTimeType {
+        utc_offset,
+        abbreviation: abbreviation.to_string(),
+    };
+    Some((local_time_type, rest))
+}
+
+/// Parses a date with an optional local time, like `M3.5.0/3`. The time defaults to 02:00.
+fn parse_rule_date(date: &str) -> Option<(RuleDate, i64)> {
+    let (date, time) = match date.split_once('/') {
+        Some((date, time)) => match parse_duration(time)? {
+            (time, "") => (date, time),
+            _ => return None,
+        },
+        None => (date, 2 * 3600),
+    };
+
+    let date = if let Some(day) = date.strip_prefix('J') {
+        RuleDate::Julian(day.parse().ok().filter(|day| (1..=365).contains(day))?)
+    } else if let Some(date) = date.strip_prefix('M') {
+        let mut parts = date.split('.').map(|part| part.parse::<u32>().ok());
+        let (Some(Some(month)), Some(Some(week)), Some(Some(weekday)), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return None;
+        };
+        if !(1..=12).contains(&month) || !(1..=5).contains(&week) || weekday > 6 {
+            return None;
+        }
+        RuleDate::MonthWeekDay(month, week, weekday)
+    } else {
+        RuleDate::Ordinal(date.parse().ok().filter(|&day| day <= 365)?)
+    };
+    Some((date, time))
+}
+
+/// Parses a signed duration like `-3:30` into seconds and returns the rest of the text
+fn parse_duration(text: &str) -> Option<(i64, &str)> {
+    let (sign, text) = match text.strip_prefix('-') {
+        Some(text) => (-1, text),
+        None => (1, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let end = text
+        .find(|c: char| !c.is_ascii_digit() && c != ':')
+        .unwrap_or(text.len());
+    let (duration, rest) = text.split_at(end);
+    if duration.is_empty() {
+        return None;
+    }
+
+    let mut seconds = 0;
+    for (index, part) in duration.split(':').enumerate() {
+        if index > 2 {
+            return None;
+        }
+        let part: i64 = part.parse().ok()?;
+        seconds += part * [3600, 60, 1][index];
+    }
+    Some((sign * seconds, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+
+    fn utc(time: &str) -> DateTime<Utc> {
+        DateTime::from_utc(
+            NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M:%S").unwrap(),
+            Utc,
+        )
+    }
+
+    fn local_time_type(rule: &str, time: &str) -> (i32, String) {
+        let rule = PosixRule::parse(rule).unwrap();
+        let local_time_type = rule.local_time_type(utc(time));
+        (local_time_type.utc_offset, local_time_type.abbreviation)
+    }
+
+    #[test]
+    fn posix_rule_without_daylight_saving() {
+        assert_eq!(
+            local_time_type("<+0530>-5:30", "2023-06-01 00:00:00"),
+            (19800, "+0530".to_string())
+        );
+        assert_eq!(
+            local_time_type("UTC0", "2023-06-01 00:00:00"),
+            (0, "UTC".to_string())
+        );
+        assert!(PosixRule::parse("UTC").is_none());
+        assert!(PosixRule::parse("U0").is_none());
+    }
+
+    #[test]
+    fn posix_rule_northern_hemisphere() {
+        let rule = "CET-1CEST,M3.5.0,M10.5.0/3";
+        // Daylight saving time starts on March 26 2023 at 01:00 UTC
+        assert_eq!(local_time_type(rule, "2023-03-26 00:59:59").0, 3600);
+        assert_eq!(
+            local_time_type(rule, "2023-03-26 01:00:00"),
+            (7200, "CEST".to_string())
+        );
+        // And ends on October 29 2023 at 01:00 UTC
+        assert_eq!(local_time_type(rule, "2023-10-29 00:59:59").0, 7200);
+        assert_eq!(
+            local_time_type(rule, "2023-10-29 01:00:00"),
+            (3600, "CET".to_string())
+        );
+
+        let rule = "PST8PDT,M3.2.0,M11.1.0";
+        assert_eq!(local_time_type(rule, "2023-03-12 09:59:59").0, -8 * 3600);
+        assert_eq!(local_time_type(rule, "2023-03-12 10:00:00").0, -7 * 3600);
+        assert_eq!(
+            local_time_type("EST5EDT", "2023-07-01 00:00:00").0,
+            -4 * 3600
+        );
+    }
+
+    #[test]
+    fn posix_rule_southern_hemisphere() {
+        let rule = "AEST-10AEDT,M10.1.0,M4.1.0/3";
+        assert_eq!(
+            local_time_type(rule, "2023-01-15 00:00:00"),
+            (11 * 3600, "AEDT".to_string())
+        );
+        assert_eq!(
+            local_time_type(rule, "2023-06-15 00:00:00"),
+            (10 * 3600, "AEST".to_string())
+        );
+        assert_eq!(local_time_type(rule, "2023-12-15 00:00:00").0, 11 * 3600);
+    }
+
+    #[test]
+    fn posix_rule_dates() {
+        assert_eq!(
+            RuleDate::Julian(60).to_date(2024),
+            NaiveDate::from_ymd_opt(2024, 3, 1)
+        );
+        assert_eq!(
+            RuleDate::Ordinal(59).to_date(2024),
+            NaiveDate::from_ymd_opt(2024, 2, 29)
+        );
+        // The fifth Sunday of April 2023 doesn't exist, so it's the last one
+        assert_eq!(
+            RuleDate::MonthWeekDay(4, 5, 0).to_date(2023),
+            NaiveDate::from_ymd_opt(2023, 4, 30)
+        );
+        assert_eq!(
+            RuleDate::MonthWeekDay(10, 5, 0).to_date(2023),
+            NaiveDate::from_ymd_opt(2023, 10, 29)
+        );
+        assert!(PosixRule::parse("CET-1CEST,M13.1.0,M10.5.0").is_none());
+    }
+
+    /// Builds a version 2 TZif file with the given transitions, types and footer
+    fn tzif(transitions: &[(i64, u8)], types: &[(i32, &str)], footer: &str) -> Vec<u8> {
+        let mut chars = Vec::new();
+        let mut infos = Vec::new();
+        for (offset, abbreviation) in types {
+            infos.extend(offset.to_be_bytes());
+            infos.push(0);
+            infos.push(chars.len() as u8);
+            chars.extend(abbreviation.bytes());
+            chars.push(0);
+        }
+
+        let header = |time_size: usize| {
+            let mut header = b"TZif2".to_vec();
+            header.extend([0; 15]);
+            for count in [0, 0, 0, transitions.len(), types.len(), chars.len()] {
+                header.extend((count as u32).to_be_bytes());
+            }
+            let mut block = Vec::new();
+            for (time, _) in transitions {
+                match time_size {
+                    4 => block.extend((*time as i32).to_be_bytes()),
+                    _ => block.extend(time.to_be_bytes()),
+                }
+            }
+            block.extend(transitions.iter().map(|(_, index)| index));
+            block.extend(&infos);
+            block.extend(&chars);
+            header.extend(block);
+            header
+        };
+
+        let mut data = header(4);
+        data.extend(header(8));
+        data.extend(format!("\n{footer}\n").bytes());
+        data
+    }
+
+    #[test]
+    fn tzif_transitions_and_rule() {
+        let data = tzif(
+            &[(0, 1), (1_000_000_000, 0)],
+            &[(3600, "CET"), (7200, "CEST")],
+            "CET-1CEST,M3.5.0,M10.5.0/3",
+        );
+        let zone = TimeZone::from_tzif(&data).unwrap();
+
+        let abbreviation = |time| zone.local_time_type(utc(time)).abbreviation;
+        // Before the first transition, the first type is used
+        assert_eq!(abbreviation("1969-12-31 23:59:59"), "CET");
+        assert_eq!(abbreviation("1970-01-01 00:00:00"), "CEST");
+        assert_eq!(abbreviation("1990-01-01 00:00:00"), "CEST");
+        // After the last transition, the rule in the footer is used
+        assert_eq!(abbreviation("2023-07-01 00:00:00"), "CEST");
+        assert_eq!(abbreviation("2023-12-01 00:00:00"), "CET");
+    }
+
+    #[test]
+    fn tzif_without_rule() {
+        let data = tzif(&[(0, 1)], &[(0, "LMT"), (-3600, "-01")], "");
+        let zone = TimeZone::from_tzif(&data).unwrap();
+        assert_eq!(
+            zone.local_time_type(utc("2023-07-01 00:00:00")),
+            LocalTimeType {
+                utc_offset: -3600,
+                abbreviation: "-01".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn tzif_invalid() {
+        assert!(TimeZone::from_tzif(b"").is_none());
+        assert!(TimeZone::from_tzif(b"TZif2").is_none());
+        for version in [b'\x01', b'1', b'5', b' '] {
+            let mut data = tzif(&[], &[(0, "UTC")], "UTC0");
+            data[4] = version;
+            assert!(TimeZone::from_tzif(&data).is_none());
+        }
+        let mut data = tzif(&[(0, 2)], &[(0, "UTC")], "");
+        assert!(TimeZone::from_tzif(&data).is_none());
+        data = tzif(&[], &[(0, "UTC")], "UTC0");
+        data.truncate(data.len() - 3);
+        assert!(TimeZone::from_tzif(&data).is_none());
+    }
+}
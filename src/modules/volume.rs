@@ -0,0 +1,383 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{Context, Module, ModuleConfig};
+
+use crate::configs::volume::VolumeConfig;
+use crate::formatter::StringFormatter;
+use crate::utils;
+
+/// Set for the background process that refreshes the cached state
+const REFRESH_ENV: &str = "STARSHIP_VOLUME_REFRESH";
+
+/// Output volume and mute state of the default audio device
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct AudioState {
+    volume: u32,
+    muted: bool,
+}
+
+/// Creates a module with the output volume and mute state
+///
+/// The state is read with `wpctl` or `pactl` on Linux and `osascript` on macOS.
+/// To keep the prompt fast, it is only read from a cache, which a background process refreshes
+/// once it is older than `cache_duration` seconds.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("volume");
+    let config = VolumeConfig::try_load(module.config);
+
+    if config.disabled {
+        return None;
+    }
+
+    let state = get_cached_state(context, &config)?;
+    let (symbol, style) = if state.muted {
+        (config.muted_symbol, config.muted_style)
+    } else {
+        (config.symbol, config.style)
+    };
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "volume" => Some(Ok(state.volume.to_string())),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            context.report_module_error("volume", &error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn get_cached_state(context: &Context, config: &VolumeConfig) -> Option<AudioState> {
+    if config.cache_duration == 0 {
+        return get_state(context);
+    }
+
+    let cache_file = utils::cache_path(context, "volume");
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+
+    if context.get_env(REFRESH_ENV).is_some() {
+        let state = get_state(context);
+        write_cache(&cache_file, now, state);
+        return None;
+    }
+
+    let (timestamp, state) = utils::read_file(&cache_file)
+        .ok()
+        .and_then(|content| parse_cache(&content))
+        .unwrap_or((0, None));
+    if now.saturating_sub(timestamp) >= config.cache_duration {
+        // The timestamp is bumped first, so that only one refresh runs at a time
+        write_cache(&cache_file, now, state);
+        refresh_in_background(context);
+    }
+    state
+}
+
+/// Runs `starship module volume` in the background to read the state into the cache
+fn refresh_in_background(context: &Context) {
+    let Ok(exe) = env::current_exe() else {
+        return;
+    };
+    context.spawn_cmd(exe, &["module", "volume"], &[(REFRESH_ENV, "1")]);
+}
+
+/// The cache holds the time of the last refresh and the state it read, if any,
+/// e.g. `1700000000 65 false`
+fn write_cache(cache_file: &Path, timestamp: u64, state: Option<AudioState>) {
+    let content = match state {
+        Some(state) => format!("{} {} {}", timestamp, state.volume, state.muted),
+        None => timestamp.to_string(),
+    };
+    if let Err(error) = fs::write(cache_file, content) {
+        log::debug!("Unable to write volume cache {:?}: {}", cache_file, error);
+    }
+}
+
+fn parse_cache(content: &str) -> Option<(u64, Option<AudioState>)> {
+    let mut parts = content.split_whitespace();
+    let timestamp = parts.next()?.parse().ok()?;
+    let state = (|| {
+        let volume = parts.next()?.parse().ok()?;
+        let muted = parts.next()?.parse().ok()?;
+        Some(AudioState { volume, muted })
+    })();
+    Some((timestamp, state))
+}
+
+#[cfg(target_os = "macos")]
+fn get_state(context: &Context) -> Option<AudioState> {
+    let output = context.exec_cmd("osascript", &["-e", "get volume settings"])?;
+    parse_osascript(&output.stdout)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn get_state(context: &Context) -> Option<AudioState> {
+    if let Some(output) = context.exec_cmd("wpctl", &["get-volume", "@DEFAULT_AUDIO_SINK@"]) {
+        return parse_wpctl(&output.stdout);
+    }
+
+    let volume = context.exec_cmd("pactl", &["get-sink-volume", "@DEFAULT_SINK@"])?;
+    let mute = context.exec_cmd("pactl", &["get-sink-mute", "@DEFAULT_SINK@"])?;
+    parse_pactl(&volume.stdout, &mute.stdout)
+}
+
+/// Parses `wpctl get-volume` output, e.g. `Volume: 0.65 [MUTED]`
+fn parse_wpctl(output: &str) -> Option<AudioState> {
+    let volume = output.trim().strip_prefix("Volume:")?;
+    let level = volume.split_whitespace().next()?.parse::<f64>().ok()?;
+    Some(AudioState {
+        volume: (level * 100.0).round() as u32,
+        muted: volume.contains("[MUTED]"),
+    })
+}
+
+/// Parses `pactl get-sink-volume` and `pactl get-sink-mute` output, using the first channel
+fn parse_pactl(volume: &str, mute: &str) -> Option<AudioState> {
+    let percent = volume
+        .split('/')
+        .map(str::trim)
+        .find_map(|part| part.strip_suffix('%'))?;
+    Some(AudioState {
+        volume: percent.parse().ok()?,
+        muted: mute.trim() == "Mute: yes",
+    })
+}
+
+/// Parses `osascript -e "get volume settings"` output,
+/// e.g. `output volume:50, input volume:75, alert volume:100, output muted:false`
+#[cfg(any(target_os = "macos", test))]
+fn parse_osascript(output: &str) -> Option<AudioState> {
+    let value = |key: &str| {
+        output
+            .split(',')
+            .find_map(|setting| setting.trim().strip_prefix(key))
+    };
+    Some(AudioState {
+        volume: value("output volume:")?.parse().ok()?,
+        muted: value("output muted:")? == "true",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{output, ModuleRenderer};
+    use nu_ansi_term::Color;
+    use std::io;
+
+    fn refresh_command() -> String {
+        format!(
+            "{} module volume",
+            env::current_exe().unwrap().to_string_lossy()
+        )
+    }
+
+    #[test]
+    fn parse_outputs() {
+        assert_eq!(
+            parse_wpctl("Volume: 0.65\n"),
+            Some(AudioState {
+                volume: 65,
+                muted: false
+            })
+        );
+        assert_eq!(
+            parse_wpctl("Volume: 0.40 [MUTED]\n"),
+            Some(AudioState {
+                volume: 40,
+                muted: true
+            })
+        );
+        assert_eq!(
+            parse_pactl(
+                "Volume: front-left: 42597 /  65% / -11.23 dB,   front-right: 42597 /  65% / -11.23 dB\n",
+                "Mute: yes\n"
+            ),
+            Some(AudioState {
+                volume: 65,
+                muted: true
+            })
+        );
+        assert_eq!(
+            parse_osascript(
+                "output volume:50, input volume:75, alert volume:100, output muted:false\n"
+            ),
+            Some(AudioState {
+                volume: 50,
+                muted: false
+            })
+        );
+        assert_eq!(
+            parse_osascript("output volume:missing value, output muted:missing value"),
+            None
+        );
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let actual = ModuleRenderer::new("volume")
+            .cmd(
+                "wpctl get-volume @DEFAULT_AUDIO_SINK@",
+                output("Volume: 0.65"),
+            )
+            .collect();
+
+        assert_eq!(None, actual);
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn wpctl_muted() {
+        let actual = ModuleRenderer::new("volume")
+            .cmd(
+                "wpctl get-volume @DEFAULT_AUDIO_SINK@",
+                output("Volume: 0.40 [MUTED]\n"),
+            )
+            .config(toml::toml! {
+                [volume]
+                disabled = false
+                cache_duration = 0
+            })
+            .collect();
+        let expected = Some(format!("{} ", Color::Red.bold().paint("🔇 40%")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn pactl_fallback() {
+        let actual = ModuleRenderer::new("volume")
+            .cmd("wpctl get-volume @DEFAULT_AUDIO_SINK@", None)
+            .cmd(
+                "pactl get-sink-volume @DEFAULT_SINK@",
+                output("Volume: front-left: 42597 /  65% / -11.23 dB\n"),
+            )
+            .cmd("pactl get-sink-mute @DEFAULT_SINK@", output("Mute: no\n"))
+            .config(toml::toml! {
+                [volume]
+                disabled = false
+                cache_duration = 0
+            })
+            .collect();
+        let expected = Some(format!("{} ", Color::White.bold().paint("🔊 65%")));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn uses_cached_state() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        fs::write(
+            cache_dir.path().join("volume"),
+            format!("{} 30 false", now.as_secs()),
+        )?;
+
+        let actual = ModuleRenderer::new("volume")
+            .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+            .cmd(
+                "wpctl get-volume @DEFAULT_AUDIO_SINK@",
+                output("Volume: 0.40 [MUTED]\n"),
+            )
+            .config(toml::toml! {
+                [volume]
+                disabled = false
+            })
+            .collect();
+        let expected = Some(format!("{} ", Color::White.bold().paint("🔊 30%")));
+
+        assert_eq!(expected, actual);
+        cache_dir.close()
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn shows_stale_cache_while_refreshing() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        fs::write(cache_dir.path().join("volume"), "0 30 false")?;
+        let refresh = refresh_command();
+
+        let actual = ModuleRenderer::new("volume")
+            .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+            .cmd(&refresh, output(""))
+            .config(toml::toml! {
+                [volume]
+                disabled = false
+            })
+            .collect();
+        let expected = Some(format!("{} ", Color::White.bold().paint("🔊 30%")));
+
+        assert_eq!(expected, actual);
+        let cache = fs::read_to_string(cache_dir.path().join("volume"))?;
+        assert!(!cache.starts_with("0 ") && cache.ends_with(" 30 false"));
+        cache_dir.close()
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn hidden_until_first_refresh() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        let refresh = refresh_command();
+
+        let actual = ModuleRenderer::new("volume")
+            .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+            .cmd(&refresh, output(""))
+            .config(toml::toml! {
+                [volume]
+                disabled = false
+            })
+            .collect();
+
+        assert_eq!(None, actual);
+        let cache = fs::read_to_string(cache_dir.path().join("volume"))?;
+        assert_eq!(parse_cache(&cache).map(|(_, state)| state), Some(None));
+        cache_dir.close()
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn background_refresh_writes_cache() -> io::Result<()> {
+        let cache_dir = tempfile::tempdir()?;
+        fs::write(cache_dir.path().join("volume"), "0 30 false")?;
+
+        let actual = ModuleRenderer::new("volume")
+            .env("STARSHIP_CACHE", cache_dir.path().to_string_lossy())
+            .env(REFRESH_ENV, "1")
+            .cmd(
+                "wpctl get-volume @DEFAULT_AUDIO_SINK@",
+                output("Volume: 0.40 [MUTED]\n"),
+            )
+            .config(toml::toml! {
+                [volume]
+                disabled = false
+            })
+            .collect();
+
+        assert_eq!(None, actual);
+        let cache = fs::read_to_string(cache_dir.path().join("volume"))?;
+        assert!(cache.ends_with(" 40 true"));
+        cache_dir.close()
+    }
+}
@@ -1,7 +1,7 @@
 use clap::{builder::PossibleValue, ValueEnum};
-use nu_ansi_term::AnsiStrings;
+use nu_ansi_term::{AnsiStrings, Style};
 use rayon::prelude::*;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{Debug, Write as FmtWrite};
 use std::io::{self, Write};
 use std::path::PathBuf;
@@ -10,15 +10,21 @@ use terminal_size::terminal_size;
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthChar;
 
-use crate::configs::PROMPT_ORDER;
+use crate::config::parse_style_string;
+use crate::configs::{EscapeAuditMode, PROMPT_ORDER};
 use crate::context::{Context, Properties, Shell, Target};
+use crate::contrast::enforce_contrast;
+use crate::escape_audit;
 use crate::formatter::string_formatter::StringFormatterError;
 use crate::formatter::{StringFormatter, VariableHolder};
+use crate::instant_prompt;
 use crate::module::Module;
 use crate::module::ALL_MODULES;
 use crate::modules;
 use crate::segment::Segment;
 use crate::shadow;
+use crate::trace::{self, TraceEvent, TraceKind};
+use crate::utils::{wrap_seq_for_shell, wrap_tmux_passthrough};
 
 pub struct Grapheme<'a>(pub &'a str);
 
@@ -59,9 +65,15 @@ fn test_grapheme_aware_width() {
 
 pub fn prompt(args: Properties, target: Target) {
     let context = Context::new(args, target);
+    let instant_prompt = instant_prompt::path_for(&context);
+    let prompt = get_prompt(context);
     let stdout = io::stdout();
     let mut handle = stdout.lock();
-    write!(handle, "{}", get_prompt(context)).unwrap();
+    write!(handle, "{prompt}").unwrap();
+
+    if let Some(path) = instant_prompt {
+        instant_prompt::save(&path, &prompt);
+    }
 }
 
 pub fn get_prompt(context: Context) -> String {
@@ -83,45 +95,39 @@ pub fn get_prompt(context: Context) -> String {
         buf.push_str("\x1b[J"); // An ASCII control code to clear screen
     }
 
-    let (formatter, modules) = load_formatter_and_modules(&context);
-
-    let formatter = formatter.map_variables_to_segments(|module| {
-        // Make $all display all modules not explicitly referenced
-        if module == "all" {
-            Some(Ok(all_modules_uniq(&modules)
-                .par_iter()
-                .flat_map(|module| {
-                    handle_module(module, &context, &modules)
-                        .into_iter()
-                        .flat_map(|module| module.segments)
-                        .collect::<Vec<Segment>>()
-                })
-                .collect::<Vec<_>>()))
-        } else if context.is_module_disabled_in_config(module) {
-            None
-        } else {
-            // Get segments from module
-            Some(Ok(handle_module(module, &context, &modules)
-                .into_iter()
-                .flat_map(|module| module.segments)
-                .collect::<Vec<Segment>>()))
-        }
-    });
+    if config.user_vars && context.target == Target::Main {
+        buf.push_str(&user_var_sequences(&context));
+    }
 
     // Creates a root module and prints it.
-    let mut root_module = Module::new("Starship Root", "The root module", None);
-    root_module.set_segments(
-        formatter
-            .parse(None, Some(&context))
-            .expect("Unexpected error returned in root format variables"),
-    );
+    let root_module = get_root_module(&context);
 
-    let module_strings = root_module.ansi_strings_for_shell(context.shell, Some(context.width));
     if config.add_newline && context.target != Target::Continuation {
         // continuation prompts normally do not include newlines, but they can
         writeln!(buf).unwrap();
     }
-    write!(buf, "{}", AnsiStrings(&module_strings)).unwrap();
+
+    let safe_prompt = match config.escape_audit {
+        EscapeAuditMode::Off => None,
+        mode => {
+            // Audit the prompt before any shell-specific wrapping is applied
+            let raw_strings =
+                root_module.ansi_strings_for_shell(Shell::Unknown, Some(context.width));
+            let raw = AnsiStrings(&raw_strings).to_string();
+            for issue in escape_audit::audit(&raw, context.shell, context.width) {
+                log::warn!("{issue}");
+            }
+            (mode == EscapeAuditMode::Fix).then(|| escape_audit::make_safe(&raw, context.shell))
+        }
+    };
+    match safe_prompt {
+        Some(prompt) => buf.push_str(&prompt),
+        None => {
+            let module_strings =
+                root_module.ansi_strings_for_shell(context.shell, Some(context.width));
+            write!(buf, "{}", AnsiStrings(&module_strings)).unwrap();
+        }
+    }
 
     if context.target == Target::Right {
         // right prompts generally do not allow newlines
@@ -138,6 +144,201 @@ pub fn get_prompt(context: Context) -> String {
     buf
 }
 
+/// Sets the `starship_cwd`, `starship_branch` and `starship_status` user variables through
+/// the escape sequence supported by iTerm2 and WezTerm, so the terminal can show them elsewhere.
+/// Unavailable values are sent empty to clear the value of the previous prompt.
+fn user_var_sequences(context: &Context) -> String {
+    let branch = context
+        .get_repo()
+        .ok()
+        .and_then(|repo| repo.branch.clone())
+        .unwrap_or_default();
+    let user_vars = [
+        (
+            "starship_cwd",
+            context.logical_dir.to_string_lossy().into_owned(),
+        ),
+        ("starship_branch", branch),
+        (
+            "starship_status",
+            context.properties.status_code.clone().unwrap_or_default(),
+        ),
+    ];
+
+    let in_tmux = context.get_env("TMUX").is_some();
+    user_vars
+        .iter()
+        .map(|(name, value)| {
+            let seq = format!("\x1b]1337;SetUserVar={name}={}\x07", base64::encode(value));
+            if in_tmux {
+                // The passthrough ends with `ESC \` rather than BEL
+                wrap_seq_for_shell(wrap_tmux_passthrough(&seq), context.shell, '\x1b', '\\')
+            } else {
+                wrap_seq_for_shell(seq, context.shell, '\x1b', '\x07')
+            }
+        })
+        .collect()
+}
+
+/// Computes all modules of the current target and collects their segments into a root module
+pub fn get_root_module<'a>(context: &'a Context) -> Module<'a> {
+    let (formatter, modules) = load_formatter_and_modules(context);
+    let use_separators = context.root_config.has_separators();
+    let fit_directory = is_directory_fit_to_line(context);
+
+    let render_module = |module: &str| {
+        // Leave a marker in place of the directory, which is rendered once the width of
+        // the rest of its line is known
+        if fit_directory && module == "directory" {
+            return vec![Segment::module_start(module)];
+        }
+        handle_module(module, context, &modules)
+            .into_iter()
+            .flat_map(|module| module_segments(module, use_separators))
+            .collect::<Vec<Segment>>()
+    };
+
+    let formatter = formatter.map_variables_to_segments(|module| {
+        // Make $all display all modules not explicitly referenced
+        if module == "all" {
+            Some(Ok(all_modules_uniq(&modules)
+                .par_iter()
+                .flat_map(|module| render_module(module))
+                .collect::<Vec<_>>()))
+        } else if context.is_module_disabled_in_config(module) {
+            None
+        } else {
+            // Get segments from module
+            Some(Ok(render_module(module)))
+        }
+    });
+
+    let mut segments = formatter
+        .parse(None, Some(context))
+        .expect("Unexpected error returned in root format variables");
+
+    if fit_directory {
+        fit_directory_to_line(&mut segments, context, &modules, use_separators);
+    }
+
+    let mut segments = if use_separators {
+        join_modules(&segments, context)
+    } else {
+        segments
+    };
+    enforce_contrast(&mut segments, context);
+
+    let mut root_module = Module::new("Starship Root", "The root module", None);
+    root_module.set_segments(segments);
+    root_module
+}
+
+/// Whether the directory is shortened to fit its line, with `truncation_length = "auto"`
+fn is_directory_fit_to_line(context: &Context) -> bool {
+    context
+        .config
+        .get_module_config("directory")
+        .and_then(|config| config.get("truncation_length"))
+        .and_then(toml::Value::as_str)
+        == Some("auto")
+}
+
+/// Replaces the directory marker with the directory, rendered in the width the other
+/// modules on its line leave
+fn fit_directory_to_line(
+    segments: &mut Vec<Segment>,
+    context: &Context,
+    modules: &BTreeSet<String>,
+    use_separators: bool,
+) {
+    let is_marker =
+        |segment: &Segment| matches!(segment, Segment::ModuleStart(name) if name == "directory");
+    let Some(index) = segments.iter().position(is_marker) else {
+        return;
+    };
+
+    let is_line_term = |segment: &Segment| matches!(segment, Segment::LineTerm);
+    let line_start = segments[..index]
+        .iter()
+        .rposition(is_line_term)
+        .map_or(0, |i| i + 1);
+    let line_end = segments[index..]
+        .iter()
+        .position(is_line_term)
+        .map_or(segments.len(), |i| index + i);
+    let other_width = segments[line_start..line_end]
+        .iter()
+        .filter(|segment| matches!(segment, Segment::Text(_)))
+        .map(|segment| segment.value().width_graphemes())
+        .sum::<usize>();
+
+    let _width = context.limit_module_width(context.width.saturating_sub(other_width));
+    let directory = handle_module("directory", context, modules)
+        .into_iter()
+        .flat_map(|module| module_segments(module, use_separators));
+    segments.splice(index..=index, directory);
+}
+
+/// Returns the segments of a module, preceded by a start marker if separators are used
+fn module_segments(module: Module, mark_start: bool) -> Vec<Segment> {
+    if mark_start && !module.is_empty() {
+        std::iter::once(Segment::module_start(module.get_name().as_str()))
+            .chain(module.segments)
+            .collect()
+    } else {
+        module.segments
+    }
+}
+
+/// Replaces the module start markers with the configured separators.
+///
+/// A separator is only placed between two modules on the same line. Unless `separator_style`
+/// is set, its foreground is the background of the module before it and its background is the
+/// background of the module after it, which is what powerline glyphs need to blend in.
+fn join_modules(segments: &[Segment], context: &Context) -> Vec<Segment> {
+    let config = &context.root_config;
+    let separator_style = match config.separator_style.as_str() {
+        "" => None,
+        style => parse_style_string(style, Some(context)),
+    };
+
+    let mut joined: Vec<Segment> = Vec::with_capacity(segments.len());
+    let mut previous_module: Option<&str> = None;
+
+    for (i, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::ModuleStart(name) => {
+                let next = segments[i + 1..].iter().find(|s| !s.value().is_empty());
+                if let (Some(previous), Some(next @ Segment::Text(_))) = (previous_module, next) {
+                    let separator = config.separator_between(previous, name);
+                    if !separator.is_empty() {
+                        let style = separator_style.unwrap_or_else(|| {
+                            let mut style = Style::new();
+                            style.foreground = joined
+                                .iter()
+                                .rev()
+                                .find(|s| !s.value().is_empty())
+                                .and_then(Segment::style)
+                                .and_then(|s| s.background);
+                            style.background = next.style().and_then(|s| s.background);
+                            style
+                        });
+                        joined.extend(Segment::from_text(Some(style), separator));
+                    }
+                }
+                previous_module = Some(name);
+            }
+            Segment::LineTerm | Segment::Fill(_) => {
+                previous_module = None;
+                joined.push(segment.clone());
+            }
+            Segment::Text(_) => joined.push(segment.clone()),
+        }
+    }
+
+    joined
+}
+
 pub fn module(module_name: &str, args: Properties) {
     let context = Context::new(args, Target::Main);
     let module = get_module(module_name, context).unwrap_or_default();
@@ -194,9 +395,13 @@ pub fn timings(args: Properties) {
     }
 }
 
-pub fn explain(args: Properties) {
+pub fn explain(args: Properties, trace_commands: bool) {
     let context = Context::new(args, Target::Main);
 
+    if trace_commands {
+        trace::start();
+    }
+
     struct ModuleInfo {
         value: String,
         value_len: usize,
@@ -223,6 +428,12 @@ pub fn explain(args: Properties) {
         })
         .collect::<Vec<ModuleInfo>>();
 
+    let trace_events = if trace_commands {
+        trace::finish()
+    } else {
+        Vec::new()
+    };
+
     let max_module_width = modules.iter().map(|i| i.value_len).max().unwrap_or(0);
 
     // In addition to the module width itself there are also 11 padding characters in each line.
@@ -287,6 +498,46 @@ pub fn explain(args: Properties) {
             );
         };
     }
+
+    if trace_commands {
+        print_trace(&trace_events);
+    }
+}
+
+/// Prints the commands run, files read and connections made while rendering, grouped by module
+fn print_trace(events: &[TraceEvent]) {
+    println!("\n Here's what your prompt accessed:");
+    if events.is_empty() {
+        println!(
+            " Nothing, no commands were run, no files were read and no connections were made."
+        );
+        return;
+    }
+
+    let mut by_module: BTreeMap<&str, Vec<&TraceEvent>> = BTreeMap::new();
+    for event in events {
+        by_module
+            .entry(event.module.as_deref().unwrap_or("(starship)"))
+            .or_default()
+            .push(event);
+    }
+
+    for (module, events) in by_module {
+        println!(" {}", Style::new().bold().paint(module));
+        for event in events {
+            let kind = match event.kind {
+                TraceKind::Command => "command",
+                TraceKind::FileRead => "file   ",
+                TraceKind::Network => "network",
+            };
+            let status = if event.success { "" } else { ", failed" };
+            println!(
+                "   {kind}  {} ({}{status})",
+                event.target,
+                format_duration(&event.duration)
+            );
+        }
+    }
 }
 
 fn compute_modules<'a>(context: &'a Context) -> Vec<Module<'a>> {
@@ -322,8 +573,11 @@ fn handle_module<'a>(
         if !context.is_module_disabled_in_config(module) {
             modules.extend(modules::handle(module, context));
         }
-    } else if module.starts_with("custom.") || module.starts_with("env_var.") {
-        // custom.<name> and env_var.<name> are special cases and handle disabled modules themselves
+    } else if module.starts_with("custom.")
+        || module.starts_with("env_var.")
+        || module.starts_with("fill.")
+    {
+        // custom.<name>, env_var.<name> and fill.<name> are special cases and handle disabled modules themselves
         modules.extend(modules::handle(module, context));
     } else if matches!(module, "custom" | "env_var") {
         // env var is a spacial case and may contain a top-level module definition
@@ -332,20 +586,26 @@ fn handle_module<'a>(
         }
 
         // Write out all custom modules, except for those that are explicitly set
-        for (child, config) in context
+        let children: Vec<String> = context
             .config
             .get_config(&[module])
             .and_then(|config| config.as_table().map(toml::map::Map::iter))
             .into_iter()
             .flatten()
-        {
             // Some env var keys may be part of a top-level module definition
-            if module == "env_var" && !config.is_table() {
-                continue;
-            } else if should_add_implicit_module(module, child, config, module_list) {
-                modules.extend(modules::handle(&format!("{module}.{child}"), context));
-            }
-        }
+            .filter(|(_, config)| module != "env_var" || config.is_table())
+            .filter(|(child, config)| {
+                should_add_implicit_module(module, child, config, module_list)
+            })
+            .map(|(child, _)| format!("{module}.{child}"))
+            .collect();
+
+        // Their commands may take a while, so they are run concurrently
+        modules.par_extend(
+            children
+                .par_iter()
+                .filter_map(|child| modules::handle(child, context)),
+        );
     } else {
         log::debug!(
             "Expected top level format to contain value from {:?}. Instead received {}",
@@ -411,8 +671,14 @@ fn load_formatter_and_modules<'a>(context: &'a Context) -> (StringFormatter<'a>,
             StringFormatter::new(&config.right_format),
             "right_format".to_string(),
         ),
+        Target::Continuation if !config.continuation_format.is_empty() => (
+            StringFormatter::new(&config.continuation_format)
+                .map(|formatter| map_continuation_variables(formatter, &context.properties)),
+            "continuation_format".to_string(),
+        ),
         Target::Continuation => (
-            StringFormatter::new(&config.continuation_prompt),
+            StringFormatter::new(&config.continuation_prompt)
+                .map(|formatter| map_continuation_variables(formatter, &context.properties)),
             "continuation_prompt".to_string(),
         ),
         Target::Profile(name) => (
@@ -446,6 +712,31 @@ fn load_formatter_and_modules<'a>(context: &'a Context) -> (StringFormatter<'a>,
     }
 }
 
+/// Maps the variables of the continuation prompt, which are passed by shells that know them
+fn map_continuation_variables<'a>(
+    formatter: StringFormatter<'a>,
+    properties: &Properties,
+) -> StringFormatter<'a> {
+    // zsh lists the open constructs from the outermost to the innermost one
+    let parser_state = properties
+        .parser_state
+        .as_deref()
+        .map(|state| state.split_whitespace().collect::<Vec<_>>())
+        .filter(|state| !state.is_empty());
+
+    formatter.map(|variable| match variable {
+        "line_number" => properties.line_number.map(|line| Ok(line.to_string())),
+        "kind" => parser_state
+            .as_ref()
+            .and_then(|state| state.last())
+            .map(|kind| Ok(kind.to_string())),
+        "depth" => parser_state
+            .as_ref()
+            .map(|state| Ok(state.len().to_string())),
+        _ => None,
+    })
+}
+
 #[cfg(feature = "config-schema")]
 pub fn print_schema() {
     let schema = schemars::schema_for!(crate::configs::FullConfig);
@@ -495,6 +786,7 @@ mod test {
     use super::*;
     use crate::test::default_context;
     use crate::utils;
+    use nu_ansi_term::Color;
 
     #[test]
     fn main_prompt() {
@@ -569,6 +861,34 @@ mod test {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn continuation_format_variables() {
+        let mut context = default_context().set_config(toml::toml! {
+                continuation_prompt="><>"
+                continuation_format="$line_number( $kind\\($depth\\)) > "
+        });
+        context.target = Target::Continuation;
+        context.properties.line_number = Some(3);
+        context.properties.parser_state = Some("dquote cmdsubst".to_string());
+
+        let expected = String::from("3 cmdsubst(2) > ");
+        let actual = get_prompt(context);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn continuation_format_without_parser_state() {
+        let mut context = default_context().set_config(toml::toml! {
+                continuation_format="$line_number( $kind\\($depth\\)) > "
+        });
+        context.target = Target::Continuation;
+        context.properties.line_number = Some(2);
+
+        let expected = String::from("2 > ");
+        let actual = get_prompt(context);
+        assert_eq!(expected, actual);
+    }
+
     #[test]
     fn preset_list_returns_one_or_more_items() {
         assert!(preset_list().trim().split('\n').count() > 0);
@@ -725,4 +1045,214 @@ mod test {
         assert_eq!(expected, actual);
         dir.close()
     }
+
+    #[test]
+    fn separator_between_modules() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut context = default_context().set_config(toml::toml! {
+                add_newline=false
+                separator="|"
+                format="${custom.a}${custom.empty}${custom.b}$line_break${custom.c}"
+                [custom.a]
+                when=true
+                format="a"
+                [custom.empty]
+                when=true
+                format=""
+                [custom.b]
+                when=true
+                format="b"
+                [custom.c]
+                when=true
+                format="c"
+        });
+        context.current_dir = dir.path().to_path_buf();
+
+        let expected = String::from("a|b\nc");
+        let actual = get_prompt(context);
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn directory_fits_rest_of_line() -> std::io::Result<()> {
+        let tmp_dir = tempfile::tempdir_in("/tmp")?;
+        let dir = tmp_dir.path().join("thrusters/rocket");
+        std::fs::create_dir_all(&dir)?;
+        let name = tmp_dir.path().file_name().unwrap().to_string_lossy();
+
+        let mut context = default_context().set_config(toml::toml! {
+            add_newline = false
+            format = "${custom.left}$directory$line_break${custom.below}"
+            [directory]
+            truncation_length = "auto"
+            format = "$path|"
+            [custom.left]
+            when = true
+            format = "12345"
+            [custom.below]
+            when = true
+            format = "a longer line than the one above"
+        });
+        context.current_dir = dir.clone();
+        context.logical_dir = dir;
+        context.width = 5 + format!("/tmp/{name}/thrusters/rocket|").len() - 1;
+
+        // Only the line of the directory counts, and `/tmp` is shortened to make room for
+        // the module before it
+        let expected =
+            format!("12345/t/{name}/thrusters/rocket|\na longer line than the one above");
+        assert_eq!(get_prompt(context), expected);
+        tmp_dir.close()
+    }
+
+    #[test]
+    fn separator_blends_backgrounds() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut context = default_context().set_config(toml::toml! {
+                add_newline=false
+                separator=">"
+                format="${custom.a}${custom.b}"
+                [custom.a]
+                when=true
+                format="[a](bg:red)"
+                [custom.b]
+                when=true
+                format="[b](bg:blue)"
+        });
+        context.current_dir = dir.path().to_path_buf();
+
+        let expected = AnsiStrings(&[
+            Style::new().on(Color::Red).paint("a"),
+            Color::Red.on(Color::Blue).paint(">"),
+            Style::new().on(Color::Blue).paint("b"),
+        ])
+        .to_string();
+        let actual = get_prompt(context);
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn separator_overrides() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut context = default_context().set_config(toml::toml! {
+                add_newline=false
+                separator="|"
+                separator_style="bold"
+                format="${custom.a}${custom.b}${custom.c}"
+                [separator_overrides]
+                "custom.a:custom.b"="-"
+                "*:custom.c"=""
+                [custom.a]
+                when=true
+                format="a"
+                [custom.b]
+                when=true
+                format="b"
+                [custom.c]
+                when=true
+                format="c"
+        });
+        context.current_dir = dir.path().to_path_buf();
+
+        let expected = format!("a{}bc", Style::new().bold().paint("-"));
+        let actual = get_prompt(context);
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn module_error_badge() {
+        // Without rate limiting, no markers are written into the real cache directory
+        let config = toml::toml! {
+            add_newline = false
+            format = "$character"
+            show_module_errors = true
+            module_error_log_interval = 0
+            [character]
+            format = "[$symbol"
+        };
+        let context = default_context().set_config(config);
+
+        let expected = format!("{} ", Color::Red.bold().paint("⚠ character"));
+        let actual = get_prompt(context);
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn module_error_badge_disabled_by_default() {
+        let context = default_context().set_config(toml::toml! {
+            add_newline = false
+            format = "$character"
+            [character]
+            format = "[$symbol"
+        });
+
+        let actual = get_prompt(context);
+        assert_eq!("", actual);
+    }
+
+    #[test]
+    fn user_vars() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut context = default_context().set_config(toml::toml! {
+            add_newline = false
+            format = "$character"
+            user_vars = true
+        });
+        context.logical_dir = dir.path().to_path_buf();
+        context.current_dir = dir.path().to_path_buf();
+        context.properties.status_code = Some("1".to_string());
+        context.shell = Shell::Zsh;
+
+        let expected = format!(
+            "%{{\x1b]1337;SetUserVar=starship_cwd={}\x07%}}\
+             %{{\x1b]1337;SetUserVar=starship_branch=\x07%}}\
+             %{{\x1b]1337;SetUserVar=starship_status=MQ==\x07%}}",
+            base64::encode(dir.path().to_string_lossy().as_bytes())
+        );
+        let actual = get_prompt(context);
+        assert!(actual.starts_with(&expected), "{actual:?}");
+        dir.close()
+    }
+
+    #[test]
+    fn escape_audit_fix_wraps_whole_sequences() {
+        let mut context = default_context().set_config(toml::toml! {
+            add_newline = false
+            format = "\x1b\\]8;;https://m.com\x1b\\\\[link](red)"
+            escape_audit = "fix"
+        });
+        context.shell = Shell::Zsh;
+
+        let expected = "%{\x1b]8;;https://m.com\x1b\\%}%{\x1b[31m%}link%{\x1b[0m%}";
+        assert_eq!(expected, get_prompt(context));
+    }
+
+    #[test]
+    fn user_vars_in_tmux() {
+        let mut context = default_context().set_config(toml::toml! {
+            add_newline = false
+            format = ""
+            user_vars = true
+        });
+        context
+            .env
+            .insert("TMUX", "/tmp/tmux-1000/default,1,0".to_string());
+
+        let actual = get_prompt(context);
+        assert!(actual
+            .contains("\x1bPtmux;\x1b\x1b]1337;SetUserVar=starship_branch=\x07\x1b\\\x1bPtmux;"));
+    }
+
+    #[test]
+    fn user_vars_disabled_by_default() {
+        let context = default_context().set_config(toml::toml! {
+            add_newline = false
+            format = ""
+        });
+
+        assert_eq!(get_prompt(context), "");
+    }
 }
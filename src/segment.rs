@@ -31,6 +31,13 @@ pub struct FillSegment {
 
     /// The string value of the current segment.
     value: String,
+
+    /// The share of the remaining space of the line, relative to the other fill segments.
+    pub weight: usize,
+
+    /// The bounds of the width of the segment.
+    pub min_width: usize,
+    pub max_width: Option<usize>,
 }
 
 impl FillSegment {
@@ -81,6 +88,9 @@ mod fill_seg_tests {
             let f = FillSegment {
                 value: String::from(*text),
                 style: Some(style),
+                weight: 1,
+                min_width: 0,
+                max_width: None,
             };
             let actual = f.ansi_string(Some(width));
             assert_eq!(style.paint(*expected), actual);
@@ -94,6 +104,8 @@ pub enum Segment {
     Text(TextSegment),
     Fill(FillSegment),
     LineTerm,
+    /// Zero-width marker for the start of a module's output, used to place module separators
+    ModuleStart(String),
 }
 
 impl Segment {
@@ -123,14 +135,48 @@ impl Segment {
         Self::Fill(FillSegment {
             style,
             value: value.into(),
+            weight: 1,
+            min_width: 0,
+            max_width: None,
         })
     }
 
+    /// Sets the share of the remaining space a fill segment takes, and the bounds of its width
+    pub fn with_fill_width(
+        mut self,
+        weight: usize,
+        min_width: usize,
+        max_width: Option<usize>,
+    ) -> Self {
+        if let Self::Fill(fs) = &mut self {
+            fs.weight = weight;
+            fs.min_width = min_width;
+            fs.max_width = max_width;
+        }
+        self
+    }
+
+    /// Creates a new marker for the start of the output of the given module
+    pub fn module_start<T>(name: T) -> Self
+    where
+        T: Into<String>,
+    {
+        Self::ModuleStart(name.into())
+    }
+
     pub fn style(&self) -> Option<Style> {
         match self {
             Self::Fill(fs) => fs.style,
             Self::Text(ts) => ts.style,
-            Self::LineTerm => None,
+            Self::LineTerm | Self::ModuleStart(_) => None,
+        }
+    }
+
+    pub fn set_style(&mut self, style: Style) {
+        match self {
+            Self::Fill(fs) => fs.style = Some(style),
+            Self::Text(ts) => ts.style = Some(style),
+            Self::LineTerm | Self::ModuleStart(_) => {}
         }
     }
 
@@ -146,7 +192,7 @@ impl Segment {
                     ts.style = style
                 }
             }
-            Self::LineTerm => {}
+            Self::LineTerm | Self::ModuleStart(_) => {}
         }
     }
 
@@ -155,6 +201,7 @@ impl Segment {
             Self::Fill(fs) => &fs.value,
             Self::Text(ts) => &ts.value,
             Self::LineTerm => LINE_TERMINATOR_STRING,
+            Self::ModuleStart(_) => "",
         }
     }
 
@@ -164,6 +211,7 @@ impl Segment {
             Self::Fill(fs) => fs.ansi_string(None),
             Self::Text(ts) => ts.ansi_string(),
             Self::LineTerm => AnsiString::from(LINE_TERMINATOR_STRING),
+            Self::ModuleStart(_) => AnsiString::from(""),
         }
     }
 
@@ -171,7 +219,7 @@ impl Segment {
         match self {
             Self::Fill(fs) => fs.value.width_graphemes(),
             Self::Text(ts) => ts.value.width_graphemes(),
-            Self::LineTerm => 0,
+            Self::LineTerm | Self::ModuleStart(_) => 0,
         }
     }
 }
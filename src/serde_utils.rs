@@ -1,6 +1,6 @@
 use crate::module::ALL_MODULES;
 use serde::de::{
-    value::{Error as ValueError, MapDeserializer, SeqDeserializer},
+    value::{Error as ValueError, MapDeserializer, SeqDeserializer, StrDeserializer},
     Deserializer, Error, IntoDeserializer, Visitor,
 };
 use std::{cmp::Ordering, fmt};
@@ -183,7 +183,8 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
             return visitor.visit_none();
         }
 
-        if !self.error_on_ignored {
+        // Every module accepts a `command_timeout`, see `Context::module_command_timeout`
+        if !self.error_on_ignored || self.current_key == Some("command_timeout") {
             return visitor.visit_none();
         }
 
@@ -217,10 +218,31 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'de> {
         visitor.visit_newtype_struct(self)
     }
 
+    /// Unit variants of enums are written as strings, e.g. `contrast_action = "warn"`
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            ValueRef::String(s) => {
+                let deserializer: StrDeserializer<ValueError> = s.into_deserializer();
+                visitor
+                    .visit_enum(deserializer)
+                    .map_err(|err| self.error(err))
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
     // Handle most deserialization cases by deferring to `deserialize_any`.
     serde::forward_to_deserialize_any! {
         bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string unit seq
-        bytes byte_buf map unit_struct tuple_struct enum tuple identifier
+        bytes byte_buf map unit_struct tuple_struct tuple identifier
     }
 }
 
@@ -352,6 +374,38 @@ mod test {
         assert_eq!(result.foo.0, "bar".to_owned());
     }
 
+    #[test]
+    fn test_deserialize_enum() {
+        let value = toml::toml! {
+            foo = "second"
+            bar = "third"
+        };
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        #[serde(rename_all = "lowercase")]
+        enum Choice {
+            First,
+            Second,
+        }
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Sample {
+            foo: Choice,
+            bar: Choice,
+        }
+
+        let deserializer = ValueDeserializer::new(&value);
+        let result = Sample::deserialize(deserializer).unwrap_err();
+        assert_eq!(
+            format!("{result}"),
+            "Error in 'Sample' at 'bar': unknown variant `third`, expected `first` or `second`"
+        );
+
+        let value = Value::String("second".to_string());
+        let result = Choice::deserialize(ValueDeserializer::new(&value)).unwrap();
+        assert_eq!(result, Choice::Second);
+    }
+
     #[test]
     fn test_deserialize_unknown() {
         let value = toml::toml! {
@@ -403,6 +457,23 @@ mod test {
         assert!(result.is_ok())
     }
 
+    #[test]
+    fn test_deserialize_module_command_timeout() {
+        let value = toml::toml! {
+            foo = "bar"
+            command_timeout = 2000
+        };
+        let deserializer = ValueDeserializer::new(&value);
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Sample {
+            foo: String,
+        }
+
+        assert!(Sample::deserialize(deserializer).is_ok());
+    }
+
     #[test]
     fn test_deserialize_unknown_typo() {
         let value = toml::toml! {
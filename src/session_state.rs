@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::utils;
+
+// Sessions that haven't stored anything for a week are assumed to be closed
+const STALE_SESSION_SECS: u64 = 60 * 60 * 24 * 7;
+// Sessions that are still used, but only read their values, are saved again after a day
+const REFRESH_SESSION_SECS: u64 = 60 * 60 * 24;
+
+/// Values that modules store for a shell session, so later prompts of the session can read them.
+///
+/// The values of a session are kept in a JSON file named after `STARSHIP_SESSION_KEY`, which is
+/// written whenever a value changes, and at least once a day while the session is used, so it isn't
+/// cleaned up. Without a session key, nothing is stored.
+#[derive(Debug, Default)]
+pub struct SessionState {
+    path: Option<PathBuf>,
+    values: BTreeMap<String, String>,
+}
+
+impl SessionState {
+    /// Loads the state of the session from the sessions directory
+    pub fn load(sessions_dir: &Path, session_key: Option<&str>) -> Self {
+        let Some(session_key) = session_key.filter(|key| is_valid_session_key(key)) else {
+            log::trace!("No valid session key, session state won't be stored");
+            return Self::default();
+        };
+
+        let path = sessions_dir.join(format!("{session_key}.json"));
+        let values = utils::read_file(&path)
+            .ok()
+            .and_then(|content| match serde_json::from_str(&content) {
+                Ok(values) => Some(values),
+                Err(error) => {
+                    log::debug!("Unable to parse session state {:?}: {}", path, error);
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        let is_old = file_age(&path).map_or(false, |age| age > REFRESH_SESSION_SECS);
+        let state = SessionState {
+            path: Some(path),
+            values,
+        };
+        if is_old && !state.values.is_empty() {
+            state.save();
+        }
+        state
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        if self.get(key) != Some(value) {
+            self.values.insert(key.to_string(), value.to_string());
+            self.save();
+        }
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        if self.values.remove(key).is_some() {
+            self.save();
+        }
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+
+        // Write to a temporary file first, so that concurrent prompts never read half a file
+        let temp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+        let result = serde_json::to_string(&self.values)
+            .map_err(std::io::Error::from)
+            .and_then(|content| fs::write(&temp_path, content))
+            .and_then(|_| fs::rename(&temp_path, path));
+        if let Err(error) = result {
+            log::debug!("Unable to write session state {:?}: {}", path, error);
+            let _ = fs::remove_file(&temp_path);
+        }
+    }
+}
+
+/// Session keys are generated by the init scripts, but may be set by anyone
+fn is_valid_session_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Deletes the state of sessions that haven't changed in a week
+pub fn cleanup_stale_sessions<P: AsRef<Path>>(path: P) {
+    let Ok(files) = fs::read_dir(path) else {
+        return;
+    };
+
+    for file in files.flatten() {
+        let path = file.path();
+        if file_age(&path).map_or(false, |age| age > STALE_SESSION_SECS) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// Seconds since the file was last modified, or `None` if it isn't a file
+fn file_age(path: &Path) -> Option<u64> {
+    fs::metadata(path)
+        .ok()
+        .filter(fs::Metadata::is_file)
+        .and_then(|metadata| metadata.modified().ok())
+        .and_then(|modified| modified.elapsed().ok())
+        .map(|elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    #[test]
+    fn values_persist_across_loads() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        let mut state = SessionState::load(dir.path(), Some("1234"));
+        assert_eq!(state.get("count"), None);
+        state.set("count", "1");
+        state.set("other", "value");
+        state.remove("other");
+
+        let state = SessionState::load(dir.path(), Some("1234"));
+        assert_eq!(state.get("count"), Some("1"));
+        assert_eq!(state.get("other"), None);
+        assert_eq!(fs::read_dir(dir.path())?.count(), 1);
+
+        let state = SessionState::load(dir.path(), Some("5678"));
+        assert_eq!(state.get("count"), None);
+        dir.close()
+    }
+
+    #[test]
+    fn nothing_stored_without_session_key() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        for key in [None, Some(""), Some("../1234")] {
+            let mut state = SessionState::load(dir.path(), key);
+            state.set("count", "1");
+            assert_eq!(state.get("count"), Some("1"));
+        }
+        assert_eq!(fs::read_dir(dir.path())?.count(), 0);
+        dir.close()
+    }
+
+    #[test]
+    fn invalid_state_is_ignored() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("1234.json"), "not json")?;
+
+        let mut state = SessionState::load(dir.path(), Some("1234"));
+        assert_eq!(state.get("count"), None);
+        state.set("count", "1");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("1234.json"))?,
+            r#"{"count":"1"}"#
+        );
+        dir.close()
+    }
+
+    #[test]
+    fn cleanup_keeps_recent_sessions() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        SessionState::load(dir.path(), Some("1234")).set("count", "1");
+
+        cleanup_stale_sessions(dir.path());
+        assert!(dir.path().join("1234.json").exists());
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn loading_keeps_sessions_alive() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        SessionState::load(dir.path(), Some("1234")).set("count", "1");
+        SessionState::load(dir.path(), Some("5678")).set("count", "1");
+        for file in ["1234.json", "5678.json"] {
+            let status = utils::create_command("touch")?
+                .args(["-t", "200001010000"])
+                .arg(dir.path().join(file))
+                .status()?;
+            assert!(status.success());
+        }
+
+        // Only the session that is still used reads its values
+        assert_eq!(
+            SessionState::load(dir.path(), Some("1234")).get("count"),
+            Some("1")
+        );
+        cleanup_stale_sessions(dir.path());
+        assert!(dir.path().join("1234.json").exists());
+        assert!(!dir.path().join("5678.json").exists());
+        dir.close()
+    }
+}
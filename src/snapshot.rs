@@ -0,0 +1,344 @@
+use clap::ValueEnum;
+use nu_ansi_term::{AnsiString, AnsiStrings, Color, Style};
+use std::fmt::Write as FmtWrite;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::context::{Context, Properties, Shell, Target};
+use crate::contrast::color_to_rgb;
+use crate::print::{get_root_module, UnicodeWidthGraphemes};
+
+/// Output format of a prompt snapshot
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SnapshotFormat {
+    /// Raw ANSI escape sequences, as printed by the terminal
+    Ansi,
+    /// A standalone HTML document with inline styles
+    Html,
+    /// A standalone SVG image
+    Svg,
+    /// Text without any styles
+    Plain,
+}
+
+// Colors used for text and background when a segment doesn't set its own.
+const DEFAULT_FOREGROUND: &str = "#e5e5e5";
+const DEFAULT_BACKGROUND: &str = "#1e1e1e";
+
+// Font metrics used to lay out the SVG snapshot.
+const SVG_FONT_SIZE: usize = 15;
+const SVG_CELL_WIDTH: usize = 9;
+const SVG_LINE_HEIGHT: usize = 20;
+const SVG_PADDING: usize = 10;
+
+/// Renders the prompt for the given properties and writes the snapshot to stdout or a file
+pub fn snapshot(args: Properties, format: SnapshotFormat, output: Option<PathBuf>) {
+    let context = Context::new(args, Target::Main);
+    let content = render_snapshot(&context, format);
+
+    if let Some(output) = output {
+        if let Err(err) = std::fs::write(output, content) {
+            eprintln!("Error writing snapshot to file: {err}");
+            std::process::exit(1);
+        }
+    } else if let Err(err) = io::stdout().write_all(content.as_bytes()) {
+        eprintln!("Error writing snapshot to stdout: {err}");
+        std::process::exit(1);
+    }
+}
+
+/// Renders the prompt of the context in the requested format
+pub fn render_snapshot(context: &Context, format: SnapshotFormat) -> String {
+    let root_module = get_root_module(context);
+    // Shell-specific escaping would only show up as noise in the snapshot
+    let strings = root_module.ansi_strings_for_shell(Shell::Unknown, Some(context.width));
+
+    match format {
+        SnapshotFormat::Ansi => format!("{}\n", AnsiStrings(&strings)),
+        SnapshotFormat::Html => to_html(&strings),
+        SnapshotFormat::Svg => to_svg(&strings),
+        SnapshotFormat::Plain => to_plain(&strings),
+    }
+}
+
+/// Splits the styled strings into lines of styled chunks
+fn styled_lines<'a>(strings: &'a [AnsiString<'a>]) -> Vec<Vec<(Style, &'a str)>> {
+    let mut lines = vec![Vec::new()];
+    for string in strings {
+        for (i, chunk) in string.as_str().split('\n').enumerate() {
+            if i > 0 {
+                lines.push(Vec::new());
+            }
+            if !chunk.is_empty() {
+                // SAFETY: `lines` is never empty
+                lines.last_mut().unwrap().push((*string.style_ref(), chunk));
+            }
+        }
+    }
+    lines
+}
+
+/// Returns the hex representation of a terminal color
+fn color_to_hex(color: Color) -> Option<String> {
+    color_to_rgb(color).map(|(r, g, b)| format!("#{r:02x}{g:02x}{b:02x}"))
+}
+
+/// Resolves the foreground and background colors of a style, taking `inverted` into account
+fn resolve_colors(style: &Style) -> (Option<String>, Option<String>) {
+    let fg = style.foreground.and_then(color_to_hex);
+    let bg = style.background.and_then(color_to_hex);
+    if style.is_reverse {
+        (
+            Some(bg.unwrap_or_else(|| DEFAULT_BACKGROUND.to_string())),
+            Some(fg.unwrap_or_else(|| DEFAULT_FOREGROUND.to_string())),
+        )
+    } else {
+        (fg, bg)
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn text_decoration(style: &Style) -> Option<&'static str> {
+    match (style.is_underline, style.is_strikethrough) {
+        (true, true) => Some("underline line-through"),
+        (true, false) => Some("underline"),
+        (false, true) => Some("line-through"),
+        (false, false) => None,
+    }
+}
+
+fn css_for_style(style: &Style) -> String {
+    let (fg, bg) = resolve_colors(style);
+    let mut css = Vec::new();
+    if style.is_hidden {
+        css.push("color:transparent".to_string());
+    } else if let Some(fg) = fg {
+        css.push(format!("color:{fg}"));
+    }
+    if let Some(bg) = bg {
+        css.push(format!("background-color:{bg}"));
+    }
+    if style.is_bold {
+        css.push("font-weight:bold".to_string());
+    }
+    if style.is_dimmed {
+        css.push("opacity:0.5".to_string());
+    }
+    if style.is_italic {
+        css.push("font-style:italic".to_string());
+    }
+    if let Some(decoration) = text_decoration(style) {
+        css.push(format!("text-decoration:{decoration}"));
+    }
+    css.join(";")
+}
+
+fn to_html(strings: &[AnsiString]) -> String {
+    let mut body = String::new();
+    for (i, line) in styled_lines(strings).into_iter().enumerate() {
+        if i > 0 {
+            body.push('\n');
+        }
+        for (style, text) in line {
+            let css = css_for_style(&style);
+            if css.is_empty() {
+                body.push_str(&escape_xml(text));
+            } else {
+                write!(body, "<span style=\"{css}\">{}</span>", escape_xml(text)).unwrap();
+            }
+        }
+    }
+
+    format!(
+        "<!DOCTYPE html>\n\
+         <html>\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <title>Starship prompt</title>\n\
+         </head>\n\
+         <body>\n\
+         <pre style=\"background-color:{DEFAULT_BACKGROUND};color:{DEFAULT_FOREGROUND};font-family:monospace;padding:1em\">{body}</pre>\n\
+         </body>\n\
+         </html>\n"
+    )
+}
+
+fn to_plain(strings: &[AnsiString]) -> String {
+    let mut plain: String = strings.iter().map(|string| string.as_str()).collect();
+    plain.push('\n');
+    plain
+}
+
+fn to_svg(strings: &[AnsiString]) -> String {
+    let lines = styled_lines(strings);
+    let columns = lines
+        .iter()
+        .map(|line| line.iter().map(|(_, text)| text.width_graphemes()).sum())
+        .max()
+        .unwrap_or(0);
+
+    let width = 2 * SVG_PADDING + columns * SVG_CELL_WIDTH;
+    let height = 2 * SVG_PADDING + lines.len() * SVG_LINE_HEIGHT;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         font-family=\"monospace\" font-size=\"{SVG_FONT_SIZE}\">"
+    )
+    .unwrap();
+    writeln!(
+        svg,
+        "<rect width=\"100%\" height=\"100%\" fill=\"{DEFAULT_BACKGROUND}\"/>"
+    )
+    .unwrap();
+
+    for (row, line) in lines.into_iter().enumerate() {
+        let top = SVG_PADDING + row * SVG_LINE_HEIGHT;
+        // Place the baseline so that descenders stay within the line
+        let baseline = top + SVG_FONT_SIZE;
+        let mut column = 0;
+        for (style, text) in line {
+            let x = SVG_PADDING + column * SVG_CELL_WIDTH;
+            let text_width = text.width_graphemes();
+            column += text_width;
+
+            let (fg, bg) = resolve_colors(&style);
+            if let Some(bg) = bg {
+                writeln!(
+                    svg,
+                    "<rect x=\"{x}\" y=\"{top}\" width=\"{}\" height=\"{SVG_LINE_HEIGHT}\" fill=\"{bg}\"/>",
+                    text_width * SVG_CELL_WIDTH
+                )
+                .unwrap();
+            }
+
+            if style.is_hidden {
+                continue;
+            }
+
+            let mut attributes = format!(
+                "x=\"{x}\" y=\"{baseline}\" fill=\"{}\"",
+                fg.as_deref().unwrap_or(DEFAULT_FOREGROUND)
+            );
+            if style.is_bold {
+                attributes.push_str(" font-weight=\"bold\"");
+            }
+            if style.is_dimmed {
+                attributes.push_str(" fill-opacity=\"0.5\"");
+            }
+            if style.is_italic {
+                attributes.push_str(" font-style=\"italic\"");
+            }
+            if let Some(decoration) = text_decoration(&style) {
+                write!(attributes, " text-decoration=\"{decoration}\"").unwrap();
+            }
+            writeln!(
+                svg,
+                "<text {attributes} xml:space=\"preserve\">{}</text>",
+                escape_xml(text)
+            )
+            .unwrap();
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::default_context;
+
+    fn render(config: toml::Table, format: SnapshotFormat) -> String {
+        let context = default_context().set_config(config);
+        render_snapshot(&context, format)
+    }
+
+    #[test]
+    fn color_conversion() {
+        assert_eq!(color_to_hex(Color::Red), Some("#cd0000".to_string()));
+        assert_eq!(color_to_hex(Color::Fixed(9)), Some("#ff0000".to_string()));
+        assert_eq!(color_to_hex(Color::Fixed(208)), Some("#ff8700".to_string()));
+        assert_eq!(color_to_hex(Color::Fixed(244)), Some("#808080".to_string()));
+        assert_eq!(
+            color_to_hex(Color::Rgb(1, 2, 255)),
+            Some("#0102ff".to_string())
+        );
+        assert_eq!(color_to_hex(Color::Default), None);
+    }
+
+    #[test]
+    fn ansi_snapshot() {
+        let actual = render(
+            toml::toml! {
+                format = "[a](bold red)b"
+            },
+            SnapshotFormat::Ansi,
+        );
+        let expected = format!("{}b\n", Color::Red.bold().paint("a"));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn plain_snapshot() {
+        let actual = render(
+            toml::toml! {
+                format = "[a](bold red)b\n[c](bg:blue)"
+            },
+            SnapshotFormat::Plain,
+        );
+        assert_eq!("ab\nc\n", actual);
+    }
+
+    #[test]
+    fn html_snapshot_styles_and_escapes() {
+        let actual = render(
+            toml::toml! {
+                format = "[<a>](bold fg:red bg:#00ff00)\n[&](inverted)"
+            },
+            SnapshotFormat::Html,
+        );
+        assert!(actual.starts_with("<!DOCTYPE html>"));
+        assert!(actual.contains(
+            "<span style=\"color:#cd0000;background-color:#00ff00;font-weight:bold\">&lt;a&gt;</span>\n"
+        ));
+        assert!(actual
+            .contains("<span style=\"color:#1e1e1e;background-color:#e5e5e5\">&amp;</span></pre>"));
+    }
+
+    #[test]
+    fn svg_snapshot_layout() {
+        let actual = render(
+            toml::toml! {
+                format = "ab[c](bg:blue)\nd"
+            },
+            SnapshotFormat::Svg,
+        );
+        assert!(actual
+            .starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"47\" height=\"60\""));
+        assert!(
+            actual.contains("<rect x=\"28\" y=\"10\" width=\"9\" height=\"20\" fill=\"#0000ee\"/>")
+        );
+        assert!(actual.contains(">ab</text>"));
+        assert!(
+            actual.contains("x=\"10\" y=\"45\" fill=\"#e5e5e5\" xml:space=\"preserve\">d</text>")
+        );
+        assert!(actual.ends_with("</svg>\n"));
+    }
+}
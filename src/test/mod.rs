@@ -1,22 +1,27 @@
+//! Helpers to render a module with mocked environment variables, commands and
+//! files, as used by the tests of the modules.
+//!
+//! Outside of starship, these are available with the `testing` feature.
+
 use crate::context::{Context, Shell, Target};
+use crate::context_env::Env;
 use crate::logger::StarshipLogger;
+pub use crate::utils::CommandOutput;
 use crate::{
     config::StarshipConfig,
-    utils::{create_command, CommandOutput},
+    utils::{self, create_command},
 };
 use log::{Level, LevelFilter};
-use once_cell::sync::Lazy;
+use once_cell::sync::OnceCell;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
 use std::sync::Once;
 use tempfile::TempDir;
 
-static FIXTURE_DIR: Lazy<PathBuf> =
-    Lazy::new(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/test/fixtures/"));
-
-static GIT_FIXTURE: Lazy<PathBuf> = Lazy::new(|| FIXTURE_DIR.join("git-repo.bundle"));
-static HG_FIXTURE: Lazy<PathBuf> = Lazy::new(|| FIXTURE_DIR.join("hg-repo.bundle"));
+// Embedded, so that the fixtures don't depend on where starship was built
+static GIT_FIXTURE: &[u8] = include_bytes!("fixtures/git-repo.bundle");
+static HG_FIXTURE: &[u8] = include_bytes!("fixtures/hg-repo.bundle");
 
 static LOGGER: Once = Once::new();
 
@@ -32,7 +37,8 @@ fn init_logger() {
     logger.set_log_level(Level::Trace);
     logger.set_log_file_path(nul);
 
-    log::set_boxed_logger(Box::new(logger)).unwrap();
+    // The crate using the harness may have installed its own logger already
+    let _ = log::set_boxed_logger(Box::new(logger));
 }
 
 pub fn default_context() -> Context<'static> {
@@ -42,9 +48,11 @@ pub fn default_context() -> Context<'static> {
         Target::Main,
         PathBuf::new(),
         PathBuf::new(),
-        Default::default(),
+        Env::mocked(),
     );
     context.config = StarshipConfig { config: None };
+    // Modules see an unknown operating system instead of the one running the tests
+    context.os_info = OnceCell::with_value(os_info::Info::default());
     context
 }
 
@@ -75,7 +83,11 @@ impl<'a> ModuleRenderer<'a> {
     }
 
     pub fn root_path(&self) -> &Path {
-        self.context.root_dir.path()
+        self.context
+            .root_dir
+            .as_ref()
+            .expect("the test context has a mocked root directory")
+            .path()
     }
 
     pub fn logical_path<T>(mut self, path: T) -> Self
@@ -104,21 +116,54 @@ impl<'a> ModuleRenderer<'a> {
         self
     }
 
+    /// Mocks the command as exiting with an error after printing `stderr`
+    pub fn cmd_failure(mut self, key: &'a str, stderr: &str) -> Self {
+        let output = CommandOutput {
+            stdout: String::new(),
+            stderr: stderr.to_string(),
+        };
+        self.context.cmd_failures.insert(key, output);
+        self
+    }
+
     pub fn shell(mut self, shell: Shell) -> Self {
         self.context.shell = shell;
         self
     }
 
+    pub fn width(mut self, width: usize) -> Self {
+        self.context.width = width;
+        self
+    }
+
     pub fn jobs(mut self, jobs: i64) -> Self {
         self.context.properties.jobs = jobs;
         self
     }
 
+    pub fn job_names(mut self, names: &[&str]) -> Self {
+        self.context.properties.job_names = Some(names.iter().map(|&name| name.into()).collect());
+        self
+    }
+
     pub fn cmd_duration(mut self, duration: u64) -> Self {
         self.context.properties.cmd_duration = Some(duration.to_string());
         self
     }
 
+    pub fn cmd_id(mut self, cmd_id: &str) -> Self {
+        self.context.properties.cmd_id = Some(cmd_id.to_string());
+        self
+    }
+
+    /// Sets the CPU times of the command in milliseconds, which shells pass in clock ticks
+    pub fn cmd_cpu_times(mut self, user_time: u64, sys_time: u64) -> Self {
+        let to_ticks = |millis: u64| u128::from(millis) * utils::clock_ticks_per_second() / 1000;
+        self.context.properties.cmd_user_ticks = Some(to_ticks(user_time).to_string());
+        self.context.properties.cmd_sys_ticks = Some(to_ticks(sys_time).to_string());
+        self
+    }
+
     pub fn keymap<T>(mut self, keymap: T) -> Self
     where
         T: Into<String>,
@@ -162,6 +207,22 @@ impl<'a> ModuleRenderer<'a> {
     }
 }
 
+/// The successful output of a mocked command, for `ModuleRenderer::cmd`
+pub fn output(stdout: &str) -> Option<CommandOutput> {
+    Some(CommandOutput {
+        stdout: stdout.to_string(),
+        stderr: String::new(),
+    })
+}
+
+/// Writes a bundle into a temporary directory, as git and hg only clone bundles from files
+fn bundle_file(bundle: &[u8]) -> io::Result<(TempDir, PathBuf)> {
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("repo.bundle");
+    fs::write(&path, bundle)?;
+    Ok((dir, path))
+}
+
 #[derive(Clone, Copy)]
 pub enum FixtureProvider {
     Fossil,
@@ -189,14 +250,24 @@ pub fn fixture_repo(provider: FixtureProvider) -> io::Result<TempDir> {
         }
         FixtureProvider::Git => {
             let path = tempfile::tempdir()?;
+            let (_bundle_dir, bundle) = bundle_file(GIT_FIXTURE)?;
 
             create_command("git")?
                 .current_dir(path.path())
                 .args(["clone", "-b", "master"])
-                .arg(GIT_FIXTURE.as_os_str())
+                .arg(&bundle)
                 .arg(path.path())
                 .output()?;
 
+            // Keep the origin for as long as the clone, e.g. for `git pull`
+            let origin = path.path().join(".git").join("origin.bundle");
+            fs::copy(&bundle, &origin)?;
+            create_command("git")?
+                .args(["remote", "set-url", "origin"])
+                .arg(&origin)
+                .current_dir(path.path())
+                .output()?;
+
             create_command("git")?
                 .args(["config", "--local", "user.email", "starship@example.com"])
                 .current_dir(path.path())
@@ -231,11 +302,12 @@ pub fn fixture_repo(provider: FixtureProvider) -> io::Result<TempDir> {
         }
         FixtureProvider::Hg => {
             let path = tempfile::tempdir()?;
+            let (_bundle_dir, bundle) = bundle_file(HG_FIXTURE)?;
 
             create_command("hg")?
                 .current_dir(path.path())
                 .arg("clone")
-                .arg(HG_FIXTURE.as_os_str())
+                .arg(&bundle)
                 .arg(path.path())
                 .output()?;
 
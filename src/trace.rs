@@ -0,0 +1,128 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// What kind of resource a module accessed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TraceKind {
+    Command,
+    FileRead,
+    Network,
+}
+
+/// A single external command, file read or network connection performed while rendering the prompt
+#[derive(Debug)]
+pub struct TraceEvent {
+    /// The module that caused the access, `None` if it happened outside of a module
+    pub module: Option<String>,
+    pub kind: TraceKind,
+    pub target: String,
+    pub duration: Duration,
+    pub success: bool,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static EVENTS: Mutex<Vec<TraceEvent>> = Mutex::new(Vec::new());
+
+thread_local! {
+    static CURRENT_MODULE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Starts recording the commands run, files read and connections made by modules
+pub fn start() {
+    EVENTS.lock().unwrap().clear();
+    ENABLED.store(true, Ordering::SeqCst);
+}
+
+/// Stops recording and returns everything recorded since `start`
+pub fn finish() -> Vec<TraceEvent> {
+    ENABLED.store(false, Ordering::SeqCst);
+    std::mem::take(&mut *EVENTS.lock().unwrap())
+}
+
+/// Returns the module recorded accesses on this thread are attributed to,
+/// so work handed to other threads can be attributed to it as well
+pub fn current_module() -> Option<String> {
+    CURRENT_MODULE.with(|current| current.borrow().clone())
+}
+
+/// Runs `f`, attributing everything recorded on this thread meanwhile to `module`
+pub fn in_module<T>(module: Option<&str>, f: impl FnOnce() -> T) -> T {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return f();
+    }
+
+    let previous = CURRENT_MODULE.with(|current| current.replace(module.map(str::to_string)));
+    let result = f();
+    CURRENT_MODULE.with(|current| *current.borrow_mut() = previous);
+    result
+}
+
+/// Records an access that began at `start`, if recording is enabled
+pub fn record(kind: TraceKind, target: impl FnOnce() -> String, start: Instant, success: bool) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let event = TraceEvent {
+        module: current_module(),
+        kind,
+        target: target(),
+        duration: start.elapsed(),
+        success,
+    };
+    EVENTS.lock().unwrap().push(event);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_accesses_per_module() {
+        start();
+        in_module(Some("trace_test"), || {
+            let module = current_module();
+            std::thread::spawn(move || {
+                in_module(module.as_deref(), || {
+                    record(
+                        TraceKind::Command,
+                        || "ls -a".to_string(),
+                        Instant::now(),
+                        true,
+                    );
+                });
+            })
+            .join()
+            .unwrap();
+            record(
+                TraceKind::FileRead,
+                || "a.txt".to_string(),
+                Instant::now(),
+                false,
+            );
+        });
+        // Tests running in parallel may record their own accesses as well
+        let events = finish()
+            .into_iter()
+            .filter(|event| event.module.as_deref() == Some("trace_test"))
+            .map(|event| (event.kind, event.target, event.success))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            events,
+            vec![
+                (TraceKind::Command, "ls -a".to_string(), true),
+                (TraceKind::FileRead, "a.txt".to_string(), false),
+            ]
+        );
+        record(
+            TraceKind::Command,
+            || "ignored".to_string(),
+            Instant::now(),
+            true,
+        );
+        assert!(finish().is_empty());
+    }
+}
@@ -9,9 +9,10 @@ use std::time::{Duration, Instant};
 
 use crate::context::Context;
 use crate::context::Shell;
+use crate::trace::{self, TraceKind};
 
 /// Create a `PathBuf` from an absolute path, where the root directory will be mocked in test
-#[cfg(not(test))]
+#[cfg(not(any(test, feature = "testing")))]
 #[inline]
 #[allow(dead_code)]
 pub fn context_path<S: AsRef<OsStr> + ?Sized>(_context: &Context, s: &S) -> PathBuf {
@@ -19,17 +20,18 @@ pub fn context_path<S: AsRef<OsStr> + ?Sized>(_context: &Context, s: &S) -> Path
 }
 
 /// Create a `PathBuf` from an absolute path, where the root directory will be mocked in test
-#[cfg(test)]
+#[cfg(any(test, feature = "testing"))]
 #[allow(dead_code)]
 pub fn context_path<S: AsRef<OsStr> + ?Sized>(context: &Context, s: &S) -> PathBuf {
     let requested_path = PathBuf::from(s);
 
-    if requested_path.is_absolute() {
-        let mut path = PathBuf::from(context.root_dir.path());
-        path.extend(requested_path.components().skip(1));
-        path
-    } else {
-        requested_path
+    match &context.root_dir {
+        Some(root_dir) if requested_path.is_absolute() => {
+            let mut path = PathBuf::from(root_dir.path());
+            path.extend(requested_path.components().skip(1));
+            path
+        }
+        _ => requested_path,
     }
 }
 
@@ -37,7 +39,14 @@ pub fn context_path<S: AsRef<OsStr> + ?Sized>(context: &Context, s: &S) -> PathB
 pub fn read_file<P: AsRef<Path> + Debug>(file_name: P) -> Result<String> {
     log::trace!("Trying to read from {:?}", file_name);
 
-    let result = read_to_string(file_name);
+    let start = Instant::now();
+    let result = read_to_string(&file_name);
+    trace::record(
+        TraceKind::FileRead,
+        || file_name.as_ref().to_string_lossy().into_owned(),
+        start,
+        result.is_ok(),
+    );
 
     if result.is_err() {
         log::debug!("Error reading file: {:?}", result);
@@ -130,7 +139,6 @@ impl PartialEq for CommandOutput {
     }
 }
 
-#[cfg(test)]
 pub fn display_command<T: AsRef<OsStr> + Debug, U: AsRef<OsStr> + Debug>(
     cmd: T,
     args: &[U],
@@ -167,6 +175,10 @@ pub fn mock_cmd<T: AsRef<OsStr> + Debug, U: AsRef<OsStr> + Debug>(
             stdout: String::from("0.1.4\n"),
             stderr: String::default(),
         }),
+        "buck2 --version" => Some(CommandOutput {
+            stdout: String::from("buck2 2024-10-15 8e2c8d3a1d0d <build-id>\n"),
+            stderr: String::default(),
+        }),
         "buf --version" => Some(CommandOutput {
             stdout: String::from("1.0.0"),
             stderr: String::default(),
@@ -429,6 +441,10 @@ Target: x86_64-apple-darwin19.4.0\n",
             ),
             stderr: String::default(),
         }),
+        "uv --version" => Some(CommandOutput {
+            stdout: String::from("uv 0.4.18 (7b55e9790 2024-10-01)\n"),
+            stderr: String::default(),
+        }),
         "vagrant --version" => Some(CommandOutput {
             stdout: String::from("Vagrant 2.2.10\n"),
             stderr: String::default(),
@@ -471,6 +487,11 @@ CMake suite maintained and supported by Kitware (kitware.com/cmake).\n",
     Some(out)
 }
 
+/// Wraps an escape sequence in a tmux DCS passthrough, so that tmux passes it on to the terminal
+pub fn wrap_tmux_passthrough(seq: &str) -> String {
+    format!("\x1bPtmux;{}\x1b\\", seq.replace('\x1b', "\x1b\x1b"))
+}
+
 /// Wraps ANSI color escape sequences in the shell-appropriate wrappers.
 pub fn wrap_colorseq_for_shell(ansi: String, shell: Shell) -> String {
     const ESCAPE_BEGIN: char = '\u{1b}';
@@ -535,7 +556,31 @@ fn internal_exec_cmd<T: AsRef<OsStr> + Debug, U: AsRef<OsStr> + Debug>(
 }
 
 pub fn exec_timeout(cmd: &mut Command, time_limit: Duration) -> Option<CommandOutput> {
+    exec_timeout_with_failure(cmd, time_limit)?.ok()
+}
+
+/// Like `exec_timeout`, but the output of a command that exited with an error is returned as
+/// `Err`, e.g. to tell from its stderr why it failed
+pub fn exec_timeout_with_failure(
+    cmd: &mut Command,
+    time_limit: Duration,
+) -> Option<std::result::Result<CommandOutput, CommandOutput>> {
     let start = Instant::now();
+    let output = internal_exec_timeout(cmd, time_limit, start);
+    trace::record(
+        TraceKind::Command,
+        || display_command(cmd.get_program(), &cmd.get_args().collect::<Vec<_>>()),
+        start,
+        matches!(output, Some(Ok(_))),
+    );
+    output
+}
+
+fn internal_exec_timeout(
+    cmd: &mut Command,
+    time_limit: Duration,
+    start: Instant,
+) -> Option<std::result::Result<CommandOutput, CommandOutput>> {
     let process = match cmd.spawn() {
         Ok(process) => process,
         Err(error) => {
@@ -573,14 +618,15 @@ pub fn exec_timeout(cmd: &mut Command, time_limit: Duration) -> Option<CommandOu
                 start.elapsed()
             );
 
-            if !output.status.success() {
-                return None;
-            }
-
-            Some(CommandOutput {
+            let command_output = CommandOutput {
                 stdout: stdout_string,
                 stderr: stderr_string,
-            })
+            };
+            if output.status.success() {
+                Some(Ok(command_output))
+            } else {
+                Some(Err(command_output))
+            }
         }
         Ok(None) => {
             log::warn!("Executing command {:?} timed out.", cmd.get_program());
@@ -598,6 +644,24 @@ pub fn exec_timeout(cmd: &mut Command, time_limit: Duration) -> Option<CommandOu
     }
 }
 
+/// Runs `f` on a separate thread and waits at most `time_limit` for its result
+///
+/// Filesystem calls on a stale network mount can block indefinitely. If the
+/// time limit passes, the thread is abandoned and `None` is returned.
+pub fn run_with_timeout<T, F>(time_limit: Duration, f: F) -> Option<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let module = trace::current_module();
+    std::thread::spawn(move || {
+        // The receiver is gone if the time limit has passed already
+        trace::in_module(module.as_deref(), || sender.send(f()).ok());
+    });
+    receiver.recv_timeout(time_limit).ok()
+}
+
 // Render the time into a nice human-readable string
 pub fn render_time(raw_millis: u128, show_millis: bool) -> String {
     // Make sure it renders something if the time equals zero instead of an empty string
@@ -633,10 +697,36 @@ fn render_time_component((component, suffix): (&u128, &&str)) -> String {
     }
 }
 
+/// The number of clock ticks per second, the unit of the CPU times that shells read from `/proc`
+#[cfg(not(windows))]
+pub fn clock_ticks_per_second() -> u128 {
+    use nix::unistd::{sysconf, SysconfVar};
+    match sysconf(SysconfVar::CLK_TCK) {
+        Ok(Some(ticks)) if ticks > 0 => ticks as u128,
+        _ => 100,
+    }
+}
+
+/// Windows has no `sysconf`, so the usual rate of Linux is assumed
+#[cfg(windows)]
+pub fn clock_ticks_per_second() -> u128 {
+    100
+}
+
 pub fn home_dir() -> Option<PathBuf> {
     dirs_next::home_dir()
 }
 
+/// Returns the path of `name` in Starship's cache directory, which is `$STARSHIP_CACHE` of the
+/// context, or else the log directory
+pub fn cache_path(context: &Context, name: &str) -> PathBuf {
+    context
+        .get_env_os("STARSHIP_CACHE")
+        .map(PathBuf::from)
+        .unwrap_or_else(crate::logger::get_log_dir)
+        .join(name)
+}
+
 const HEXTABLE: &[char] = &[
     '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'a', 'b', 'c', 'd', 'e', 'f',
 ];
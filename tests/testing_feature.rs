@@ -0,0 +1,52 @@
+//! Renders modules through `starship::test`, the way crates using the `testing` feature do.
+#![cfg(feature = "testing")]
+
+use nu_ansi_term::Color;
+use starship::test::{output, ModuleRenderer};
+use std::fs::File;
+use std::io;
+
+#[test]
+fn renders_mocked_env_var() {
+    let actual = ModuleRenderer::new("env_var.TEST_VAR")
+        .config(toml::toml! {
+            [env_var.TEST_VAR]
+            format = "with [$env_value]($style) "
+        })
+        .env("TEST_VAR", "mocked")
+        .collect();
+    let expected = Some(format!(
+        "with {} ",
+        Color::Black.bold().dimmed().paint("mocked")
+    ));
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn renders_mocked_command() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("bun.lockb"))?.sync_all()?;
+    let actual = ModuleRenderer::new("bun")
+        .path(dir.path())
+        .cmd("bun --version", output("1.0.0\n"))
+        .collect();
+    let expected = Some(format!("via {}", Color::Red.bold().paint("🍞 v1.0.0 ")));
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn commands_without_mocks_are_not_canned() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("bun.lockb"))?.sync_all()?;
+    // The built-in outputs of starship's own tests don't apply, so an unmocked command runs
+    let actual = ModuleRenderer::new("bun")
+        .path(dir.path())
+        .config(toml::toml! {
+            [bun]
+            format = "$version"
+        })
+        .collect();
+    assert_ne!(actual.as_deref(), Some("v0.1.4"));
+    dir.close()
+}